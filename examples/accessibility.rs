@@ -0,0 +1,38 @@
+use bevy::prelude::*;
+use bevy_iced::iced::widget::{button, column, text};
+use bevy_iced::{AnnouncePriority, IcedContext, IcedPlugin};
+
+#[derive(Event, Clone)]
+pub enum UiMessage {
+    Craft,
+}
+
+#[derive(Resource, Default)]
+pub struct Crafted(u32);
+
+pub fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(IcedPlugin::default())
+        .add_event::<UiMessage>()
+        .init_resource::<Crafted>()
+        .add_systems(Update, (ui_system, craft_system))
+        .run();
+}
+
+fn craft_system(mut messages: EventReader<UiMessage>, mut crafted: ResMut<Crafted>) {
+    for UiMessage::Craft in messages.read() {
+        crafted.0 += 1;
+    }
+}
+
+fn ui_system(crafted: Res<Crafted>, mut ctx: IcedContext<UiMessage>) {
+    if crafted.is_changed() && crafted.0 > 0 {
+        // Screen readers read this out without the focus moving.
+        ctx.announce("Item crafted", AnnouncePriority::Polite);
+    }
+    ctx.display(column![
+        text(format!("Crafted {} items", crafted.0)),
+        button("Craft").on_press(UiMessage::Craft),
+    ]);
+}
@@ -0,0 +1,67 @@
+use bevy::prelude::*;
+use bevy_iced::iced::widget::{button, container, row, text};
+use bevy_iced::iced::{Point, Vector};
+use bevy_iced::widget::{stack, Anchor};
+use bevy_iced::{IcedContext, IcedPlugin};
+
+#[derive(Clone, Event)]
+enum UiMessage {
+    Slot(usize),
+}
+
+#[derive(Resource, Default)]
+pub struct Damage(Vec<(Point, f32, u32)>);
+
+pub fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(IcedPlugin::default())
+        .add_event::<UiMessage>()
+        .init_resource::<Damage>()
+        .add_systems(Startup, build_program)
+        .add_systems(Update, (hit_system, ui_system))
+        .run();
+}
+
+fn build_program(mut commands: Commands) {
+    commands.spawn(Camera2dBundle::default());
+}
+
+// Pressing a slot of the hotbar deals damage, shown where the slot's number floats up.
+fn hit_system(mut messages: EventReader<UiMessage>, mut damage: ResMut<Damage>, time: Res<Time>) {
+    let now = time.elapsed_seconds();
+    for msg in messages.read() {
+        let UiMessage::Slot(slot) = msg;
+        let position = Point::new(200.0 + 60.0 * *slot as f32, 300.0);
+        damage.0.push((position, now, 10 * (*slot as u32 + 1)));
+    }
+    damage.0.retain(|(_, since, _)| now - since < 1.5);
+}
+
+fn ui_system(mut ctx: IcedContext<UiMessage>, damage: Res<Damage>, time: Res<Time>) {
+    let now = time.elapsed_seconds();
+    let minimap = container(text("Minimap"))
+        .width(160)
+        .height(160)
+        .center_x()
+        .center_y()
+        .style(bevy_iced::iced::theme::Container::Box);
+    let hotbar = row((0..5).map(|slot| {
+        button(text(slot + 1))
+            .on_press(UiMessage::Slot(slot))
+            .into()
+    }))
+    .spacing(8);
+
+    let mut hud = stack()
+        .anchor(Anchor::TopRight, Vector::new(16.0, 16.0), minimap)
+        .anchor(Anchor::Bottom, Vector::new(0.0, 24.0), hotbar);
+    for (position, since, amount) in &damage.0 {
+        let risen = (now - since) * 40.0;
+        hud = hud.float_at(
+            Point::new(position.x, position.y - risen),
+            text(format!("-{amount}")).size(24),
+        );
+    }
+    ctx.display(hud);
+}
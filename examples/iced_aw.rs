@@ -0,0 +1,59 @@
+use bevy::prelude::*;
+use bevy_iced::iced::widget::{column, text};
+use bevy_iced::iced::Alignment;
+use bevy_iced::{IcedContext, IcedPlugin};
+use iced_aw::{number_input, TabBar, TabLabel};
+
+#[derive(Clone, Event)]
+enum UiMessage {
+    TabSelected(usize),
+    Amount(u32),
+}
+
+#[derive(Resource, Default)]
+pub struct UiData {
+    tab: usize,
+    amount: u32,
+}
+
+pub fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(IcedPlugin::default())
+        .add_event::<UiMessage>()
+        .init_resource::<UiData>()
+        .add_systems(Update, (update_data, ui_system))
+        .run();
+}
+
+fn update_data(mut messages: EventReader<UiMessage>, mut data: ResMut<UiData>) {
+    for msg in messages.read() {
+        match msg {
+            UiMessage::TabSelected(tab) => data.tab = *tab,
+            UiMessage::Amount(amount) => data.amount = *amount,
+        }
+    }
+}
+
+fn ui_system(mut ctx: IcedContext<UiMessage>, data: Res<UiData>) {
+    let tabs = ["Inventory", "Shop", "Settings"]
+        .into_iter()
+        .enumerate()
+        .fold(TabBar::new(UiMessage::TabSelected), |bar, (id, label)| {
+            bar.push(id, TabLabel::Text(label.to_owned()))
+        })
+        .set_active_tab(&data.tab);
+    let content = match data.tab {
+        0 => column![
+            text("Items to drop"),
+            number_input(data.amount, 99, UiMessage::Amount)
+        ],
+        1 => column![text(format!("Buying {} items", data.amount))],
+        _ => column![text("Nothing to configure")],
+    };
+    ctx.display(
+        column![tabs, content.spacing(10).align_items(Alignment::Center)]
+            .spacing(20)
+            .align_items(Alignment::Center),
+    );
+}
@@ -6,7 +6,7 @@ use bevy::{
 use bevy_iced::iced::{
     self,
     widget::{slider, text, text_input, Button, Column, Row},
-    Alignment, Style,
+    Alignment,
 };
 use bevy_iced::{IcedContext, IcedPlugin, IcedSettings};
 use rand::random as rng;
@@ -37,7 +37,7 @@ pub fn main() {
             ..Default::default()
         }))
         .add_plugins((
-            IcedPlugin::default(),
+            IcedPlugin::new(IcedSettings::default().with_theme(iced::Theme::Light)),
             FrameTimeDiagnosticsPlugin,
             LogDiagnosticsPlugin::default(),
         ))
@@ -47,13 +47,6 @@ pub fn main() {
             scale: 50.0,
             text: "Welcome to Iced!".to_owned(),
         })
-        .insert_resource(IcedSettings {
-            scale_factor: None,
-            theme: iced::Theme::Light,
-            style: Style {
-                text_color: iced::Color::from_rgb(0.0, 1.0, 1.0),
-            },
-        })
         .add_systems(Startup, build_program)
         .add_systems(
             Update,
@@ -116,7 +109,7 @@ fn update_scale_factor(
     for event in wheel.read() {
         let scale_factor =
             (iced_settings.scale_factor.unwrap_or(1.0) + (event.y / 10.0) as f64).max(1.0);
-        iced_settings.set_scale_factor(scale_factor);
+        iced_settings.scale_factor = Some(scale_factor);
     }
 }
 
@@ -0,0 +1,55 @@
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use bevy_iced::iced::widget::{button, column, slider, text};
+use bevy_iced::iced::Alignment;
+use bevy_iced::{IcedContext, IcedPlugin, IcedSettings};
+
+#[derive(Clone, Event)]
+enum UiMessage {
+    Scale(f64),
+    FollowWindow,
+}
+
+pub fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(IcedPlugin::default())
+        .add_event::<UiMessage>()
+        .add_systems(Update, (scale_system, ui_system))
+        .run();
+}
+
+// The UI is laid out again at the new scale on the next frame, without resizing the
+// window. Without a scale of its own, it follows the window's, e.g. when it's moved to a
+// monitor with another DPI.
+fn scale_system(mut messages: EventReader<UiMessage>, mut settings: ResMut<IcedSettings>) {
+    for msg in messages.read() {
+        match msg {
+            UiMessage::Scale(scale) => settings.scale_factor = Some(*scale),
+            UiMessage::FollowWindow => settings.scale_factor = None,
+        }
+    }
+}
+
+fn ui_system(
+    mut ctx: IcedContext<UiMessage>,
+    settings: Res<IcedSettings>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+) {
+    let window_scale = windows
+        .get_single()
+        .map_or(1.0, |window| f64::from(window.scale_factor()));
+    let scale = settings.scale_factor.unwrap_or(window_scale);
+    ctx.display(
+        column![
+            text(format!("UI scale: {scale:.2} (window: {window_scale:.2})")),
+            slider(0.5..=3.0, scale, UiMessage::Scale)
+                .step(0.05)
+                .width(300),
+            button("Follow the window").on_press(UiMessage::FollowWindow),
+        ]
+        .spacing(10)
+        .padding(20)
+        .align_items(Alignment::Center),
+    );
+}
@@ -0,0 +1,42 @@
+use bevy::prelude::*;
+use bevy_iced::iced::widget::{column, container, row, text};
+use bevy_iced::iced::{Alignment, Color, Theme};
+use bevy_iced::{IcedContext, IcedPlugin};
+
+#[derive(Event)]
+pub enum UiMessage {}
+
+const SWATCHES: [(u8, u8, u8); 4] = [
+    (0x33, 0x66, 0x99),
+    (0xcc, 0x33, 0x33),
+    (0x33, 0x99, 0x66),
+    (0x80, 0x80, 0x80),
+];
+
+pub fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(IcedPlugin::default())
+        .add_event::<UiMessage>()
+        .add_systems(Update, ui_system)
+        .run();
+}
+
+// Run this natively and in the browser, and compare the swatches with a color picker: with
+// the `web-colors` feature enabled on the wasm build, both read the values they're labeled
+// with. Without it, the browser's are lighter, as its framebuffer isn't sRGB.
+fn ui_system(mut ctx: IcedContext<UiMessage>) {
+    let swatches = SWATCHES.map(|(r, g, b)| {
+        let swatch = container(text(""))
+            .width(96)
+            .height(96)
+            .style(move |_: &Theme| container::Appearance {
+                background: Some(Color::from_rgb8(r, g, b).into()),
+                ..Default::default()
+            });
+        column![swatch, text(format!("#{r:02x}{g:02x}{b:02x}"))]
+            .align_items(Alignment::Center)
+            .into()
+    });
+    ctx.display(row(swatches).spacing(24).padding(24));
+}
@@ -0,0 +1,146 @@
+use bevy::prelude::*;
+use bevy::render::render_resource::{
+    Extent3d, TextureDescriptor, TextureDimension, TextureFormat, TextureUsages,
+};
+use bevy::window::PrimaryWindow;
+use bevy_iced::iced::widget::{button, column, text};
+use bevy_iced::iced::{Alignment, Length};
+use bevy_iced::{IcedContext, IcedPlugin, IcedTextureTarget};
+
+const SCREEN_SIZE: Vec2 = Vec2::new(4.0, 2.25);
+
+#[derive(Clone, Event)]
+enum UiMessage {
+    Reboot,
+}
+
+#[derive(Component)]
+struct Screen;
+
+#[derive(Resource, Default)]
+struct Reboots(u32);
+
+pub fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(IcedPlugin::default())
+        .add_event::<UiMessage>()
+        .init_resource::<Reboots>()
+        .add_systems(Startup, build_program)
+        .add_systems(Update, (cursor_system, reboot_system, ui_system).chain())
+        .run();
+}
+
+fn build_program(
+    mut commands: Commands,
+    mut images: ResMut<Assets<Image>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let size = Extent3d {
+        width: 640,
+        height: 360,
+        depth_or_array_layers: 1,
+    };
+    let mut image = Image {
+        texture_descriptor: TextureDescriptor {
+            label: None,
+            size,
+            dimension: TextureDimension::D2,
+            format: TextureFormat::Bgra8UnormSrgb,
+            mip_level_count: 1,
+            sample_count: 1,
+            usage: TextureUsages::TEXTURE_BINDING
+                | TextureUsages::COPY_DST
+                | TextureUsages::RENDER_ATTACHMENT,
+            view_formats: &[],
+        },
+        ..default()
+    };
+    image.resize(size);
+    let image = images.add(image);
+
+    commands.spawn((
+        Screen,
+        IcedTextureTarget::new(image.clone()),
+        PbrBundle {
+            mesh: meshes.add(Rectangle::from_size(SCREEN_SIZE)),
+            material: materials.add(StandardMaterial {
+                base_color_texture: Some(image),
+                unlit: true,
+                ..default()
+            }),
+            transform: Transform::from_rotation(Quat::from_rotation_y(0.4)),
+            ..default()
+        },
+    ));
+    commands.spawn(Camera3dBundle {
+        transform: Transform::from_xyz(0.0, 0.5, 5.0).looking_at(Vec3::ZERO, Vec3::Y),
+        ..default()
+    });
+}
+
+// Point at the screen with the mouse: the window's cursor is cast onto the screen's
+// quad, and where it hits is the cursor of the UI, in the image's pixels.
+fn cursor_system(
+    windows: Query<&Window, With<PrimaryWindow>>,
+    cameras: Query<(&Camera, &GlobalTransform)>,
+    mut screens: Query<(&GlobalTransform, &mut IcedTextureTarget), With<Screen>>,
+    images: Res<Assets<Image>>,
+) {
+    let (camera, camera_transform) = cameras.single();
+    let ray = windows
+        .get_single()
+        .ok()
+        .and_then(Window::cursor_position)
+        .and_then(|cursor| camera.viewport_to_world(camera_transform, cursor));
+    for (transform, mut target) in &mut screens {
+        let Some(image) = images.get(&target.image) else {
+            continue;
+        };
+        let normal = Direction3d::new(transform.back()).unwrap();
+        let cursor = ray.and_then(|ray| {
+            let distance = ray.intersect_plane(transform.translation(), Plane3d::new(*normal))?;
+            let local = transform
+                .affine()
+                .inverse()
+                .transform_point3(ray.get_point(distance));
+            let uv = Vec2::new(local.x, -local.y) / SCREEN_SIZE + 0.5;
+            let inside = uv.cmpge(Vec2::ZERO).all() && uv.cmple(Vec2::ONE).all();
+            inside.then(|| uv * image.size_f32() / target.scale_factor as f32)
+        });
+        if target.cursor != cursor {
+            target.cursor = cursor;
+        }
+    }
+}
+
+fn reboot_system(mut messages: EventReader<UiMessage>, mut reboots: ResMut<Reboots>) {
+    for msg in messages.read() {
+        match msg {
+            UiMessage::Reboot => reboots.0 += 1,
+        }
+    }
+}
+
+fn ui_system(
+    mut ctx: IcedContext<UiMessage>,
+    screens: Query<Entity, With<Screen>>,
+    reboots: Res<Reboots>,
+) {
+    for screen in &screens {
+        ctx.display_in_target(
+            column![
+                text("Mainframe").size(48),
+                text(format!("Rebooted {} times", reboots.0)),
+                button("Reboot").on_press(UiMessage::Reboot),
+            ]
+            .spacing(20)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .align_items(Alignment::Center)
+            .padding(40),
+            screen,
+        );
+    }
+}
@@ -0,0 +1,152 @@
+use bevy_ecs::change_detection::DetectChanges;
+use bevy_ecs::prelude::{Query, With};
+use bevy_ecs::system::{Res, Resource, SystemParam};
+use bevy_render::renderer::{RenderDevice, RenderQueue};
+use bevy_window::PrimaryWindow;
+use iced_core::text::Renderer as _;
+use iced_core::Color;
+use iced_widget::style::theme::Palette;
+use iced_widget::style::Theme;
+
+use crate::{iced, IcedRenderTarget, IcedResource, IcedSettings, IcedWindowSettings};
+
+/// Accessibility preferences that bevy_iced applies to every UI.
+///
+/// Bevy doesn't report the platform's contrast and motion preferences, so the
+/// application has to set them, e.g. from its own settings menu.
+#[derive(Clone, Debug, Resource)]
+pub struct IcedAccessibility {
+    /// Whether to render with [`IcedAccessibility::high_contrast_theme`] instead of
+    /// [`IcedSettings::theme`].
+    pub high_contrast: bool,
+    /// The theme used with `high_contrast`. Defaults to white and yellow on black, whose
+    /// contrast exceeds the WCAG AAA ratio of 7:1.
+    pub high_contrast_theme: Theme,
+    /// Whether [`IcedAnim`](crate::anim::IcedAnim)s and the built-in toasts should jump
+    /// to their targets instead of animating.
+    pub reduced_motion: bool,
+    /// The factor applied to the default text size, on top of
+    /// [`IcedSettings::text_scale`]. Text with an explicit size isn't scaled.
+    pub text_scale: f32,
+}
+
+impl Default for IcedAccessibility {
+    fn default() -> Self {
+        Self {
+            high_contrast: false,
+            high_contrast_theme: Theme::custom(
+                "High Contrast".to_owned(),
+                Palette {
+                    background: Color::BLACK,
+                    text: Color::WHITE,
+                    primary: Color::from_rgb8(0xFF, 0xD7, 0x00),
+                    success: Color::from_rgb8(0x4C, 0xFF, 0x4C),
+                    danger: Color::from_rgb8(0xFF, 0x70, 0x70),
+                },
+            ),
+            reduced_motion: false,
+            text_scale: 1.0,
+        }
+    }
+}
+
+/// The settings and accessibility preferences that decide how UIs are drawn.
+#[derive(SystemParam)]
+pub struct Appearance<'w, 's> {
+    settings: Res<'w, IcedSettings>,
+    accessibility: Res<'w, IcedAccessibility>,
+    target: Res<'w, IcedRenderTarget>,
+    window_settings: Query<'w, 's, &'static IcedWindowSettings, With<PrimaryWindow>>,
+}
+
+impl Appearance<'_, '_> {
+    pub fn settings(&self) -> &IcedSettings {
+        &self.settings
+    }
+
+    // The overrides of the window the UIs are presented to, none for a texture.
+    fn window_settings(&self) -> Option<&IcedWindowSettings> {
+        self.window_settings
+            .get_single()
+            .ok()
+            .filter(|_| *self.target == IcedRenderTarget::PrimaryWindow)
+    }
+
+    pub fn scale_factor(&self) -> Option<f64> {
+        self.window_settings()
+            .and_then(|window| window.scale_factor)
+            .or(self.settings.scale_factor)
+    }
+
+    // Whether the settings or preferences changed since the system last ran.
+    pub fn is_changed(&self) -> bool {
+        self.settings.is_changed() || self.accessibility.is_changed()
+    }
+
+    pub fn reduced_motion(&self) -> bool {
+        self.accessibility.reduced_motion
+    }
+
+    pub fn theme(&self) -> &Theme {
+        if self.accessibility.high_contrast {
+            &self.accessibility.high_contrast_theme
+        } else {
+            self.window_settings()
+                .and_then(|window| window.theme.as_ref())
+                .unwrap_or(&self.settings.theme)
+        }
+    }
+
+    // The theme of a UI with its own theme, unless high contrast is on.
+    pub fn theme_or<'a>(&'a self, theme: Option<&'a Theme>) -> &'a Theme {
+        match theme {
+            Some(theme) if !self.accessibility.high_contrast => theme,
+            _ => self.theme(),
+        }
+    }
+
+    // The style of a UI with its own style and theme, unless high contrast is on. Without
+    // a style, the text color is the one of the UI's theme.
+    pub fn style_or(&self, theme: Option<&Theme>, style: Option<iced::Style>) -> iced::Style {
+        let style = if self.accessibility.high_contrast {
+            None
+        } else {
+            style
+                .or_else(|| self.window_settings().and_then(|window| window.style))
+                .or(self.settings.style)
+        };
+        style.unwrap_or_else(|| iced::Style {
+            text_color: self.theme_or(theme).palette().text,
+        })
+    }
+
+    pub fn style(&self) -> iced::Style {
+        self.style_or(None, None)
+    }
+}
+
+// The default text size is baked into the renderer, so it's rebuilt when the size or
+// scale changes.
+pub fn apply_text_scale(
+    appearance: Appearance,
+    props: Res<IcedResource>,
+    device: Option<Res<RenderDevice>>,
+    queue: Option<Res<RenderQueue>>,
+) {
+    if !appearance.is_changed() {
+        return;
+    }
+    let props = &mut *props.lock().unwrap();
+    let settings = appearance.settings();
+    let scale = settings.text_scale * appearance.accessibility.text_scale;
+    let size = settings
+        .default_text_size
+        .unwrap_or(props.settings.default_text_size)
+        * scale.max(0.0);
+    if props.renderer.default_size() == size {
+        return;
+    }
+    let device = device.as_ref().map(|device| device.wgpu_device());
+    let queue = queue.as_ref().map(|queue| queue.as_ref().as_ref());
+    props.rebuild_renderer(device.zip(queue), size);
+}
@@ -0,0 +1,271 @@
+use std::marker::PhantomData;
+use std::time::Duration;
+
+use bevy_app::{App, Plugin, PreUpdate};
+use bevy_ecs::prelude::{Component, Entity, Event, EventWriter, Query};
+use bevy_ecs::system::{Res, ResMut, Resource};
+use bevy_time::Time;
+use iced_core::{Color, Padding, Point, Size, Vector};
+
+use crate::IcedAccessibility;
+
+/// A value that can be interpolated.
+pub trait Lerp: Clone + Send + Sync + 'static {
+    /// The value at `t` between `self` (at `0.0`) and `to` (at `1.0`).
+    fn lerp(&self, to: &Self, t: f32) -> Self;
+}
+
+impl Lerp for f32 {
+    fn lerp(&self, to: &Self, t: f32) -> Self {
+        self + (to - self) * t
+    }
+}
+
+impl Lerp for Color {
+    fn lerp(&self, to: &Self, t: f32) -> Self {
+        Color::from_rgba(
+            self.r.lerp(&to.r, t),
+            self.g.lerp(&to.g, t),
+            self.b.lerp(&to.b, t),
+            self.a.lerp(&to.a, t),
+        )
+    }
+}
+
+impl Lerp for Padding {
+    fn lerp(&self, to: &Self, t: f32) -> Self {
+        Padding {
+            top: self.top.lerp(&to.top, t),
+            right: self.right.lerp(&to.right, t),
+            bottom: self.bottom.lerp(&to.bottom, t),
+            left: self.left.lerp(&to.left, t),
+        }
+    }
+}
+
+impl Lerp for Point {
+    fn lerp(&self, to: &Self, t: f32) -> Self {
+        Point::new(self.x.lerp(&to.x, t), self.y.lerp(&to.y, t))
+    }
+}
+
+impl Lerp for Vector {
+    fn lerp(&self, to: &Self, t: f32) -> Self {
+        Vector::new(self.x.lerp(&to.x, t), self.y.lerp(&to.y, t))
+    }
+}
+
+impl Lerp for Size {
+    fn lerp(&self, to: &Self, t: f32) -> Self {
+        Size::new(
+            self.width.lerp(&to.width, t),
+            self.height.lerp(&to.height, t),
+        )
+    }
+}
+
+/// How an animation progresses over its duration.
+#[derive(Clone, Copy, Debug, Default)]
+pub enum Easing {
+    /// At a constant rate.
+    #[default]
+    Linear,
+    /// Starting slowly.
+    EaseIn,
+    /// Ending slowly.
+    EaseOut,
+    /// Starting and ending slowly.
+    EaseInOut,
+    /// Following a custom curve, mapping the elapsed fraction of the duration to the
+    /// progress of the animation, both from `0.0` to `1.0`.
+    Custom(fn(f32) -> f32),
+}
+
+impl Easing {
+    /// The progress of the animation after the given fraction of its duration.
+    pub fn apply(self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Easing::Linear => t,
+            Easing::EaseIn => t * t * t,
+            Easing::EaseOut => 1.0 - (1.0 - t).powi(3),
+            Easing::EaseInOut if t < 0.5 => 4.0 * t * t * t,
+            Easing::EaseInOut => 1.0 - (-2.0 * t + 2.0).powi(3) / 2.0,
+            Easing::Custom(curve) => curve(t),
+        }
+    }
+}
+
+/// A value animated towards a target, stored in a resource or component and read by
+/// UI systems to build their elements.
+///
+/// Add an [`IcedAnimPlugin`] for `T` to advance the animations with bevy's [`Time`].
+///
+/// ```
+/// # use std::time::Duration;
+/// # use bevy::prelude::*;
+/// # use bevy_iced::anim::{Easing, IcedAnim};
+/// # use bevy_iced::iced::widget::{container, text};
+/// # use bevy_iced::iced::Padding;
+/// # use bevy_iced::IcedContext;
+/// # #[derive(Event)]
+/// # enum UiMessage {}
+/// # fn panel() -> bevy_iced::iced::Element<'static, UiMessage> {
+/// #     text("Inventory").into()
+/// # }
+/// fn open_panel(mut offset: ResMut<IcedAnim<f32>>) {
+///     offset.animate_to(0.0, Duration::from_millis(300), Easing::EaseOut);
+/// }
+///
+/// fn ui_system(offset: Res<IcedAnim<f32>>, mut ctx: IcedContext<UiMessage>) {
+///     ctx.display(container(panel()).padding(Padding { left: *offset.get(), ..Padding::ZERO }));
+/// }
+/// # App::new().add_systems(Update, (open_panel, ui_system));
+/// ```
+#[derive(Resource, Component, Clone, Debug)]
+pub struct IcedAnim<T: Lerp> {
+    from: T,
+    to: T,
+    current: T,
+    elapsed: Duration,
+    duration: Duration,
+    easing: Easing,
+    animating: bool,
+    just_finished: bool,
+}
+
+impl<T: Lerp + Default> Default for IcedAnim<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<T: Lerp> IcedAnim<T> {
+    /// A value resting at `value`.
+    pub fn new(value: T) -> Self {
+        Self {
+            from: value.clone(),
+            to: value.clone(),
+            current: value,
+            elapsed: Duration::ZERO,
+            duration: Duration::ZERO,
+            easing: Easing::Linear,
+            animating: false,
+            just_finished: false,
+        }
+    }
+
+    /// The current value.
+    pub fn get(&self) -> &T {
+        &self.current
+    }
+
+    /// The value being animated to, or the current value when at rest.
+    pub fn target(&self) -> &T {
+        &self.to
+    }
+
+    /// Animate from the current value to `target`, replacing any running animation.
+    pub fn animate_to(&mut self, target: T, duration: Duration, easing: Easing) {
+        self.from = self.current.clone();
+        self.to = target;
+        self.elapsed = Duration::ZERO;
+        self.duration = duration;
+        self.easing = easing;
+        self.animating = true;
+    }
+
+    /// Jump to `value`, stopping any running animation without finishing it.
+    pub fn set(&mut self, value: T) {
+        self.from = value.clone();
+        self.to = value.clone();
+        self.current = value;
+        self.animating = false;
+    }
+
+    /// Whether an animation is running.
+    pub fn is_animating(&self) -> bool {
+        self.animating
+    }
+
+    /// Whether the animation finished this frame, e.g. to start the next transition.
+    pub fn just_finished(&self) -> bool {
+        self.just_finished
+    }
+
+    // Advance by `delta`, returning whether the animation finished.
+    fn advance(&mut self, delta: Duration) -> bool {
+        self.just_finished = false;
+        if !self.animating {
+            return false;
+        }
+        self.elapsed = self.elapsed.saturating_add(delta);
+        if self.elapsed >= self.duration {
+            self.current = self.to.clone();
+            self.animating = false;
+            self.just_finished = true;
+            return true;
+        }
+        let t = self.elapsed.as_secs_f32() / self.duration.as_secs_f32();
+        self.current = self.from.lerp(&self.to, self.easing.apply(t));
+        false
+    }
+}
+
+/// Sent when an [`IcedAnim<T>`] reaches its target.
+#[derive(Event, Clone, Debug)]
+pub struct IcedAnimFinished<T: Lerp> {
+    /// The entity of the animated component, or `None` for the [`IcedAnim<T>`] resource.
+    pub entity: Option<Entity>,
+    /// The value the animation finished at.
+    pub value: T,
+}
+
+/// Advances the [`IcedAnim<T>`] resource and components in [`PreUpdate`], so that UI
+/// systems see this frame's values.
+pub struct IcedAnimPlugin<T>(PhantomData<T>);
+
+impl<T> Default for IcedAnimPlugin<T> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<T: Lerp> Plugin for IcedAnimPlugin<T> {
+    fn build(&self, app: &mut App) {
+        app.add_event::<IcedAnimFinished<T>>()
+            .add_systems(PreUpdate, advance_animations::<T>);
+    }
+}
+
+fn advance_animations<T: Lerp>(
+    time: Option<Res<Time>>,
+    accessibility: Option<Res<IcedAccessibility>>,
+    resource: Option<ResMut<IcedAnim<T>>>,
+    mut components: Query<(Entity, &mut IcedAnim<T>)>,
+    mut finished: EventWriter<IcedAnimFinished<T>>,
+) {
+    let delta = if accessibility.is_some_and(|a| a.reduced_motion) {
+        Duration::MAX
+    } else {
+        time.map_or(Duration::ZERO, |time| time.delta())
+    };
+    // Only borrow the animations mutably when they change, to keep change detection useful.
+    let needs_update = |anim: &IcedAnim<T>| anim.animating || anim.just_finished;
+    if let Some(mut anim) = resource.filter(|anim| needs_update(anim)) {
+        if anim.advance(delta) {
+            finished.send(IcedAnimFinished {
+                entity: None,
+                value: anim.current.clone(),
+            });
+        }
+    }
+    for (entity, mut anim) in &mut components {
+        if needs_update(&anim) && anim.advance(delta) {
+            finished.send(IcedAnimFinished {
+                entity: Some(entity),
+                value: anim.current.clone(),
+            });
+        }
+    }
+}
@@ -0,0 +1,102 @@
+use std::collections::VecDeque;
+
+use bevy_a11y::accesskit::{Live, NodeBuilder, Role};
+use bevy_a11y::{AccessibilityNode, AccessibilityRequested};
+use bevy_ecs::prelude::{Commands, Entity, Query};
+use bevy_ecs::system::{Res, ResMut, Resource};
+
+/// The most announcements kept while no assistive technology is listening.
+const MAX_PENDING: usize = 16;
+
+/// How urgently an announcement from [`IcedContext::announce`](crate::IcedContext::announce)
+/// is read out.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum AnnouncePriority {
+    /// Read out once the screen reader is idle, e.g. "Item crafted".
+    #[default]
+    Polite,
+    /// Read out immediately, interrupting the screen reader, e.g. "Connection lost".
+    Assertive,
+}
+
+// A live region, whose name changes are read out by screen readers.
+struct Region {
+    entity: Entity,
+    // Toggled on every announcement, so that repeating a message still changes the name.
+    parity: bool,
+}
+
+#[derive(Resource, Default)]
+pub struct Announcements {
+    pending: VecDeque<(String, AnnouncePriority)>,
+    regions: Option<[Region; 2]>,
+}
+
+impl Announcements {
+    pub fn push(&mut self, text: String, priority: AnnouncePriority) {
+        if self.pending.len() == MAX_PENDING {
+            self.pending.pop_front();
+        }
+        self.pending.push_back((text, priority));
+    }
+}
+
+fn live_region(live: Live) -> AccessibilityNode {
+    let mut node = NodeBuilder::new(Role::Status);
+    node.set_live(live);
+    AccessibilityNode(node)
+}
+
+// Forward the pending announcements to the accessibility tree, one per priority and
+// frame, so that none replaces another before it's read out.
+pub fn update_live_regions(
+    mut commands: Commands,
+    mut announcements: ResMut<Announcements>,
+    requested: Option<Res<AccessibilityRequested>>,
+    mut nodes: Query<&mut AccessibilityNode>,
+) {
+    let Some(requested) = requested else {
+        // Without accessibility support, there's no one to announce to.
+        announcements.pending.clear();
+        return;
+    };
+    if !requested.get() || announcements.pending.is_empty() {
+        return;
+    }
+
+    let announcements = &mut *announcements;
+    let Some(regions) = &mut announcements.regions else {
+        // Announcements are made by changing the regions, so they have to exist first.
+        let mut spawn = |live| Region {
+            entity: commands.spawn(live_region(live)).id(),
+            parity: false,
+        };
+        announcements.regions = Some([spawn(Live::Polite), spawn(Live::Assertive)]);
+        return;
+    };
+
+    for (index, priority) in [AnnouncePriority::Polite, AnnouncePriority::Assertive]
+        .into_iter()
+        .enumerate()
+    {
+        let Some(position) = announcements
+            .pending
+            .iter()
+            .position(|(_, p)| *p == priority)
+        else {
+            continue;
+        };
+        let (text, _) = announcements.pending.remove(position).unwrap();
+        let region = &mut regions[index];
+        let Ok(mut node) = nodes.get_mut(region.entity) else {
+            continue;
+        };
+        region.parity = !region.parity;
+        let name = if region.parity {
+            text
+        } else {
+            format!("{text}\u{a0}")
+        };
+        node.set_name(name);
+    }
+}
@@ -0,0 +1,442 @@
+use bevy_ecs::system::Resource;
+use bevy_math::UVec2;
+use iced_core::{Color, Rectangle, Size};
+use iced_wgpu::wgpu;
+
+/// A frosted-glass effect: the scene behind the UI is blurred and tinted, e.g. behind
+/// the panel of a pause menu. See [`IcedContext::blur_backdrop`](crate::IcedContext::blur_backdrop).
+///
+/// The scene is read from the highest-order camera rendering to the primary window, so
+/// it doesn't include the output of other render graph nodes drawing to the window.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BackdropBlur {
+    /// The area to blur, in logical coordinates, or `None` for the whole window.
+    pub region: Option<Rectangle>,
+    /// The radius of the blur, in logical pixels.
+    pub radius: f32,
+    /// The color mixed into the blurred scene, by its alpha.
+    pub tint: Color,
+}
+
+impl Default for BackdropBlur {
+    fn default() -> Self {
+        Self {
+            region: None,
+            radius: 12.0,
+            tint: Color::from_rgba(0.0, 0.0, 0.0, 0.2),
+        }
+    }
+}
+
+impl BackdropBlur {
+    /// Blur the whole window with the given radius, in logical pixels.
+    pub fn new(radius: f32) -> Self {
+        Self {
+            radius,
+            ..Default::default()
+        }
+    }
+
+    /// Only blur the given area, in logical coordinates, e.g. the bounds of a panel.
+    pub fn region(mut self, region: Rectangle) -> Self {
+        self.region = Some(region);
+        self
+    }
+
+    /// Mix the given color into the blurred scene, by its alpha.
+    pub fn tint(mut self, tint: Color) -> Self {
+        self.tint = tint;
+        self
+    }
+}
+
+// The blurs requested along with the last UI drawn, and the scale factor it was drawn at.
+#[derive(Resource, Default)]
+pub struct ExtractedBackdrops {
+    pub blurs: Vec<BackdropBlur>,
+    pub scale_factor: f64,
+}
+
+const SHADER: &str = r"
+struct Params {
+    // The blur direction, in texels of the source.
+    direction: vec2<f32>,
+    radius: f32,
+    _padding: f32,
+    tint: vec4<f32>,
+    // Maps the target's coordinates to the source's.
+    uv_scale: vec2<f32>,
+    uv_offset: vec2<f32>,
+};
+
+@group(0) @binding(0) var source: texture_2d<f32>;
+@group(0) @binding(1) var source_sampler: sampler;
+@group(0) @binding(2) var<uniform> params: Params;
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+};
+
+// A single triangle covering the whole target.
+@vertex
+fn vs_main(@builtin(vertex_index) index: u32) -> VertexOutput {
+    let uv = vec2<f32>(f32((index << 1u) & 2u), f32(index & 2u));
+    var out: VertexOutput;
+    out.position = vec4<f32>(uv * vec2<f32>(2.0, -2.0) + vec2<f32>(-1.0, 1.0), 0.0, 1.0);
+    out.uv = uv;
+    return out;
+}
+
+// One direction of a separable gaussian, spreading at most 32 taps per side over the
+// radius.
+@fragment
+fn fs_blur(in: VertexOutput) -> @location(0) vec4<f32> {
+    let uv = in.uv * params.uv_scale + params.uv_offset;
+    let texel = params.direction / vec2<f32>(textureDimensions(source));
+    let sigma = max(params.radius / 2.0, 0.5);
+    let taps = i32(min(ceil(params.radius), 32.0));
+    let stride = max(params.radius / 32.0, 1.0);
+    var sum = vec4<f32>(0.0);
+    var total = 0.0;
+    for (var i = -taps; i <= taps; i++) {
+        let offset = f32(i) * stride;
+        let weight = exp(-offset * offset / (2.0 * sigma * sigma));
+        sum += textureSampleLevel(source, source_sampler, uv + texel * offset, 0.0) * weight;
+        total += weight;
+    }
+    return sum / total;
+}
+
+@fragment
+fn fs_composite(in: VertexOutput) -> @location(0) vec4<f32> {
+    let color = textureSampleLevel(source, source_sampler, in.uv, 0.0);
+    return vec4<f32>(mix(color.rgb, params.tint.rgb, params.tint.a), 1.0);
+}
+";
+
+// Uniform offsets must be aligned to this.
+const PARAMS_STRIDE: u64 = 256;
+
+// A region to blur, in physical pixels of the window.
+#[derive(Clone, Copy)]
+struct Region {
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
+}
+
+struct Targets {
+    size: Size<u32>,
+    // The horizontal pass, then the vertical one.
+    views: [wgpu::TextureView; 2],
+}
+
+/// Blurs the scene behind the UI, before the UI is presented over it: a horizontal and
+/// a vertical pass into intermediate textures, then a tinted composite onto the surface,
+/// all restricted to the requested regions.
+pub struct Backdrop {
+    format: wgpu::TextureFormat,
+    blur: wgpu::RenderPipeline,
+    composite: wgpu::RenderPipeline,
+    bind_group_layout: wgpu::BindGroupLayout,
+    sampler: wgpu::Sampler,
+    params: Option<(wgpu::Buffer, u64)>,
+    targets: Option<Targets>,
+}
+
+/// Where the scene of the camera rendering to the window is found.
+pub struct Scene<'a> {
+    pub view: &'a wgpu::TextureView,
+    /// The camera's viewport within the window, in physical pixels.
+    pub position: UVec2,
+    pub size: UVec2,
+}
+
+impl Backdrop {
+    pub fn new(device: &wgpu::Device, format: wgpu::TextureFormat) -> Self {
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("bevy_iced.backdrop.shader"),
+            source: wgpu::ShaderSource::Wgsl(SHADER.into()),
+        });
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("bevy_iced.backdrop.bind_group_layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: true,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+            ],
+        });
+        let layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("bevy_iced.backdrop.pipeline_layout"),
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+        let pipeline = |label, entry_point| {
+            device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                label: Some(label),
+                layout: Some(&layout),
+                vertex: wgpu::VertexState {
+                    module: &shader,
+                    entry_point: "vs_main",
+                    buffers: &[],
+                },
+                fragment: Some(wgpu::FragmentState {
+                    module: &shader,
+                    entry_point,
+                    targets: &[Some(wgpu::ColorTargetState {
+                        format,
+                        blend: None,
+                        write_mask: wgpu::ColorWrites::ALL,
+                    })],
+                }),
+                primitive: wgpu::PrimitiveState::default(),
+                depth_stencil: None,
+                multisample: wgpu::MultisampleState::default(),
+                multiview: None,
+            })
+        };
+        let blur = pipeline("bevy_iced.backdrop.blur_pipeline", "fs_blur");
+        let composite = pipeline("bevy_iced.backdrop.composite_pipeline", "fs_composite");
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("bevy_iced.backdrop.sampler"),
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+
+        Self {
+            format,
+            blur,
+            composite,
+            bind_group_layout,
+            sampler,
+            params: None,
+            targets: None,
+        }
+    }
+
+    pub fn format(&self) -> wgpu::TextureFormat {
+        self.format
+    }
+
+    fn resize_targets(&mut self, device: &wgpu::Device, size: Size<u32>) {
+        if self.targets.as_ref().map(|targets| targets.size) != Some(size) {
+            let view = || {
+                device
+                    .create_texture(&wgpu::TextureDescriptor {
+                        label: Some("bevy_iced.backdrop.texture"),
+                        size: wgpu::Extent3d {
+                            width: size.width,
+                            height: size.height,
+                            depth_or_array_layers: 1,
+                        },
+                        mip_level_count: 1,
+                        sample_count: 1,
+                        dimension: wgpu::TextureDimension::D2,
+                        format: self.format,
+                        usage: wgpu::TextureUsages::RENDER_ATTACHMENT
+                            | wgpu::TextureUsages::TEXTURE_BINDING,
+                        view_formats: &[],
+                    })
+                    .create_view(&wgpu::TextureViewDescriptor::default())
+            };
+            self.targets = Some(Targets {
+                size,
+                views: [view(), view()],
+            });
+        }
+    }
+
+    fn reserve_params(&mut self, device: &wgpu::Device, size: u64) {
+        if self
+            .params
+            .as_ref()
+            .is_none_or(|(_, capacity)| *capacity < size)
+        {
+            let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some("bevy_iced.backdrop.params"),
+                size,
+                usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            });
+            self.params = Some((buffer, size));
+        }
+    }
+
+    fn bind_group(
+        &self,
+        device: &wgpu::Device,
+        view: &wgpu::TextureView,
+        params: &wgpu::Buffer,
+    ) -> wgpu::BindGroup {
+        device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("bevy_iced.backdrop.bind_group"),
+            layout: &self.bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::Sampler(&self.sampler),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: wgpu::BindingResource::Buffer(wgpu::BufferBinding {
+                        buffer: params,
+                        offset: 0,
+                        size: wgpu::BufferSize::new(48),
+                    }),
+                },
+            ],
+        })
+    }
+
+    /// Blur the scene into the regions of `blurs` on `view`, whose size is `size`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn draw(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        encoder: &mut wgpu::CommandEncoder,
+        view: &wgpu::TextureView,
+        size: Size<u32>,
+        scene: Scene<'_>,
+        backdrops: &ExtractedBackdrops,
+    ) {
+        let scale = backdrops.scale_factor as f32;
+        let window = Rectangle::with_size(Size::new(size.width as f32, size.height as f32));
+        let regions: Vec<_> = backdrops
+            .blurs
+            .iter()
+            .filter_map(|blur| {
+                let radius = blur.radius.max(0.0) * scale;
+                let bounds = blur.region.map_or(window, |region| region * scale);
+                let bounds = bounds.intersection(&window)?.snap();
+                // Snapping rounds the size up, which may overflow the window.
+                let region = Region {
+                    x: bounds.x,
+                    y: bounds.y,
+                    width: bounds.width.min(size.width.saturating_sub(bounds.x)),
+                    height: bounds.height.min(size.height.saturating_sub(bounds.y)),
+                };
+                (region.width > 0 && region.height > 0).then_some((region, radius, blur.tint))
+            })
+            .collect();
+        if regions.is_empty() {
+            return;
+        }
+
+        // The horizontal pass, the vertical pass and the composite of every region.
+        let window_size = [size.width as f32, size.height as f32];
+        let uv_scale = [
+            window_size[0] / scene.size.x.max(1) as f32,
+            window_size[1] / scene.size.y.max(1) as f32,
+        ];
+        let uv_offset = [
+            -(scene.position.x as f32) / scene.size.x.max(1) as f32,
+            -(scene.position.y as f32) / scene.size.y.max(1) as f32,
+        ];
+        let mut params = vec![0; regions.len() * 3 * PARAMS_STRIDE as usize];
+        for (index, (_, radius, tint)) in regions.iter().enumerate() {
+            let passes = [
+                ([1.0, 0.0], uv_scale, uv_offset),
+                ([0.0, 1.0], [1.0, 1.0], [0.0, 0.0]),
+                ([0.0, 0.0], [1.0, 1.0], [0.0, 0.0]),
+            ];
+            for (pass, (direction, uv_scale, uv_offset)) in passes.into_iter().enumerate() {
+                let values = [
+                    direction[0],
+                    direction[1],
+                    *radius,
+                    0.0,
+                    tint.r,
+                    tint.g,
+                    tint.b,
+                    tint.a,
+                    uv_scale[0],
+                    uv_scale[1],
+                    uv_offset[0],
+                    uv_offset[1],
+                ];
+                let start = (index * 3 + pass) * PARAMS_STRIDE as usize;
+                for (i, value) in values.iter().enumerate() {
+                    params[start + i * 4..start + i * 4 + 4].copy_from_slice(&value.to_ne_bytes());
+                }
+            }
+        }
+        self.reserve_params(device, params.len() as u64);
+        self.resize_targets(device, size);
+        let (params_buffer, _) = self.params.as_ref().unwrap();
+        queue.write_buffer(params_buffer, 0, &params);
+
+        let [horizontal, vertical] = &self.targets.as_ref().unwrap().views;
+        let bind_groups = [
+            self.bind_group(device, scene.view, params_buffer),
+            self.bind_group(device, horizontal, params_buffer),
+            self.bind_group(device, vertical, params_buffer),
+        ];
+        let passes = [
+            (horizontal, &self.blur),
+            (vertical, &self.blur),
+            (view, &self.composite),
+        ];
+        for (pass_index, (target, pipeline)) in passes.into_iter().enumerate() {
+            let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("bevy_iced.backdrop.pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: target,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Load,
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+            pass.set_pipeline(pipeline);
+            for (index, (region, radius, _)) in regions.iter().enumerate() {
+                // The vertical pass reads the horizontal one above and below the region.
+                let spread = if pass_index == 0 {
+                    radius.ceil() as u32
+                } else {
+                    0
+                };
+                let top = region.y.saturating_sub(spread);
+                let bottom = (region.y + region.height + spread).min(size.height);
+                pass.set_scissor_rect(region.x, top, region.width, bottom - top);
+                let offset = ((index * 3 + pass_index) as u64 * PARAMS_STRIDE) as u32;
+                pass.set_bind_group(0, &bind_groups[pass_index], &[offset]);
+                pass.draw(0..3, 0..1);
+            }
+        }
+    }
+}
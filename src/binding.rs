@@ -0,0 +1,531 @@
+use std::any::TypeId;
+use std::borrow::Borrow;
+use std::ops::RangeInclusive;
+use std::sync::{Arc, Mutex};
+
+use bevy_ecs::component::Component;
+use bevy_ecs::entity::Entity;
+use bevy_ecs::system::{Res, Resource, SystemParam};
+use bevy_ecs::world::World;
+use iced_core::event::{self, Event};
+use iced_core::layout::{self, Layout};
+use iced_core::mouse;
+use iced_core::overlay;
+use iced_core::renderer;
+use iced_core::widget::operation::{Focusable, Scrollable, TextInput};
+use iced_core::widget::{tree, Id, Operation, Tree};
+use iced_core::{Clipboard, Element, Length, Point, Rectangle, Shell, Size, Vector, Widget};
+use iced_widget::style::Theme;
+use iced_widget::{checkbox, pick_list, slider, text_input, Slider};
+
+use crate::{utils, Renderer};
+
+type Write = Box<dyn FnOnce(&mut World) + Send>;
+type Set<V> = Arc<dyn Fn(&mut World, V) + Send + Sync>;
+
+// The field a binding writes to, so that later writes to it replace earlier ones.
+#[derive(Clone, PartialEq)]
+struct Key {
+    target: TypeId,
+    entity: Option<Entity>,
+    field: Field,
+}
+
+#[derive(Clone, PartialEq)]
+enum Field {
+    // The address of the lens, which is the same for every binding made by the same code.
+    Lens(usize),
+    #[cfg(feature = "reflect-ui")]
+    Path(String),
+}
+
+// The writes of the bound widgets, applied after the frame's systems.
+#[derive(Resource, Clone, Default)]
+pub struct BindingQueue(Arc<Mutex<Vec<(Key, Write)>>>);
+
+impl BindingQueue {
+    fn push(&self, key: Key, write: Write) {
+        let mut writes = self.0.lock().unwrap();
+        writes.retain(|(queued, _)| *queued != key);
+        writes.push((key, write));
+    }
+}
+
+// Apply the writes in PostUpdate, once the systems handling this frame's messages ran,
+// so that the UI built next frame shows the new values.
+pub fn apply_bindings(world: &mut World) {
+    let writes = std::mem::take(&mut *world.resource::<BindingQueue>().0.lock().unwrap());
+    for (_, write) in writes {
+        write(world);
+    }
+}
+
+/// Binds widgets to fields of resources and components, so that editing a setting
+/// doesn't need a message, a variant for it and a system applying it.
+///
+/// A [`Binding`] reads the current value of the field when the UI is built. The values
+/// its widgets produce are written back in `PostUpdate`, after this frame's messages were
+/// handled; when a field is written several times in a frame, the last write wins. The
+/// fields are only written through [`Mut`](bevy_ecs::world::Mut), so change detection
+/// sees them. Writes to a removed resource or a despawned entity are dropped.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_iced::iced::widget::{button, column};
+/// # use bevy_iced::{IcedBindings, IcedContext};
+/// # #[derive(Event, Clone)]
+/// # enum UiMessage {
+/// #     Close,
+/// # }
+/// # #[derive(Resource)]
+/// # struct AudioSettings {
+/// #     volume: f32,
+/// #     muted: bool,
+/// # }
+/// fn ui_system(
+///     mut ctx: IcedContext<UiMessage>,
+///     bindings: IcedBindings,
+///     audio: Res<AudioSettings>,
+/// ) {
+///     let volume = bindings.resource(&*audio, |a| &a.volume, |a| &mut a.volume);
+///     let muted = bindings.resource(&*audio, |a| &a.muted, |a| &mut a.muted);
+///     ctx.display(column![
+///         volume.slider(0.0..=1.0),
+///         muted.checkbox("Muted"),
+///         button("Done").on_press(UiMessage::Close),
+///     ]);
+/// }
+/// # App::new().add_systems(Update, ui_system);
+/// ```
+#[derive(SystemParam)]
+pub struct IcedBindings<'w> {
+    queue: Res<'w, BindingQueue>,
+}
+
+impl IcedBindings<'_> {
+    /// Bind the field of `resource` selected by `field` and `field_mut`.
+    pub fn resource<R: Resource, V: Clone + Send + 'static>(
+        &self,
+        resource: &R,
+        field: fn(&R) -> &V,
+        field_mut: fn(&mut R) -> &mut V,
+    ) -> Binding<V> {
+        Binding {
+            value: field(resource).clone(),
+            key: Key {
+                target: TypeId::of::<R>(),
+                entity: None,
+                field: Field::Lens(field_mut as usize),
+            },
+            queue: self.queue.clone(),
+            set: Arc::new(move |world, value| {
+                if let Some(mut resource) = world.get_resource_mut::<R>() {
+                    *field_mut(&mut resource) = value;
+                }
+            }),
+        }
+    }
+
+    /// Bind the field of the `component` of `entity` selected by `field` and `field_mut`.
+    pub fn component<C: Component, V: Clone + Send + 'static>(
+        &self,
+        entity: Entity,
+        component: &C,
+        field: fn(&C) -> &V,
+        field_mut: fn(&mut C) -> &mut V,
+    ) -> Binding<V> {
+        Binding {
+            value: field(component).clone(),
+            key: Key {
+                target: TypeId::of::<C>(),
+                entity: Some(entity),
+                field: Field::Lens(field_mut as usize),
+            },
+            queue: self.queue.clone(),
+            set: Arc::new(move |world, value| {
+                if let Some(mut component) = world.get_mut::<C>(entity) {
+                    *field_mut(&mut component) = value;
+                }
+            }),
+        }
+    }
+
+    /// Bind the field of `resource` at the reflection `path`, e.g. `".audio.volume"`, or
+    /// `None` if there's no `V` at the path. Requires the `reflect-ui` feature.
+    #[cfg(feature = "reflect-ui")]
+    pub fn resource_path<R, V>(&self, resource: &R, path: &str) -> Option<Binding<V>>
+    where
+        R: Resource + bevy_reflect::Reflect,
+        V: bevy_reflect::Reflect + Clone,
+    {
+        use bevy_reflect::GetPath;
+
+        let value = resource.path::<V>(path).ok()?.clone();
+        let owned = path.to_owned();
+        Some(Binding {
+            value,
+            key: Key {
+                target: TypeId::of::<R>(),
+                entity: None,
+                field: Field::Path(owned.clone()),
+            },
+            queue: self.queue.clone(),
+            set: Arc::new(move |world, value| {
+                if let Some(mut resource) = world.get_resource_mut::<R>() {
+                    if let Ok(field) = resource.path_mut::<V>(owned.as_str()) {
+                        *field = value;
+                    }
+                }
+            }),
+        })
+    }
+}
+
+/// The current value of a field and the means to write it back, see [`IcedBindings`].
+///
+/// The bound widgets are built by [`slider`](Binding::slider),
+/// [`checkbox`](Binding::checkbox), [`text_input`](Binding::text_input) and
+/// [`pick_list`](Binding::pick_list), or by [`bind`](Binding::bind) to style them or to
+/// bind other widgets.
+#[allow(missing_debug_implementations)]
+pub struct Binding<V> {
+    value: V,
+    key: Key,
+    queue: BindingQueue,
+    set: Set<V>,
+}
+
+impl<V: Send + 'static> Binding<V> {
+    /// The value of the field when the binding was made.
+    pub fn value(&self) -> &V {
+        &self.value
+    }
+
+    /// Write the values `content` produces as messages to the field, e.g. to style a
+    /// bound slider:
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_iced::iced::widget::slider;
+    /// # use bevy_iced::{IcedBindings, IcedContext};
+    /// # #[derive(Event)]
+    /// # enum UiMessage {}
+    /// # #[derive(Resource)]
+    /// # struct AudioSettings {
+    /// #     volume: f32,
+    /// # }
+    /// # fn ui_system(
+    /// #     mut ctx: IcedContext<UiMessage>,
+    /// #     bindings: IcedBindings,
+    /// #     audio: Res<AudioSettings>,
+    /// # ) {
+    /// # let volume = bindings.resource(&*audio, |a| &a.volume, |a| &mut a.volume);
+    /// let value = *volume.value();
+    /// ctx.display(volume.bind(slider(0.0..=1.0, value, |volume| volume).step(0.05)));
+    /// # }
+    /// ```
+    pub fn bind<'a, M: 'a>(
+        self,
+        content: impl Into<Element<'a, V, Theme, Renderer>>,
+    ) -> Element<'a, M, Theme, Renderer>
+    where
+        V: 'a,
+    {
+        Element::new(Bound {
+            content: content.into(),
+            writer: Arc::new(Writer {
+                key: self.key,
+                queue: self.queue,
+                set: self.set,
+            }),
+        })
+    }
+}
+
+impl<V> Binding<V>
+where
+    V: Copy + From<u8> + PartialOrd + Send + 'static,
+    for<'b> Slider<'b, V, V>: Into<Element<'b, V, Theme, Renderer>>,
+{
+    /// A slider over `range` bound to the field.
+    pub fn slider<'a, M: 'a>(self, range: RangeInclusive<V>) -> Element<'a, M, Theme, Renderer> {
+        let value = self.value;
+        self.bind(slider(range, value, |value| value))
+    }
+}
+
+impl Binding<bool> {
+    /// A checkbox labelled `label` bound to the field.
+    pub fn checkbox<'a, M: 'a>(self, label: impl Into<String>) -> Element<'a, M, Theme, Renderer> {
+        let value = self.value;
+        self.bind(checkbox(label, value).on_toggle(|value| value))
+    }
+}
+
+impl Binding<String> {
+    /// A text input showing `placeholder` when empty, bound to the field.
+    pub fn text_input<'a, M: 'a>(self, placeholder: &str) -> Element<'a, M, Theme, Renderer> {
+        let input = text_input(placeholder, &self.value).on_input(|value| value);
+        self.bind(input)
+    }
+}
+
+impl<V> Binding<V>
+where
+    V: ToString + PartialEq + Clone + Send + 'static,
+{
+    /// A pick list of `options` bound to the field.
+    pub fn pick_list<'a, M: 'a>(
+        self,
+        options: impl Borrow<[V]> + 'a,
+    ) -> Element<'a, M, Theme, Renderer> {
+        let selected = self.value.clone();
+        self.bind(pick_list(options, Some(selected), |value| value))
+    }
+}
+
+struct Writer<V> {
+    key: Key,
+    queue: BindingQueue,
+    set: Set<V>,
+}
+
+impl<V: Send + 'static> Writer<V> {
+    // Run `f` with a shell of its own, queueing the values it publishes as writes instead
+    // of publishing them to the UI's shell.
+    fn capture<M, R>(&self, shell: &mut Shell<'_, M>, f: impl FnOnce(&mut Shell<'_, V>) -> R) -> R {
+        let (result, values) = utils::capture(shell, f);
+        for value in values {
+            let set = self.set.clone();
+            self.queue
+                .push(self.key.clone(), Box::new(move |world| set(world, value)));
+        }
+        result
+    }
+}
+
+// A bound widget, which publishes no messages to the UI.
+struct Bound<'a, V> {
+    content: Element<'a, V, Theme, Renderer>,
+    writer: Arc<Writer<V>>,
+}
+
+impl<'a, V: Send + 'static, M> Widget<M, Theme, Renderer> for Bound<'a, V> {
+    fn tag(&self) -> tree::Tag {
+        self.content.as_widget().tag()
+    }
+
+    fn state(&self) -> tree::State {
+        self.content.as_widget().state()
+    }
+
+    fn children(&self) -> Vec<Tree> {
+        self.content.as_widget().children()
+    }
+
+    fn diff(&self, tree: &mut Tree) {
+        self.content.as_widget().diff(tree);
+    }
+
+    fn size(&self) -> Size<Length> {
+        self.content.as_widget().size()
+    }
+
+    fn layout(
+        &self,
+        tree: &mut Tree,
+        renderer: &Renderer,
+        limits: &layout::Limits,
+    ) -> layout::Node {
+        self.content.as_widget().layout(tree, renderer, limits)
+    }
+
+    fn operate(
+        &self,
+        tree: &mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        operation: &mut dyn Operation<M>,
+    ) {
+        self.content
+            .as_widget()
+            .operate(tree, layout, renderer, &mut Forward(operation));
+    }
+
+    fn on_event(
+        &mut self,
+        tree: &mut Tree,
+        event: Event,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, M>,
+        viewport: &Rectangle,
+    ) -> event::Status {
+        let content = &mut self.content;
+        self.writer.capture(shell, |shell| {
+            content.as_widget_mut().on_event(
+                tree, event, layout, cursor, renderer, clipboard, shell, viewport,
+            )
+        })
+    }
+
+    fn mouse_interaction(
+        &self,
+        tree: &Tree,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+        renderer: &Renderer,
+    ) -> mouse::Interaction {
+        self.content
+            .as_widget()
+            .mouse_interaction(tree, layout, cursor, viewport, renderer)
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+    ) {
+        self.content
+            .as_widget()
+            .draw(tree, renderer, theme, style, layout, cursor, viewport);
+    }
+
+    fn overlay<'b>(
+        &'b mut self,
+        tree: &'b mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        translation: Vector,
+    ) -> Option<overlay::Element<'b, M, Theme, Renderer>> {
+        let overlay = self
+            .content
+            .as_widget_mut()
+            .overlay(tree, layout, renderer, translation)?;
+        Some(overlay::Element::new(Box::new(BoundOverlay {
+            content: overlay,
+            writer: self.writer.clone(),
+        })))
+    }
+}
+
+// The overlay of a bound widget, e.g. the menu of a pick list.
+struct BoundOverlay<'a, V> {
+    content: overlay::Element<'a, V, Theme, Renderer>,
+    writer: Arc<Writer<V>>,
+}
+
+impl<'a, V: Send + 'static, M> overlay::Overlay<M, Theme, Renderer> for BoundOverlay<'a, V> {
+    fn layout(&mut self, renderer: &Renderer, bounds: Size) -> layout::Node {
+        self.content.layout(renderer, bounds)
+    }
+
+    fn draw(
+        &self,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+    ) {
+        self.content.draw(renderer, theme, style, layout, cursor);
+    }
+
+    fn operate(
+        &mut self,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        operation: &mut dyn Operation<M>,
+    ) {
+        self.content
+            .operate(layout, renderer, &mut Forward(operation));
+    }
+
+    fn on_event(
+        &mut self,
+        event: Event,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, M>,
+    ) -> event::Status {
+        let content = &mut self.content;
+        self.writer.capture(shell, |shell| {
+            content.on_event(event, layout, cursor, renderer, clipboard, shell)
+        })
+    }
+
+    fn mouse_interaction(
+        &self,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+        renderer: &Renderer,
+    ) -> mouse::Interaction {
+        self.content
+            .mouse_interaction(layout, cursor, viewport, renderer)
+    }
+
+    fn is_over(&self, layout: Layout<'_>, renderer: &Renderer, cursor_position: Point) -> bool {
+        self.content.is_over(layout, renderer, cursor_position)
+    }
+
+    fn overlay<'b>(
+        &'b mut self,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+    ) -> Option<overlay::Element<'b, M, Theme, Renderer>> {
+        let overlay = self.content.overlay(layout, renderer)?;
+        Some(overlay::Element::new(Box::new(BoundOverlay {
+            content: overlay,
+            writer: self.writer.clone(),
+        })))
+    }
+}
+
+// Pass the UI's operations, e.g. focusing and scrolling, on to the bound widget.
+struct Forward<'a, M>(&'a mut dyn Operation<M>);
+
+impl<V, M> Operation<V> for Forward<'_, M> {
+    fn container(
+        &mut self,
+        id: Option<&Id>,
+        bounds: Rectangle,
+        operate_on_children: &mut dyn FnMut(&mut dyn Operation<V>),
+    ) {
+        self.0.container(id, bounds, &mut |operation| {
+            operate_on_children(&mut Forward(operation));
+        });
+    }
+
+    fn focusable(&mut self, state: &mut dyn Focusable, id: Option<&Id>) {
+        self.0.focusable(state, id);
+    }
+
+    fn scrollable(
+        &mut self,
+        state: &mut dyn Scrollable,
+        id: Option<&Id>,
+        bounds: Rectangle,
+        translation: Vector,
+    ) {
+        self.0.scrollable(state, id, bounds, translation);
+    }
+
+    fn text_input(&mut self, state: &mut dyn TextInput, id: Option<&Id>) {
+        self.0.text_input(state, id);
+    }
+
+    fn custom(&mut self, state: &mut dyn std::any::Any, id: Option<&Id>) {
+        self.0.custom(state, id);
+    }
+}
@@ -0,0 +1,108 @@
+use std::any::{Any, TypeId};
+use std::time::Duration;
+
+use bevy_diagnostic::{DiagnosticPath, Diagnostics};
+use bevy_ecs::system::{NonSendMut, Res};
+use bevy_utils::HashMap;
+use iced_runtime::user_interface::Cache;
+
+use crate::{IcedPlugin, IcedSettings};
+
+impl IcedPlugin {
+    /// The diagnostic counting the UIs whose widget state is kept, one per message type
+    /// and display in a frame.
+    pub const CACHED_UIS: DiagnosticPath = DiagnosticPath::const_new("iced/cached_uis");
+    /// The diagnostic measuring the time spent laying out, updating and drawing the UIs
+    /// of a frame, in milliseconds.
+    pub const UI_TIME: DiagnosticPath = DiagnosticPath::const_new("iced/ui_time");
+}
+
+struct Entry {
+    cache: Option<Cache>,
+    // The frame the UI was last displayed in.
+    last_used: u64,
+}
+
+// The widget state of every UI, by message type and the order it's displayed in within a
+// frame. Shaped text is kept in the state of its widget, so UIs of the same message type
+// must not share an entry, or each would reshape all of its text against the other's.
+#[derive(Default)]
+pub struct IcedCache {
+    entries: HashMap<(TypeId, usize), Entry>,
+    // The number of UIs displayed so far this frame, by message type.
+    displayed: HashMap<TypeId, usize>,
+    frame: u64,
+    spent: Duration,
+}
+
+impl IcedCache {
+    pub fn get<M: Any>(&mut self) -> &mut Option<Cache> {
+        let displayed = self.displayed.entry(TypeId::of::<M>()).or_default();
+        let key = (TypeId::of::<M>(), *displayed);
+        *displayed += 1;
+        self.entry(key)
+    }
+
+    // The widget state of the context menu of `M`, kept apart from its UIs so that opening
+    // the menu doesn't shift them to other entries.
+    pub fn get_context_menu<M: Any>(&mut self) -> &mut Option<Cache> {
+        self.entry((TypeId::of::<M>(), usize::MAX))
+    }
+
+    fn entry(&mut self, key: (TypeId, usize)) -> &mut Option<Cache> {
+        let frame = self.frame;
+        let entry = self.entries.entry(key).or_insert_with(|| Entry {
+            cache: Some(Default::default()),
+            last_used: frame,
+        });
+        entry.last_used = frame;
+        &mut entry.cache
+    }
+
+    // The number of UIs kept.
+    #[cfg(feature = "debug")]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    // The UIs kept for each message type, and how many frames ago one was last displayed.
+    #[cfg(feature = "debug")]
+    pub fn summary(&self) -> impl Iterator<Item = (TypeId, usize, u64)> + '_ {
+        let mut summary = HashMap::<TypeId, (usize, u64)>::new();
+        for ((type_id, _), entry) in &self.entries {
+            let (count, last_used) = summary.entry(*type_id).or_default();
+            *count += 1;
+            *last_used = (*last_used).max(entry.last_used);
+        }
+        let frame = self.frame;
+        summary
+            .into_iter()
+            .map(move |(type_id, (count, last_used))| (type_id, count, frame - last_used))
+    }
+
+    // Record the time a UI took to display.
+    pub fn spend(&mut self, duration: Duration) {
+        self.spent += duration;
+    }
+}
+
+// Drop the state of the UIs that weren't displayed for a while, which then start afresh
+// the next time they are.
+pub fn evict_unused(
+    mut cache: NonSendMut<IcedCache>,
+    settings: Res<IcedSettings>,
+    mut diagnostics: Diagnostics,
+) {
+    let cache = &mut *cache;
+    if let Some(frames) = settings.cache_eviction_frames {
+        let frame = cache.frame;
+        cache
+            .entries
+            .retain(|_, entry| frame - entry.last_used < u64::from(frames.max(1)));
+    }
+    cache.frame += 1;
+    cache.displayed.clear();
+    diagnostics.add_measurement(&IcedPlugin::CACHED_UIS, || cache.entries.len() as f64);
+    let spent = std::mem::take(&mut cache.spent);
+    diagnostics.add_measurement(&IcedPlugin::UI_TIME, || spent.as_secs_f64() * 1000.0);
+}
@@ -0,0 +1,195 @@
+use std::collections::HashSet;
+
+use bevy_ecs::event::Events;
+use bevy_ecs::prelude::{Query, With};
+use bevy_ecs::system::{Res, ResMut, Resource};
+use bevy_input::keyboard::KeyCode;
+use bevy_input::mouse::{MouseButton, MouseWheel};
+use bevy_input::touch::Touches;
+use bevy_input::ButtonInput;
+use bevy_window::{PrimaryWindow, Window};
+use iced_core::event::Status;
+use iced_core::mouse::{self, Cursor};
+use iced_core::{touch, Event as IcedEvent};
+
+use crate::render::ViewportResource;
+use crate::{
+    utils, IcedCursorOverride, IcedEventQueue, IcedModal, IcedRenderTarget, IcedResource,
+    IcedSettings, IcedWantsKeyboard,
+};
+
+/// Whether the UI takes this frame's pointer and keyboard input, so that game systems,
+/// like picking or dragging the camera, can leave it alone instead of handling a click
+/// on a button too.
+///
+/// It's updated in [`IcedSet::ProcessInput`](crate::IcedSet::ProcessInput), before
+/// `Update`, from the UIs displayed last frame: the pointer is captured while it's over
+/// one of their widgets handling it, like buttons, sliders, text inputs and scrollables,
+/// but not over text or containers, even with an id, so that a UI filling the window
+/// leaves the game the pointer around its buttons. The keyboard is captured while one of
+/// their widgets has the focus, like [`IcedWantsKeyboard`]. A press keeps the pointer as
+/// it was when it started until it's released, so dragging a slider out of the UI stays
+/// captured, and dragging the camera over the UI doesn't. While a modal UI
+/// [`is_open`](IcedModal::is_open), both are captured.
+///
+/// Game systems in `Update` thus see the status of this frame's input, judged against
+/// last frame's UIs. In `PostUpdate`, both are also set when this frame's UIs captured
+/// pointer or keyboard events, e.g. over widgets not counted above, like a
+/// `mouse_area`.
+///
+/// See [`IcedSettings::block_captured_input`] to keep the captured input from the game
+/// altogether.
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct IcedEventCaptureStatus {
+    /// Whether the UI takes the mouse buttons, the wheel and touches.
+    pub pointer_captured: bool,
+    /// Whether the UI takes the keys.
+    pub keyboard_captured: bool,
+}
+
+// The input the UIs saw, from the events fed to them.
+#[derive(Default)]
+pub struct Capture {
+    // Whether the pointer is over a widget taking it in the UIs displayed last frame.
+    pub pointer_over: bool,
+    // Whether the press being held started over the UIs, or was captured by them.
+    held: Option<bool>,
+    buttons: HashSet<mouse::Button>,
+    fingers: HashSet<touch::Finger>,
+    // Whether the UIs displayed this frame captured pointer and keyboard events.
+    pointer_events: bool,
+    keyboard_events: bool,
+}
+
+impl Capture {
+    // Record what a UI did with the events it was fed.
+    pub fn record(&mut self, events: &[IcedEvent], statuses: &[Status]) {
+        for (event, status) in events.iter().zip(statuses) {
+            if *status != Status::Captured {
+                continue;
+            }
+            match event {
+                IcedEvent::Mouse(_) | IcedEvent::Touch(_) => self.pointer_events = true,
+                IcedEvent::Keyboard(_) => self.keyboard_events = true,
+                IcedEvent::Window(..) => {}
+            }
+        }
+    }
+
+    // Follow the buttons and fingers held down through this frame's events.
+    fn track_presses(&mut self, events: &[IcedEvent]) {
+        for event in events {
+            match event {
+                IcedEvent::Mouse(mouse::Event::ButtonPressed(button)) => {
+                    self.buttons.insert(*button);
+                }
+                IcedEvent::Mouse(mouse::Event::ButtonReleased(button)) => {
+                    self.buttons.remove(button);
+                }
+                IcedEvent::Touch(touch::Event::FingerPressed { id, .. }) => {
+                    self.fingers.insert(*id);
+                }
+                IcedEvent::Touch(
+                    touch::Event::FingerLifted { id, .. } | touch::Event::FingerLost { id, .. },
+                ) => {
+                    self.fingers.remove(id);
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+// Judge this frame's input against the UIs displayed last frame. Presses are followed
+// through the events rather than `ButtonInput`, which `block_captured_input` resets.
+#[allow(clippy::too_many_arguments)]
+pub fn update_status(
+    props: Res<IcedResource>,
+    settings: Res<IcedSettings>,
+    viewport: Res<ViewportResource>,
+    target: Res<IcedRenderTarget>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    cursor_override: Res<IcedCursorOverride>,
+    touches: Res<Touches>,
+    events: Res<IcedEventQueue>,
+    modal: Res<IcedModal>,
+    wants_keyboard: Res<IcedWantsKeyboard>,
+    mut status: ResMut<IcedEventCaptureStatus>,
+) {
+    let props = &mut *props.lock().unwrap();
+    // The window's cursor doesn't point at a UI presented to a texture.
+    let window = windows
+        .get_single()
+        .ok()
+        .filter(|_| *target == IcedRenderTarget::PrimaryWindow);
+    let cursor = props.replayed_cursor.unwrap_or_else(|| {
+        utils::cursor(
+            cursor_override.0,
+            settings.touch_input.then_some(&*touches),
+            &events,
+            &viewport,
+            window,
+        )
+    });
+    let over = match cursor {
+        Cursor::Available(position) => props.hit_targets.captures(position),
+        Cursor::Unavailable => false,
+    };
+    let capture = &mut props.capture;
+    capture.pointer_over = over;
+    capture.track_presses(&events);
+    let pointer = if capture.buttons.is_empty() && capture.fingers.is_empty() {
+        capture.held = None;
+        over
+    } else {
+        *capture.held.get_or_insert(over)
+    };
+    let next = IcedEventCaptureStatus {
+        pointer_captured: pointer || modal.is_open(),
+        keyboard_captured: **wants_keyboard || modal.is_open(),
+    };
+    if *status != next {
+        *status = next;
+    }
+}
+
+// Add what this frame's UIs captured, once they've all been displayed.
+pub fn finish_frame(props: Res<IcedResource>, mut status: ResMut<IcedEventCaptureStatus>) {
+    let capture = &mut props.lock().unwrap().capture;
+    let pointer = std::mem::take(&mut capture.pointer_events);
+    let keyboard = std::mem::take(&mut capture.keyboard_events);
+    if let Some(held) = &mut capture.held {
+        *held |= pointer;
+    }
+    let next = IcedEventCaptureStatus {
+        pointer_captured: status.pointer_captured || pointer,
+        keyboard_captured: status.keyboard_captured || keyboard,
+    };
+    if *status != next {
+        *status = next;
+    }
+}
+
+// Keep the input captured by the UI from the game, with
+// `IcedSettings::block_captured_input`. Resetting the keys doesn't lose the modifiers the
+// UI sees, which are followed from the keyboard events in `HeldKeys`.
+pub fn block_captured_input(
+    settings: Res<IcedSettings>,
+    status: Res<IcedEventCaptureStatus>,
+    mouse_buttons: Option<ResMut<ButtonInput<MouseButton>>>,
+    mut keys: ResMut<ButtonInput<KeyCode>>,
+    mut wheel: ResMut<Events<MouseWheel>>,
+) {
+    if !settings.block_captured_input {
+        return;
+    }
+    if status.pointer_captured {
+        if let Some(mut mouse_buttons) = mouse_buttons {
+            mouse_buttons.reset_all();
+        }
+        wheel.clear();
+    }
+    if status.keyboard_captured {
+        keys.reset_all();
+    }
+}
@@ -0,0 +1,97 @@
+use iced_core::clipboard::Kind;
+
+#[cfg(all(feature = "clipboard", not(target_arch = "wasm32")))]
+use bevy_ecs::prelude::{Query, With};
+#[cfg(all(feature = "clipboard", not(target_arch = "wasm32")))]
+use bevy_ecs::system::NonSendMut;
+#[cfg(all(feature = "clipboard", not(target_arch = "wasm32")))]
+use bevy_utils::tracing::warn;
+#[cfg(all(feature = "clipboard", not(target_arch = "wasm32")))]
+use bevy_window::{PrimaryWindow, RawHandleWrapper};
+
+// The system clipboard, connected through the display of the primary window once it's
+// created. Without the `clipboard` feature, and on the web, where there's no synchronous
+// clipboard, it's always empty and ignores writes, like Iced's `Null` clipboard.
+#[derive(Default)]
+pub struct IcedClipboard {
+    #[cfg(all(feature = "clipboard", not(target_arch = "wasm32")))]
+    state: State,
+}
+
+#[cfg(all(feature = "clipboard", not(target_arch = "wasm32")))]
+#[derive(Default)]
+enum State {
+    #[default]
+    Disconnected,
+    Connected(window_clipboard::Clipboard),
+    // Connecting failed, and isn't tried again for the same window.
+    Unavailable,
+}
+
+#[cfg(all(feature = "clipboard", not(target_arch = "wasm32")))]
+impl iced_core::Clipboard for IcedClipboard {
+    fn read(&self, kind: Kind) -> Option<String> {
+        let State::Connected(clipboard) = &self.state else {
+            return None;
+        };
+        match kind {
+            Kind::Standard => clipboard.read().ok(),
+            Kind::Primary => clipboard.read_primary().and_then(Result::ok),
+        }
+    }
+
+    fn write(&mut self, kind: Kind, contents: String) {
+        let State::Connected(clipboard) = &mut self.state else {
+            return;
+        };
+        let result = match kind {
+            Kind::Standard => clipboard.write(contents),
+            Kind::Primary => match clipboard.write_primary(contents) {
+                Some(result) => result,
+                None => return,
+            },
+        };
+        if let Err(err) = result {
+            warn!("Failed to write to the clipboard: {err}");
+        }
+    }
+}
+
+#[cfg(not(all(feature = "clipboard", not(target_arch = "wasm32"))))]
+impl iced_core::Clipboard for IcedClipboard {
+    fn read(&self, _kind: Kind) -> Option<String> {
+        None
+    }
+
+    fn write(&mut self, _kind: Kind, _contents: String) {}
+}
+
+// Connect to the clipboard once the primary window is created, and let go of it when the
+// window is closed, as it may use the window's display.
+#[cfg(all(feature = "clipboard", not(target_arch = "wasm32")))]
+pub fn connect(
+    windows: Query<&RawHandleWrapper, With<PrimaryWindow>>,
+    mut clipboard: NonSendMut<IcedClipboard>,
+) {
+    let Ok(handle) = windows.get_single() else {
+        if !matches!(clipboard.state, State::Disconnected) {
+            clipboard.state = State::Disconnected;
+        }
+        return;
+    };
+    if !matches!(clipboard.state, State::Disconnected) {
+        return;
+    }
+    // SAFETY: this system runs on the main thread, as it takes a non-send resource, which
+    // the handle must only be used from. The clipboard is dropped before the display is
+    // closed, along with the window.
+    #[allow(unsafe_code)]
+    let connected = unsafe { window_clipboard::Clipboard::connect(&handle.get_handle()) };
+    clipboard.state = match connected {
+        Ok(connected) => State::Connected(connected),
+        Err(err) => {
+            warn!("Failed to connect to the clipboard, copying and pasting won't work: {err}");
+            State::Unavailable
+        }
+    };
+}
@@ -0,0 +1,597 @@
+use iced_core::event::{self, Event};
+use iced_core::gradient::Linear;
+use iced_core::layout::{self, Layout};
+use iced_core::mouse;
+use iced_core::renderer::{self, Quad, Renderer as _};
+use iced_core::touch;
+use iced_core::widget::{tree, Operation, Tree};
+use iced_core::{
+    Border, Clipboard, Color, Degrees, Element, Gradient, Length, Point, Rectangle, Shell, Size,
+    Widget,
+};
+use iced_widget::style::Theme;
+use iced_widget::text_input::{self, TextInput};
+
+use crate::{utils, Renderer};
+
+const SPACING: f32 = 8.0;
+const STRIP_HEIGHT: f32 = 16.0;
+const CHECKER_SIZE: f32 = 8.0;
+
+/// A color picker, with a saturation and value square, a hue strip, an alpha strip and a
+/// hex field, see [`color_picker`]. Requires the `color-picker` feature.
+#[allow(missing_debug_implementations)]
+pub struct ColorPicker<'a, Message> {
+    color: Color,
+    width: f32,
+    on_change: Box<dyn Fn(Color) -> Message + 'a>,
+    on_commit: Option<Box<dyn Fn(Color) -> Message + 'a>>,
+}
+
+/// Pick a color, starting from `color`, producing the message of `on_change` for every
+/// change, e.g. continuously while dragging. Requires the `color-picker` feature.
+///
+/// The hex field takes `#RRGGBB` and `#RRGGBBAA` codes, typed or pasted, and changes the
+/// color as soon as the code is complete. Use [`ColorPicker::on_commit`] to learn when a
+/// drag is released or the code is submitted, e.g. to record undo steps. Bevy colors are
+/// converted with [`conversions::color`](crate::conversions::color) and
+/// [`conversions::bevy_color`](crate::conversions::bevy_color):
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_iced::conversions;
+/// # use bevy_iced::widget::color_picker;
+/// # use bevy_iced::IcedContext;
+/// # #[derive(Event, Clone)]
+/// # enum UiMessage {
+/// #     Tint(Color),
+/// #     SaveTint,
+/// # }
+/// # fn ui_system(mut ctx: IcedContext<UiMessage>, sprite: &Sprite) {
+/// ctx.display(
+///     color_picker(conversions::color(sprite.color), |color| {
+///         UiMessage::Tint(conversions::bevy_color(color))
+///     })
+///     .on_commit(|_| UiMessage::SaveTint),
+/// );
+/// # }
+/// ```
+pub fn color_picker<'a, Message>(
+    color: Color,
+    on_change: impl Fn(Color) -> Message + 'a,
+) -> ColorPicker<'a, Message> {
+    ColorPicker {
+        color,
+        width: 200.0,
+        on_change: Box::new(on_change),
+        on_commit: None,
+    }
+}
+
+impl<'a, Message> ColorPicker<'a, Message> {
+    /// Set the width of the picker, 200 by default.
+    #[must_use]
+    pub fn width(mut self, width: f32) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// Produce the message of `on_commit` when a drag is released or a hex code is
+    /// submitted with Enter.
+    #[must_use]
+    pub fn on_commit(mut self, on_commit: impl Fn(Color) -> Message + 'a) -> Self {
+        self.on_commit = Some(Box::new(on_commit));
+        self
+    }
+
+    fn parts(&self, bounds: Rectangle) -> [Rectangle; 3] {
+        let square = Rectangle::new(bounds.position(), Size::new(self.width, self.width * 0.75));
+        let strip =
+            |y: f32| Rectangle::new(Point::new(bounds.x, y), Size::new(self.width, STRIP_HEIGHT));
+        let hue = strip(square.y + square.height + SPACING);
+        let alpha = strip(hue.y + hue.height + SPACING);
+        [square, hue, alpha]
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Part {
+    Square,
+    Hue,
+    Alpha,
+}
+
+#[derive(Clone)]
+enum Edit {
+    Input(String),
+    Submit,
+}
+
+// The hex field, rebuilt from the code being typed whenever it's needed.
+fn hex_input(hex: &str) -> TextInput<'_, Edit, Theme, Renderer> {
+    TextInput::new("#RRGGBBAA", hex)
+        .on_input(Edit::Input)
+        .on_submit(Edit::Submit)
+        .size(14)
+        .padding(4)
+}
+
+type InputState = text_input::State<<Renderer as iced_core::text::Renderer>::Paragraph>;
+
+// A color as hue, from 0 to 360 degrees, saturation, value and alpha.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+struct Hsva {
+    h: f32,
+    s: f32,
+    v: f32,
+    a: f32,
+}
+
+impl Hsva {
+    fn from_color(color: Color) -> Self {
+        let max = color.r.max(color.g).max(color.b);
+        let min = color.r.min(color.g).min(color.b);
+        let delta = max - min;
+        let h = if delta == 0.0 {
+            0.0
+        } else if max == color.r {
+            60.0 * ((color.g - color.b) / delta).rem_euclid(6.0)
+        } else if max == color.g {
+            60.0 * ((color.b - color.r) / delta + 2.0)
+        } else {
+            60.0 * ((color.r - color.g) / delta + 4.0)
+        };
+        Self {
+            h,
+            s: if max == 0.0 { 0.0 } else { delta / max },
+            v: max,
+            a: color.a,
+        }
+    }
+
+    fn to_color(self) -> Color {
+        let c = self.v * self.s;
+        let h = self.h.rem_euclid(360.0) / 60.0;
+        let x = c * (1.0 - (h.rem_euclid(2.0) - 1.0).abs());
+        let (r, g, b) = match h as u8 {
+            0 => (c, x, 0.0),
+            1 => (x, c, 0.0),
+            2 => (0.0, c, x),
+            3 => (0.0, x, c),
+            4 => (x, 0.0, c),
+            _ => (c, 0.0, x),
+        };
+        let m = self.v - c;
+        Color::from_rgba(r + m, g + m, b + m, self.a)
+    }
+}
+
+fn to_hex(color: Color) -> String {
+    let [r, g, b, a] = color.into_rgba8();
+    if a == u8::MAX {
+        format!("#{r:02X}{g:02X}{b:02X}")
+    } else {
+        format!("#{r:02X}{g:02X}{b:02X}{a:02X}")
+    }
+}
+
+fn parse_hex(hex: &str) -> Option<Color> {
+    let hex = hex.trim().trim_start_matches('#');
+    if !matches!(hex.len(), 6 | 8) || !hex.is_ascii() {
+        return None;
+    }
+    let channel = |i: usize| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok();
+    let alpha = if hex.len() == 8 { channel(6)? } else { u8::MAX };
+    Some(Color::from_rgba8(
+        channel(0)?,
+        channel(2)?,
+        channel(4)?,
+        f32::from(alpha) / 255.0,
+    ))
+}
+
+#[derive(Default)]
+struct State {
+    // The color as hue, saturation and value, which keeps the hue and saturation while
+    // the color is black or gray, and the color it was last taken from or changed to.
+    hsva: Hsva,
+    color: Option<Color>,
+    dragging: Option<Part>,
+    // The hex code being typed, while the field is focused.
+    hex: String,
+}
+
+impl State {
+    fn hsva(&self, color: Color) -> Hsva {
+        if self.color == Some(color) {
+            self.hsva
+        } else {
+            Hsva::from_color(color)
+        }
+    }
+
+    fn set(&mut self, hsva: Hsva) -> Color {
+        let color = hsva.to_color();
+        self.hsva = hsva;
+        self.color = Some(color);
+        color
+    }
+}
+
+impl<'a, Message> Widget<Message, Theme, Renderer> for ColorPicker<'a, Message> {
+    fn tag(&self) -> tree::Tag {
+        tree::Tag::of::<State>()
+    }
+
+    fn state(&self) -> tree::State {
+        tree::State::new(State::default())
+    }
+
+    fn children(&self) -> Vec<Tree> {
+        vec![Tree::new(&hex_input("") as &dyn Widget<_, _, _>)]
+    }
+
+    fn diff(&self, tree: &mut Tree) {
+        tree.children[0].diff(&hex_input("") as &dyn Widget<_, _, _>);
+    }
+
+    fn size(&self) -> Size<Length> {
+        Size::new(Length::Fixed(self.width), Length::Shrink)
+    }
+
+    fn layout(
+        &self,
+        tree: &mut Tree,
+        renderer: &Renderer,
+        limits: &layout::Limits,
+    ) -> layout::Node {
+        let [_, _, alpha] = self.parts(Rectangle::with_size(Size::ZERO));
+        let top = alpha.y + alpha.height + SPACING;
+        let state = tree.state.downcast_ref::<State>();
+        let input = Widget::<Edit, Theme, Renderer>::layout(
+            &hex_input(&state.hex),
+            &mut tree.children[0],
+            renderer,
+            &layout::Limits::new(Size::ZERO, Size::new(self.width, f32::INFINITY)),
+        );
+        let height = top + input.size().height;
+        let size = limits.resolve(
+            Length::Fixed(self.width),
+            Length::Shrink,
+            Size::new(self.width, height),
+        );
+        layout::Node::with_children(size, vec![input.move_to(Point::new(0.0, top))])
+    }
+
+    fn operate(
+        &self,
+        tree: &mut Tree,
+        _layout: Layout<'_>,
+        _renderer: &Renderer,
+        operation: &mut dyn Operation<Message>,
+    ) {
+        // The field can be focused like any other text input, e.g. with Tab.
+        let input = tree.children[0].state.downcast_mut::<InputState>();
+        operation.focusable(input, None);
+    }
+
+    fn on_event(
+        &mut self,
+        tree: &mut Tree,
+        event: Event,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+        viewport: &Rectangle,
+    ) -> event::Status {
+        let Tree {
+            state, children, ..
+        } = tree;
+        let state = state.downcast_mut::<State>();
+        let mut hsva = state.hsva(self.color);
+        // The field shows the color until it's focused, then the code being typed.
+        if !children[0].state.downcast_ref::<InputState>().is_focused() {
+            state.hex = to_hex(self.color);
+        }
+
+        let input_layout = layout.children().next().unwrap();
+        let (status, edits) = utils::capture(shell, |input_shell| {
+            Widget::<Edit, Theme, Renderer>::on_event(
+                &mut hex_input(&state.hex),
+                &mut children[0],
+                event.clone(),
+                input_layout,
+                cursor,
+                renderer,
+                clipboard,
+                input_shell,
+                viewport,
+            )
+        });
+        for edit in edits {
+            match edit {
+                Edit::Input(hex) => {
+                    state.hex = hex;
+                    if let Some(color) = parse_hex(&state.hex) {
+                        hsva = Hsva::from_color(color);
+                        shell.publish((self.on_change)(state.set(hsva)));
+                    }
+                }
+                Edit::Submit => {
+                    if let (Some(color), Some(on_commit)) = (parse_hex(&state.hex), &self.on_commit)
+                    {
+                        shell.publish(on_commit(color));
+                    }
+                }
+            }
+        }
+        if status == event::Status::Captured {
+            return status;
+        }
+
+        let [square, hue, alpha] = self.parts(layout.bounds());
+        let position = match event {
+            Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left))
+            | Event::Touch(touch::Event::FingerPressed { .. }) => {
+                let position = match event {
+                    Event::Touch(touch::Event::FingerPressed { position, .. }) => position,
+                    _ => match cursor.position() {
+                        Some(position) => position,
+                        None => return event::Status::Ignored,
+                    },
+                };
+                state.dragging = [
+                    (square, Part::Square),
+                    (hue, Part::Hue),
+                    (alpha, Part::Alpha),
+                ]
+                .into_iter()
+                .find(|(bounds, _)| bounds.contains(position))
+                .map(|(_, part)| part);
+                position
+            }
+            Event::Mouse(mouse::Event::CursorMoved { position })
+            | Event::Touch(touch::Event::FingerMoved { position, .. })
+                if state.dragging.is_some() =>
+            {
+                position
+            }
+            Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left))
+            | Event::Touch(touch::Event::FingerLifted { .. })
+            | Event::Touch(touch::Event::FingerLost { .. }) => {
+                if state.dragging.take().is_none() {
+                    return event::Status::Ignored;
+                }
+                if let Some(on_commit) = &self.on_commit {
+                    shell.publish(on_commit(hsva.to_color()));
+                }
+                return event::Status::Captured;
+            }
+            _ => return event::Status::Ignored,
+        };
+
+        let fraction = |bounds: Rectangle| {
+            (
+                ((position.x - bounds.x) / bounds.width).clamp(0.0, 1.0),
+                ((position.y - bounds.y) / bounds.height).clamp(0.0, 1.0),
+            )
+        };
+        match state.dragging {
+            Some(Part::Square) => {
+                let (x, y) = fraction(square);
+                hsva.s = x;
+                hsva.v = 1.0 - y;
+            }
+            Some(Part::Hue) => hsva.h = fraction(hue).0 * 360.0,
+            Some(Part::Alpha) => hsva.a = fraction(alpha).0,
+            None => return event::Status::Ignored,
+        }
+        shell.publish((self.on_change)(state.set(hsva)));
+        event::Status::Captured
+    }
+
+    fn mouse_interaction(
+        &self,
+        tree: &Tree,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+        renderer: &Renderer,
+    ) -> mouse::Interaction {
+        let state = tree.state.downcast_ref::<State>();
+        let [square, hue, alpha] = self.parts(layout.bounds());
+        match state.dragging {
+            Some(Part::Square) => return mouse::Interaction::Crosshair,
+            Some(_) => return mouse::Interaction::Grabbing,
+            None => {}
+        }
+        if cursor.is_over(square) {
+            mouse::Interaction::Crosshair
+        } else if cursor.is_over(hue) || cursor.is_over(alpha) {
+            mouse::Interaction::Pointer
+        } else {
+            Widget::<Edit, Theme, Renderer>::mouse_interaction(
+                &hex_input(&state.hex),
+                &tree.children[0],
+                layout.children().next().unwrap(),
+                cursor,
+                viewport,
+                renderer,
+            )
+        }
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+    ) {
+        let state = tree.state.downcast_ref::<State>();
+        let hsva = state.hsva(self.color);
+        let color = hsva.to_color();
+        let [square, hue, alpha] = self.parts(layout.bounds());
+        let radius = Border::with_radius(2);
+        let quad = |bounds| Quad {
+            bounds,
+            border: radius,
+            ..Quad::default()
+        };
+        let gradient = |angle: f32, stops: &[Color]| {
+            let last = (stops.len() - 1) as f32;
+            let linear = stops
+                .iter()
+                .enumerate()
+                .fold(Linear::new(Degrees(angle)), |linear, (i, stop)| {
+                    linear.add_stop(i as f32 / last, *stop)
+                });
+            Gradient::Linear(linear)
+        };
+
+        // The square blends the pure hue with white to the left and black to the bottom.
+        let pure = Hsva {
+            s: 1.0,
+            v: 1.0,
+            a: 1.0,
+            ..hsva
+        }
+        .to_color();
+        renderer.fill_quad(quad(square), pure);
+        renderer.fill_quad(
+            quad(square),
+            gradient(90.0, &[Color::WHITE, Color::TRANSPARENT]),
+        );
+        renderer.fill_quad(
+            quad(square),
+            gradient(180.0, &[Color::TRANSPARENT, Color::BLACK]),
+        );
+
+        let hues: Vec<_> = (0..=6)
+            .map(|i| {
+                Hsva {
+                    h: i as f32 * 60.0,
+                    s: 1.0,
+                    v: 1.0,
+                    a: 1.0,
+                }
+                .to_color()
+            })
+            .collect();
+        renderer.fill_quad(quad(hue), gradient(90.0, &hues));
+
+        // The alpha strip fades the color in over a checkerboard.
+        renderer.with_layer(alpha, |renderer| {
+            let columns = (alpha.width / CHECKER_SIZE).ceil() as usize;
+            let rows = (alpha.height / CHECKER_SIZE).ceil() as usize;
+            for (column, row) in (0..columns).flat_map(|c| (0..rows).map(move |r| (c, r))) {
+                let shade = if (column + row) % 2 == 0 { 0.8 } else { 0.6 };
+                let cell = Rectangle::new(
+                    Point::new(
+                        alpha.x + column as f32 * CHECKER_SIZE,
+                        alpha.y + row as f32 * CHECKER_SIZE,
+                    ),
+                    Size::new(CHECKER_SIZE, CHECKER_SIZE),
+                );
+                renderer.fill_quad(
+                    Quad {
+                        bounds: cell,
+                        ..Quad::default()
+                    },
+                    Color::from_rgb(shade, shade, shade),
+                );
+            }
+        });
+        let opaque = Color { a: 1.0, ..color };
+        renderer.fill_quad(
+            quad(alpha),
+            gradient(90.0, &[Color { a: 0.0, ..opaque }, opaque]),
+        );
+
+        // The markers, outlined in white and black to stand out on any color.
+        let marker = |renderer: &mut Renderer, bounds: Rectangle, radius: f32| {
+            renderer.fill_quad(
+                Quad {
+                    bounds: bounds.expand(1.0),
+                    border: Border {
+                        color: Color::BLACK,
+                        width: 1.0,
+                        radius: (radius + 1.0).into(),
+                    },
+                    ..Quad::default()
+                },
+                Color::TRANSPARENT,
+            );
+            renderer.fill_quad(
+                Quad {
+                    bounds,
+                    border: Border {
+                        color: Color::WHITE,
+                        width: 2.0,
+                        radius: radius.into(),
+                    },
+                    ..Quad::default()
+                },
+                Color::TRANSPARENT,
+            );
+        };
+        let at = |x: f32, y: f32, size: Size| {
+            Rectangle::new(
+                Point::new(x - size.width / 2.0, y - size.height / 2.0),
+                size,
+            )
+        };
+        let dot = Size::new(12.0, 12.0);
+        let bar = Size::new(6.0, STRIP_HEIGHT + 4.0);
+        marker(
+            renderer,
+            at(
+                square.x + hsva.s * square.width,
+                square.y + (1.0 - hsva.v) * square.height,
+                dot,
+            ),
+            6.0,
+        );
+        marker(
+            renderer,
+            at(hue.x + hsva.h / 360.0 * hue.width, hue.center_y(), bar),
+            2.0,
+        );
+        marker(
+            renderer,
+            at(alpha.x + hsva.a * alpha.width, alpha.center_y(), bar),
+            2.0,
+        );
+
+        let focused = tree.children[0]
+            .state
+            .downcast_ref::<InputState>()
+            .is_focused();
+        let hex = if focused {
+            state.hex.clone()
+        } else {
+            to_hex(color)
+        };
+        Widget::<Edit, Theme, Renderer>::draw(
+            &hex_input(&hex),
+            &tree.children[0],
+            renderer,
+            theme,
+            style,
+            layout.children().next().unwrap(),
+            cursor,
+            viewport,
+        );
+    }
+}
+
+impl<'a, Message: 'a> From<ColorPicker<'a, Message>> for Element<'a, Message, Theme, Renderer> {
+    fn from(picker: ColorPicker<'a, Message>) -> Self {
+        Element::new(picker)
+    }
+}
@@ -0,0 +1,125 @@
+use std::any::{Any, TypeId};
+use std::sync::mpsc::{self, Receiver, Sender};
+
+use bevy_ecs::event::Event;
+use bevy_ecs::world::World;
+use bevy_tasks::futures_lite::StreamExt;
+use bevy_tasks::{AsyncComputeTaskPool, TaskPool};
+use bevy_utils::HashMap;
+use iced_core::widget::operation::{Operation, Outcome};
+use iced_runtime::futures::{BoxFuture, BoxStream};
+use iced_runtime::user_interface::UserInterface;
+use iced_widget::style::Theme;
+
+use crate::{IcedResource, Renderer};
+
+// The widget operations of the commands run by `IcedContext::run_command`, by message
+// type, until a UI of that type is displayed.
+#[derive(Default)]
+pub struct Operations {
+    pending: HashMap<TypeId, Box<dyn Any>>,
+}
+
+impl Operations {
+    pub fn push<M: 'static>(&mut self, operation: Box<dyn Operation<M>>) {
+        self.pending
+            .entry(TypeId::of::<M>())
+            .or_insert_with(|| Box::new(Vec::<Box<dyn Operation<M>>>::new()))
+            .downcast_mut::<Vec<Box<dyn Operation<M>>>>()
+            .unwrap()
+            .push(operation);
+    }
+
+    pub fn take<M: 'static>(&mut self) -> Vec<Box<dyn Operation<M>>> {
+        self.pending
+            .remove(&TypeId::of::<M>())
+            .and_then(|operations| operations.downcast().ok())
+            .map(|operations| *operations)
+            .unwrap_or_default()
+    }
+}
+
+// Run the operations on a UI, with the ones they chain to, like Iced's runtime does.
+pub fn apply<M>(
+    operations: Vec<Box<dyn Operation<M>>>,
+    ui: &mut UserInterface<'_, M, Theme, Renderer>,
+    renderer: &Renderer,
+    messages: &mut Vec<M>,
+) {
+    for operation in operations {
+        let mut current = Some(operation);
+        while let Some(mut operation) = current.take() {
+            ui.operate(renderer, operation.as_mut());
+            match operation.finish() {
+                Outcome::None => {}
+                Outcome::Some(message) => messages.push(message),
+                Outcome::Chain(next) => current = Some(next),
+            }
+        }
+    }
+}
+
+type Delivery = Box<dyn FnOnce(&mut World) + Send>;
+
+// The messages produced by the futures and streams of commands, sent as the events of
+// their message type once they're ready.
+pub struct Tasks {
+    sender: Sender<Delivery>,
+    receiver: Receiver<Delivery>,
+}
+
+impl Default for Tasks {
+    fn default() -> Self {
+        let (sender, receiver) = mpsc::channel();
+        Self { sender, receiver }
+    }
+}
+
+impl Tasks {
+    pub fn spawn_future<M: Event>(&self, future: BoxFuture<M>) {
+        let sender = self.sender.clone();
+        task_pool()
+            .spawn(async move {
+                let message = future.await;
+                // The app is gone when the receiver is.
+                let _ = sender.send(deliver(message));
+            })
+            .detach();
+    }
+
+    pub fn spawn_stream<M: Event>(&self, mut stream: BoxStream<M>) {
+        let sender = self.sender.clone();
+        task_pool()
+            .spawn(async move {
+                while let Some(message) = stream.next().await {
+                    if sender.send(deliver(message)).is_err() {
+                        break;
+                    }
+                }
+            })
+            .detach();
+    }
+}
+
+// Bevy's pool for background work, also set up without its `TaskPoolPlugin`, e.g. in
+// tests. On the web, its tasks run on the browser's event loop.
+fn task_pool() -> &'static AsyncComputeTaskPool {
+    AsyncComputeTaskPool::get_or_init(TaskPool::default)
+}
+
+fn deliver<M: Event>(message: M) -> Delivery {
+    Box::new(move |world| {
+        world.send_event(message);
+    })
+}
+
+// Send the messages produced since last frame, before `Update`.
+pub fn send_task_messages(world: &mut World) {
+    let deliveries: Vec<_> = {
+        let props = world.resource::<IcedResource>().lock().unwrap();
+        props.tasks.receiver.try_iter().collect()
+    };
+    for deliver in deliveries {
+        deliver(world);
+    }
+}
@@ -1,329 +1,568 @@
+//! These are the conversions [`IcedPlugin`](crate::IcedPlugin) uses to fill the
+//! [`IcedEventQueue`](crate::IcedEventQueue), so events converted from another input
+//! source, e.g. input replicated over the network, match the ones UIs receive from the
+//! local input.
+//!
+//! ```
+//! use bevy::input::mouse::MouseButton;
+//! use bevy_iced::conversions;
+//! use bevy_iced::iced::mouse;
+//!
+//! assert_eq!(conversions::mouse_button(MouseButton::Left), mouse::Button::Left);
+//! ```
+
 use crate::iced::{
     touch::{self, Finger},
     Point,
 };
-use bevy_input::keyboard::Key as BevyKey;
+use crate::utils;
+use bevy_input::keyboard::{Key as BevyKey, KeyCode, KeyboardInput};
+use bevy_input::mouse::{MouseButtonInput, MouseWheel};
 use bevy_input::prelude::MouseButton;
 use bevy_input::touch::{TouchInput, TouchPhase};
+use bevy_input::{ButtonInput, ButtonState};
 use bevy_math::Vec2;
-use iced_core::keyboard::Key as IcedKey;
+use bevy_render::color::Color as BevyColor;
+use bevy_window::{CursorIcon, ReceivedCharacter, Window};
+use iced_core::keyboard::key::Named;
+use iced_core::keyboard::{self, Key as IcedKey, Location, Modifiers};
+use iced_core::mouse::{self, Interaction};
+use iced_core::{Color, SmolStr};
+use iced_widget::graphics::Viewport;
+
+// Bevy and Iced name their named keys identically, so one list generates both the
+// conversion and the name lookups.
+macro_rules! named_keys {
+    ($($name:ident),* $(,)?) => {
+        fn named_key(key: &BevyKey) -> Option<Named> {
+            match key {
+                $(BevyKey::$name => Some(Named::$name),)*
+                _ => None,
+            }
+        }
+
+        #[cfg(feature = "record")]
+        pub(crate) fn named_key_name(named: Named) -> Option<&'static str> {
+            #[allow(unreachable_patterns)]
+            match named {
+                $(Named::$name => Some(stringify!($name)),)*
+                _ => None,
+            }
+        }
+
+        #[cfg(feature = "record")]
+        pub(crate) fn named_key_from_name(name: &str) -> Option<Named> {
+            match name {
+                $(stringify!($name) => Some(Named::$name),)*
+                _ => None,
+            }
+        }
+    };
+}
+
+named_keys!(
+    Alt,
+    AltGraph,
+    CapsLock,
+    Control,
+    Fn,
+    FnLock,
+    NumLock,
+    ScrollLock,
+    Shift,
+    Symbol,
+    SymbolLock,
+    Meta,
+    Hyper,
+    Super,
+    Enter,
+    Tab,
+    Space,
+    ArrowDown,
+    ArrowLeft,
+    ArrowRight,
+    ArrowUp,
+    End,
+    Home,
+    PageDown,
+    PageUp,
+    Backspace,
+    Clear,
+    Copy,
+    CrSel,
+    Cut,
+    Delete,
+    EraseEof,
+    ExSel,
+    Insert,
+    Paste,
+    Redo,
+    Undo,
+    Accept,
+    Again,
+    Attn,
+    Cancel,
+    ContextMenu,
+    Escape,
+    Execute,
+    Find,
+    Help,
+    Pause,
+    Play,
+    Props,
+    Select,
+    ZoomIn,
+    ZoomOut,
+    BrightnessDown,
+    BrightnessUp,
+    Eject,
+    LogOff,
+    Power,
+    PowerOff,
+    PrintScreen,
+    Hibernate,
+    Standby,
+    WakeUp,
+    AllCandidates,
+    Alphanumeric,
+    CodeInput,
+    Compose,
+    Convert,
+    FinalMode,
+    GroupFirst,
+    GroupLast,
+    GroupNext,
+    GroupPrevious,
+    ModeChange,
+    NextCandidate,
+    NonConvert,
+    PreviousCandidate,
+    Process,
+    SingleCandidate,
+    HangulMode,
+    HanjaMode,
+    JunjaMode,
+    Eisu,
+    Hankaku,
+    Hiragana,
+    HiraganaKatakana,
+    KanaMode,
+    KanjiMode,
+    Katakana,
+    Romaji,
+    Zenkaku,
+    ZenkakuHankaku,
+    Soft1,
+    Soft2,
+    Soft3,
+    Soft4,
+    ChannelDown,
+    ChannelUp,
+    Close,
+    MailForward,
+    MailReply,
+    MailSend,
+    MediaClose,
+    MediaFastForward,
+    MediaPause,
+    MediaPlay,
+    MediaPlayPause,
+    MediaRecord,
+    MediaRewind,
+    MediaStop,
+    MediaTrackNext,
+    MediaTrackPrevious,
+    New,
+    Open,
+    Print,
+    Save,
+    SpellCheck,
+    Key11,
+    Key12,
+    AudioBalanceLeft,
+    AudioBalanceRight,
+    AudioBassBoostDown,
+    AudioBassBoostToggle,
+    AudioBassBoostUp,
+    AudioFaderFront,
+    AudioFaderRear,
+    AudioSurroundModeNext,
+    AudioTrebleDown,
+    AudioTrebleUp,
+    AudioVolumeDown,
+    AudioVolumeUp,
+    AudioVolumeMute,
+    MicrophoneToggle,
+    MicrophoneVolumeDown,
+    MicrophoneVolumeUp,
+    MicrophoneVolumeMute,
+    SpeechCorrectionList,
+    SpeechInputToggle,
+    LaunchApplication1,
+    LaunchApplication2,
+    LaunchCalendar,
+    LaunchContacts,
+    LaunchMail,
+    LaunchMediaPlayer,
+    LaunchMusicPlayer,
+    LaunchPhone,
+    LaunchScreenSaver,
+    LaunchSpreadsheet,
+    LaunchWebBrowser,
+    LaunchWebCam,
+    LaunchWordProcessor,
+    BrowserBack,
+    BrowserFavorites,
+    BrowserForward,
+    BrowserHome,
+    BrowserRefresh,
+    BrowserSearch,
+    BrowserStop,
+    AppSwitch,
+    Call,
+    Camera,
+    CameraFocus,
+    EndCall,
+    GoBack,
+    GoHome,
+    HeadsetHook,
+    LastNumberRedial,
+    Notification,
+    MannerMode,
+    VoiceDial,
+    TV,
+    TV3DMode,
+    TVAntennaCable,
+    TVAudioDescription,
+    TVAudioDescriptionMixDown,
+    TVAudioDescriptionMixUp,
+    TVContentsMenu,
+    TVDataService,
+    TVInput,
+    TVInputComponent1,
+    TVInputComponent2,
+    TVInputComposite1,
+    TVInputComposite2,
+    TVInputHDMI1,
+    TVInputHDMI2,
+    TVInputHDMI3,
+    TVInputHDMI4,
+    TVInputVGA1,
+    TVMediaContext,
+    TVNetwork,
+    TVNumberEntry,
+    TVPower,
+    TVRadioService,
+    TVSatellite,
+    TVSatelliteBS,
+    TVSatelliteCS,
+    TVSatelliteToggle,
+    TVTerrestrialAnalog,
+    TVTerrestrialDigital,
+    TVTimer,
+    AVRInput,
+    AVRPower,
+    ColorF0Red,
+    ColorF1Green,
+    ColorF2Yellow,
+    ColorF3Blue,
+    ColorF4Grey,
+    ColorF5Brown,
+    ClosedCaptionToggle,
+    Dimmer,
+    DisplaySwap,
+    DVR,
+    Exit,
+    FavoriteClear0,
+    FavoriteClear1,
+    FavoriteClear2,
+    FavoriteClear3,
+    FavoriteRecall0,
+    FavoriteRecall1,
+    FavoriteRecall2,
+    FavoriteRecall3,
+    FavoriteStore0,
+    FavoriteStore1,
+    FavoriteStore2,
+    FavoriteStore3,
+    Guide,
+    GuideNextDay,
+    GuidePreviousDay,
+    Info,
+    InstantReplay,
+    Link,
+    ListProgram,
+    LiveContent,
+    Lock,
+    MediaApps,
+    MediaAudioTrack,
+    MediaLast,
+    MediaSkipBackward,
+    MediaSkipForward,
+    MediaStepBackward,
+    MediaStepForward,
+    MediaTopMenu,
+    NavigateIn,
+    NavigateNext,
+    NavigateOut,
+    NavigatePrevious,
+    NextFavoriteChannel,
+    NextUserProfile,
+    OnDemand,
+    Pairing,
+    PinPDown,
+    PinPMove,
+    PinPToggle,
+    PinPUp,
+    PlaySpeedDown,
+    PlaySpeedReset,
+    PlaySpeedUp,
+    RandomToggle,
+    RcLowBattery,
+    RecordSpeedNext,
+    RfBypass,
+    ScanChannelsToggle,
+    ScreenModeNext,
+    Settings,
+    SplitScreenToggle,
+    STBInput,
+    STBPower,
+    Subtitle,
+    Teletext,
+    VideoModeNext,
+    Wink,
+    ZoomToggle,
+    F1,
+    F2,
+    F3,
+    F4,
+    F5,
+    F6,
+    F7,
+    F8,
+    F9,
+    F10,
+    F11,
+    F12,
+    F13,
+    F14,
+    F15,
+    F16,
+    F17,
+    F18,
+    F19,
+    F20,
+    F21,
+    F22,
+    F23,
+    F24,
+    F25,
+    F26,
+    F27,
+    F28,
+    F29,
+    F30,
+    F31,
+    F32,
+    F33,
+    F34,
+    F35,
+);
 
+/// The Iced key for a bevy logical key, or [`IcedKey::Unidentified`] for the keys that
+/// Iced doesn't know.
 pub fn key_code(virtual_keycode: &BevyKey) -> IcedKey {
-    use iced_core::keyboard::key::Named;
     match virtual_keycode {
         BevyKey::Character(s) => IcedKey::Character(s.clone()),
-        BevyKey::Alt => IcedKey::Named(Named::Alt),
-        BevyKey::AltGraph => IcedKey::Named(Named::AltGraph),
-        BevyKey::CapsLock => IcedKey::Named(Named::CapsLock),
-        BevyKey::Control => IcedKey::Named(Named::Control),
-        BevyKey::Fn => IcedKey::Named(Named::Fn),
-        BevyKey::FnLock => IcedKey::Named(Named::FnLock),
-        BevyKey::NumLock => IcedKey::Named(Named::NumLock),
-        BevyKey::ScrollLock => IcedKey::Named(Named::ScrollLock),
-        BevyKey::Shift => IcedKey::Named(Named::Shift),
-        BevyKey::Symbol => IcedKey::Named(Named::Symbol),
-        BevyKey::SymbolLock => IcedKey::Named(Named::SymbolLock),
-        BevyKey::Meta => IcedKey::Named(Named::Meta),
-        BevyKey::Hyper => IcedKey::Named(Named::Hyper),
-        BevyKey::Super => IcedKey::Named(Named::Super),
-        BevyKey::Enter => IcedKey::Named(Named::Enter),
-        BevyKey::Tab => IcedKey::Named(Named::Tab),
-        BevyKey::Space => IcedKey::Named(Named::Space),
-        BevyKey::ArrowDown => IcedKey::Named(Named::ArrowDown),
-        BevyKey::ArrowLeft => IcedKey::Named(Named::ArrowLeft),
-        BevyKey::ArrowRight => IcedKey::Named(Named::ArrowRight),
-        BevyKey::ArrowUp => IcedKey::Named(Named::ArrowUp),
-        BevyKey::End => IcedKey::Named(Named::End),
-        BevyKey::Home => IcedKey::Named(Named::Home),
-        BevyKey::PageDown => IcedKey::Named(Named::PageDown),
-        BevyKey::PageUp => IcedKey::Named(Named::PageUp),
-        BevyKey::Backspace => IcedKey::Named(Named::Backspace),
-        BevyKey::Clear => IcedKey::Named(Named::Clear),
-        BevyKey::Copy => IcedKey::Named(Named::Copy),
-        BevyKey::CrSel => IcedKey::Named(Named::CrSel),
-        BevyKey::Cut => IcedKey::Named(Named::Cut),
-        BevyKey::Delete => IcedKey::Named(Named::Delete),
-        BevyKey::EraseEof => IcedKey::Named(Named::EraseEof),
-        BevyKey::ExSel => IcedKey::Named(Named::ExSel),
-        BevyKey::Insert => IcedKey::Named(Named::Insert),
-        BevyKey::Paste => IcedKey::Named(Named::Paste),
-        BevyKey::Redo => IcedKey::Named(Named::Redo),
-        BevyKey::Undo => IcedKey::Named(Named::Undo),
-        BevyKey::Accept => IcedKey::Named(Named::Accept),
-        BevyKey::Again => IcedKey::Named(Named::Again),
-        BevyKey::Attn => IcedKey::Named(Named::Attn),
-        BevyKey::Cancel => IcedKey::Named(Named::Cancel),
-        BevyKey::ContextMenu => IcedKey::Named(Named::ContextMenu),
-        BevyKey::Escape => IcedKey::Named(Named::Escape),
-        BevyKey::Execute => IcedKey::Named(Named::Execute),
-        BevyKey::Find => IcedKey::Named(Named::Find),
-        BevyKey::Help => IcedKey::Named(Named::Help),
-        BevyKey::Pause => IcedKey::Named(Named::Pause),
-        BevyKey::Play => IcedKey::Named(Named::Play),
-        BevyKey::Props => IcedKey::Named(Named::Props),
-        BevyKey::Select => IcedKey::Named(Named::Select),
-        BevyKey::ZoomIn => IcedKey::Named(Named::ZoomIn),
-        BevyKey::ZoomOut => IcedKey::Named(Named::ZoomOut),
-        BevyKey::BrightnessDown => IcedKey::Named(Named::BrightnessDown),
-        BevyKey::BrightnessUp => IcedKey::Named(Named::BrightnessUp),
-        BevyKey::Eject => IcedKey::Named(Named::Eject),
-        BevyKey::LogOff => IcedKey::Named(Named::LogOff),
-        BevyKey::Power => IcedKey::Named(Named::Power),
-        BevyKey::PowerOff => IcedKey::Named(Named::PowerOff),
-        BevyKey::PrintScreen => IcedKey::Named(Named::PrintScreen),
-        BevyKey::Hibernate => IcedKey::Named(Named::Hibernate),
-        BevyKey::Standby => IcedKey::Named(Named::Standby),
-        BevyKey::WakeUp => IcedKey::Named(Named::WakeUp),
-        BevyKey::AllCandidates => IcedKey::Named(Named::AllCandidates),
-        BevyKey::Alphanumeric => IcedKey::Named(Named::Alphanumeric),
-        BevyKey::CodeInput => IcedKey::Named(Named::CodeInput),
-        BevyKey::Compose => IcedKey::Named(Named::Compose),
-        BevyKey::Convert => IcedKey::Named(Named::Convert),
-        BevyKey::FinalMode => IcedKey::Named(Named::FinalMode),
-        BevyKey::GroupFirst => IcedKey::Named(Named::GroupFirst),
-        BevyKey::GroupLast => IcedKey::Named(Named::GroupLast),
-        BevyKey::GroupNext => IcedKey::Named(Named::GroupNext),
-        BevyKey::GroupPrevious => IcedKey::Named(Named::GroupPrevious),
-        BevyKey::ModeChange => IcedKey::Named(Named::ModeChange),
-        BevyKey::NextCandidate => IcedKey::Named(Named::NextCandidate),
-        BevyKey::NonConvert => IcedKey::Named(Named::NonConvert),
-        BevyKey::PreviousCandidate => IcedKey::Named(Named::PreviousCandidate),
-        BevyKey::Process => IcedKey::Named(Named::Process),
-        BevyKey::SingleCandidate => IcedKey::Named(Named::SingleCandidate),
-        BevyKey::HangulMode => IcedKey::Named(Named::HangulMode),
-        BevyKey::HanjaMode => IcedKey::Named(Named::HanjaMode),
-        BevyKey::JunjaMode => IcedKey::Named(Named::JunjaMode),
-        BevyKey::Eisu => IcedKey::Named(Named::Eisu),
-        BevyKey::Hankaku => IcedKey::Named(Named::Hankaku),
-        BevyKey::Hiragana => IcedKey::Named(Named::Hiragana),
-        BevyKey::HiraganaKatakana => IcedKey::Named(Named::HiraganaKatakana),
-        BevyKey::KanaMode => IcedKey::Named(Named::KanaMode),
-        BevyKey::KanjiMode => IcedKey::Named(Named::KanjiMode),
-        BevyKey::Katakana => IcedKey::Named(Named::Katakana),
-        BevyKey::Romaji => IcedKey::Named(Named::Romaji),
-        BevyKey::Zenkaku => IcedKey::Named(Named::Zenkaku),
-        BevyKey::ZenkakuHankaku => IcedKey::Named(Named::ZenkakuHankaku),
-        BevyKey::Soft1 => IcedKey::Named(Named::Soft1),
-        BevyKey::Soft2 => IcedKey::Named(Named::Soft2),
-        BevyKey::Soft3 => IcedKey::Named(Named::Soft3),
-        BevyKey::Soft4 => IcedKey::Named(Named::Soft4),
-        BevyKey::ChannelDown => IcedKey::Named(Named::ChannelDown),
-        BevyKey::ChannelUp => IcedKey::Named(Named::ChannelUp),
-        BevyKey::Close => IcedKey::Named(Named::Close),
-        BevyKey::MailForward => IcedKey::Named(Named::MailForward),
-        BevyKey::MailReply => IcedKey::Named(Named::MailReply),
-        BevyKey::MailSend => IcedKey::Named(Named::MailSend),
-        BevyKey::MediaClose => IcedKey::Named(Named::MediaClose),
-        BevyKey::MediaFastForward => IcedKey::Named(Named::MediaFastForward),
-        BevyKey::MediaPause => IcedKey::Named(Named::MediaPause),
-        BevyKey::MediaPlay => IcedKey::Named(Named::MediaPlay),
-        BevyKey::MediaPlayPause => IcedKey::Named(Named::MediaPlayPause),
-        BevyKey::MediaRecord => IcedKey::Named(Named::MediaRecord),
-        BevyKey::MediaRewind => IcedKey::Named(Named::MediaRewind),
-        BevyKey::MediaStop => IcedKey::Named(Named::MediaStop),
-        BevyKey::MediaTrackNext => IcedKey::Named(Named::MediaTrackNext),
-        BevyKey::MediaTrackPrevious => IcedKey::Named(Named::MediaTrackPrevious),
-        BevyKey::New => IcedKey::Named(Named::New),
-        BevyKey::Open => IcedKey::Named(Named::Open),
-        BevyKey::Print => IcedKey::Named(Named::Print),
-        BevyKey::Save => IcedKey::Named(Named::Save),
-        BevyKey::SpellCheck => IcedKey::Named(Named::SpellCheck),
-        BevyKey::Key11 => IcedKey::Named(Named::Key11),
-        BevyKey::Key12 => IcedKey::Named(Named::Key12),
-        BevyKey::AudioBalanceLeft => IcedKey::Named(Named::AudioBalanceLeft),
-        BevyKey::AudioBalanceRight => IcedKey::Named(Named::AudioBalanceRight),
-        BevyKey::AudioBassBoostDown => IcedKey::Named(Named::AudioBassBoostDown),
-        BevyKey::AudioBassBoostToggle => IcedKey::Named(Named::AudioBassBoostToggle),
-        BevyKey::AudioBassBoostUp => IcedKey::Named(Named::AudioBassBoostUp),
-        BevyKey::AudioFaderFront => IcedKey::Named(Named::AudioFaderFront),
-        BevyKey::AudioFaderRear => IcedKey::Named(Named::AudioFaderRear),
-        BevyKey::AudioSurroundModeNext => IcedKey::Named(Named::AudioSurroundModeNext),
-        BevyKey::AudioTrebleDown => IcedKey::Named(Named::AudioTrebleDown),
-        BevyKey::AudioTrebleUp => IcedKey::Named(Named::AudioTrebleUp),
-        BevyKey::AudioVolumeDown => IcedKey::Named(Named::AudioVolumeDown),
-        BevyKey::AudioVolumeUp => IcedKey::Named(Named::AudioVolumeUp),
-        BevyKey::AudioVolumeMute => IcedKey::Named(Named::AudioVolumeMute),
-        BevyKey::MicrophoneToggle => IcedKey::Named(Named::MicrophoneToggle),
-        BevyKey::MicrophoneVolumeDown => IcedKey::Named(Named::MicrophoneVolumeDown),
-        BevyKey::MicrophoneVolumeUp => IcedKey::Named(Named::MicrophoneVolumeUp),
-        BevyKey::MicrophoneVolumeMute => IcedKey::Named(Named::MicrophoneVolumeMute),
-        BevyKey::SpeechCorrectionList => IcedKey::Named(Named::SpeechCorrectionList),
-        BevyKey::SpeechInputToggle => IcedKey::Named(Named::SpeechInputToggle),
-        BevyKey::LaunchApplication1 => IcedKey::Named(Named::LaunchApplication1),
-        BevyKey::LaunchApplication2 => IcedKey::Named(Named::LaunchApplication2),
-        BevyKey::LaunchCalendar => IcedKey::Named(Named::LaunchCalendar),
-        BevyKey::LaunchContacts => IcedKey::Named(Named::LaunchContacts),
-        BevyKey::LaunchMail => IcedKey::Named(Named::LaunchMail),
-        BevyKey::LaunchMediaPlayer => IcedKey::Named(Named::LaunchMediaPlayer),
-        BevyKey::LaunchMusicPlayer => IcedKey::Named(Named::LaunchMusicPlayer),
-        BevyKey::LaunchPhone => IcedKey::Named(Named::LaunchPhone),
-        BevyKey::LaunchScreenSaver => IcedKey::Named(Named::LaunchScreenSaver),
-        BevyKey::LaunchSpreadsheet => IcedKey::Named(Named::LaunchSpreadsheet),
-        BevyKey::LaunchWebBrowser => IcedKey::Named(Named::LaunchWebBrowser),
-        BevyKey::LaunchWebCam => IcedKey::Named(Named::LaunchWebCam),
-        BevyKey::LaunchWordProcessor => IcedKey::Named(Named::LaunchWordProcessor),
-        BevyKey::BrowserBack => IcedKey::Named(Named::BrowserBack),
-        BevyKey::BrowserFavorites => IcedKey::Named(Named::BrowserFavorites),
-        BevyKey::BrowserForward => IcedKey::Named(Named::BrowserForward),
-        BevyKey::BrowserHome => IcedKey::Named(Named::BrowserHome),
-        BevyKey::BrowserRefresh => IcedKey::Named(Named::BrowserRefresh),
-        BevyKey::BrowserSearch => IcedKey::Named(Named::BrowserSearch),
-        BevyKey::BrowserStop => IcedKey::Named(Named::BrowserStop),
-        BevyKey::AppSwitch => IcedKey::Named(Named::AppSwitch),
-        BevyKey::Call => IcedKey::Named(Named::Call),
-        BevyKey::Camera => IcedKey::Named(Named::Camera),
-        BevyKey::CameraFocus => IcedKey::Named(Named::CameraFocus),
-        BevyKey::EndCall => IcedKey::Named(Named::EndCall),
-        BevyKey::GoBack => IcedKey::Named(Named::GoBack),
-        BevyKey::GoHome => IcedKey::Named(Named::GoHome),
-        BevyKey::HeadsetHook => IcedKey::Named(Named::HeadsetHook),
-        BevyKey::LastNumberRedial => IcedKey::Named(Named::LastNumberRedial),
-        BevyKey::Notification => IcedKey::Named(Named::Notification),
-        BevyKey::MannerMode => IcedKey::Named(Named::MannerMode),
-        BevyKey::VoiceDial => IcedKey::Named(Named::VoiceDial),
-        BevyKey::TV => IcedKey::Named(Named::TV),
-        BevyKey::TV3DMode => IcedKey::Named(Named::TV3DMode),
-        BevyKey::TVAntennaCable => IcedKey::Named(Named::TVAntennaCable),
-        BevyKey::TVAudioDescription => IcedKey::Named(Named::TVAudioDescription),
-        BevyKey::TVAudioDescriptionMixDown => IcedKey::Named(Named::TVAudioDescriptionMixDown),
-        BevyKey::TVAudioDescriptionMixUp => IcedKey::Named(Named::TVAudioDescriptionMixUp),
-        BevyKey::TVContentsMenu => IcedKey::Named(Named::TVContentsMenu),
-        BevyKey::TVDataService => IcedKey::Named(Named::TVDataService),
-        BevyKey::TVInput => IcedKey::Named(Named::TVInput),
-        BevyKey::TVInputComponent1 => IcedKey::Named(Named::TVInputComponent1),
-        BevyKey::TVInputComponent2 => IcedKey::Named(Named::TVInputComponent2),
-        BevyKey::TVInputComposite1 => IcedKey::Named(Named::TVInputComposite1),
-        BevyKey::TVInputComposite2 => IcedKey::Named(Named::TVInputComposite2),
-        BevyKey::TVInputHDMI1 => IcedKey::Named(Named::TVInputHDMI1),
-        BevyKey::TVInputHDMI2 => IcedKey::Named(Named::TVInputHDMI2),
-        BevyKey::TVInputHDMI3 => IcedKey::Named(Named::TVInputHDMI3),
-        BevyKey::TVInputHDMI4 => IcedKey::Named(Named::TVInputHDMI4),
-        BevyKey::TVInputVGA1 => IcedKey::Named(Named::TVInputVGA1),
-        BevyKey::TVMediaContext => IcedKey::Named(Named::TVMediaContext),
-        BevyKey::TVNetwork => IcedKey::Named(Named::TVNetwork),
-        BevyKey::TVNumberEntry => IcedKey::Named(Named::TVNumberEntry),
-        BevyKey::TVPower => IcedKey::Named(Named::TVPower),
-        BevyKey::TVRadioService => IcedKey::Named(Named::TVRadioService),
-        BevyKey::TVSatellite => IcedKey::Named(Named::TVSatellite),
-        BevyKey::TVSatelliteBS => IcedKey::Named(Named::TVSatelliteBS),
-        BevyKey::TVSatelliteCS => IcedKey::Named(Named::TVSatelliteCS),
-        BevyKey::TVSatelliteToggle => IcedKey::Named(Named::TVSatelliteToggle),
-        BevyKey::TVTerrestrialAnalog => IcedKey::Named(Named::TVTerrestrialAnalog),
-        BevyKey::TVTerrestrialDigital => IcedKey::Named(Named::TVTerrestrialDigital),
-        BevyKey::TVTimer => IcedKey::Named(Named::TVTimer),
-        BevyKey::AVRInput => IcedKey::Named(Named::AVRInput),
-        BevyKey::AVRPower => IcedKey::Named(Named::AVRPower),
-        BevyKey::ColorF0Red => IcedKey::Named(Named::ColorF0Red),
-        BevyKey::ColorF1Green => IcedKey::Named(Named::ColorF1Green),
-        BevyKey::ColorF2Yellow => IcedKey::Named(Named::ColorF2Yellow),
-        BevyKey::ColorF3Blue => IcedKey::Named(Named::ColorF3Blue),
-        BevyKey::ColorF4Grey => IcedKey::Named(Named::ColorF4Grey),
-        BevyKey::ColorF5Brown => IcedKey::Named(Named::ColorF5Brown),
-        BevyKey::ClosedCaptionToggle => IcedKey::Named(Named::ClosedCaptionToggle),
-        BevyKey::Dimmer => IcedKey::Named(Named::Dimmer),
-        BevyKey::DisplaySwap => IcedKey::Named(Named::DisplaySwap),
-        BevyKey::DVR => IcedKey::Named(Named::DVR),
-        BevyKey::Exit => IcedKey::Named(Named::Exit),
-        BevyKey::FavoriteClear0 => IcedKey::Named(Named::FavoriteClear0),
-        BevyKey::FavoriteClear1 => IcedKey::Named(Named::FavoriteClear1),
-        BevyKey::FavoriteClear2 => IcedKey::Named(Named::FavoriteClear2),
-        BevyKey::FavoriteClear3 => IcedKey::Named(Named::FavoriteClear3),
-        BevyKey::FavoriteRecall0 => IcedKey::Named(Named::FavoriteRecall0),
-        BevyKey::FavoriteRecall1 => IcedKey::Named(Named::FavoriteRecall1),
-        BevyKey::FavoriteRecall2 => IcedKey::Named(Named::FavoriteRecall2),
-        BevyKey::FavoriteRecall3 => IcedKey::Named(Named::FavoriteRecall3),
-        BevyKey::FavoriteStore0 => IcedKey::Named(Named::FavoriteStore0),
-        BevyKey::FavoriteStore1 => IcedKey::Named(Named::FavoriteStore1),
-        BevyKey::FavoriteStore2 => IcedKey::Named(Named::FavoriteStore2),
-        BevyKey::FavoriteStore3 => IcedKey::Named(Named::FavoriteStore3),
-        BevyKey::Guide => IcedKey::Named(Named::Guide),
-        BevyKey::GuideNextDay => IcedKey::Named(Named::GuideNextDay),
-        BevyKey::GuidePreviousDay => IcedKey::Named(Named::GuidePreviousDay),
-        BevyKey::Info => IcedKey::Named(Named::Info),
-        BevyKey::InstantReplay => IcedKey::Named(Named::InstantReplay),
-        BevyKey::Link => IcedKey::Named(Named::Link),
-        BevyKey::ListProgram => IcedKey::Named(Named::ListProgram),
-        BevyKey::LiveContent => IcedKey::Named(Named::LiveContent),
-        BevyKey::Lock => IcedKey::Named(Named::Lock),
-        BevyKey::MediaApps => IcedKey::Named(Named::MediaApps),
-        BevyKey::MediaAudioTrack => IcedKey::Named(Named::MediaAudioTrack),
-        BevyKey::MediaLast => IcedKey::Named(Named::MediaLast),
-        BevyKey::MediaSkipBackward => IcedKey::Named(Named::MediaSkipBackward),
-        BevyKey::MediaSkipForward => IcedKey::Named(Named::MediaSkipForward),
-        BevyKey::MediaStepBackward => IcedKey::Named(Named::MediaStepBackward),
-        BevyKey::MediaStepForward => IcedKey::Named(Named::MediaStepForward),
-        BevyKey::MediaTopMenu => IcedKey::Named(Named::MediaTopMenu),
-        BevyKey::NavigateIn => IcedKey::Named(Named::NavigateIn),
-        BevyKey::NavigateNext => IcedKey::Named(Named::NavigateNext),
-        BevyKey::NavigateOut => IcedKey::Named(Named::NavigateOut),
-        BevyKey::NavigatePrevious => IcedKey::Named(Named::NavigatePrevious),
-        BevyKey::NextFavoriteChannel => IcedKey::Named(Named::NextFavoriteChannel),
-        BevyKey::NextUserProfile => IcedKey::Named(Named::NextUserProfile),
-        BevyKey::OnDemand => IcedKey::Named(Named::OnDemand),
-        BevyKey::Pairing => IcedKey::Named(Named::Pairing),
-        BevyKey::PinPDown => IcedKey::Named(Named::PinPDown),
-        BevyKey::PinPMove => IcedKey::Named(Named::PinPMove),
-        BevyKey::PinPToggle => IcedKey::Named(Named::PinPToggle),
-        BevyKey::PinPUp => IcedKey::Named(Named::PinPUp),
-        BevyKey::PlaySpeedDown => IcedKey::Named(Named::PlaySpeedDown),
-        BevyKey::PlaySpeedReset => IcedKey::Named(Named::PlaySpeedReset),
-        BevyKey::PlaySpeedUp => IcedKey::Named(Named::PlaySpeedUp),
-        BevyKey::RandomToggle => IcedKey::Named(Named::RandomToggle),
-        BevyKey::RcLowBattery => IcedKey::Named(Named::RcLowBattery),
-        BevyKey::RecordSpeedNext => IcedKey::Named(Named::RecordSpeedNext),
-        BevyKey::RfBypass => IcedKey::Named(Named::RfBypass),
-        BevyKey::ScanChannelsToggle => IcedKey::Named(Named::ScanChannelsToggle),
-        BevyKey::ScreenModeNext => IcedKey::Named(Named::ScreenModeNext),
-        BevyKey::Settings => IcedKey::Named(Named::Settings),
-        BevyKey::SplitScreenToggle => IcedKey::Named(Named::SplitScreenToggle),
-        BevyKey::STBInput => IcedKey::Named(Named::STBInput),
-        BevyKey::STBPower => IcedKey::Named(Named::STBPower),
-        BevyKey::Subtitle => IcedKey::Named(Named::Subtitle),
-        BevyKey::Teletext => IcedKey::Named(Named::Teletext),
-        BevyKey::VideoModeNext => IcedKey::Named(Named::VideoModeNext),
-        BevyKey::Wink => IcedKey::Named(Named::Wink),
-        BevyKey::ZoomToggle => IcedKey::Named(Named::ZoomToggle),
-        BevyKey::F1 => IcedKey::Named(Named::F1),
-        BevyKey::F2 => IcedKey::Named(Named::F2),
-        BevyKey::F3 => IcedKey::Named(Named::F3),
-        BevyKey::F4 => IcedKey::Named(Named::F4),
-        BevyKey::F5 => IcedKey::Named(Named::F5),
-        BevyKey::F6 => IcedKey::Named(Named::F6),
-        BevyKey::F7 => IcedKey::Named(Named::F7),
-        BevyKey::F8 => IcedKey::Named(Named::F8),
-        BevyKey::F9 => IcedKey::Named(Named::F9),
-        BevyKey::F10 => IcedKey::Named(Named::F10),
-        BevyKey::F11 => IcedKey::Named(Named::F11),
-        BevyKey::F12 => IcedKey::Named(Named::F12),
-        BevyKey::F13 => IcedKey::Named(Named::F13),
-        BevyKey::F14 => IcedKey::Named(Named::F14),
-        BevyKey::F15 => IcedKey::Named(Named::F15),
-        BevyKey::F16 => IcedKey::Named(Named::F16),
-        BevyKey::F17 => IcedKey::Named(Named::F17),
-        BevyKey::F18 => IcedKey::Named(Named::F18),
-        BevyKey::F19 => IcedKey::Named(Named::F19),
-        BevyKey::F20 => IcedKey::Named(Named::F20),
-        BevyKey::F21 => IcedKey::Named(Named::F21),
-        BevyKey::F22 => IcedKey::Named(Named::F22),
-        BevyKey::F23 => IcedKey::Named(Named::F23),
-        BevyKey::F24 => IcedKey::Named(Named::F24),
-        BevyKey::F25 => IcedKey::Named(Named::F25),
-        BevyKey::F26 => IcedKey::Named(Named::F26),
-        BevyKey::F27 => IcedKey::Named(Named::F27),
-        BevyKey::F28 => IcedKey::Named(Named::F28),
-        BevyKey::F29 => IcedKey::Named(Named::F29),
-        BevyKey::F30 => IcedKey::Named(Named::F30),
-        BevyKey::F31 => IcedKey::Named(Named::F31),
-        BevyKey::F32 => IcedKey::Named(Named::F32),
-        BevyKey::F33 => IcedKey::Named(Named::F33),
-        BevyKey::F34 => IcedKey::Named(Named::F34),
-        BevyKey::F35 => IcedKey::Named(Named::F35),
-        BevyKey::Unidentified(_) | BevyKey::Dead(_) => IcedKey::Unidentified,
-        _ => IcedKey::Unidentified,
+        key => named_key(key).map_or(IcedKey::Unidentified, IcedKey::Named),
     }
 }
 
-pub const fn mouse_button(button: MouseButton) -> iced_core::mouse::Button {
+/// The keyboard modifiers held in `input`.
+pub fn modifiers(input: &ButtonInput<KeyCode>) -> Modifiers {
+    let mut modifiers = Modifiers::default();
+    if input.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]) {
+        modifiers |= Modifiers::CTRL;
+    }
+    if input.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]) {
+        modifiers |= Modifiers::SHIFT;
+    }
+    if input.any_pressed([KeyCode::AltLeft, KeyCode::AltRight]) {
+        modifiers |= Modifiers::ALT;
+    }
+    if input.any_pressed([KeyCode::SuperLeft, KeyCode::SuperRight]) {
+        modifiers |= Modifiers::LOGO;
+    }
+    modifiers
+}
+
+/// The Iced event for a key press or release, given the [`modifiers`] held.
+///
+/// Presses and releases of modifier keys become [`keyboard::Event::ModifiersChanged`].
+/// The text typed by a key press is sent separately, see [`character_events`].
+///
+/// The key is the logical one, so shortcuts follow the user's layout: the key at the
+/// QWERTY Q position is `a` on AZERTY, and the one at the QWERTY C position is `j` on
+/// Dvorak.
+///
+/// ```
+/// use bevy::input::keyboard::{Key, KeyCode, KeyboardInput};
+/// use bevy::input::ButtonState;
+/// use bevy::prelude::Entity;
+/// use bevy_iced::conversions;
+/// use bevy_iced::iced::keyboard::{self, Modifiers};
+///
+/// let press = |key_code, character: &str| KeyboardInput {
+///     key_code,
+///     logical_key: Key::Character(character.into()),
+///     state: ButtonState::Pressed,
+///     window: Entity::PLACEHOLDER,
+/// };
+/// let key = |input| match conversions::key_event(&input, Modifiers::CTRL) {
+///     keyboard::Event::KeyPressed { key, .. } => key,
+///     event => panic!("{event:?}"),
+/// };
+/// // AZERTY
+/// assert_eq!(key(press(KeyCode::KeyQ, "a")), keyboard::Key::Character("a".into()));
+/// // Dvorak
+/// assert_eq!(key(press(KeyCode::KeyC, "j")), keyboard::Key::Character("j".into()));
+/// ```
+pub fn key_event(input: &KeyboardInput, modifiers: Modifiers) -> keyboard::Event {
+    match input.key_code {
+        KeyCode::ControlLeft
+        | KeyCode::ControlRight
+        | KeyCode::ShiftLeft
+        | KeyCode::ShiftRight
+        | KeyCode::AltLeft
+        | KeyCode::AltRight
+        | KeyCode::SuperLeft
+        | KeyCode::SuperRight => keyboard::Event::ModifiersChanged(modifiers),
+        _ => {
+            let key = key_code(&input.logical_key);
+            let location = location(input.key_code);
+            if input.state.is_pressed() {
+                keyboard::Event::KeyPressed {
+                    key,
+                    modifiers,
+                    location,
+                    text: None,
+                }
+            } else {
+                keyboard::Event::KeyReleased {
+                    key,
+                    modifiers,
+                    location,
+                }
+            }
+        }
+    }
+}
+
+/// Where the physical key is on the keyboard, e.g. [`Location::Numpad`] for the numpad's
+/// Enter.
+///
+/// The [`Key`](IcedKey) of Iced's key events is the logical key bevy reports, which
+/// follows the user's layout, so that Ctrl+A selects all on AZERTY too. Bindings tied to
+/// a position on the keyboard, like WASD, should read bevy's [`KeyCode`] instead.
+pub const fn location(key_code: KeyCode) -> Location {
+    match key_code {
+        KeyCode::ShiftLeft | KeyCode::ControlLeft | KeyCode::AltLeft | KeyCode::SuperLeft => {
+            Location::Left
+        }
+        KeyCode::ShiftRight | KeyCode::ControlRight | KeyCode::AltRight | KeyCode::SuperRight => {
+            Location::Right
+        }
+        KeyCode::NumLock
+        | KeyCode::Numpad0
+        | KeyCode::Numpad1
+        | KeyCode::Numpad2
+        | KeyCode::Numpad3
+        | KeyCode::Numpad4
+        | KeyCode::Numpad5
+        | KeyCode::Numpad6
+        | KeyCode::Numpad7
+        | KeyCode::Numpad8
+        | KeyCode::Numpad9
+        | KeyCode::NumpadAdd
+        | KeyCode::NumpadBackspace
+        | KeyCode::NumpadClear
+        | KeyCode::NumpadClearEntry
+        | KeyCode::NumpadComma
+        | KeyCode::NumpadDecimal
+        | KeyCode::NumpadDivide
+        | KeyCode::NumpadEnter
+        | KeyCode::NumpadEqual
+        | KeyCode::NumpadHash
+        | KeyCode::NumpadMemoryAdd
+        | KeyCode::NumpadMemoryClear
+        | KeyCode::NumpadMemoryRecall
+        | KeyCode::NumpadMemoryStore
+        | KeyCode::NumpadMemorySubtract
+        | KeyCode::NumpadMultiply
+        | KeyCode::NumpadParenLeft
+        | KeyCode::NumpadParenRight
+        | KeyCode::NumpadStar
+        | KeyCode::NumpadSubtract => Location::Numpad,
+        _ => Location::Standard,
+    }
+}
+
+/// The Iced key presses typing the characters of `input`, one per character.
+pub fn character_events(
+    input: &ReceivedCharacter,
+    modifiers: Modifiers,
+) -> impl Iterator<Item = keyboard::Event> + '_ {
+    input.char.chars().map(move |char| {
+        let smol_str = SmolStr::new(char.to_string());
+        keyboard::Event::KeyPressed {
+            key: IcedKey::Character(smol_str.clone()),
+            modifiers,
+            // NOTE: This is a winit thing we don't get from bevy events
+            location: Location::Standard,
+            text: Some(smol_str),
+        }
+    })
+}
+
+/// The logical position in `viewport` of a position in `window`'s coordinates, as
+/// reported by bevy's cursor and touch events. The two differ when
+/// [`IcedSettings::scale_factor`](crate::IcedSettings::scale_factor) is overridden.
+///
+/// The last physical pixel of the window is inside the viewport's logical size at
+/// fractional scale factors too:
+///
+/// ```
+/// use bevy::math::Vec2;
+/// use bevy::window::Window;
+/// use bevy_iced::conversions::cursor_position;
+/// use bevy_iced::iced::advanced::graphics::Viewport;
+/// use bevy_iced::iced::{Rectangle, Size};
+///
+/// for scale_factor in [1.25, 1.5, 1.75] {
+///     let mut window = Window::default();
+///     window.resolution.set_scale_factor(scale_factor);
+///     window.resolution.set_physical_resolution(1001, 751);
+///     let viewport = Viewport::with_physical_size(Size::new(1001, 751), scale_factor.into());
+///     let bounds = Rectangle::with_size(viewport.logical_size());
+///     let last_pixel = Vec2::new(1000.5, 750.5) / scale_factor;
+///     let position = cursor_position(last_pixel, &viewport, &window);
+///     assert!(bounds.contains(position), "{position:?} at {scale_factor}");
+/// }
+/// ```
+pub fn cursor_position(position: Vec2, viewport: &Viewport, window: &Window) -> Point {
+    utils::process_cursor_position(position, viewport, window)
+}
+
+/// The Iced event for the cursor moving to a position in `window`'s coordinates, see
+/// [`cursor_position`].
+pub fn cursor_moved(position: Vec2, viewport: &Viewport, window: &Window) -> mouse::Event {
+    mouse::Event::CursorMoved {
+        position: cursor_position(position, viewport, window),
+    }
+}
+
+/// The Iced mouse button for a bevy one.
+pub const fn mouse_button(button: MouseButton) -> mouse::Button {
     use iced_core::mouse::Button;
     match button {
         MouseButton::Left => Button::Left,
@@ -335,43 +574,62 @@ pub const fn mouse_button(button: MouseButton) -> iced_core::mouse::Button {
     }
 }
 
-pub const fn touch_event(bevy_touch_input: &TouchInput) -> touch::Event {
-    match *bevy_touch_input {
-        TouchInput {
-            phase: TouchPhase::Started,
-            position: Vec2 { x, y },
-            id: finger,
-            ..
-        } => touch::Event::FingerPressed {
-            id: Finger(finger),
-            position: Point { x, y },
-        },
-        TouchInput {
-            phase: TouchPhase::Canceled,
-            position: Vec2 { x, y },
-            id: finger,
-            ..
-        } => touch::Event::FingerLost {
-            id: Finger(finger),
-            position: Point { x, y },
-        },
-        TouchInput {
-            phase: TouchPhase::Ended,
-            position: Vec2 { x, y },
-            id: finger,
-            ..
-        } => touch::Event::FingerLifted {
-            id: Finger(finger),
-            position: Point { x, y },
-        },
-        TouchInput {
-            phase: TouchPhase::Moved,
-            position: Vec2 { x, y },
-            id: finger,
-            ..
-        } => touch::Event::FingerMoved {
-            id: Finger(finger),
-            position: Point { x, y },
+/// The Iced event for a mouse button press or release.
+pub const fn mouse_button_event(input: &MouseButtonInput) -> mouse::Event {
+    let button = mouse_button(input.button);
+    match input.state {
+        ButtonState::Pressed => mouse::Event::ButtonPressed(button),
+        ButtonState::Released => mouse::Event::ButtonReleased(button),
+    }
+}
+
+/// The Iced event for a mouse wheel scroll.
+pub const fn mouse_wheel(input: &MouseWheel) -> mouse::Event {
+    mouse::Event::WheelScrolled {
+        delta: mouse::ScrollDelta::Pixels {
+            x: input.x,
+            y: input.y,
         },
     }
 }
+
+/// The Iced event for a touch, at its `position` in the viewport, see
+/// [`cursor_position`].
+pub const fn touch_event(bevy_touch_input: &TouchInput, position: Point) -> touch::Event {
+    let id = Finger(bevy_touch_input.id);
+    match bevy_touch_input.phase {
+        TouchPhase::Started => touch::Event::FingerPressed { id, position },
+        TouchPhase::Canceled => touch::Event::FingerLost { id, position },
+        TouchPhase::Ended => touch::Event::FingerLifted { id, position },
+        TouchPhase::Moved => touch::Event::FingerMoved { id, position },
+    }
+}
+
+/// The bevy cursor icon showing an Iced mouse interaction.
+pub fn cursor_icon(interaction: Interaction) -> CursorIcon {
+    match interaction {
+        Interaction::Idle => CursorIcon::Default,
+        Interaction::Pointer => CursorIcon::Pointer,
+        Interaction::Grab => CursorIcon::Grab,
+        Interaction::Text => CursorIcon::Text,
+        Interaction::Crosshair => CursorIcon::Crosshair,
+        Interaction::Working => CursorIcon::Progress,
+        Interaction::Grabbing => CursorIcon::Grabbing,
+        Interaction::ResizingHorizontally => CursorIcon::EwResize,
+        Interaction::ResizingVertically => CursorIcon::NsResize,
+        Interaction::NotAllowed => CursorIcon::NotAllowed,
+        Interaction::ZoomIn => CursorIcon::ZoomIn,
+    }
+}
+
+/// The Iced color of a bevy color, e.g. to show a sprite's tint in a
+/// [`color_picker`](crate::widget::color_picker). Iced's colors are in sRGB space.
+pub fn color(color: BevyColor) -> Color {
+    let [r, g, b, a] = color.as_rgba_f32();
+    Color::from_rgba(r, g, b, a)
+}
+
+/// The bevy color of an Iced color, see [`color`].
+pub fn bevy_color(color: Color) -> BevyColor {
+    BevyColor::rgba(color.r, color.g, color.b, color.a)
+}
@@ -0,0 +1,60 @@
+//! Iced lays UIs out in logical pixels with the viewport's scale factor, which can be
+//! overridden with [`IcedSettings::scale_factor`](crate::IcedSettings::scale_factor),
+//! while bevy's physical window coordinates count the pixels of the surface. Both have
+//! their origin at the top left corner of the window, with `y` pointing down.
+//!
+//! To place sprites over widgets, convert the widget bounds to physical pixels, divide
+//! them by the window's scale factor and pass them to
+//! [`Camera::viewport_to_world_2d`](https://docs.rs/bevy/0.13/bevy/render/camera/struct.Camera.html#method.viewport_to_world_2d).
+//!
+//! ```
+//! use bevy::math::Vec2;
+//! use bevy_iced::coordinates::{to_bevy_physical, to_iced_logical};
+//! use bevy_iced::iced::advanced::graphics::Viewport;
+//! use bevy_iced::iced::Size;
+//!
+//! for scale_factor in [1.0, 1.25, 1.5, 1.75, 2.0] {
+//!     let viewport = Viewport::with_physical_size(Size::new(1280, 720), scale_factor);
+//!     let physical = Vec2::new(641.0, 359.0);
+//!     let logical = to_iced_logical(physical, &viewport);
+//!     assert!((to_bevy_physical(logical, &viewport) - physical).length() < 1e-3);
+//! }
+//! ```
+
+use bevy_math::{Rect, Vec2};
+use iced_core::{Point, Rectangle};
+use iced_widget::graphics::Viewport;
+
+/// The position in the viewport's logical pixels of a position in the window's physical
+/// pixels.
+pub fn to_iced_logical(window_physical: Vec2, viewport: &Viewport) -> Point {
+    let logical = window_physical.as_dvec2() / viewport.scale_factor();
+    Point::new(logical.x as f32, logical.y as f32)
+}
+
+/// The position in the window's physical pixels of a position in the viewport's logical
+/// pixels, e.g. of a widget's corner.
+pub fn to_bevy_physical(point: Point, viewport: &Viewport) -> Vec2 {
+    let physical = Vec2::new(point.x, point.y).as_dvec2() * viewport.scale_factor();
+    physical.as_vec2()
+}
+
+/// The bounds in the viewport's logical pixels of a rectangle in the window's physical
+/// pixels.
+pub fn to_iced_logical_rect(window_physical: Rect, viewport: &Viewport) -> Rectangle {
+    let min = to_iced_logical(window_physical.min, viewport);
+    let max = to_iced_logical(window_physical.max, viewport);
+    Rectangle::new(min, iced_core::Size::new(max.x - min.x, max.y - min.y))
+}
+
+/// The rectangle in the window's physical pixels of bounds in the viewport's logical
+/// pixels, e.g. those of a widget.
+pub fn to_bevy_physical_rect(bounds: Rectangle, viewport: &Viewport) -> Rect {
+    Rect::from_corners(
+        to_bevy_physical(bounds.position(), viewport),
+        to_bevy_physical(
+            Point::new(bounds.x + bounds.width, bounds.y + bounds.height),
+            viewport,
+        ),
+    )
+}
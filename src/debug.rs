@@ -0,0 +1,277 @@
+use std::any::{type_name, TypeId};
+use std::fmt::Write as _;
+
+use bevy_app::{App, PostUpdate, PreUpdate};
+use bevy_ecs::schedule::IntoSystemConfigs;
+use bevy_ecs::system::{NonSend, NonSendMut, Res, ResMut, Resource};
+use bevy_utils::tracing::info;
+use bevy_utils::HashMap;
+use iced_core::event::Status;
+use iced_core::keyboard::{self, key, Modifiers};
+use iced_core::mouse::Cursor;
+use iced_core::{Element, Font, Length};
+use iced_runtime::user_interface::{self, UserInterface};
+use iced_widget::style::Theme;
+use iced_widget::{column, container, text};
+
+use crate::accessibility::Appearance;
+use crate::cache::IcedCache;
+use crate::hotkeys::Chord;
+use crate::layout_dump::LayoutNode;
+use crate::modal::Layer;
+use crate::render::ViewportResource;
+use crate::toast::{self, Background};
+use crate::{cache, DidDraw, IcedEventQueue, IcedResource, IcedSet, Renderer};
+
+pub(crate) fn setup(app: &mut App) {
+    app.init_resource::<IcedDebugPanel>()
+        .init_non_send_resource::<DebugCache>()
+        .add_systems(PreUpdate, toggle_debug_panel.after(IcedSet::ProcessInput))
+        .add_systems(
+            PostUpdate,
+            draw_debug_panel
+                .after(toast::draw_toasts)
+                .before(cache::evict_unused),
+        );
+}
+
+/// A panel showing the internal state of bevy_iced, e.g. to find out why events don't
+/// reach a UI or why the widget state keeps growing. Requires the `debug` feature.
+///
+/// It's drawn over every UI, including toasts, and never receives input. It lists the
+/// viewport, the renderer, this frame's events and primitives, what the displayed UIs
+/// did with the events, and the widget state kept for each message type.
+#[derive(Resource, Clone, Debug)]
+pub struct IcedDebugPanel {
+    /// Whether the panel is shown.
+    pub visible: bool,
+    /// The key chord toggling the panel, F12 by default, or `None` to only toggle it
+    /// through [`IcedDebugPanel::visible`].
+    pub toggle: Option<Chord>,
+    /// The key chord logging the layout of every UI displayed in that frame once, as
+    /// printed by [`LayoutNode`], Shift+F12 by default. It works while the panel is
+    /// hidden too.
+    pub log_layouts: Option<Chord>,
+}
+
+impl Default for IcedDebugPanel {
+    fn default() -> Self {
+        Self {
+            visible: false,
+            toggle: Some(Chord::named(Modifiers::empty(), key::Named::F12)),
+            log_layouts: Some(Chord::named(Modifiers::SHIFT, key::Named::F12)),
+        }
+    }
+}
+
+// The panel's own message type, so that its UI never shares a cache entry or events
+// with the app's. It never receives input, so there are no messages.
+enum DebugMessage {}
+
+struct UiStats {
+    type_id: TypeId,
+    message: &'static str,
+    modal: bool,
+    events: usize,
+    captured: usize,
+    layout: Option<LayoutNode>,
+}
+
+// What the UIs displayed this frame did with their events and how they were laid out,
+// the names of the message types of the cached UIs, and the layouts of last frame's.
+#[derive(Default)]
+pub struct Stats {
+    uis: Vec<UiStats>,
+    names: HashMap<TypeId, &'static str>,
+    layouts: HashMap<TypeId, LayoutNode>,
+    log_layouts: bool,
+}
+
+impl Stats {
+    pub fn record<M: 'static>(
+        &mut self,
+        statuses: &[Status],
+        modal: bool,
+        layout: Option<LayoutNode>,
+    ) {
+        self.names.insert(TypeId::of::<M>(), type_name::<M>());
+        self.uis.push(UiStats {
+            type_id: TypeId::of::<M>(),
+            message: type_name::<M>(),
+            modal,
+            events: statuses.len(),
+            captured: statuses
+                .iter()
+                .filter(|status| **status == Status::Captured)
+                .count(),
+            layout,
+        });
+    }
+
+    // The layout of the last UI of `M` displayed this frame, or else last frame.
+    pub fn layout<M: 'static>(&self) -> Option<LayoutNode> {
+        let type_id = TypeId::of::<M>();
+        match self.uis.iter().rev().find(|ui| ui.type_id == type_id) {
+            Some(ui) => ui.layout.clone(),
+            None => self.layouts.get(&type_id).cloned(),
+        }
+    }
+}
+
+// The widget state of the panel, which owns the text its primitives refer to.
+#[derive(Default)]
+struct DebugCache(Option<user_interface::Cache>);
+
+fn toggle_debug_panel(
+    mut panel: ResMut<IcedDebugPanel>,
+    events: Res<IcedEventQueue>,
+    props: Res<IcedResource>,
+) {
+    let presses = |chord: &Option<Chord>| {
+        let Some(chord) = chord else {
+            return 0;
+        };
+        events
+            .iter()
+            .filter(|event| {
+                matches!(
+                    event,
+                    iced_core::Event::Keyboard(keyboard::Event::KeyPressed { key, modifiers, .. })
+                        if chord.matches(key, *modifiers)
+                )
+            })
+            .count()
+    };
+    if presses(&panel.toggle) % 2 == 1 {
+        panel.visible = !panel.visible;
+    }
+    if presses(&panel.log_layouts) > 0 {
+        props.lock().unwrap().debug_stats.log_layouts = true;
+    }
+}
+
+// Draw the panel over everything drawn this frame, after the toasts.
+fn draw_debug_panel(
+    panel: Res<IcedDebugPanel>,
+    props: Res<IcedResource>,
+    (viewport, appearance, events): (Res<ViewportResource>, Appearance, Res<IcedEventQueue>),
+    (did_draw, ui_cache): (Res<DidDraw>, NonSend<IcedCache>),
+    mut cache: NonSendMut<DebugCache>,
+) {
+    let props = &mut *props.lock().unwrap();
+    let mut uis = std::mem::take(&mut props.debug_stats.uis);
+    let stats = &mut props.debug_stats;
+    if std::mem::take(&mut stats.log_layouts) {
+        for ui in &uis {
+            if let Some(layout) = &ui.layout {
+                let modal = if ui.modal { " (modal)" } else { "" };
+                info!("Layout of {}{modal}:\n{layout}", ui.message);
+            }
+        }
+    }
+    stats.layouts.clear();
+    for ui in &mut uis {
+        if let Some(layout) = ui.layout.take() {
+            stats.layouts.insert(ui.type_id, layout);
+        }
+    }
+    if !panel.visible {
+        cache.0 = None;
+        return;
+    }
+
+    let released = props.released.is_some();
+    let renderer = &mut props.renderer;
+    // Without a UI this frame, the renderer still holds the last one's primitives.
+    let drew = did_draw.load(std::sync::atomic::Ordering::Relaxed);
+    let below = Layer::take(renderer);
+    let primitives = if drew { below.len() } else { 0 };
+
+    let mut report = String::new();
+    let size = viewport.physical_size();
+    let logical = viewport.logical_size();
+    let _ = writeln!(
+        report,
+        "Viewport: {}x{} px at {} ({}x{})",
+        size.width,
+        size.height,
+        viewport.scale_factor(),
+        logical.width,
+        logical.height
+    );
+    // The panel itself doesn't recreate a renderer released while idle.
+    let backend = match renderer {
+        _ if released => "released while idle",
+        Renderer::Wgpu(_) => "wgpu",
+        Renderer::TinySkia(_) => "tiny-skia",
+    };
+    let _ = writeln!(report, "Renderers: {} ({backend})", u8::from(!released));
+    let _ = writeln!(report, "Events this frame: {}", events.len());
+    let _ = writeln!(report, "Primitives: {primitives}");
+    let _ = writeln!(report, "UIs this frame: {}", uis.len());
+    for ui in &uis {
+        let _ = writeln!(
+            report,
+            "  {}{}: {} events, {} captured",
+            ui.message,
+            if ui.modal { " (modal)" } else { "" },
+            ui.events,
+            ui.captured
+        );
+    }
+    let mut cached: Vec<_> = ui_cache
+        .summary()
+        .map(|(type_id, entries, idle)| {
+            let name = props.debug_stats.names.get(&type_id).copied();
+            (name.unwrap_or("?"), entries, idle)
+        })
+        .collect();
+    cached.sort();
+    let _ = writeln!(report, "Cached UIs: {}", ui_cache.len());
+    for (name, entries, idle) in cached {
+        let _ = writeln!(
+            report,
+            "  {name}: {entries}, last displayed {idle} frames ago"
+        );
+    }
+
+    let theme = appearance.theme();
+    let palette = theme.extended_palette().background.strong;
+    let element: Element<'_, DebugMessage, Theme, Renderer> = container(
+        container(column![
+            text("bevy_iced").font(Font::MONOSPACE).size(14),
+            text(report.trim_end()).font(Font::MONOSPACE).size(12),
+        ])
+        .padding([8, 12])
+        .style(iced_widget::style::theme::Container::Custom(Box::new(
+            Background(palette.color),
+        ))),
+    )
+    .width(Length::Fill)
+    .height(Length::Fill)
+    .padding(16)
+    .into();
+
+    let mut ui = UserInterface::build(
+        element,
+        logical,
+        cache.0.take().unwrap_or_default(),
+        renderer,
+    );
+    ui.draw(
+        renderer,
+        theme,
+        &iced_core::renderer::Style {
+            text_color: palette.text,
+        },
+        Cursor::Unavailable,
+    );
+    cache.0 = Some(ui.into_cache());
+    let layer = Layer::take(renderer);
+    if drew {
+        below.draw(renderer);
+    }
+    layer.draw(renderer);
+    props.drawn_scale_factor = viewport.scale_factor();
+    did_draw.store(true, std::sync::atomic::Ordering::Relaxed);
+}
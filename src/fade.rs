@@ -0,0 +1,280 @@
+use iced_core::Size;
+use iced_wgpu::wgpu;
+
+const SHADER: &str = r"
+@group(0) @binding(0) var layer: texture_2d<f32>;
+@group(0) @binding(1) var layer_sampler: sampler;
+@group(0) @binding(2) var<uniform> opacity: vec4<f32>;
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+};
+
+// A single triangle covering the whole target.
+@vertex
+fn vs_main(@builtin(vertex_index) index: u32) -> VertexOutput {
+    let uv = vec2<f32>(f32((index << 1u) & 2u), f32(index & 2u));
+    var out: VertexOutput;
+    out.position = vec4<f32>(uv * vec2<f32>(2.0, -2.0) + vec2<f32>(-1.0, 1.0), 0.0, 1.0);
+    out.uv = uv;
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    return textureSample(layer, layer_sampler, in.uv) * opacity.x;
+}
+";
+
+struct Target {
+    size: Size<u32>,
+    format: wgpu::TextureFormat,
+    usage: wgpu::TextureUsages,
+    texture: wgpu::Texture,
+    view: wgpu::TextureView,
+    bind_group: wgpu::BindGroup,
+}
+
+/// Composites the Iced layer with a uniform opacity: the UI is presented to an
+/// intermediate texture, which is then blended onto the surface. Rendering the layer
+/// first means overlapping widgets, text and images all fade together. The texture may
+/// be smaller than the surface, in which case it's upscaled with linear filtering. A UI
+/// rendered on the CPU is uploaded to it instead.
+pub struct Fade {
+    format: wgpu::TextureFormat,
+    pipeline: wgpu::RenderPipeline,
+    bind_group_layout: wgpu::BindGroupLayout,
+    sampler: wgpu::Sampler,
+    uniforms: wgpu::Buffer,
+    target: Option<Target>,
+}
+
+impl Fade {
+    pub fn new(device: &wgpu::Device, format: wgpu::TextureFormat) -> Self {
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("bevy_iced.fade.shader"),
+            source: wgpu::ShaderSource::Wgsl(SHADER.into()),
+        });
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("bevy_iced.fade.bind_group_layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+            ],
+        });
+        let layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("bevy_iced.fade.pipeline_layout"),
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("bevy_iced.fade.pipeline"),
+            layout: Some(&layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: "vs_main",
+                buffers: &[],
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: "fs_main",
+                // Iced blends onto a transparent texture, leaving premultiplied colors.
+                targets: &[Some(wgpu::ColorTargetState {
+                    format,
+                    blend: Some(wgpu::BlendState::PREMULTIPLIED_ALPHA_BLENDING),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+            }),
+            primitive: wgpu::PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+        });
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("bevy_iced.fade.sampler"),
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+        let uniforms = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("bevy_iced.fade.uniforms"),
+            size: 16,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        Self {
+            format,
+            pipeline,
+            bind_group_layout,
+            sampler,
+            uniforms,
+            target: None,
+        }
+    }
+
+    pub fn format(&self) -> wgpu::TextureFormat {
+        self.format
+    }
+
+    /// The intermediate texture to present the UI to, resized if needed.
+    pub fn target(&mut self, device: &wgpu::Device, size: Size<u32>) -> &wgpu::TextureView {
+        self.resize(
+            device,
+            size,
+            self.format,
+            wgpu::TextureUsages::RENDER_ATTACHMENT,
+        );
+        &self.target.as_ref().unwrap().view
+    }
+
+    /// Upload a UI rendered on the CPU to the intermediate texture, as premultiplied sRGB
+    /// pixels in BGRA order.
+    pub fn upload(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        size: Size<u32>,
+        pixels: &[u8],
+    ) {
+        let format = wgpu::TextureFormat::Bgra8UnormSrgb;
+        self.resize(device, size, format, wgpu::TextureUsages::COPY_DST);
+        let target = self.target.as_ref().unwrap();
+        queue.write_texture(
+            wgpu::ImageCopyTexture {
+                texture: &target.texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d::ZERO,
+                aspect: wgpu::TextureAspect::All,
+            },
+            pixels,
+            wgpu::ImageDataLayout {
+                offset: 0,
+                bytes_per_row: Some(size.width * 4),
+                rows_per_image: None,
+            },
+            wgpu::Extent3d {
+                width: size.width,
+                height: size.height,
+                depth_or_array_layers: 1,
+            },
+        );
+    }
+
+    fn resize(
+        &mut self,
+        device: &wgpu::Device,
+        size: Size<u32>,
+        format: wgpu::TextureFormat,
+        usage: wgpu::TextureUsages,
+    ) {
+        let usage = usage | wgpu::TextureUsages::TEXTURE_BINDING;
+        if self.target.as_ref().is_some_and(|target| {
+            target.size == size && target.format == format && target.usage == usage
+        }) {
+            return;
+        }
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("bevy_iced.fade.texture"),
+            size: wgpu::Extent3d {
+                width: size.width,
+                height: size.height,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format,
+            usage,
+            view_formats: &[],
+        });
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("bevy_iced.fade.bind_group"),
+            layout: &self.bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(&view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::Sampler(&self.sampler),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: self.uniforms.as_entire_binding(),
+                },
+            ],
+        });
+        self.target = Some(Target {
+            size,
+            format,
+            usage,
+            texture,
+            view,
+            bind_group,
+        });
+    }
+
+    /// Blend the intermediate texture onto `view` with the given opacity.
+    pub fn composite(
+        &self,
+        queue: &wgpu::Queue,
+        encoder: &mut wgpu::CommandEncoder,
+        view: &wgpu::TextureView,
+        opacity: f32,
+    ) {
+        let Some(target) = &self.target else {
+            return;
+        };
+        let uniforms: Vec<u8> = [opacity, 0.0, 0.0, 0.0]
+            .iter()
+            .flat_map(|value| value.to_ne_bytes())
+            .collect();
+        queue.write_buffer(&self.uniforms, 0, &uniforms);
+
+        let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("bevy_iced.fade.pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+        pass.set_pipeline(&self.pipeline);
+        pass.set_bind_group(0, &target.bind_group, &[]);
+        pass.draw(0..3, 0..1);
+    }
+}
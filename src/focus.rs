@@ -0,0 +1,200 @@
+use bevy_derive::Deref;
+use bevy_ecs::system::{Res, ResMut, Resource};
+use iced_core::keyboard::{self, key};
+use iced_core::renderer::{self, Renderer as _};
+use iced_core::widget::{self, operation, Operation};
+use iced_core::{mouse, touch, Border, Color, Event as IcedEvent, Rectangle, Size, Vector};
+use iced_runtime::user_interface::UserInterface;
+use iced_widget::style::Theme;
+
+use crate::{IcedResource, Renderer};
+
+/// Whether a widget of the UIs displayed last frame has the keyboard focus, e.g. a
+/// text input being typed in, so that game input and
+/// [`IcedHotkeys`](crate::IcedHotkeys) can leave the keys to it.
+#[derive(Resource, Deref, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct IcedWantsKeyboard(bool);
+
+/// The outline drawn around the focused widget, see
+/// [`IcedSettings::focus_ring`](crate::IcedSettings::focus_ring).
+///
+/// Widgets don't report their own bounds to Iced's widget operations, so the ring is
+/// drawn around the innermost container or scrollable with an id that holds the focused
+/// widget: give focusable widgets a [`container`](crate::iced::widget::container) with an
+/// id to outline them.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FocusRing {
+    /// The color of the outline.
+    pub color: Color,
+    /// The width of the outline, in logical pixels.
+    pub thickness: f32,
+    /// The corner radius of the outline, in logical pixels.
+    pub radius: f32,
+    /// Whether to show the outline when the widget was focused with the mouse or by
+    /// touch too. By default it's only shown while navigating with the keyboard.
+    pub always_visible: bool,
+}
+
+impl Default for FocusRing {
+    fn default() -> Self {
+        Self {
+            color: Color::from_rgb8(0x4C, 0x9A, 0xFF),
+            thickness: 2.0,
+            radius: 4.0,
+            always_visible: false,
+        }
+    }
+}
+
+// Whether the last interaction was keyboard navigation rather than pointing, so that the
+// ring is only shown when it's needed, like CSS's `:focus-visible`.
+pub fn update_modality(keyboard_navigation: bool, events: &[IcedEvent]) -> bool {
+    events
+        .iter()
+        .fold(keyboard_navigation, |navigating, event| match event {
+            IcedEvent::Keyboard(keyboard::Event::KeyPressed {
+                key:
+                    keyboard::Key::Named(
+                        key::Named::Tab
+                        | key::Named::ArrowUp
+                        | key::Named::ArrowDown
+                        | key::Named::ArrowLeft
+                        | key::Named::ArrowRight,
+                    ),
+                ..
+            }) => true,
+            IcedEvent::Mouse(mouse::Event::ButtonPressed(_))
+            | IcedEvent::Touch(touch::Event::FingerPressed { .. }) => false,
+            _ => navigating,
+        })
+}
+
+// The visible bounds of the focused widget, and the area they are clipped to.
+pub fn focused_bounds<M>(
+    ui: &mut UserInterface<'_, M, Theme, Renderer>,
+    renderer: &Renderer,
+    bounds: Size,
+) -> Option<(Rectangle, Rectangle)> {
+    let mut finder = FindFocused {
+        frame: Frame {
+            translation: Vector::ZERO,
+            clip: Rectangle::with_size(bounds),
+            outline: None,
+        },
+        scroll: None,
+        found: None,
+    };
+    ui.operate(renderer, &mut finder);
+    finder.found
+}
+
+pub fn draw(renderer: &mut Renderer, ring: &FocusRing, (bounds, clip): (Rectangle, Rectangle)) {
+    renderer.with_layer(clip, |renderer| {
+        renderer.fill_quad(
+            renderer::Quad {
+                bounds: bounds.expand(ring.thickness),
+                border: Border {
+                    color: ring.color,
+                    width: ring.thickness,
+                    radius: (ring.radius + ring.thickness).into(),
+                },
+                ..Default::default()
+            },
+            Color::TRANSPARENT,
+        );
+    });
+}
+
+// Whether any widget of the UI has the keyboard focus.
+pub fn has_focus<M>(ui: &mut UserInterface<'_, M, Theme, Renderer>, renderer: &Renderer) -> bool {
+    let mut finder = AnyFocused(false);
+    ui.operate(renderer, &mut finder);
+    finder.0
+}
+
+// Publish the keyboard focus of this frame's UIs, once they've all been displayed.
+pub fn publish_keyboard_focus(props: Res<IcedResource>, mut wants: ResMut<IcedWantsKeyboard>) {
+    let focused = std::mem::take(&mut props.lock().unwrap().keyboard_focus);
+    if wants.0 != focused {
+        wants.0 = focused;
+    }
+}
+
+struct AnyFocused(bool);
+
+impl<T> Operation<T> for AnyFocused {
+    fn container(
+        &mut self,
+        _id: Option<&widget::Id>,
+        _bounds: Rectangle,
+        operate_on_children: &mut dyn FnMut(&mut dyn Operation<T>),
+    ) {
+        if !self.0 {
+            operate_on_children(self);
+        }
+    }
+
+    fn focusable(&mut self, state: &mut dyn operation::Focusable, _id: Option<&widget::Id>) {
+        self.0 |= state.is_focused();
+    }
+}
+
+#[derive(Clone, Copy)]
+struct Frame {
+    // The scroll offset of the content, and the visible area of scrollables around it.
+    translation: Vector,
+    clip: Rectangle,
+    // The innermost container with an id.
+    outline: Option<Rectangle>,
+}
+
+struct FindFocused {
+    frame: Frame,
+    // The translation of the scrollable whose content is operated on next.
+    scroll: Option<Vector>,
+    found: Option<(Rectangle, Rectangle)>,
+}
+
+impl<T> Operation<T> for FindFocused {
+    fn container(
+        &mut self,
+        id: Option<&widget::Id>,
+        bounds: Rectangle,
+        operate_on_children: &mut dyn FnMut(&mut dyn Operation<T>),
+    ) {
+        let parent = self.frame;
+        let visible = bounds - parent.translation;
+        if id.is_some() {
+            self.frame.outline = Some(visible);
+        }
+        // Scrollables report their offset, then operate on their content as a container.
+        if let Some(translation) = self.scroll.take() {
+            self.frame.translation = parent.translation + translation;
+            self.frame.clip = parent.clip.intersection(&visible).unwrap_or_default();
+        }
+        operate_on_children(self);
+        self.frame = parent;
+    }
+
+    fn scrollable(
+        &mut self,
+        _state: &mut dyn operation::Scrollable,
+        _id: Option<&widget::Id>,
+        _bounds: Rectangle,
+        translation: Vector,
+    ) {
+        self.scroll = Some(translation);
+    }
+
+    fn focusable(&mut self, state: &mut dyn operation::Focusable, _id: Option<&widget::Id>) {
+        if !state.is_focused() || self.found.is_some() {
+            return;
+        }
+        let clip = self.frame.clip;
+        self.found = self
+            .frame
+            .outline
+            .filter(|outline| outline.intersects(&clip))
+            .map(|outline| (outline, clip));
+    }
+}
@@ -0,0 +1,96 @@
+use std::borrow::Cow;
+
+use bevy_app::{App, PreUpdate};
+use bevy_asset::io::Reader;
+use bevy_asset::{Asset, AssetApp, AssetEvent, AssetLoader, AssetServer, Assets, AsyncReadExt};
+use bevy_asset::{BoxedFuture, LoadContext};
+use bevy_ecs::event::EventReader;
+use bevy_ecs::system::Res;
+use bevy_reflect::TypePath;
+use iced_core::text::Renderer as _;
+
+use crate::IcedResource;
+
+/// A font file for Iced, e.g. loaded with `asset_server.load::<IcedFont>("fonts/ui.ttf")`
+/// from a `.ttf` or `.otf` file, or added to `Assets<IcedFont>` from downloaded bytes.
+///
+/// Fonts are loaded into Iced once they're added, as the ones of
+/// [`IcedPlugin::fonts`](crate::IcedPlugin::fonts) are on startup, and UIs can then use
+/// them by name with [`Font::with_name`](crate::iced::Font::with_name). Text laid out
+/// before a font is loaded is shaped again with it. Fonts are shared by every renderer,
+/// including the ones created later, and stay loaded once the asset is dropped. Bevy's
+/// own `Font` assets are loaded from the same files, so ask for an `IcedFont` explicitly.
+///
+/// Font assets need bevy's `AssetPlugin`.
+/// [`IcedContext::load_font`](crate::IcedContext::load_font) takes the bytes of a font
+/// directly.
+#[derive(Asset, TypePath, Clone, Debug)]
+pub struct IcedFont {
+    bytes: Vec<u8>,
+}
+
+impl IcedFont {
+    /// A font from the contents of a font file.
+    pub fn new(bytes: impl Into<Vec<u8>>) -> Self {
+        Self {
+            bytes: bytes.into(),
+        }
+    }
+
+    /// The contents of the font file.
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+#[derive(Default)]
+struct IcedFontLoader;
+
+impl AssetLoader for IcedFontLoader {
+    type Asset = IcedFont;
+    type Settings = ();
+    type Error = std::io::Error;
+
+    fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader,
+        _settings: &'a (),
+        _load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<IcedFont, std::io::Error>> {
+        Box::pin(async move {
+            let mut bytes = Vec::new();
+            reader.read_to_end(&mut bytes).await?;
+            Ok(IcedFont::new(bytes))
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["ttf", "otf"]
+    }
+}
+
+// Font assets need the asset server, which isn't there without `AssetPlugin`.
+pub fn setup(app: &mut App) {
+    if !app.world.contains_resource::<AssetServer>() {
+        return;
+    }
+    app.init_asset::<IcedFont>()
+        .init_asset_loader::<IcedFontLoader>()
+        .add_systems(PreUpdate, load_fonts);
+}
+
+fn load_fonts(
+    mut events: EventReader<AssetEvent<IcedFont>>,
+    fonts: Res<Assets<IcedFont>>,
+    props: Res<IcedResource>,
+) {
+    for event in events.read() {
+        let AssetEvent::Added { id } = event else {
+            continue;
+        };
+        if let Some(font) = fonts.get(*id) {
+            let bytes = Cow::Owned(font.bytes.clone());
+            props.lock().unwrap().renderer.load_font(bytes);
+        }
+    }
+}
@@ -0,0 +1,182 @@
+//! Drive Iced UIs from tests, without a window system or a GPU.
+//!
+//! ```
+//! # use bevy::prelude::*;
+//! # use bevy_iced::harness::IcedTestHarness;
+//! # use bevy_iced::iced::{self, widget::button};
+//! # use bevy_iced::IcedContext;
+//! #[derive(Event, Clone, Debug, PartialEq)]
+//! enum UiMessage {
+//!     Clicked,
+//! }
+//!
+//! fn ui_system(mut ctx: IcedContext<UiMessage>) {
+//!     ctx.display(button("Click me").on_press(UiMessage::Clicked));
+//! }
+//!
+//! let mut harness = IcedTestHarness::new(800.0, 600.0);
+//! harness
+//!     .app
+//!     .add_event::<UiMessage>()
+//!     .add_systems(Update, ui_system);
+//! harness.click(iced::Point::new(10.0, 10.0));
+//! harness.run_frames(1);
+//! assert_eq!(harness.drain_messages::<UiMessage>(), vec![UiMessage::Clicked]);
+//! ```
+//!
+//! Tests that only check layouts can skip the app entirely with [`measure`], which uses
+//! a [`MeasuringRenderer`] with fixed text metrics.
+
+mod layout;
+#[cfg(doctest)]
+mod regressions;
+
+pub use crate::layout::Layouts;
+pub use layout::{measure, FixedEditor, FixedParagraph, MeasuringRenderer};
+
+use bevy_app::{App, PluginsState};
+use bevy_ecs::entity::Entity;
+use bevy_ecs::event::{Event, Events};
+use bevy_input::mouse::{MouseButton, MouseButtonInput};
+use bevy_input::touch::{TouchInput, TouchPhase};
+use bevy_input::ButtonState;
+use bevy_math::Vec2;
+use bevy_window::{CursorMoved, PrimaryWindow, ReceivedCharacter, Window, WindowResolution};
+use iced_core::{widget, SmolStr};
+use iced_wgpu::wgpu::TextureFormat;
+
+use crate::{iced, IcedPlugin, IcedResource};
+
+/// A minimal [`App`] running [`IcedPlugin`] headless, with a virtual primary window.
+pub struct IcedTestHarness {
+    /// The app being driven. Add your UI systems and message events to it.
+    pub app: App,
+    window: Entity,
+}
+
+impl IcedTestHarness {
+    /// Create a harness whose virtual window has the given logical size.
+    pub fn new(width: f32, height: f32) -> Self {
+        Self::with_plugin(IcedPlugin::default(), width, height)
+    }
+
+    /// Create a harness using a custom [`IcedPlugin`] configuration.
+    pub fn with_plugin(plugin: IcedPlugin, width: f32, height: f32) -> Self {
+        let mut app = App::new();
+        app.add_plugins(plugin);
+        let window = app
+            .world
+            .spawn((
+                Window {
+                    resolution: WindowResolution::new(width, height),
+                    ..Default::default()
+                },
+                PrimaryWindow,
+            ))
+            .id();
+        Self { app, window }
+    }
+
+    /// The entity of the virtual window.
+    pub fn window(&self) -> Entity {
+        self.window
+    }
+
+    /// Move the cursor to the given logical position.
+    pub fn move_cursor(&mut self, position: iced::Point) {
+        let position = Vec2::new(position.x, position.y);
+        if let Some(mut window) = self.app.world.get_mut::<Window>(self.window) {
+            window.set_cursor_position(Some(position));
+        }
+        self.app.world.send_event(CursorMoved {
+            window: self.window,
+            position,
+            delta: None,
+        });
+    }
+
+    /// Press and release the left mouse button at the given logical position.
+    ///
+    /// The events are processed by the next call to [`IcedTestHarness::run_frames`].
+    pub fn click(&mut self, position: iced::Point) {
+        self.move_cursor(position);
+        for state in [ButtonState::Pressed, ButtonState::Released] {
+            self.app.world.send_event(MouseButtonInput {
+                button: MouseButton::Left,
+                state,
+                window: self.window,
+            });
+        }
+    }
+
+    /// Send a touch of the finger `id` at the given logical position, e.g.
+    /// [`TouchPhase::Started`], then [`TouchPhase::Moved`] over a few frames to drag.
+    ///
+    /// The event is processed by the next call to [`IcedTestHarness::run_frames`].
+    pub fn touch(&mut self, id: u64, phase: TouchPhase, position: iced::Point) {
+        self.app.world.send_event(TouchInput {
+            phase,
+            position: Vec2::new(position.x, position.y),
+            window: self.window,
+            force: None,
+            id,
+        });
+    }
+
+    /// Type the given text into the focused widget.
+    ///
+    /// The events are processed by the next call to [`IcedTestHarness::run_frames`].
+    pub fn type_str(&mut self, text: &str) {
+        for char in text.chars() {
+            self.app.world.send_event(ReceivedCharacter {
+                window: self.window,
+                char: SmolStr::new(char.to_string()),
+            });
+        }
+    }
+
+    /// Run `n` updates of the app.
+    pub fn run_frames(&mut self, n: usize) {
+        if self.app.plugins_state() == PluginsState::Ready {
+            self.app.finish();
+            self.app.cleanup();
+        }
+        for _ in 0..n {
+            if let Some(props) = self.app.world.get_resource::<IcedResource>() {
+                props.lock().unwrap().layouts.clear();
+            }
+            self.app.update();
+        }
+    }
+
+    /// Hand the UI a surface of the given format, as the render node does before presenting
+    /// each frame. Returns whether the backend had to be rebuilt for it.
+    ///
+    /// There is no GPU here, so only the CPU renderer's side of the rebuild runs.
+    pub fn present_to(&mut self, format: TextureFormat) -> bool {
+        let Some(props) = self.app.world.get_resource::<IcedResource>() else {
+            return false;
+        };
+        props.lock().unwrap().adopt_surface_format(None, format)
+    }
+
+    /// The bounds of the widget with the given id, as laid out in the last frame.
+    ///
+    /// See [`Layouts`] for the widgets that can be located.
+    pub fn layout_of(&self, id: impl Into<widget::Id>) -> Option<iced::Rectangle> {
+        let props = self.app.world.get_resource::<IcedResource>()?;
+        let layouts = &props.lock().unwrap().layouts;
+        layouts.get(id)
+    }
+
+    /// Take the messages of type `M` sent during the last two frames.
+    ///
+    /// Like any bevy event, messages older than that have already been dropped.
+    pub fn drain_messages<M: Event>(&mut self) -> Vec<M> {
+        self.app
+            .world
+            .get_resource_mut::<Events<M>>()
+            .map(|mut events| events.drain().collect())
+            .unwrap_or_default()
+    }
+}
@@ -0,0 +1,305 @@
+use std::borrow::Cow;
+
+use iced_core::alignment;
+use iced_core::renderer::{self, Renderer};
+use iced_core::text::{self, editor, highlighter, Highlighter, LineHeight, Text};
+use iced_core::{Background, Color, Element, Font, Pixels, Point, Rectangle, Size, Transformation};
+use iced_runtime::user_interface::{self, UserInterface};
+use iced_widget::style::Theme;
+
+use crate::layout::Layouts;
+
+/// The advance of every character, relative to the text size.
+const CHAR_ADVANCE: f32 = 0.5;
+
+/// Lay out `element` in a viewport of the given logical size with a [`MeasuringRenderer`].
+///
+/// Write the UI generically over its renderer to lay it out both here and in the app:
+///
+/// ```
+/// # use bevy_iced::harness::measure;
+/// # use bevy_iced::iced::{self, widget::{container, text}};
+/// # enum Message {}
+/// fn panel<'a, R: iced::advanced::text::Renderer + 'a>() -> iced::Element<'a, Message, iced::Theme, R> {
+///     container(text("Inventory")).id(container::Id::new("panel")).into()
+/// }
+///
+/// let layouts = measure(panel(), iced::Size::new(800.0, 600.0));
+/// assert_eq!(layouts.get(container::Id::new("panel")).unwrap().width, 72.0);
+/// ```
+pub fn measure<'a, M>(
+    element: impl Into<Element<'a, M, Theme, MeasuringRenderer>>,
+    size: Size,
+) -> Layouts {
+    let mut renderer = MeasuringRenderer;
+    let mut ui = UserInterface::build(
+        element,
+        size,
+        user_interface::Cache::default(),
+        &mut renderer,
+    );
+    Layouts::collect(&mut ui, &renderer)
+}
+
+/// A renderer that draws nothing and measures text with a fixed metric, so that layouts
+/// are identical on every platform.
+///
+/// Every character advances by half the text size, and every line is as tall as its
+/// line height; text never wraps. Widgets that need other renderer capabilities, like
+/// images, can't be laid out with it.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MeasuringRenderer;
+
+impl Renderer for MeasuringRenderer {
+    fn with_layer(&mut self, _bounds: Rectangle, _f: impl FnOnce(&mut Self)) {}
+
+    fn with_transformation(&mut self, _transformation: Transformation, _f: impl FnOnce(&mut Self)) {
+    }
+
+    fn fill_quad(&mut self, _quad: renderer::Quad, _background: impl Into<Background>) {}
+
+    fn clear(&mut self) {}
+}
+
+impl text::Renderer for MeasuringRenderer {
+    type Font = Font;
+    type Paragraph = FixedParagraph;
+    type Editor = FixedEditor;
+
+    const ICON_FONT: Font = Font::DEFAULT;
+    const CHECKMARK_ICON: char = '0';
+    const ARROW_DOWN_ICON: char = '0';
+
+    fn default_font(&self) -> Font {
+        Font::default()
+    }
+
+    fn default_size(&self) -> Pixels {
+        Pixels(16.0)
+    }
+
+    fn load_font(&mut self, _font: Cow<'static, [u8]>) {}
+
+    fn fill_paragraph(
+        &mut self,
+        _paragraph: &FixedParagraph,
+        _position: Point,
+        _color: Color,
+        _clip_bounds: Rectangle,
+    ) {
+    }
+
+    fn fill_editor(
+        &mut self,
+        _editor: &FixedEditor,
+        _position: Point,
+        _color: Color,
+        _clip_bounds: Rectangle,
+    ) {
+    }
+
+    fn fill_text(
+        &mut self,
+        _text: Text<'_, Font>,
+        _position: Point,
+        _color: Color,
+        _clip_bounds: Rectangle,
+    ) {
+    }
+}
+
+fn measure_lines<'a>(
+    lines: impl Iterator<Item = &'a str>,
+    size: Pixels,
+    line_height: LineHeight,
+) -> Size {
+    let (mut width, mut count) = (0.0f32, 0);
+    for line in lines {
+        width = width.max(line.chars().count() as f32 * size.0 * CHAR_ADVANCE);
+        count += 1;
+    }
+    Size::new(width, count.max(1) as f32 * line_height.to_absolute(size).0)
+}
+
+/// A paragraph measured by [`MeasuringRenderer`].
+#[derive(Debug, Clone)]
+pub struct FixedParagraph {
+    content: String,
+    bounds: Size,
+    size: Pixels,
+    line_height: LineHeight,
+    font: Font,
+    horizontal_alignment: alignment::Horizontal,
+    vertical_alignment: alignment::Vertical,
+    shaping: text::Shaping,
+    min_bounds: Size,
+}
+
+impl Default for FixedParagraph {
+    fn default() -> Self {
+        text::Paragraph::with_text(Text {
+            content: "",
+            bounds: Size::ZERO,
+            size: Pixels(16.0),
+            line_height: LineHeight::default(),
+            font: Font::default(),
+            horizontal_alignment: alignment::Horizontal::Left,
+            vertical_alignment: alignment::Vertical::Top,
+            shaping: text::Shaping::default(),
+        })
+    }
+}
+
+impl text::Paragraph for FixedParagraph {
+    type Font = Font;
+
+    fn with_text(text: Text<'_, Font>) -> Self {
+        Self {
+            content: text.content.to_owned(),
+            bounds: text.bounds,
+            size: text.size,
+            line_height: text.line_height,
+            font: text.font,
+            horizontal_alignment: text.horizontal_alignment,
+            vertical_alignment: text.vertical_alignment,
+            shaping: text.shaping,
+            min_bounds: measure_lines(text.content.lines(), text.size, text.line_height),
+        }
+    }
+
+    fn resize(&mut self, new_bounds: Size) {
+        self.bounds = new_bounds;
+    }
+
+    fn compare(&self, text: Text<'_, Font>) -> text::Difference {
+        if self.content != text.content
+            || self.size != text.size
+            || self.line_height != text.line_height
+            || self.font != text.font
+            || self.horizontal_alignment != text.horizontal_alignment
+            || self.vertical_alignment != text.vertical_alignment
+            || self.shaping != text.shaping
+        {
+            text::Difference::Shape
+        } else if self.bounds != text.bounds {
+            text::Difference::Bounds
+        } else {
+            text::Difference::None
+        }
+    }
+
+    fn horizontal_alignment(&self) -> alignment::Horizontal {
+        self.horizontal_alignment
+    }
+
+    fn vertical_alignment(&self) -> alignment::Vertical {
+        self.vertical_alignment
+    }
+
+    fn min_bounds(&self) -> Size {
+        self.min_bounds
+    }
+
+    fn hit_test(&self, point: Point) -> Option<text::Hit> {
+        let advance = self.size.0 * CHAR_ADVANCE;
+        let chars = self.content.chars().count();
+        let index = (point.x / advance).round().max(0.0) as usize;
+        Some(text::Hit::CharOffset(index.min(chars)))
+    }
+
+    fn grapheme_position(&self, line: usize, index: usize) -> Option<Point> {
+        let line_height = self.line_height.to_absolute(self.size).0;
+        self.content.lines().nth(line).map(|_| {
+            Point::new(
+                index as f32 * self.size.0 * CHAR_ADVANCE,
+                line as f32 * line_height,
+            )
+        })
+    }
+}
+
+/// A text editor measured by [`MeasuringRenderer`]. Editing actions are ignored.
+#[derive(Debug, Clone)]
+pub struct FixedEditor {
+    lines: Vec<String>,
+    bounds: Size,
+    size: Pixels,
+    line_height: LineHeight,
+}
+
+impl Default for FixedEditor {
+    fn default() -> Self {
+        Self {
+            lines: Vec::new(),
+            bounds: Size::ZERO,
+            size: Pixels(16.0),
+            line_height: LineHeight::default(),
+        }
+    }
+}
+
+impl text::Editor for FixedEditor {
+    type Font = Font;
+
+    fn with_text(text: &str) -> Self {
+        Self {
+            lines: text.lines().map(ToOwned::to_owned).collect(),
+            ..Default::default()
+        }
+    }
+
+    fn cursor(&self) -> editor::Cursor {
+        editor::Cursor::Caret(Point::ORIGIN)
+    }
+
+    fn cursor_position(&self) -> (usize, usize) {
+        (0, 0)
+    }
+
+    fn selection(&self) -> Option<String> {
+        None
+    }
+
+    fn line(&self, index: usize) -> Option<&str> {
+        self.lines.get(index).map(String::as_str)
+    }
+
+    fn line_count(&self) -> usize {
+        self.lines.len()
+    }
+
+    fn perform(&mut self, _action: editor::Action) {}
+
+    fn bounds(&self) -> Size {
+        self.bounds
+    }
+
+    fn min_bounds(&self) -> Size {
+        measure_lines(
+            self.lines.iter().map(String::as_str),
+            self.size,
+            self.line_height,
+        )
+    }
+
+    fn update(
+        &mut self,
+        new_bounds: Size,
+        _new_font: Font,
+        new_size: Pixels,
+        new_line_height: LineHeight,
+        _new_highlighter: &mut impl Highlighter,
+    ) {
+        self.bounds = new_bounds;
+        self.size = new_size;
+        self.line_height = new_line_height;
+    }
+
+    fn highlight<H: Highlighter>(
+        &mut self,
+        _font: Font,
+        _highlighter: &mut H,
+        _format_highlight: impl Fn(&H::Highlight) -> highlighter::Format<Font>,
+    ) {
+    }
+}
@@ -0,0 +1,745 @@
+//! Regression tests driving [`IcedTestHarness`](super::IcedTestHarness), run as doctests.
+//! The ones needing another feature are on the items below, which are only there with it.
+//!
+//! A UI displayed into a secondary window is told it isn't supported, and once that window
+//! is closed, that it's gone, rather than panicking, and the UIs of the primary window
+//! aren't disturbed:
+//!
+//! ```
+//! use bevy::prelude::*;
+//! use bevy_iced::harness::IcedTestHarness;
+//! use bevy_iced::iced::widget::text;
+//! use bevy_iced::{IcedContext, IcedDisplayError};
+//!
+//! #[derive(Event)]
+//! struct UiMessage;
+//!
+//! #[derive(Resource, Default)]
+//! struct Displayed {
+//!     primary: Option<Entity>,
+//!     tool: Option<Entity>,
+//!     results: Vec<(Result<(), IcedDisplayError>, Result<(), IcedDisplayError>)>,
+//! }
+//!
+//! fn ui_system(mut ctx: IcedContext<UiMessage>, mut displayed: ResMut<Displayed>) {
+//!     let primary = ctx.display_in_window(text("Main"), displayed.primary.unwrap());
+//!     let tool = ctx.display_in_window(text("Tool"), displayed.tool.unwrap());
+//!     displayed.results.push((primary, tool));
+//! }
+//!
+//! let mut harness = IcedTestHarness::new(800.0, 600.0);
+//! harness
+//!     .app
+//!     .add_event::<UiMessage>()
+//!     .init_resource::<Displayed>()
+//!     .add_systems(Update, ui_system);
+//! let primary = harness.window();
+//! for _ in 0..3 {
+//!     let tool = harness.app.world.spawn(Window::default()).id();
+//!     *harness.app.world.resource_mut::<Displayed>() = Displayed {
+//!         primary: Some(primary),
+//!         tool: Some(tool),
+//!         results: Vec::new(),
+//!     };
+//!     harness.run_frames(2);
+//!     harness.app.world.despawn(tool);
+//!     harness.run_frames(2);
+//!
+//!     let not_driven = (Ok(()), Err(IcedDisplayError::WindowNotDriven(tool)));
+//!     let not_found = (Ok(()), Err(IcedDisplayError::WindowNotFound(tool)));
+//!     let results = &harness.app.world.resource::<Displayed>().results;
+//!     assert_eq!(results, &[not_driven, not_driven, not_found, not_found]);
+//! }
+//! ```
+//!
+//! A UI is displayed in the very first update after its window is created, before the
+//! window's `WindowCreated` event is handled:
+//!
+//! ```
+//! use bevy::prelude::*;
+//! use bevy::window::WindowCreated;
+//! use bevy_iced::harness::IcedTestHarness;
+//! use bevy_iced::iced::widget::{container, text};
+//! use bevy_iced::iced::{Length, Rectangle};
+//! use bevy_iced::IcedContext;
+//!
+//! #[derive(Event)]
+//! struct UiMessage;
+//!
+//! fn splash_system(mut ctx: IcedContext<UiMessage>) {
+//!     ctx.display(
+//!         container(text("Loading"))
+//!             .id(container::Id::new("splash"))
+//!             .width(Length::Fill)
+//!             .height(Length::Fill),
+//!     );
+//! }
+//!
+//! let mut harness = IcedTestHarness::new(800.0, 600.0);
+//! harness
+//!     .app
+//!     .add_event::<UiMessage>()
+//!     .add_systems(Update, splash_system);
+//! let window = harness.window();
+//! harness.app.world.send_event(WindowCreated { window });
+//! harness.run_frames(1);
+//! assert_eq!(
+//!     harness.layout_of(container::Id::new("splash")),
+//!     Some(Rectangle::new(Default::default(), [800.0, 600.0].into())),
+//! );
+//! ```
+//!
+//! A popup window created and closed within a frame, with its events still to be handled,
+//! doesn't crash the app, and the UIs keep being laid out for the primary window:
+//!
+//! ```
+//! use bevy::prelude::*;
+//! use bevy::window::{WindowClosed, WindowCreated, WindowResized, WindowResolution};
+//! use bevy_iced::harness::IcedTestHarness;
+//! use bevy_iced::iced::widget::{container, text};
+//! use bevy_iced::iced::{Length, Rectangle};
+//! use bevy_iced::IcedContext;
+//!
+//! #[derive(Event)]
+//! struct UiMessage;
+//!
+//! fn ui_system(mut ctx: IcedContext<UiMessage>) {
+//!     ctx.display(
+//!         container(text("Main"))
+//!             .id(container::Id::new("main"))
+//!             .width(Length::Fill)
+//!             .height(Length::Fill),
+//!     );
+//! }
+//!
+//! let mut harness = IcedTestHarness::new(800.0, 600.0);
+//! harness
+//!     .app
+//!     .add_event::<UiMessage>()
+//!     .add_systems(Update, ui_system);
+//! harness.run_frames(1);
+//! for _ in 0..3 {
+//!     let popup = harness
+//!         .app
+//!         .world
+//!         .spawn(Window {
+//!             resolution: WindowResolution::new(200.0, 100.0),
+//!             ..default()
+//!         })
+//!         .id();
+//!     harness.app.world.send_event(WindowCreated { window: popup });
+//!     harness.app.world.send_event(WindowResized {
+//!         window: popup,
+//!         width: 200.0,
+//!         height: 100.0,
+//!     });
+//!     harness.app.world.despawn(popup);
+//!     harness.app.world.send_event(WindowClosed { window: popup });
+//!     harness.run_frames(2);
+//!     assert_eq!(
+//!         harness.layout_of(container::Id::new("main")),
+//!         Some(Rectangle::new(Default::default(), [800.0, 600.0].into())),
+//!     );
+//! }
+//! ```
+//!
+//! With [`IcedSettings::scale_factor`](crate::IcedSettings::scale_factor) overriding the
+//! window's, a button is pressed where it's drawn rather than where it would be at the
+//! window's scale factor:
+//!
+//! ```
+//! use bevy::prelude::*;
+//! use bevy_iced::harness::IcedTestHarness;
+//! use bevy_iced::iced::widget::{button, container};
+//! use bevy_iced::iced::{Length, Point, Rectangle, Size};
+//! use bevy_iced::{IcedContext, IcedPlugin, IcedSettings};
+//!
+//! #[derive(Event, Clone, Debug, PartialEq)]
+//! enum UiMessage {
+//!     Pressed,
+//! }
+//!
+//! fn ui_system(mut ctx: IcedContext<UiMessage>) {
+//!     ctx.display(
+//!         container(
+//!             container(
+//!                 button("Press")
+//!                     .on_press(UiMessage::Pressed)
+//!                     .width(Length::Fill)
+//!                     .height(Length::Fill),
+//!             )
+//!             .id(container::Id::new("button"))
+//!             .width(100)
+//!             .height(40),
+//!         )
+//!         .padding(50),
+//!     );
+//! }
+//!
+//! let plugin = IcedPlugin {
+//!     iced_settings: IcedSettings::default().with_scale_factor(Some(2.0)),
+//!     ..default()
+//! };
+//! let mut harness = IcedTestHarness::with_plugin(plugin, 800.0, 600.0);
+//! harness
+//!     .app
+//!     .add_event::<UiMessage>()
+//!     .add_systems(Update, ui_system);
+//! harness.run_frames(1);
+//! let bounds = harness.layout_of(container::Id::new("button")).unwrap();
+//! assert_eq!(bounds, Rectangle::new(Point::new(50.0, 50.0), Size::new(100.0, 40.0)));
+//!
+//! // Where the button would be at the window's scale factor of 1.0, it's drawn at 2.0.
+//! harness.click(Point::new(60.0, 60.0));
+//! harness.run_frames(1);
+//! assert_eq!(harness.drain_messages::<UiMessage>(), vec![]);
+//!
+//! harness.click(Point::new(250.0, 160.0));
+//! harness.run_frames(1);
+//! assert_eq!(harness.drain_messages::<UiMessage>(), vec![UiMessage::Pressed]);
+//! ```
+//!
+//! A UI displayed after the window is resized in the same frame is laid out at the new
+//! size, which the frame is presented at:
+//!
+//! ```
+//! use bevy::prelude::*;
+//! use bevy::window::PrimaryWindow;
+//! use bevy_iced::harness::IcedTestHarness;
+//! use bevy_iced::iced::widget::{container, text};
+//! use bevy_iced::iced::{Length, Rectangle};
+//! use bevy_iced::IcedContext;
+//!
+//! #[derive(Event)]
+//! struct UiMessage;
+//!
+//! #[derive(Resource)]
+//! struct Width(f32);
+//!
+//! fn resize_system(mut windows: Query<&mut Window, With<PrimaryWindow>>, width: Res<Width>) {
+//!     windows.single_mut().resolution.set(width.0, 600.0);
+//! }
+//!
+//! fn ui_system(mut ctx: IcedContext<UiMessage>) {
+//!     ctx.display(
+//!         container(text("Inventory"))
+//!             .id(container::Id::new("inventory"))
+//!             .width(Length::Fill)
+//!             .height(Length::Fill),
+//!     );
+//! }
+//!
+//! let mut harness = IcedTestHarness::new(800.0, 600.0);
+//! harness
+//!     .app
+//!     .add_event::<UiMessage>()
+//!     .insert_resource(Width(800.0))
+//!     .add_systems(Update, (resize_system, ui_system).chain());
+//! harness.run_frames(1);
+//! for width in [640.0, 1024.0, 1025.0] {
+//!     harness.app.world.resource_mut::<Width>().0 = width;
+//!     harness.run_frames(1);
+//!     assert_eq!(
+//!         harness.layout_of(container::Id::new("inventory")),
+//!         Some(Rectangle::new(Default::default(), [width, 600.0].into())),
+//!     );
+//! }
+//! ```
+//!
+//! A UI filling the window, like a HUD laid out in a container with an id, only captures
+//! the pointer over its widgets handling it, and leaves the game the rest of the window:
+//!
+//! ```
+//! use bevy::prelude::*;
+//! use bevy_iced::harness::IcedTestHarness;
+//! use bevy_iced::iced::widget::{button, column, container, text};
+//! use bevy_iced::iced::{Length, Point};
+//! use bevy_iced::{IcedContext, IcedEventCaptureStatus};
+//!
+//! #[derive(Event, Clone)]
+//! enum UiMessage {
+//!     Pause,
+//! }
+//!
+//! fn hud_system(mut ctx: IcedContext<UiMessage>) {
+//!     ctx.display(
+//!         container(column![
+//!             button("Pause").on_press(UiMessage::Pause),
+//!             text("Score: 12"),
+//!         ])
+//!         .id(container::Id::new("hud"))
+//!         .width(Length::Fill)
+//!         .height(Length::Fill),
+//!     );
+//! }
+//!
+//! let mut harness = IcedTestHarness::new(800.0, 600.0);
+//! harness
+//!     .app
+//!     .add_event::<UiMessage>()
+//!     .add_systems(Update, hud_system);
+//! harness.run_frames(1);
+//! let captured = |harness: &IcedTestHarness| {
+//!     harness
+//!         .app
+//!         .world
+//!         .resource::<IcedEventCaptureStatus>()
+//!         .pointer_captured
+//! };
+//!
+//! // Over the text and the rest of the container.
+//! for position in [Point::new(10.0, 40.0), Point::new(400.0, 300.0)] {
+//!     harness.move_cursor(position);
+//!     harness.run_frames(1);
+//!     assert!(!captured(&harness), "captured at {position:?}");
+//! }
+//! // Over the button.
+//! harness.move_cursor(Point::new(10.0, 10.0));
+//! harness.run_frames(1);
+//! assert!(captured(&harness));
+//! ```
+//!
+//! Two UIs drawing to the window every frame both stay interactive: a click where the
+//! UI with the higher [`z_index`](crate::DisplayOptions::z_index) is drawn only reaches
+//! it, whichever is displayed first, and a click next to it reaches the UI below:
+//!
+//! ```
+//! use bevy::prelude::*;
+//! use bevy_iced::harness::IcedTestHarness;
+//! use bevy_iced::iced::widget::{button, container};
+//! use bevy_iced::iced::{Length, Point};
+//! use bevy_iced::{DisplayOptions, IcedContext};
+//!
+//! #[derive(Event, Clone, Debug, PartialEq)]
+//! enum HudMessage {
+//!     Clicked,
+//! }
+//!
+//! #[derive(Event, Clone, Debug, PartialEq)]
+//! enum MenuMessage {
+//!     Clicked,
+//! }
+//!
+//! fn hud_system(mut ctx: IcedContext<HudMessage>) {
+//!     ctx.display(
+//!         button("World")
+//!             .on_press(HudMessage::Clicked)
+//!             .width(Length::Fill)
+//!             .height(Length::Fill),
+//!     );
+//! }
+//!
+//! fn menu_system(mut ctx: IcedContext<MenuMessage>) {
+//!     let menu = container(
+//!         button("Menu")
+//!             .on_press(MenuMessage::Clicked)
+//!             .width(100)
+//!             .height(40),
+//!     )
+//!     .padding(50);
+//!     ctx.display_with(menu, DisplayOptions { z_index: 1, ..Default::default() });
+//! }
+//!
+//! for menu_first in [false, true] {
+//!     let mut harness = IcedTestHarness::new(800.0, 600.0);
+//!     harness
+//!         .app
+//!         .add_event::<HudMessage>()
+//!         .add_event::<MenuMessage>();
+//!     if menu_first {
+//!         harness.app.add_systems(Update, (menu_system, hud_system).chain());
+//!     } else {
+//!         harness.app.add_systems(Update, (hud_system, menu_system).chain());
+//!     }
+//!     harness.run_frames(1);
+//!     for _ in 0..2 {
+//!         harness.click(Point::new(100.0, 70.0));
+//!         harness.run_frames(1);
+//!         assert_eq!(harness.drain_messages::<MenuMessage>(), vec![MenuMessage::Clicked]);
+//!         assert_eq!(harness.drain_messages::<HudMessage>(), vec![]);
+//!
+//!         harness.click(Point::new(400.0, 300.0));
+//!         harness.run_frames(1);
+//!         assert_eq!(harness.drain_messages::<MenuMessage>(), vec![]);
+//!         assert_eq!(harness.drain_messages::<HudMessage>(), vec![HudMessage::Clicked]);
+//!     }
+//! }
+//! ```
+//!
+//! With a text input in each of two UIs, the keys only go to the focused one, including
+//! on the frame the focus moves from one UI to the other:
+//!
+//! ```
+//! use bevy::prelude::*;
+//! use bevy_iced::harness::IcedTestHarness;
+//! use bevy_iced::iced::widget::{container, text_input};
+//! use bevy_iced::iced::Point;
+//! use bevy_iced::{DisplayOptions, IcedContext};
+//!
+//! #[derive(Event, Clone, Debug, PartialEq)]
+//! enum ChatMessage {
+//!     Typed(String),
+//! }
+//!
+//! #[derive(Event, Clone, Debug, PartialEq)]
+//! enum SearchMessage {
+//!     Typed(String),
+//! }
+//!
+//! fn chat_system(mut ctx: IcedContext<ChatMessage>) {
+//!     ctx.display(text_input("Chat", "").on_input(ChatMessage::Typed).width(300));
+//! }
+//!
+//! fn search_system(mut ctx: IcedContext<SearchMessage>) {
+//!     let search = container(text_input("Search", "").on_input(SearchMessage::Typed).width(300))
+//!         .padding([100, 0, 0, 0]);
+//!     ctx.display_with(search, DisplayOptions { z_index: 1, ..Default::default() });
+//! }
+//!
+//! for search_first in [false, true] {
+//!     let mut harness = IcedTestHarness::new(800.0, 600.0);
+//!     harness
+//!         .app
+//!         .add_event::<ChatMessage>()
+//!         .add_event::<SearchMessage>();
+//!     if search_first {
+//!         harness.app.add_systems(Update, (search_system, chat_system).chain());
+//!     } else {
+//!         harness.app.add_systems(Update, (chat_system, search_system).chain());
+//!     }
+//!     harness.run_frames(1);
+//!     let (chat, search) = (Point::new(20.0, 15.0), Point::new(20.0, 115.0));
+//!     let typed = |harness: &mut IcedTestHarness| {
+//!         let chat = harness.drain_messages::<ChatMessage>();
+//!         let search = harness.drain_messages::<SearchMessage>();
+//!         (chat, search)
+//!     };
+//!
+//!     // Focusing a frame before typing.
+//!     harness.click(chat);
+//!     harness.run_frames(1);
+//!     harness.type_str("a");
+//!     harness.run_frames(1);
+//!     assert_eq!(typed(&mut harness), (vec![ChatMessage::Typed("a".into())], vec![]));
+//!
+//!     // Focusing the other UI on the frame the keys are typed, up and down.
+//!     harness.click(search);
+//!     harness.type_str("b");
+//!     harness.run_frames(1);
+//!     assert_eq!(typed(&mut harness), (vec![], vec![SearchMessage::Typed("b".into())]));
+//!
+//!     harness.click(chat);
+//!     harness.type_str("c");
+//!     harness.run_frames(1);
+//!     assert_eq!(typed(&mut harness), (vec![ChatMessage::Typed("c".into())], vec![]));
+//! }
+//! ```
+//!
+//! With [`IcedSettings::block_captured_input`](crate::IcedSettings::block_captured_input),
+//! the keys are kept from the game while a text input has the focus, but a held Shift
+//! still selects for the text input, frames after it was pressed:
+//!
+//! ```
+//! use bevy::input::keyboard::{Key, KeyboardInput};
+//! use bevy::input::ButtonState;
+//! use bevy::prelude::*;
+//! use bevy_iced::harness::IcedTestHarness;
+//! use bevy_iced::iced::widget::text_input;
+//! use bevy_iced::iced::Point;
+//! use bevy_iced::{IcedContext, IcedPlugin, IcedSettings};
+//!
+//! #[derive(Event, Clone)]
+//! enum UiMessage {
+//!     Edited(String),
+//! }
+//!
+//! #[derive(Resource)]
+//! struct Name(String);
+//!
+//! fn ui_system(mut ctx: IcedContext<UiMessage>, name: Res<Name>) {
+//!     ctx.display(text_input("Name", &name.0).on_input(UiMessage::Edited).width(300));
+//! }
+//!
+//! fn edit_system(mut messages: EventReader<UiMessage>, mut name: ResMut<Name>) {
+//!     for UiMessage::Edited(text) in messages.read() {
+//!         name.0 = text.clone();
+//!     }
+//! }
+//!
+//! let plugin = IcedPlugin {
+//!     iced_settings: IcedSettings::default().with_block_captured_input(true),
+//!     ..default()
+//! };
+//! let mut harness = IcedTestHarness::with_plugin(plugin, 800.0, 600.0);
+//! harness
+//!     .app
+//!     .add_event::<UiMessage>()
+//!     .insert_resource(Name("hello".into()))
+//!     .add_systems(Update, (ui_system, edit_system).chain());
+//! harness.run_frames(1);
+//! harness.click(Point::new(290.0, 15.0));
+//! harness.run_frames(2);
+//!
+//! let window = harness.window();
+//! let mut key = |harness: &mut IcedTestHarness, key_code, logical_key, state| {
+//!     harness.app.world.send_event(KeyboardInput {
+//!         key_code,
+//!         logical_key,
+//!         state,
+//!         window,
+//!     });
+//! };
+//! key(&mut harness, KeyCode::ShiftLeft, Key::Shift, ButtonState::Pressed);
+//! harness.run_frames(3);
+//! let keys = harness.app.world.resource::<ButtonInput<KeyCode>>();
+//! assert!(!keys.pressed(KeyCode::ShiftLeft));
+//!
+//! // Select the last two characters, and replace them.
+//! for _ in 0..2 {
+//!     key(&mut harness, KeyCode::ArrowLeft, Key::ArrowLeft, ButtonState::Pressed);
+//!     key(&mut harness, KeyCode::ArrowLeft, Key::ArrowLeft, ButtonState::Released);
+//!     harness.run_frames(1);
+//! }
+//! key(&mut harness, KeyCode::ShiftLeft, Key::Shift, ButtonState::Released);
+//! harness.type_str("p");
+//! harness.run_frames(2);
+//! assert_eq!(harness.app.world.resource::<Name>().0, "help");
+//! ```
+//!
+//! While a modal UI blocks the game's input, with
+//! [`IcedSettings::modal_blocks_game_input`](crate::IcedSettings::modal_blocks_game_input),
+//! a Ctrl pressed before it opened is still
+//! held for the modal's text input, though the game's keys are released:
+//!
+//! ```
+//! use bevy::input::keyboard::{Key, KeyboardInput};
+//! use bevy::input::{ButtonState, InputPlugin};
+//! use bevy::prelude::*;
+//! use bevy_iced::harness::IcedTestHarness;
+//! use bevy_iced::iced::widget::{container, text_input};
+//! use bevy_iced::{IcedContext, IcedModal, IcedPlugin, IcedSettings};
+//!
+//! #[derive(Event, Clone)]
+//! enum UiMessage {
+//!     Renamed(String),
+//! }
+//!
+//! #[derive(Resource)]
+//! struct Rename(Option<String>);
+//!
+//! fn rename_system(mut ctx: IcedContext<UiMessage>, rename: Res<Rename>) {
+//!     if let Some(name) = &rename.0 {
+//!         let input = text_input("Name", name).on_input(UiMessage::Renamed).width(300);
+//!         ctx.display_modal(container(input).id(container::Id::new("rename")));
+//!     }
+//! }
+//!
+//! fn edit_system(mut messages: EventReader<UiMessage>, mut rename: ResMut<Rename>) {
+//!     for UiMessage::Renamed(name) in messages.read() {
+//!         rename.0 = Some(name.clone());
+//!     }
+//! }
+//!
+//! let plugin = IcedPlugin {
+//!     iced_settings: IcedSettings::default().with_modal_blocks_game_input(true),
+//!     ..default()
+//! };
+//! let mut harness = IcedTestHarness::with_plugin(plugin, 800.0, 600.0);
+//! harness
+//!     .app
+//!     .add_event::<UiMessage>()
+//!     .add_plugins(InputPlugin)
+//!     .insert_resource(Rename(None))
+//!     .add_systems(Update, (rename_system, edit_system).chain());
+//! let window = harness.window();
+//! let mut key = |harness: &mut IcedTestHarness, key_code, logical_key, state| {
+//!     harness.app.world.send_event(KeyboardInput {
+//!         key_code,
+//!         logical_key,
+//!         state,
+//!         window,
+//!     });
+//! };
+//! key(&mut harness, KeyCode::ControlLeft, Key::Control, ButtonState::Pressed);
+//! harness.run_frames(1);
+//! let keys = harness.app.world.resource::<ButtonInput<KeyCode>>();
+//! assert!(keys.pressed(KeyCode::ControlLeft));
+//!
+//! harness.app.world.resource_mut::<Rename>().0 = Some("Sword".into());
+//! harness.run_frames(1);
+//! let bounds = harness.layout_of(container::Id::new("rename")).unwrap();
+//! harness.click(bounds.center());
+//! harness.run_frames(2);
+//! assert!(harness.app.world.resource::<IcedModal>().is_open());
+//! let keys = harness.app.world.resource::<ButtonInput<KeyCode>>();
+//! assert!(!keys.pressed(KeyCode::ControlLeft));
+//!
+//! // Ctrl+A selects all of the name, which typing then replaces.
+//! let a = Key::Character("a".into());
+//! key(&mut harness, KeyCode::KeyA, a.clone(), ButtonState::Pressed);
+//! key(&mut harness, KeyCode::KeyA, a, ButtonState::Released);
+//! harness.run_frames(1);
+//! key(&mut harness, KeyCode::ControlLeft, Key::Control, ButtonState::Released);
+//! harness.run_frames(1);
+//! harness.type_str("Axe");
+//! harness.run_frames(2);
+//! assert_eq!(harness.app.world.resource::<Rename>().0.as_deref(), Some("Axe"));
+//! ```
+//!
+//! With the UI rendered on the CPU through
+//! [`IcedSettings::software_rendering`](crate::IcedSettings::software_rendering), the
+//! backend is only rebuilt for the first frame presented to a surface of another format,
+//! not for every frame after it:
+//!
+//! ```
+//! use bevy::prelude::*;
+//! use bevy::render::render_resource::TextureFormat;
+//! use bevy_iced::harness::IcedTestHarness;
+//! use bevy_iced::iced::widget::text;
+//! use bevy_iced::{IcedContext, IcedPlugin, IcedSettings};
+//!
+//! #[derive(Event)]
+//! struct UiMessage;
+//!
+//! fn ui_system(mut ctx: IcedContext<UiMessage>) {
+//!     ctx.display(text("Score: 0"));
+//! }
+//!
+//! let plugin = IcedPlugin {
+//!     iced_settings: IcedSettings::default().with_software_rendering(true),
+//!     ..default()
+//! };
+//! let mut harness = IcedTestHarness::with_plugin(plugin, 800.0, 600.0);
+//! harness
+//!     .app
+//!     .add_event::<UiMessage>()
+//!     .add_systems(Update, ui_system);
+//! let mut rebuilt = Vec::new();
+//! for _ in 0..5 {
+//!     harness.run_frames(1);
+//!     rebuilt.push(harness.present_to(TextureFormat::Rgba8UnormSrgb));
+//! }
+//! assert_eq!(rebuilt, [true, false, false, false, false]);
+//! ```
+//!
+//! With [`IcedSettings::catch_panics`](crate::IcedSettings::catch_panics), a view that
+//! panics while being built by [`IcedContext::display_view`](crate::IcedContext::display_view) shows the error banner in its
+//! place, and is only reported once while it keeps panicking:
+//!
+//! ```
+//! use bevy::prelude::*;
+//! use bevy_iced::harness::IcedTestHarness;
+//! use bevy_iced::iced::widget::text;
+//! use bevy_iced::snapshot::Snapshot;
+//! use bevy_iced::{IcedContext, IcedPanic, IcedPlugin, IcedSettings};
+//!
+//! #[derive(Event)]
+//! struct UiMessage;
+//!
+//! fn ui_system(mut ctx: IcedContext<UiMessage>) {
+//!     let levels: Vec<u32> = Vec::new();
+//!     ctx.display_view(|| text(format!("Level {}", levels[0])).into());
+//! }
+//!
+//! let plugin = IcedPlugin {
+//!     iced_settings: IcedSettings::default().with_catch_panics(true),
+//!     ..default()
+//! };
+//! let mut harness = IcedTestHarness::with_plugin(plugin, 800.0, 600.0);
+//! harness
+//!     .app
+//!     .add_event::<UiMessage>()
+//!     .add_systems(Update, ui_system);
+//! let mut panics = Vec::new();
+//! for _ in 0..5 {
+//!     harness.run_frames(1);
+//!     panics.extend(harness.drain_messages::<IcedPanic>());
+//! }
+//! assert_eq!(panics.len(), 1);
+//! assert!(panics[0].message.as_deref().unwrap().contains("index out of bounds"));
+//!
+//! // The banner is drawn across the top of the window, in an opaque color.
+//! let snapshot = Snapshot::capture(&harness.app.world).unwrap();
+//! let top_left = &snapshot.pixels[..4];
+//! assert_eq!(top_left[3], u8::MAX);
+//! assert!(top_left[0] > top_left[1]);
+//! ```
+//!
+//! The root of a UI is laid out within the insets of [`IcedSafeArea`](crate::IcedSafeArea)
+//! while it has some, and back at the window's origin once they're cleared:
+//!
+//! ```
+//! use bevy::prelude::*;
+//! use bevy_iced::harness::IcedTestHarness;
+//! use bevy_iced::iced::widget::{button, container};
+//! use bevy_iced::iced::{Padding, Point};
+//! use bevy_iced::{IcedContext, IcedSafeArea};
+//!
+//! #[derive(Event, Clone)]
+//! struct UiMessage;
+//!
+//! fn ui_system(mut ctx: IcedContext<UiMessage>) {
+//!     let menu = button("Menu").on_press(UiMessage);
+//!     ctx.display(container(menu).id(container::Id::new("menu")));
+//! }
+//!
+//! let mut harness = IcedTestHarness::new(800.0, 600.0);
+//! harness
+//!     .app
+//!     .add_event::<UiMessage>()
+//!     .add_systems(Update, ui_system);
+//! let mut positions = Vec::new();
+//! for insets in [Padding::ZERO, Padding::new(40.0), Padding::ZERO] {
+//!     harness.app.world.resource_mut::<IcedSafeArea>().insets = insets;
+//!     harness.run_frames(1);
+//!     let bounds = harness.layout_of(container::Id::new("menu")).unwrap();
+//!     positions.push(bounds.position());
+//! }
+//! assert_eq!(positions, [Point::ORIGIN, Point::new(40.0, 40.0), Point::ORIGIN]);
+//! ```
+
+/// A replayed click lands where it was recorded, wherever the window's cursor is while
+/// it's replayed:
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_iced::harness::IcedTestHarness;
+/// use bevy_iced::iced::widget::button;
+/// use bevy_iced::iced::Point;
+/// use bevy_iced::record::{IcedEventRecorder, IcedEventReplayer};
+/// use bevy_iced::IcedContext;
+///
+/// #[derive(Event, Clone, Debug, PartialEq)]
+/// enum UiMessage {
+///     Clicked,
+/// }
+///
+/// fn ui_system(mut ctx: IcedContext<UiMessage>) {
+///     ctx.display(button("Click me").on_press(UiMessage::Clicked));
+/// }
+///
+/// let mut harness = IcedTestHarness::new(800.0, 600.0);
+/// harness
+///     .app
+///     .add_event::<UiMessage>()
+///     .add_systems(Update, ui_system);
+/// harness.run_frames(1);
+///
+/// harness.app.world.resource_mut::<IcedEventRecorder>().start();
+/// harness.click(Point::new(10.0, 10.0));
+/// harness.run_frames(1);
+/// let session = harness.app.world.resource_mut::<IcedEventRecorder>().stop();
+/// assert_eq!(harness.drain_messages::<UiMessage>(), vec![UiMessage::Clicked]);
+///
+/// harness.move_cursor(Point::new(600.0, 400.0));
+/// harness.run_frames(1);
+/// harness.app.world.resource_mut::<IcedEventReplayer>().play(session);
+/// let mut replayed = Vec::new();
+/// for _ in 0..3 {
+///     harness.run_frames(1);
+///     replayed.extend(harness.drain_messages::<UiMessage>());
+/// }
+/// assert_eq!(replayed, vec![UiMessage::Clicked]);
+/// ```
+#[cfg(feature = "record")]
+pub struct Replay;
@@ -0,0 +1,155 @@
+use bevy_ecs::system::Res;
+use iced_core::widget;
+use iced_core::{Point, Rectangle, Vector};
+
+use crate::probe::Node;
+use crate::routing::Order;
+use crate::IcedResource;
+
+/// The widget at a point of the window, see
+/// [`IcedContext::hit_test`](crate::IcedContext::hit_test).
+#[derive(Clone, Debug, PartialEq)]
+pub struct HitInfo {
+    /// The id of the widget, or of the container or scrollable laid out with the same
+    /// bounds around it. Only containers, scrollables and text inputs report their ids.
+    pub widget_id: Option<widget::Id>,
+    /// The part of the widget on screen, in logical coordinates: within the scrollables
+    /// around it, and offset by how far they're scrolled.
+    pub bounds: Rectangle,
+    /// Whether the widget handles clicks, like buttons, toggles, sliders, text inputs and
+    /// pick lists do.
+    pub interactive: bool,
+}
+
+// A widget of a UI, with whether it takes the pointer from what's below it: the widgets
+// handling clicks, and the scrollables and pane grids handling the wheel and drags, but
+// not text or containers, even with an id.
+struct Target {
+    info: HitInfo,
+    captures: bool,
+}
+
+// The widgets of the UIs displayed last frame, from the bottom one to the top one, with
+// the place of their UI, and the UIs displayed so far this frame, with whether they're
+// modal and their place.
+#[derive(Default)]
+pub struct HitTargets {
+    targets: Vec<(Order, Target)>,
+    next_targets: Vec<(bool, Order, Vec<Target>)>,
+}
+
+impl HitTargets {
+    pub fn hit_test(&self, point: Point) -> Option<HitInfo> {
+        self.targets()
+            .rev()
+            .find(|target| target.bounds.contains(point))
+            .cloned()
+    }
+
+    // Whether a widget taking the pointer is at `point`.
+    pub fn captures(&self, point: Point) -> bool {
+        self.targets
+            .iter()
+            .any(|(_, target)| target.captures && target.info.bounds.contains(point))
+    }
+
+    // The place of the topmost UI with a widget taking the pointer at `point`.
+    pub fn captor(&self, point: Point) -> Option<Order> {
+        self.targets
+            .iter()
+            .rev()
+            .find(|(_, target)| target.captures && target.info.bounds.contains(point))
+            .map(|(order, _)| *order)
+    }
+
+    // Whether a widget of a UI drawn above the one at `order` takes the pointer at `point`.
+    pub fn is_covered(&self, point: Point, order: Order) -> bool {
+        self.targets.iter().any(|(above, target)| {
+            *above > order && target.captures && target.info.bounds.contains(point)
+        })
+    }
+
+    fn targets(&self) -> impl DoubleEndedIterator<Item = &HitInfo> {
+        self.targets.iter().map(|(_, target)| &target.info)
+    }
+
+    pub fn nearest_interactive(&self, point: Point, max_distance: f32) -> Option<HitInfo> {
+        let distance = |bounds: &Rectangle| {
+            let dx = (bounds.x - point.x).max(point.x - bounds.x - bounds.width);
+            let dy = (bounds.y - point.y).max(point.y - bounds.y - bounds.height);
+            Vector::new(dx.max(0.0), dy.max(0.0))
+        };
+        // The topmost of widgets as near is the one on screen.
+        self.targets()
+            .rev()
+            .filter(|target| target.interactive)
+            .map(|target| {
+                let Vector { x, y } = distance(&target.bounds);
+                (x.hypot(y), target)
+            })
+            .filter(|(distance, _)| *distance <= max_distance)
+            .min_by(|(a, _), (b, _)| a.total_cmp(b))
+            .map(|(_, target)| target.clone())
+    }
+
+    // Record the widgets of a UI laid out as `root`.
+    pub fn collect(&mut self, root: &Node, modal: bool, order: Order) {
+        let mut targets = Vec::new();
+        collect(root, root.bounds, Vector::ZERO, None, &mut targets);
+        self.next_targets.push((modal, order, targets));
+    }
+}
+
+fn collect(
+    node: &Node,
+    clip: Rectangle,
+    offset: Vector,
+    enclosing_id: Option<&widget::Id>,
+    targets: &mut Vec<Target>,
+) {
+    let bounds = node.bounds + offset;
+    let visible = bounds.intersection(&clip);
+    // Ids of containers laid out right around a widget are the widget's.
+    let id = node.id.as_ref().or(enclosing_id);
+    let interactive = matches!(
+        node.kind,
+        Some("button" | "toggle" | "slider" | "vertical_slider" | "text_input" | "pick_list")
+    );
+    if let Some(visible) = visible.filter(|_| node.kind.is_some() || id.is_some()) {
+        targets.push(Target {
+            info: HitInfo {
+                widget_id: id.cloned(),
+                bounds: visible,
+                interactive,
+            },
+            captures: interactive || matches!(node.kind, Some("scrollable" | "pane_grid")),
+        });
+    }
+    // What's inside a widget handling clicks, like the label of a button, is part of it.
+    if interactive {
+        return;
+    }
+    let (clip, offset) = match node.translation {
+        Some(translation) => (visible.unwrap_or_default(), offset - translation),
+        None => (clip, offset),
+    };
+    for child in &node.children {
+        let enclosing_id = id.filter(|_| child.bounds == node.bounds);
+        collect(child, clip, offset, enclosing_id, targets);
+    }
+}
+
+// Publish the widgets of this frame's UIs, once they've all been displayed, in the order
+// they're drawn in. Modal UIs are drawn over the others, which they cover.
+pub fn finish_frame(props: Res<IcedResource>) {
+    let hit_targets = &mut props.lock().unwrap().hit_targets;
+    let mut layers = std::mem::take(&mut hit_targets.next_targets);
+    if layers.iter().any(|(modal, _, _)| *modal) {
+        layers.retain(|(modal, _, _)| *modal);
+    }
+    layers.sort_by_key(|(_, order, _)| *order);
+    hit_targets.targets = layers
+        .into_iter()
+        .flat_map(|(_, order, targets)| targets.into_iter().map(move |target| (order, target)))
+        .collect();
+}
@@ -0,0 +1,188 @@
+use std::marker::PhantomData;
+
+use bevy_app::{App, Plugin, PreUpdate};
+use bevy_ecs::prelude::{Event, EventWriter, IntoSystemConfigs};
+use bevy_ecs::system::{Res, Resource};
+use bevy_utils::tracing::warn;
+use iced_core::keyboard::{self, key, Key, Modifiers};
+use iced_core::Event as IcedEvent;
+
+use crate::{IcedEventQueue, IcedSet, IcedWantsKeyboard};
+
+/// A key combination, like Ctrl+S.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Chord {
+    /// The modifiers that must be held, and no others.
+    pub modifiers: Modifiers,
+    /// The logical key. Characters match regardless of case, so that Ctrl+Shift+S
+    /// matches `"s"` with `Modifiers::CTRL | Modifiers::SHIFT`.
+    pub key: Key,
+}
+
+impl Chord {
+    /// The chord of `key` while holding `modifiers`.
+    pub fn new(modifiers: Modifiers, key: Key) -> Self {
+        Self { modifiers, key }
+    }
+
+    /// The chord of typing `character` while holding `modifiers`, e.g. `"s"` for Ctrl+S.
+    pub fn character(modifiers: Modifiers, character: &str) -> Self {
+        Self::new(modifiers, Key::Character(character.to_lowercase().into()))
+    }
+
+    /// The chord of a named key while holding `modifiers`, e.g. F1.
+    pub fn named(modifiers: Modifiers, named: key::Named) -> Self {
+        Self::new(modifiers, Key::Named(named))
+    }
+
+    pub(crate) fn matches(&self, key: &Key, modifiers: Modifiers) -> bool {
+        if self.modifiers != modifiers {
+            return false;
+        }
+        match (&self.key, key) {
+            (Key::Character(chord), Key::Character(pressed)) => {
+                chord.to_lowercase() == pressed.to_lowercase()
+            }
+            (chord, pressed) => chord == pressed,
+        }
+    }
+}
+
+struct Hotkey<M> {
+    chord: Chord,
+    message: Box<dyn Fn() -> M + Send + Sync>,
+    while_typing: bool,
+}
+
+/// Global keyboard shortcuts of the UI, like Ctrl+S to save or F1 for help, that
+/// produce UI messages like widgets do.
+///
+/// Add an [`IcedHotkeysPlugin`] for `M` to fire them. Hotkeys are matched against the
+/// key presses of the [`IcedEventQueue`] once input has been processed, and don't fire
+/// while a widget has the keyboard focus (see [`IcedWantsKeyboard`]) unless registered
+/// with [`IcedHotkeys::register_while_typing`].
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_iced::iced::keyboard::{key::Named, Modifiers};
+/// # use bevy_iced::{Chord, IcedHotkeys};
+/// # enum UiMessage {
+/// #     Save,
+/// #     Help,
+/// # }
+/// fn setup(mut hotkeys: ResMut<IcedHotkeys<UiMessage>>) {
+///     hotkeys
+///         .register(Chord::character(Modifiers::CTRL, "s"), || UiMessage::Save)
+///         .register(Chord::named(Modifiers::empty(), Named::F1), || UiMessage::Help);
+/// }
+/// ```
+#[derive(Resource)]
+pub struct IcedHotkeys<M> {
+    hotkeys: Vec<Hotkey<M>>,
+}
+
+impl<M> Default for IcedHotkeys<M> {
+    fn default() -> Self {
+        Self {
+            hotkeys: Vec::new(),
+        }
+    }
+}
+
+impl<M> IcedHotkeys<M> {
+    /// Produce `message()` when `chord` is pressed, unless a widget has the keyboard
+    /// focus. Registering a chord again replaces its hotkey, with a warning.
+    pub fn register(
+        &mut self,
+        chord: Chord,
+        message: impl Fn() -> M + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.insert(chord, Box::new(message), false)
+    }
+
+    /// Produce `message()` when `chord` is pressed, even while a widget has the keyboard
+    /// focus, e.g. for Escape to close a form.
+    pub fn register_while_typing(
+        &mut self,
+        chord: Chord,
+        message: impl Fn() -> M + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.insert(chord, Box::new(message), true)
+    }
+
+    /// Remove the hotkey of `chord`, returning whether there was one.
+    pub fn remove(&mut self, chord: &Chord) -> bool {
+        let len = self.hotkeys.len();
+        self.hotkeys
+            .retain(|hotkey| !hotkey.chord.matches(&chord.key, chord.modifiers));
+        self.hotkeys.len() != len
+    }
+
+    /// Whether `chord` has a hotkey.
+    pub fn contains(&self, chord: &Chord) -> bool {
+        self.hotkeys
+            .iter()
+            .any(|hotkey| hotkey.chord.matches(&chord.key, chord.modifiers))
+    }
+
+    fn insert(
+        &mut self,
+        chord: Chord,
+        message: Box<dyn Fn() -> M + Send + Sync>,
+        while_typing: bool,
+    ) -> &mut Self {
+        if self.remove(&chord) {
+            warn!(
+                "The hotkey {:?}+{:?} was registered twice, only the last one is used",
+                chord.modifiers, chord.key
+            );
+        }
+        self.hotkeys.push(Hotkey {
+            chord,
+            message,
+            while_typing,
+        });
+        self
+    }
+}
+
+/// Adds the [`IcedHotkeys<M>`] resource and fires its hotkeys in [`PreUpdate`], after
+/// [`IcedSet::ProcessInput`], so that UI systems see the messages in the same frame.
+pub struct IcedHotkeysPlugin<M>(PhantomData<M>);
+
+impl<M> Default for IcedHotkeysPlugin<M> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<M: Event> Plugin for IcedHotkeysPlugin<M> {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<IcedHotkeys<M>>()
+            .add_systems(PreUpdate, fire_hotkeys::<M>.after(IcedSet::ProcessInput));
+    }
+}
+
+fn fire_hotkeys<M: Event>(
+    hotkeys: Res<IcedHotkeys<M>>,
+    events: Res<IcedEventQueue>,
+    wants_keyboard: Option<Res<IcedWantsKeyboard>>,
+    mut messages: EventWriter<M>,
+) {
+    if hotkeys.hotkeys.is_empty() {
+        return;
+    }
+    let typing = wants_keyboard.is_some_and(|wants| **wants);
+    for event in events.iter() {
+        let IcedEvent::Keyboard(keyboard::Event::KeyPressed { key, modifiers, .. }) = event else {
+            continue;
+        };
+        let hotkey = hotkeys
+            .hotkeys
+            .iter()
+            .find(|hotkey| hotkey.chord.matches(key, *modifiers));
+        if let Some(hotkey) = hotkey.filter(|hotkey| hotkey.while_typing || !typing) {
+            messages.send((hotkey.message)());
+        }
+    }
+}
@@ -1,6 +1,5 @@
 // Largely taken from https://github.com/iced-rs/iced/blob/0.10/src/lib.rs
 
-use iced_widget::renderer;
 use iced_widget::style;
 
 pub use style::theme;
@@ -11,10 +10,28 @@ pub use iced_core::event;
 pub use iced_core::gradient;
 pub use iced_core::{
     color, Alignment, Background, Border, Color, ContentFit, Degrees, Gradient, Length, Padding,
-    Pixels, Point, Radians, Rectangle, Size, Vector,
+    Pixels, Point, Radians, Rectangle, Shadow, Size, Transformation, Vector,
 };
 pub use iced_runtime::Command;
 
+pub mod advanced {
+    //! Leverage advanced concepts like custom widgets.
+    //!
+    //! Third-party widget crates written against `iced::advanced` (e.g. `iced_aw`)
+    //! resolve to the same types through this module.
+    pub use iced_core::clipboard::{self, Clipboard};
+    pub use iced_core::image;
+    pub use iced_core::layout::{self, Layout};
+    pub use iced_core::mouse;
+    pub use iced_core::overlay::{self, Overlay};
+    pub use iced_core::renderer::{self, Renderer};
+    pub use iced_core::svg;
+    pub use iced_core::text::{self, Text};
+    pub use iced_core::widget::{self, Widget};
+    pub use iced_core::{Hasher, Shell};
+    pub use iced_widget::graphics;
+}
+
 pub mod clipboard {
     //! Access the clipboard.
     pub use iced_runtime::clipboard::{read, write};
@@ -28,6 +45,7 @@ pub mod font {
 
 pub mod keyboard {
     //! Listen and react to keyboard events.
+    pub use iced_core::keyboard::key;
     pub use iced_core::keyboard::{Event, Key, Location, Modifiers};
 }
 
@@ -55,6 +73,12 @@ pub mod touch {
     pub use iced_core::touch::{Event, Finger};
 }
 
+pub mod window {
+    //! Configure the window of your application, see [`IcedContext::run_command`](crate::IcedContext::run_command).
+    pub use iced_core::window::{Icon, Id, Level, Mode, UserAttention};
+    pub use iced_runtime::window::*;
+}
+
 #[allow(hidden_glob_reexports)]
 pub mod widget {
     //! Use the built-in widgets or create your own.
@@ -74,7 +98,7 @@ pub use font::Font;
 pub use theme::Theme;
 
 /// The default renderer.
-pub type Renderer = renderer::Renderer;
+pub type Renderer = crate::Renderer;
 
 /// A generic widget.
 ///
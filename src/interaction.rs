@@ -0,0 +1,142 @@
+use std::any::TypeId;
+use std::time::Duration;
+
+use bevy_ecs::event::{Event, EventWriter};
+use bevy_ecs::system::{ResMut, Resource, SystemParam};
+use bevy_utils::HashMap;
+use iced_core::event::Status;
+use iced_core::mouse::{self, Cursor, Interaction};
+use iced_core::time::Instant;
+use iced_core::{touch, Event as IcedEvent, Rectangle};
+use iced_core::{widget, window};
+
+use crate::layout::Layouts;
+
+/// The widget an [`IcedWidgetEvent`] refers to.
+///
+/// Iced widgets don't expose their identity, so the target is the interactive widget
+/// under the cursor, as reported by the cursor [`Interaction`] it requests. Its `id` and
+/// `bounds` are those of the innermost container or scrollable with an id around it,
+/// if there is one: give ids to the containers of the widgets you want to tell apart.
+#[derive(Clone, Debug, PartialEq)]
+pub struct WidgetTarget {
+    /// The id of the innermost container with an id around the widget.
+    pub id: Option<widget::Id>,
+    /// The bounds of that container, in logical coordinates.
+    pub bounds: Option<Rectangle>,
+    /// The cursor interaction requested by the widget, e.g. [`Interaction::Pointer`]
+    /// for buttons and [`Interaction::Text`] for text inputs.
+    pub interaction: Interaction,
+}
+
+/// Sent when the widgets of a displayed UI are hovered or pressed, e.g. to play sounds.
+///
+/// Only sent when [`IcedSettings::widget_events`](crate::IcedSettings::widget_events)
+/// is enabled, since finding the target widgets adds work to every frame.
+#[derive(Event, Clone, Debug, PartialEq)]
+pub enum IcedWidgetEvent {
+    /// The cursor moved onto an interactive widget.
+    HoverEntered(WidgetTarget),
+    /// The cursor left an interactive widget.
+    HoverExited(WidgetTarget),
+    /// A widget captured a press of the left mouse button or of a finger.
+    Pressed(WidgetTarget),
+    /// A widget captured a release of the left mouse button or of a finger.
+    Released(WidgetTarget),
+    /// A widget captured a second press within
+    /// [`IcedSettings::double_click_interval`](crate::IcedSettings::double_click_interval)
+    /// of the first, e.g. to open a list item. It's sent after the second `Pressed`.
+    DoubleClicked(WidgetTarget),
+}
+
+// The hovered widget of every UI, by message type.
+#[derive(Resource, Default)]
+pub struct HoveredWidgets(HashMap<TypeId, WidgetTarget>);
+
+// The last press of every UI that may start a double-click, by message type.
+#[derive(Resource, Default)]
+pub struct LastPresses(HashMap<TypeId, (WidgetTarget, Instant)>);
+
+#[derive(SystemParam)]
+pub struct WidgetEvents<'w> {
+    hovered: ResMut<'w, HoveredWidgets>,
+    presses: ResMut<'w, LastPresses>,
+    events: EventWriter<'w, IcedWidgetEvent>,
+}
+
+impl WidgetEvents<'_> {
+    pub fn update<M: 'static>(
+        &mut self,
+        layouts: &Layouts,
+        cursor: Cursor,
+        interaction: Interaction,
+        events: &[IcedEvent],
+        statuses: &[Status],
+        double_click_interval: Duration,
+    ) {
+        let Some(position) = cursor.position() else {
+            return;
+        };
+        let target_at = |interaction| {
+            let hit = layouts.hit_test(position);
+            WidgetTarget {
+                id: hit.map(|(id, _)| id.clone()),
+                bounds: hit.map(|(_, bounds)| bounds),
+                interaction,
+            }
+        };
+
+        let hovered = (interaction != Interaction::Idle).then(|| target_at(interaction));
+        let previous = self.hovered.0.get(&TypeId::of::<M>());
+        if previous != hovered.as_ref() {
+            if let Some(previous) = self.hovered.0.remove(&TypeId::of::<M>()) {
+                self.events.send(IcedWidgetEvent::HoverExited(previous));
+            }
+            if let Some(hovered) = hovered.clone() {
+                self.events
+                    .send(IcedWidgetEvent::HoverEntered(hovered.clone()));
+                self.hovered.0.insert(TypeId::of::<M>(), hovered);
+            }
+        }
+
+        // Bevy doesn't timestamp input, so presses are timed by the start of the frame
+        // they arrived in, like the redraw the queue starts with.
+        let now = events
+            .iter()
+            .find_map(|event| match event {
+                IcedEvent::Window(_, window::Event::RedrawRequested(time)) => Some(*time),
+                _ => None,
+            })
+            .unwrap_or_else(Instant::now);
+        for (event, status) in events.iter().zip(statuses) {
+            if *status != Status::Captured {
+                continue;
+            }
+            let target = || hovered.clone().unwrap_or_else(|| target_at(interaction));
+            match event {
+                IcedEvent::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left))
+                | IcedEvent::Touch(touch::Event::FingerPressed { .. }) => {
+                    let target = target();
+                    self.events.send(IcedWidgetEvent::Pressed(target.clone()));
+                    let previous = self.presses.0.remove(&TypeId::of::<M>());
+                    match previous {
+                        Some((previous, time))
+                            if previous == target
+                                && now.saturating_duration_since(time) <= double_click_interval =>
+                        {
+                            self.events.send(IcedWidgetEvent::DoubleClicked(target));
+                        }
+                        _ => {
+                            self.presses.0.insert(TypeId::of::<M>(), (target, now));
+                        }
+                    }
+                }
+                IcedEvent::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left))
+                | IcedEvent::Touch(touch::Event::FingerLifted { .. }) => {
+                    self.events.send(IcedWidgetEvent::Released(target()));
+                }
+                _ => {}
+            }
+        }
+    }
+}
@@ -0,0 +1,75 @@
+use std::collections::HashMap;
+
+use iced_core::widget::{self, operation, Operation};
+use iced_core::{Point, Rectangle, Renderer, Vector};
+use iced_runtime::user_interface::UserInterface;
+use iced_widget::style::Theme;
+
+/// The bounds of the widgets that have an id, collected after laying out a UI.
+///
+/// Only containers and scrollables report their bounds to Iced's widget operations,
+/// so give the widgets you want to locate a [`container`](crate::iced::widget::container)
+/// with an id.
+#[derive(Debug, Clone, Default)]
+pub struct Layouts(HashMap<widget::Id, Rectangle>);
+
+impl Layouts {
+    pub(crate) fn collect<M, R: Renderer>(
+        ui: &mut UserInterface<'_, M, Theme, R>,
+        renderer: &R,
+    ) -> Self {
+        let mut layouts = Self::default();
+        ui.operate(renderer, &mut layouts);
+        layouts
+    }
+
+    /// The bounds of the widget with the given id, in logical coordinates.
+    pub fn get(&self, id: impl Into<widget::Id>) -> Option<Rectangle> {
+        self.0.get(&id.into()).copied()
+    }
+
+    #[cfg(feature = "test-utils")]
+    pub(crate) fn extend(&mut self, other: Self) {
+        self.0.extend(other.0);
+    }
+
+    #[cfg(feature = "test-utils")]
+    pub(crate) fn clear(&mut self) {
+        self.0.clear();
+    }
+
+    // The innermost widget with an id that contains `point`.
+    pub(crate) fn hit_test(&self, point: Point) -> Option<(&widget::Id, Rectangle)> {
+        self.0
+            .iter()
+            .filter(|(_, bounds)| bounds.contains(point))
+            .min_by(|(_, a), (_, b)| a.area().total_cmp(&b.area()))
+            .map(|(id, bounds)| (id, *bounds))
+    }
+}
+
+impl<T> Operation<T> for Layouts {
+    fn container(
+        &mut self,
+        id: Option<&widget::Id>,
+        bounds: Rectangle,
+        operate_on_children: &mut dyn FnMut(&mut dyn Operation<T>),
+    ) {
+        if let Some(id) = id {
+            self.0.insert(id.clone(), bounds);
+        }
+        operate_on_children(self);
+    }
+
+    fn scrollable(
+        &mut self,
+        _state: &mut dyn operation::Scrollable,
+        id: Option<&widget::Id>,
+        bounds: Rectangle,
+        _translation: Vector,
+    ) {
+        if let Some(id) = id {
+            self.0.insert(id.clone(), bounds);
+        }
+    }
+}
@@ -0,0 +1,147 @@
+use std::fmt;
+
+use iced_core::widget;
+use iced_core::{Padding, Rectangle};
+use serde::Serialize;
+
+use crate::probe::Node;
+
+/// A widget in a dump of the layout of a UI, see
+/// [`IcedContext::debug_layout`](crate::IcedContext::debug_layout). Requires the `debug`
+/// feature.
+///
+/// Widgets don't report what they are to Iced, so the kind is only known for the
+/// built-in widgets with their own state, and the id only for containers and
+/// scrollables. Print it for an indented tree, or use [`LayoutNode::to_ron`] to diff
+/// the layouts of two frames.
+#[derive(Serialize, Clone, Debug)]
+pub struct LayoutNode {
+    /// The kind of widget, like `"button"`, if it's known.
+    pub kind: Option<&'static str>,
+    /// The id of the widget, if it has one: its name, or `#` and a number for unique
+    /// ids.
+    pub id: Option<String>,
+    /// The bounds of the widget, in logical coordinates.
+    #[serde(with = "RectangleDef")]
+    pub bounds: Rectangle,
+    /// The space between the widget's bounds and its children, like the padding of a
+    /// container whose content fills it.
+    #[serde(with = "PaddingDef")]
+    pub padding: Padding,
+    /// The widgets laid out within it.
+    pub children: Vec<LayoutNode>,
+}
+
+#[derive(Serialize)]
+#[serde(remote = "Rectangle")]
+struct RectangleDef {
+    x: f32,
+    y: f32,
+    width: f32,
+    height: f32,
+}
+
+#[derive(Serialize)]
+#[serde(remote = "Padding")]
+struct PaddingDef {
+    top: f32,
+    right: f32,
+    bottom: f32,
+    left: f32,
+}
+
+impl LayoutNode {
+    /// The tree as a RON document, e.g. to diff against the layout of another frame.
+    pub fn to_ron(&self) -> Result<String, ron::Error> {
+        ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+    }
+
+    fn new(node: &Node) -> Self {
+        let bounds = node.bounds;
+        let children: Vec<_> = node.children.iter().map(Self::new).collect();
+        // Rounded, since the bounds of content laid out within padding rarely add up to
+        // exactly the bounds around it.
+        let inset = |inset: f32| (inset.max(0.0) * 100.0).round() / 100.0;
+        let padding = children
+            .iter()
+            .map(|child| child.bounds)
+            .reduce(|a, b| a.union(&b))
+            .map_or(Padding::ZERO, |content| Padding {
+                top: inset(content.y - bounds.y),
+                right: inset(bounds.x + bounds.width - content.x - content.width),
+                bottom: inset(bounds.y + bounds.height - content.y - content.height),
+                left: inset(content.x - bounds.x),
+            });
+        Self {
+            kind: node.kind,
+            id: node.id.as_ref().map(name),
+            bounds,
+            padding,
+            children,
+        }
+    }
+
+    fn write(&self, f: &mut fmt::Formatter<'_>, depth: usize) -> fmt::Result {
+        let round = |value: f32| (value * 100.0).round() / 100.0;
+        let Rectangle {
+            x,
+            y,
+            width,
+            height,
+        } = self.bounds;
+        let (x, y, width, height) = (round(x), round(y), round(width), round(height));
+        write!(
+            f,
+            "{:indent$}{}",
+            "",
+            self.kind.unwrap_or("widget"),
+            indent = depth * 2
+        )?;
+        if let Some(id) = &self.id {
+            write!(f, " {id}")?;
+        }
+        write!(f, " at ({x}, {y}), {width}x{height}")?;
+        let Padding {
+            top,
+            right,
+            bottom,
+            left,
+        } = self.padding;
+        if [top, right, bottom, left] != [0.0; 4] {
+            write!(f, ", padding {top} {right} {bottom} {left}")?;
+        }
+        writeln!(f)?;
+        self.children
+            .iter()
+            .try_for_each(|child| child.write(f, depth + 1))
+    }
+}
+
+impl fmt::Display for LayoutNode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write(f, 0)
+    }
+}
+
+// Dump the layout of a UI captured by its probe.
+pub fn dump(root: &Node) -> LayoutNode {
+    LayoutNode::new(root)
+}
+
+// Ids only tell their names through `Debug`, as `Id(Custom("name"))` or `Id(Unique(1))`.
+fn name(id: &widget::Id) -> String {
+    let debug = format!("{id:?}");
+    if let Some(custom) = debug
+        .strip_prefix("Id(Custom(\"")
+        .and_then(|custom| custom.strip_suffix("\"))"))
+    {
+        return custom.to_owned();
+    }
+    if let Some(unique) = debug
+        .strip_prefix("Id(Unique(")
+        .and_then(|unique| unique.strip_suffix("))"))
+    {
+        return format!("#{unique}");
+    }
+    debug
+}
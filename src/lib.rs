@@ -28,27 +28,39 @@
 #![deny(unsafe_code)]
 #![deny(missing_docs)]
 
-use std::any::{Any, TypeId};
-
 use std::borrow::Cow;
+use std::collections::HashMap;
 use std::sync::Arc;
 use std::sync::Mutex;
 
+use crate::cache::IcedCache;
 use crate::render::{extract_iced_data, IcedNode, ViewportResource};
 
-use bevy_app::{App, Plugin, Update};
+use bevy_app::{App, Plugin, PostUpdate, PreUpdate, Update};
 use bevy_derive::{Deref, DerefMut};
-use bevy_ecs::prelude::{EventWriter, Query, With};
-use bevy_ecs::system::{NonSendMut, Res, ResMut, Resource, SystemParam};
-use bevy_input::touch::Touches;
+use bevy_diagnostic::{Diagnostic, RegisterDiagnostic};
+use bevy_ecs::event::Events;
+use bevy_ecs::prelude::{
+    Component, Entity, IntoSystemConfigs, IntoSystemSetConfigs, Query, SystemSet, With,
+};
+use bevy_ecs::system::{NonSendMut, Res, ResMut, Resource, SystemParam, SystemState};
+use bevy_ecs::world::{Mut, World};
+use bevy_input::keyboard::{KeyCode, KeyboardInput};
+use bevy_input::mouse::{MouseButtonInput, MouseWheel};
+use bevy_input::touch::{TouchInput, Touches};
+use bevy_input::{ButtonInput, InputSystem};
 use bevy_render::render_graph::RenderGraph;
 use bevy_render::renderer::{RenderDevice, RenderQueue};
 use bevy_render::{ExtractSchedule, RenderApp};
-use bevy_utils::HashMap;
-use bevy_window::{PrimaryWindow, Window};
+use bevy_utils::tracing::warn;
+use bevy_window::{
+    ApplicationLifetime, CursorEntered, CursorLeft, CursorMoved, PrimaryWindow, ReceivedCharacter,
+    Window, WindowMoved,
+};
 use iced_core::mouse::Cursor;
+use iced_core::text::Renderer as _;
 use iced_runtime::user_interface::UserInterface;
-use iced_widget::graphics::backend::Text;
+use iced_wgpu::wgpu::TextureFormat;
 use iced_widget::graphics::Viewport;
 use iced_widget::style::Theme;
 
@@ -58,89 +70,643 @@ use iced_widget::style::Theme;
 /// as much as possible.
 pub mod iced;
 
-mod conversions;
+/// Golden-image testing utilities.
+#[cfg(feature = "test-utils")]
+pub mod snapshot;
+
+/// A headless app for driving UIs from tests.
+#[cfg(feature = "test-utils")]
+pub mod harness;
+
+/// Recording and replaying of UI interaction sessions.
+#[cfg(feature = "record")]
+pub mod record;
+
+/// Saving and restoring `pane_grid` layouts.
+#[cfg(feature = "pane-layout")]
+pub mod pane_layout;
+
+#[cfg(feature = "color-picker")]
+mod color_picker;
+#[cfg(feature = "debug")]
+mod debug;
+#[cfg(feature = "reflect-ui")]
+mod reflect;
+
+mod accessibility;
+mod announce;
+mod backdrop;
+mod binding;
+mod cache;
+mod capture;
+mod clipboard;
+mod command;
+mod fade;
+mod focus;
+mod fonts;
+mod hit;
+mod hotkeys;
+mod interaction;
+mod layout;
+#[cfg(feature = "debug")]
+mod layout_dump;
+mod loading;
+mod menu;
+mod modal;
+mod node;
+mod panics;
+mod prewarm;
+mod probe;
 mod render;
+mod retain;
+mod rich_text;
+mod routing;
+mod scroll;
+mod software;
+mod stack;
 mod systems;
+mod texture;
+mod theme;
+mod toast;
+mod tracking;
 mod utils;
+#[cfg(feature = "virtual-keyboard")]
+mod virtual_keyboard;
+mod window;
+mod zoom;
 
-use systems::IcedEventQueue;
+/// Widgets that integrate with Bevy.
+pub mod widget;
+
+/// Tweened values for animating UIs.
+pub mod anim;
+
+/// Conversions from bevy's input types to Iced's.
+pub mod conversions;
+
+/// Conversions between bevy's window coordinates and Iced's logical coordinates.
+pub mod coordinates;
+
+pub use accessibility::IcedAccessibility;
+pub use announce::AnnouncePriority;
+pub use backdrop::BackdropBlur;
+pub use binding::{Binding, IcedBindings};
+pub use capture::IcedEventCaptureStatus;
+#[cfg(feature = "debug")]
+pub use debug::IcedDebugPanel;
+pub use focus::{FocusRing, IcedWantsKeyboard};
+pub use fonts::IcedFont;
+pub use hit::HitInfo;
+pub use hotkeys::{Chord, IcedHotkeys, IcedHotkeysPlugin};
+pub use interaction::{IcedWidgetEvent, WidgetTarget};
+#[cfg(feature = "debug")]
+pub use layout_dump::LayoutNode;
+pub use loading::{IcedLoading, LoadFailure, LoadingProgress};
+pub use modal::{IcedModal, ModalOptions};
+pub use panics::IcedPanic;
+#[cfg(feature = "reflect-ui")]
+pub use reflect::ReflectEdit;
+pub use render::IcedRenderTarget;
+pub use systems::{IcedEventQueue, IcedRawEvent};
+pub use texture::IcedTextureTarget;
+pub use theme::ActiveIcedTheme;
+pub use toast::{IcedToasts, ToastPosition};
+pub use tracking::{IcedWidgetInfo, TrackedWidget};
+pub use window::ResizeEdge;
+pub use zoom::{IcedZoom, ZoomConfig};
+
+/// The system sets added by [`IcedPlugin`].
+#[derive(SystemSet, Clone, Debug, Hash, PartialEq, Eq)]
+pub enum IcedSet {
+    /// Updates the viewport from the window, converts this frame's input into
+    /// [`IcedEventQueue`] and updates the [`IcedEventCaptureStatus`]. Runs in
+    /// [`PreUpdate`].
+    ProcessInput,
+    /// Clears the game's input while a modal UI is open, with
+    /// [`IcedSettings::modal_blocks_game_input`], and the input captured by the UI, with
+    /// [`IcedSettings::block_captured_input`]. Runs in [`PreUpdate`], after
+    /// [`IcedSet::ProcessInput`].
+    BlockGameInput,
+}
 
 /// The default renderer.
 pub type Renderer = iced_renderer::Renderer;
 
 /// The main feature of `bevy_iced`.
 /// Add this to your [`App`] by calling `app.add_plugin(bevy_iced::IcedPlugin::default())`.
-#[derive(Default)]
+///
+/// When no render device is available (e.g. when running with `MinimalPlugins`),
+/// the plugin falls back to a headless mode: events are processed and messages are
+/// produced as usual, but the UI is never presented.
 pub struct IcedPlugin {
     /// The settings that Iced should use.
     pub settings: iced::Settings,
-    /// Font file contents
+    /// Font file contents, loaded on startup. See [`IcedFont`] and
+    /// [`IcedContext::load_font`] to load fonts while the app runs.
     pub fonts: Vec<&'static [u8]>,
+    /// Whether to also send the Iced events converted from the input as
+    /// [`IcedRawEvent`]s, e.g. to observe the UI's input outside of UI systems.
+    pub raw_events: bool,
+    /// Whether to convert bevy's input into Iced events, see
+    /// [`IcedPlugin::without_default_input`].
+    pub default_input: bool,
+    /// The [`IcedSettings`] resource the app starts with.
+    pub iced_settings: IcedSettings,
+    /// Whether to rasterize the printable ASCII characters of the default font at the
+    /// default text size on the first frame, see [`IcedContext::prewarm_text`].
+    pub prewarm_ascii: bool,
+}
+
+impl Default for IcedPlugin {
+    fn default() -> Self {
+        Self {
+            settings: iced::Settings::default(),
+            fonts: Vec::new(),
+            raw_events: false,
+            default_input: true,
+            iced_settings: IcedSettings::default(),
+            prewarm_ascii: false,
+        }
+    }
+}
+
+impl IcedPlugin {
+    /// A plugin starting with the given [`IcedSettings`], e.g. built with their `with_*`
+    /// setters.
+    pub fn new(settings: impl Into<IcedSettings>) -> Self {
+        Self {
+            iced_settings: settings.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Don't convert bevy's input into Iced events, to feed the [`IcedEventQueue`] from
+    /// your own input handling instead, e.g. with the functions in [`conversions`].
+    ///
+    /// The queue is still cleared at the start of every frame, in
+    /// [`IcedSet::ProcessInput`]. Push this frame's events in a system that runs after
+    /// that set and before your UI systems, e.g. in `PreUpdate`.
+    pub fn without_default_input(mut self) -> Self {
+        self.default_input = false;
+        self
+    }
 }
 
+// Marks the app as set up by an `IcedPlugin`, to skip the ones added after the first.
+#[derive(Resource)]
+struct IcedPluginAdded;
+
 impl Plugin for IcedPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, (systems::process_input, render::update_viewport))
-            .insert_resource(DidDraw::default())
-            .insert_resource(IcedSettings::default())
-            .insert_non_send_resource(IcedCache::default())
-            .insert_resource(IcedEventQueue::default());
+        if app.world.contains_resource::<IcedPluginAdded>() {
+            warn!("IcedPlugin was added more than once, only the first one is used");
+            return;
+        }
+        app.insert_resource(IcedPluginAdded);
+
+        // Input is converted before `Update`, so UI systems always see this frame's events.
+        app.add_systems(
+            PreUpdate,
+            (
+                render::update_viewport,
+                systems::begin_frame,
+                routing::begin_frame,
+            )
+                .chain()
+                .in_set(IcedSet::ProcessInput),
+        )
+        .add_systems(
+            PreUpdate,
+            (
+                accessibility::apply_text_scale,
+                theme::update_active_theme,
+                loading::record_load_failures,
+                command::send_task_messages,
+            ),
+        )
+        .add_event::<IcedRawEvent>()
+        .add_systems(Update, render::handle_application_lifetime)
+        .add_systems(
+            PostUpdate,
+            (
+                window::apply_window_commands,
+                announce::update_live_regions,
+                (modal::finish_frame, toast::draw_toasts).chain(),
+                cache::evict_unused,
+                focus::publish_keyboard_focus,
+                scroll::finish_frame,
+                hit::finish_frame,
+                capture::finish_frame,
+                panics::publish_panics,
+                tracking::publish_widget_info,
+                prewarm::draw_prewarm,
+                binding::apply_bindings,
+            ),
+        )
+        .add_systems(
+            PostUpdate,
+            render::release_idle_renderer
+                .after(toast::draw_toasts)
+                .after(prewarm::draw_prewarm),
+        )
+        .init_resource::<window::WindowCommandQueue>()
+        .init_resource::<interaction::HoveredWidgets>()
+        .init_resource::<interaction::LastPresses>()
+        .init_resource::<IcedModal>()
+        .init_resource::<IcedToasts>()
+        .init_resource::<announce::Announcements>()
+        .init_resource::<IcedAccessibility>()
+        .init_resource::<ActiveIcedTheme>()
+        .init_resource::<IcedWantsKeyboard>()
+        .init_resource::<IcedEventCaptureStatus>()
+        .init_resource::<IcedWidgetInfo>()
+        .init_resource::<IcedZoom>()
+        .init_resource::<binding::BindingQueue>()
+        .init_resource::<loading::LoadFailures>()
+        .add_event::<bevy_asset::UntypedAssetLoadFailedEvent>()
+        .add_event::<IcedWidgetEvent>()
+        .add_event::<IcedPanic>()
+        .add_event::<bevy_window::RequestRedraw>()
+        .insert_resource(DidDraw::default())
+        .insert_resource(self.iced_settings.clone())
+        .init_resource::<IcedSafeArea>()
+        .init_resource::<IcedEnabled>()
+        .init_resource::<IcedCursorOverride>()
+        .init_resource::<IcedRenderTarget>()
+        .init_resource::<render::ViewportWindow>()
+        .insert_non_send_resource(IcedCache::default())
+        .init_non_send_resource::<clipboard::IcedClipboard>()
+        .init_non_send_resource::<command::Operations>()
+        .register_diagnostic(Diagnostic::new(IcedPlugin::CACHED_UIS))
+        .register_diagnostic(Diagnostic::new(IcedPlugin::UI_TIME).with_suffix("ms"))
+        .register_diagnostic(Diagnostic::new(IcedPlugin::LIVE_RENDERERS))
+        .init_non_send_resource::<toast::ToastCaches>()
+        .insert_resource(IcedEventQueue::default());
+
+        // Register everything the input handling reads, so that the plugin also works
+        // without `InputPlugin` and `WindowPlugin` (e.g. with `MinimalPlugins` in tests).
+        app.add_event::<CursorEntered>()
+            .add_event::<CursorLeft>()
+            .add_event::<CursorMoved>()
+            .add_event::<MouseButtonInput>()
+            .add_event::<MouseWheel>()
+            .add_event::<ReceivedCharacter>()
+            .add_event::<KeyboardInput>()
+            .add_event::<TouchInput>()
+            .add_event::<ApplicationLifetime>()
+            .add_event::<WindowMoved>()
+            .init_resource::<ButtonInput<KeyCode>>()
+            .init_resource::<systems::HeldKeys>()
+            .init_resource::<Touches>();
+
+        if self.default_input {
+            app.add_systems(
+                PreUpdate,
+                (
+                    systems::process_input
+                        .after(systems::begin_frame)
+                        .in_set(IcedSet::ProcessInput),
+                    zoom::ctrl_scroll_zoom
+                        .after(systems::track_held_keys)
+                        .before(IcedSet::ProcessInput),
+                    systems::track_held_keys
+                        .after(InputSystem)
+                        .before(IcedSet::ProcessInput),
+                ),
+            );
+        }
+        app.configure_sets(
+            PreUpdate,
+            IcedSet::BlockGameInput
+                .after(IcedSet::ProcessInput)
+                .after(InputSystem),
+        )
+        .add_systems(
+            PreUpdate,
+            (
+                capture::update_status
+                    .after(systems::begin_frame)
+                    .after(systems::process_input)
+                    .after(InputSystem)
+                    .in_set(IcedSet::ProcessInput),
+                (modal::block_game_input, capture::block_captured_input)
+                    .in_set(IcedSet::BlockGameInput),
+            ),
+        );
+
+        if self.raw_events {
+            app.add_systems(
+                PreUpdate,
+                systems::send_raw_events
+                    .after(systems::begin_frame)
+                    .after(systems::process_input)
+                    .in_set(IcedSet::ProcessInput),
+            );
+        }
+
+        #[cfg(all(feature = "clipboard", not(target_arch = "wasm32")))]
+        app.add_systems(PreUpdate, clipboard::connect);
+        #[cfg(feature = "record")]
+        record::setup(app);
+        #[cfg(feature = "debug")]
+        debug::setup(app);
     }
 
     fn finish(&self, app: &mut App) {
+        if app.world.contains_resource::<IcedResource>() {
+            return;
+        }
+        fonts::setup(app);
         let default_viewport = Viewport::with_physical_size(iced_core::Size::new(1600, 900), 1.0);
         let default_viewport = ViewportResource(default_viewport);
         let iced_resource: IcedResource = IcedProps::new(app, self).into();
 
-        app.insert_resource(default_viewport.clone())
+        app.insert_resource(default_viewport)
             .insert_resource(iced_resource.clone());
 
-        let render_app = app.sub_app_mut(RenderApp);
+        // Without a render app Iced runs headless: UIs are still updated and drawn,
+        // but nothing is ever presented.
+        let Ok(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
         render_app
-            .insert_resource(default_viewport)
             .insert_resource(iced_resource)
+            .init_resource::<render::PendingPresent>()
+            .init_resource::<backdrop::ExtractedBackdrops>()
+            .init_resource::<texture::ExtractedTextures>()
             .add_systems(ExtractSchedule, extract_iced_data);
         setup_pipeline(&mut render_app.world.get_resource_mut().unwrap());
     }
+
+    // Adding the plugin twice, e.g. from sub-plugins, is detected in `build` instead of
+    // panicking, and the duplicates are skipped.
+    fn is_unique(&self) -> bool {
+        false
+    }
 }
 
 struct IcedProps {
     renderer: Renderer,
     debug: iced_runtime::Debug,
-    clipboard: iced_core::clipboard::Null,
+    settings: iced::Settings,
+    format: TextureFormat,
+    suspended: bool,
+    // When a UI was last drawn, and the GPU the wgpu renderer was dropped from after
+    // `IcedSettings::renderer_idle_timeout`, to recreate it on.
+    last_drawn: std::time::Instant,
+    released: Option<(RenderDevice, RenderQueue)>,
+    // The modal UI displayed this frame, drawn over the others in `PostUpdate`.
+    modal_layer: Option<modal::Layer>,
+    // The UIs displayed this frame with a `DisplayOptions::z_index` other than 0, in the
+    // order they were, drawn with the others in `PostUpdate`.
+    z_layers: Vec<(i32, modal::Layer)>,
+    // The UIs displayed into images this frame, presented by the render node.
+    texture_layers: Vec<texture::TextureLayer>,
+    // The context menu opened by a UI, until it's dismissed.
+    context_menu: Option<menu::ContextMenu>,
+    // Whether the focus ring is shown, as the user last navigated with the keyboard.
+    keyboard_navigation: bool,
+    // Whether a widget of the UIs displayed this frame has the keyboard focus.
+    keyboard_focus: bool,
+    scrolling: scroll::Scrolling,
+    // The widgets of the UIs displayed last frame, for `IcedContext::hit_test`.
+    hit_targets: hit::HitTargets,
+    // Which of the frame's events each UI is fed, from the top one down.
+    routing: routing::Routing,
+    // The pointer and keyboard input taken by the UIs, for `IcedEventCaptureStatus`.
+    capture: capture::Capture,
+    // The cursor of the session being replayed, seen by the UIs instead of the window's.
+    replayed_cursor: Option<Cursor>,
+    prewarm: prewarm::Prewarm,
+    // The widgets wrapped in `widget::tracked` of the UIs displayed this frame.
+    tracked_widgets: HashMap<iced_core::widget::Id, TrackedWidget>,
+    // The scale factor of the viewport the renderer's primitives were drawn in.
+    drawn_scale_factor: f64,
+    // The blurs requested for the UI drawn this frame.
+    backdrops: Vec<BackdropBlur>,
+    // The UIs drawn while the window wasn't focused, presented again instead of drawing
+    // them with `UnfocusedUpdatePolicy::EventsOnly` and `Paused`, and whether this
+    // frame's are to be kept.
+    retained: Option<retain::Retained>,
+    retain: bool,
+    // The panics caught while displaying UIs, with `IcedSettings::catch_panics`.
+    panics: panics::Panics,
+    // The futures and streams of the commands run by `IcedContext::run_command`.
+    tasks: command::Tasks,
+    // The navigation sent to virtual keyboards, until a UI holding them is displayed.
+    #[cfg(feature = "virtual-keyboard")]
+    keyboard_navigations: virtual_keyboard::Navigations,
+    // The widget bounds of this frame's UIs, for `IcedTestHarness::layout_of`.
+    #[cfg(feature = "test-utils")]
+    layouts: layout::Layouts,
+    // What this frame's UIs did with their events, for the debug panel.
+    #[cfg(feature = "debug")]
+    debug_stats: debug::Stats,
 }
 
 impl IcedProps {
     fn new(app: &App, config: &IcedPlugin) -> Self {
-        let render_world = &app.sub_app(RenderApp).world;
-        let device = render_world
-            .get_resource::<RenderDevice>()
-            .unwrap()
-            .wgpu_device();
-        let queue = render_world.get_resource::<RenderQueue>().unwrap();
-        let mut backend =
-            iced_wgpu::Backend::new(device, queue.as_ref(), config.settings, render::TEXTURE_FMT);
+        let render_world = app.get_sub_app(RenderApp).ok().map(|app| &app.world);
+        let device = render_world.and_then(|world| world.get_resource::<RenderDevice>());
+        let queue = render_world.and_then(|world| world.get_resource::<RenderQueue>());
+        let gpu = device
+            .zip(queue)
+            .map(|(device, queue)| (device.wgpu_device(), queue.as_ref()))
+            .filter(|_| !config.iced_settings.software_rendering);
+        let mut renderer = Self::create_renderer(
+            gpu,
+            config.settings,
+            render::TEXTURE_FMT,
+            config.settings.default_text_size,
+        );
         for font in &config.fonts {
-            backend.load_font(Cow::Borrowed(*font));
+            renderer.load_font(Cow::Borrowed(*font));
+        }
+        let mut prewarm = prewarm::Prewarm::default();
+        if config.prewarm_ascii {
+            let size = config
+                .iced_settings
+                .default_text_size
+                .unwrap_or(config.settings.default_text_size);
+            prewarm.request_ascii(config.settings.default_font, size);
         }
 
         Self {
-            renderer: Renderer::Wgpu(iced_wgpu::Renderer::new(
+            renderer,
+            debug: iced_runtime::Debug::new(),
+            settings: config.settings,
+            format: render::TEXTURE_FMT,
+            suspended: false,
+            last_drawn: std::time::Instant::now(),
+            released: None,
+            modal_layer: None,
+            z_layers: Vec::new(),
+            texture_layers: Vec::new(),
+            context_menu: None,
+            keyboard_navigation: false,
+            keyboard_focus: false,
+            scrolling: scroll::Scrolling::default(),
+            hit_targets: hit::HitTargets::default(),
+            routing: routing::Routing::default(),
+            capture: capture::Capture::default(),
+            replayed_cursor: None,
+            retained: None,
+            retain: false,
+            panics: panics::Panics::default(),
+            tasks: command::Tasks::default(),
+            #[cfg(feature = "virtual-keyboard")]
+            keyboard_navigations: virtual_keyboard::Navigations::default(),
+            tracked_widgets: HashMap::new(),
+            prewarm,
+            drawn_scale_factor: 1.0,
+            backdrops: Vec::new(),
+            #[cfg(feature = "test-utils")]
+            layouts: layout::Layouts::default(),
+            #[cfg(feature = "debug")]
+            debug_stats: debug::Stats::default(),
+        }
+    }
+
+    fn create_renderer(
+        gpu: Option<(&iced_wgpu::wgpu::Device, &iced_wgpu::wgpu::Queue)>,
+        settings: iced::Settings,
+        format: TextureFormat,
+        text_size: iced::Pixels,
+    ) -> Renderer {
+        let backend =
+            gpu.and_then(|(device, queue)| Self::create_backend(device, queue, settings, format));
+        match backend {
+            Some(backend) => Renderer::Wgpu(iced_wgpu::Renderer::new(
                 backend,
-                config.settings.default_font,
-                config.settings.default_text_size,
+                settings.default_font,
+                text_size,
             )),
-            debug: iced_runtime::Debug::new(),
-            clipboard: iced_core::clipboard::Null,
+            None => Renderer::TinySkia(iced_tiny_skia::Renderer::new(
+                iced_tiny_skia::Backend::new(),
+                settings.default_font,
+                text_size,
+            )),
+        }
+    }
+
+    /// The wgpu backend, or `None` if the GPU can't run it, in which case the UI is
+    /// rendered on the CPU instead.
+    fn create_backend(
+        device: &iced_wgpu::wgpu::Device,
+        queue: &iced_wgpu::wgpu::Queue,
+        settings: iced::Settings,
+        format: TextureFormat,
+    ) -> Option<iced_wgpu::Backend> {
+        // wgpu reports the errors of unsupported devices, like failing to create a
+        // pipeline, by panicking.
+        std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            iced_wgpu::Backend::new(device, queue, settings, format)
+        }))
+        .map_err(|_| {
+            bevy_utils::tracing::warn!(
+                "Iced's wgpu renderer failed to start, falling back to rendering the UI on the CPU"
+            );
+        })
+        .ok()
+    }
+
+    /// Replace the renderer to change its default text size. Fonts are loaded into
+    /// iced's global font system, so they don't have to be loaded again.
+    fn rebuild_renderer(
+        &mut self,
+        gpu: Option<(&iced_wgpu::wgpu::Device, &iced_wgpu::wgpu::Queue)>,
+        text_size: iced::Pixels,
+    ) {
+        let gpu = gpu.filter(|_| matches!(self.renderer, Renderer::Wgpu(_)));
+        self.renderer = Self::create_renderer(gpu, self.settings, self.format, text_size);
+    }
+
+    /// Switch to the format of the surface presented to, rebuilding the backend if it
+    /// changed. Returns whether it did.
+    fn adopt_surface_format(
+        &mut self,
+        gpu: Option<(&iced_wgpu::wgpu::Device, &iced_wgpu::wgpu::Queue)>,
+        format: TextureFormat,
+    ) -> bool {
+        if format == self.format {
+            return false;
+        }
+        if !format.is_srgb() && !cfg!(feature = "web-colors") {
+            // WebGL2 commonly hands out non-sRGB surfaces, which iced doesn't correct for.
+            warn!("Presenting Iced to a non-sRGB surface ({format:?}), colors may look washed out; consider enabling the `web-colors` feature");
+        }
+        self.rebuild_backend(gpu, format);
+        true
+    }
+
+    /// Replace the wgpu backend, keeping the primitives recorded by the renderer.
+    /// Without a working backend, the UI is rendered on the CPU from the next frame on.
+    fn rebuild_backend(
+        &mut self,
+        gpu: Option<(&iced_wgpu::wgpu::Device, &iced_wgpu::wgpu::Queue)>,
+        format: TextureFormat,
+    ) {
+        // The format is kept on the CPU too, for the composite pipeline and for the wgpu
+        // renderer recreated once a released one is woken up.
+        self.format = format;
+        let (Renderer::Wgpu(renderer), Some((device, queue))) = (&mut self.renderer, gpu) else {
+            return;
+        };
+        match Self::create_backend(device, queue, self.settings, format) {
+            Some(new) => renderer.with_primitives(|backend, _| *backend = new),
+            None => {
+                self.renderer = Renderer::TinySkia(iced_tiny_skia::Renderer::new(
+                    iced_tiny_skia::Backend::new(),
+                    renderer.default_font(),
+                    renderer.default_size(),
+                ));
+            }
+        }
+    }
+
+    /// Drop the wgpu renderer, keeping a CPU renderer with the same font and text size in
+    /// its place until [`IcedProps::wake_renderer`] recreates it on the same GPU.
+    fn release_renderer(&mut self, device: &RenderDevice, queue: &RenderQueue) {
+        let Renderer::Wgpu(renderer) = &self.renderer else {
+            return;
+        };
+        self.renderer = Renderer::TinySkia(iced_tiny_skia::Renderer::new(
+            iced_tiny_skia::Backend::new(),
+            renderer.default_font(),
+            renderer.default_size(),
+        ));
+        self.released = Some((device.clone(), queue.clone()));
+    }
+
+    /// Present the UIs kept in [`IcedProps::retained`] again, unless a UI was already
+    /// drawn this frame.
+    fn present_retained(&mut self, did_draw: &DidDraw) {
+        self.wake_renderer();
+        if !did_draw.swap(true, std::sync::atomic::Ordering::Relaxed) {
+            if let Some(retained) = &self.retained {
+                retained.present(&mut self.renderer);
+            }
         }
     }
+
+    /// Recreate the wgpu renderer dropped by [`IcedProps::release_renderer`], before
+    /// anything is drawn with it.
+    fn wake_renderer(&mut self) {
+        self.last_drawn = std::time::Instant::now();
+        let Some((device, queue)) = self.released.take() else {
+            return;
+        };
+        let gpu = Some((device.wgpu_device(), queue.as_ref()));
+        let text_size = self.renderer.default_size();
+        self.renderer = Self::create_renderer(gpu, self.settings, self.format, text_size);
+    }
 }
 
 #[derive(Resource, Clone)]
 struct IcedResource(Arc<Mutex<IcedProps>>);
 
 impl IcedResource {
-    fn lock(&self) -> std::sync::LockResult<std::sync::MutexGuard<IcedProps>> {
+    fn lock(&self) -> std::sync::LockResult<std::sync::MutexGuard<'_, IcedProps>> {
         self.0.lock()
     }
 }
@@ -151,44 +717,260 @@ impl From<IcedProps> for IcedResource {
     }
 }
 
+// Graphs set up manually may already have the pass.
 fn setup_pipeline(graph: &mut RenderGraph) {
+    if graph.get_node_state(render::IcedPass).is_ok() {
+        return;
+    }
     graph.add_node(render::IcedPass, IcedNode::new());
 
     graph.add_node_edge(bevy_render::graph::CameraDriverLabel, render::IcedPass);
 }
 
-#[derive(Default)]
-struct IcedCache {
-    cache: HashMap<TypeId, Option<iced_runtime::user_interface::Cache>>,
-}
-
-impl IcedCache {
-    fn get<M: Any>(&mut self) -> &mut Option<iced_runtime::user_interface::Cache> {
-        let id = TypeId::of::<M>();
-        if !self.cache.contains_key(&id) {
-            self.cache.insert(id, Some(Default::default()));
-        }
-        self.cache.get_mut(&id).unwrap()
-    }
-}
-
 /// Settings used to independently customize Iced rendering.
+///
+/// Build them from the defaults with the `with_*` setters, e.g. to pass to
+/// [`IcedPlugin::new`], and change the fields of the resource at runtime:
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_iced::{iced, IcedPlugin, IcedSettings};
+/// let plugin = IcedPlugin::new(
+///     IcedSettings::default()
+///         .with_scale_factor(1.5)
+///         .with_theme(iced::Theme::Light)
+///         .with_text_scale(1.25),
+/// );
+/// assert_eq!(plugin.iced_settings.scale_factor, Some(1.5));
+///
+/// fn zoom_in(mut settings: ResMut<IcedSettings>) {
+///     settings.scale_factor = Some(settings.scale_factor.unwrap_or(1.0) + 0.25);
+/// }
+/// # App::new().add_systems(Update, zoom_in);
+/// ```
 #[derive(Clone, Resource)]
 pub struct IcedSettings {
     /// The scale factor to use for rendering Iced elements.
     /// Setting this to `None` defaults to using the `Window`s scale factor.
+    /// Mouse and touch positions are converted with the same factor, so widgets are
+    /// hit where they're drawn. Changing it, or the window's scale factor changing, e.g.
+    /// when it's moved to a monitor with another DPI, takes effect on the next UI
+    /// displayed, without the window being resized.
     pub scale_factor: Option<f64>,
     /// The theme to use for rendering Iced elements.
     pub theme: iced_widget::style::Theme,
-    /// The style to use for rendering Iced elements.
-    pub style: iced::Style,
+    /// The style to use for rendering Iced elements, or `None` to take the default text
+    /// color from the theme's palette, so that it stays legible on light themes.
+    pub style: Option<iced::Style>,
+    /// Whether to send [`IcedWidgetEvent`]s for the widgets of the displayed UIs.
+    pub widget_events: bool,
+    /// The longest time between two presses of a widget for them to be sent as an
+    /// [`IcedWidgetEvent::DoubleClicked`], e.g. the OS setting on platforms where it's
+    /// known. It only applies to these events, sent with [`IcedSettings::widget_events`].
+    ///
+    /// Widgets detecting double-clicks themselves, like text inputs selecting a word and
+    /// [`widget::drag_region`], use iced's fixed 300 ms instead. Iced events carry no
+    /// timestamps, so they time the presses when the UI handles them, once per frame:
+    /// the interval is measured to within a frame, and two presses in the same frame are
+    /// a double-click, at any frame rate.
+    pub double_click_interval: std::time::Duration,
+    /// The opacity of the whole UI, from `0.0` to `1.0`. Tween it to fade the UI in and
+    /// out; at `0.0` the UI isn't presented at all.
+    pub opacity: f32,
+    /// The resolution the UI is rasterized at, relative to the window's, from `0.0` to
+    /// `1.0`, e.g. `0.75` to save GPU time on handhelds. The UI is upscaled to the window,
+    /// so text looks softer, but layout and input stay at full resolution.
+    pub render_scale: f32,
+    /// The outline drawn around the focused widget while navigating with the keyboard,
+    /// or `None` to leave focus styling to the widgets.
+    pub focus_ring: Option<FocusRing>,
+    /// The number of frames after which the widget state of a UI that isn't displayed is
+    /// dropped, e.g. for rarely opened panels. The UI starts afresh the next time it's
+    /// displayed, as if it never was. Setting this to `None` keeps the state forever.
+    pub cache_eviction_frames: Option<u32>,
+    /// Whether touches are fed to Iced. Turn this off to only ever take the cursor from
+    /// the mouse, e.g. on desktops with spurious touchscreen input.
+    pub touch_input: bool,
+    /// Whether to set the window's cursor icon from the UI's mouse interaction, e.g. a
+    /// pointer over buttons. The app's own cursor is restored once the mouse leaves the
+    /// interactive widgets, so game systems can still set it elsewhere.
+    pub apply_cursor_icon: bool,
+    /// Whether a modal UI also keeps the game from seeing input: while
+    /// [`IcedModal::is_open`], the mouse buttons and keys are released, and the mouse
+    /// motion and wheel events are dropped, in [`IcedSet::BlockGameInput`]. Game systems
+    /// reading input in `PreUpdate` must run after that set.
+    pub modal_blocks_game_input: bool,
+    /// Whether the input the UI captures is kept from the game: while the
+    /// [`IcedEventCaptureStatus`] has the pointer captured, the mouse buttons are released
+    /// and the wheel events are dropped, and while it has the keyboard captured, the keys
+    /// are released, in [`IcedSet::BlockGameInput`]. The UI still gets all of it,
+    /// including the modifiers held since before the keyboard was captured, e.g. for
+    /// Ctrl+C or Shift+arrow selection in a focused text input.
+    pub block_captured_input: bool,
+    /// Whether to render the UI on the CPU with tiny-skia and upload it as a texture,
+    /// e.g. as a setting for machines whose GPU drivers can't run iced's wgpu renderer.
+    /// This is also the fallback when the wgpu renderer fails to start. It's only read
+    /// when the plugin is set up.
+    pub software_rendering: bool,
+    /// Whether Ctrl+wheel over the window zooms its UI within the configured bounds, and
+    /// Ctrl+0 resets the zoom, as in browsers. The wheel then doesn't reach the UI, so
+    /// scrollables don't scroll along. The zoom is kept in [`IcedZoom`].
+    pub ctrl_scroll_zoom: Option<ZoomConfig>,
+    /// How long the UI may go without being drawn before the wgpu renderer is dropped,
+    /// releasing its GPU memory, e.g. for games that only show a UI in menus. It's
+    /// recreated the next time a UI is displayed, keeping the fonts and widget state.
+    /// Setting this to `None` keeps the renderer alive for the lifetime of the app.
+    pub renderer_idle_timeout: Option<std::time::Duration>,
+    /// The size of text without an explicit size.
+    /// Setting this to `None` defaults to [`iced::Settings::default_text_size`].
+    pub default_text_size: Option<iced::Pixels>,
+    /// The factor applied to the default text size, without scaling the rest of the
+    /// layout like `scale_factor` does. Containers grow to fit the scaled text, and the
+    /// factor is combined with [`IcedAccessibility::text_scale`]. Text with an explicit
+    /// size isn't scaled.
+    pub text_scale: f32,
+    /// What displaying a UI does while the window doesn't have the OS focus, e.g. to
+    /// save the frame budget of tool windows in the background. The UI is displayed in
+    /// full again as soon as the window is focused.
+    pub unfocused_update_policy: UnfocusedUpdatePolicy,
+    /// Whether a panic while laying out, updating or drawing a UI is caught, e.g. in a
+    /// tooltip formatting its text, rather than taking the app down with it. The UI
+    /// shows an error banner instead and starts afresh, with its widget state reset, and
+    /// an [`IcedPanic`] is sent. Panics while building the element passed to
+    /// [`IcedContext::display`] happen before it's called, so they aren't caught: build
+    /// it in the closure of [`IcedContext::display_view`] to catch them too.
+    pub catch_panics: bool,
 }
 
 impl IcedSettings {
     /// Set the `scale_factor` used to render Iced elements.
+    #[deprecated(note = "assign `IcedSettings::scale_factor`, or use `with_scale_factor`")]
     pub fn set_scale_factor(&mut self, factor: impl Into<Option<f64>>) {
         self.scale_factor = factor.into();
     }
+
+    /// Set [`IcedSettings::scale_factor`].
+    pub fn with_scale_factor(mut self, factor: impl Into<Option<f64>>) -> Self {
+        self.scale_factor = factor.into();
+        self
+    }
+
+    /// Set [`IcedSettings::theme`].
+    pub fn with_theme(mut self, theme: iced_widget::style::Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    /// Set [`IcedSettings::style`].
+    pub fn with_style(mut self, style: iced::Style) -> Self {
+        self.style = Some(style);
+        self
+    }
+
+    /// Set [`IcedSettings::widget_events`].
+    pub fn with_widget_events(mut self, enabled: bool) -> Self {
+        self.widget_events = enabled;
+        self
+    }
+
+    /// Set [`IcedSettings::double_click_interval`].
+    pub fn with_double_click_interval(mut self, interval: std::time::Duration) -> Self {
+        self.double_click_interval = interval;
+        self
+    }
+
+    /// Set [`IcedSettings::opacity`].
+    pub fn with_opacity(mut self, opacity: f32) -> Self {
+        self.opacity = opacity;
+        self
+    }
+
+    /// Set [`IcedSettings::render_scale`].
+    pub fn with_render_scale(mut self, scale: f32) -> Self {
+        self.render_scale = scale;
+        self
+    }
+
+    /// Set [`IcedSettings::focus_ring`].
+    pub fn with_focus_ring(mut self, ring: impl Into<Option<FocusRing>>) -> Self {
+        self.focus_ring = ring.into();
+        self
+    }
+
+    /// Set [`IcedSettings::cache_eviction_frames`].
+    pub fn with_cache_eviction_frames(mut self, frames: impl Into<Option<u32>>) -> Self {
+        self.cache_eviction_frames = frames.into();
+        self
+    }
+
+    /// Set [`IcedSettings::touch_input`].
+    pub fn with_touch_input(mut self, enabled: bool) -> Self {
+        self.touch_input = enabled;
+        self
+    }
+
+    /// Set [`IcedSettings::apply_cursor_icon`].
+    pub fn with_apply_cursor_icon(mut self, enabled: bool) -> Self {
+        self.apply_cursor_icon = enabled;
+        self
+    }
+
+    /// Set [`IcedSettings::modal_blocks_game_input`].
+    pub fn with_modal_blocks_game_input(mut self, enabled: bool) -> Self {
+        self.modal_blocks_game_input = enabled;
+        self
+    }
+
+    /// Set [`IcedSettings::block_captured_input`].
+    pub fn with_block_captured_input(mut self, enabled: bool) -> Self {
+        self.block_captured_input = enabled;
+        self
+    }
+
+    /// Set [`IcedSettings::software_rendering`].
+    pub fn with_software_rendering(mut self, enabled: bool) -> Self {
+        self.software_rendering = enabled;
+        self
+    }
+
+    /// Set [`IcedSettings::ctrl_scroll_zoom`].
+    pub fn with_ctrl_scroll_zoom(mut self, zoom: impl Into<Option<ZoomConfig>>) -> Self {
+        self.ctrl_scroll_zoom = zoom.into();
+        self
+    }
+
+    /// Set [`IcedSettings::renderer_idle_timeout`].
+    pub fn with_renderer_idle_timeout(
+        mut self,
+        timeout: impl Into<Option<std::time::Duration>>,
+    ) -> Self {
+        self.renderer_idle_timeout = timeout.into();
+        self
+    }
+
+    /// Set [`IcedSettings::default_text_size`].
+    pub fn with_default_text_size(mut self, size: impl Into<Option<iced::Pixels>>) -> Self {
+        self.default_text_size = size.into();
+        self
+    }
+
+    /// Set [`IcedSettings::text_scale`].
+    pub fn with_text_scale(mut self, scale: f32) -> Self {
+        self.text_scale = scale;
+        self
+    }
+
+    /// Set [`IcedSettings::unfocused_update_policy`].
+    pub fn with_unfocused_update_policy(mut self, policy: UnfocusedUpdatePolicy) -> Self {
+        self.unfocused_update_policy = policy;
+        self
+    }
+
+    /// Set [`IcedSettings::catch_panics`].
+    pub fn with_catch_panics(mut self, enabled: bool) -> Self {
+        self.catch_panics = enabled;
+        self
+    }
 }
 
 impl Default for IcedSettings {
@@ -196,88 +978,1334 @@ impl Default for IcedSettings {
         Self {
             scale_factor: None,
             theme: iced_widget::style::Theme::Dark,
-            style: iced::Style {
-                text_color: iced_core::Color::WHITE,
-            },
+            style: None,
+            widget_events: false,
+            double_click_interval: std::time::Duration::from_millis(300),
+            opacity: 1.0,
+            render_scale: 1.0,
+            focus_ring: Some(FocusRing::default()),
+            cache_eviction_frames: None,
+            touch_input: true,
+            apply_cursor_icon: true,
+            modal_blocks_game_input: false,
+            block_captured_input: false,
+            software_rendering: false,
+            ctrl_scroll_zoom: None,
+            renderer_idle_timeout: None,
+            default_text_size: None,
+            text_scale: 1.0,
+            unfocused_update_policy: UnfocusedUpdatePolicy::Full,
+            catch_panics: false,
+        }
+    }
+}
+
+/// What displaying a UI does while the window doesn't have the OS focus, see
+/// [`IcedSettings::unfocused_update_policy`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum UnfocusedUpdatePolicy {
+    /// Lay out, update and draw the UI every frame, as when the window is focused.
+    #[default]
+    Full,
+    /// Lay out the UI and hand it the frame's events, so that its messages are still
+    /// sent, but keep presenting what it drew last instead of drawing it again. Changes
+    /// to the UI, like hovered buttons, only show once the window is focused.
+    EventsOnly,
+    /// Skip the UI entirely and keep presenting what it drew last. Its widget state is
+    /// kept, and the frame's events are dropped.
+    Paused,
+}
+
+/// Overrides of [`IcedSettings`] for the primary window, e.g. to give an editor the
+/// scale factor of its monitor without changing the settings of the others it may be
+/// moved to. It's not read on other windows: UIs can't be displayed in secondary windows
+/// (see [`IcedContext::display_in_window`]), so a preview window with its own theme isn't
+/// supported. It's not read either while UIs are presented to an offscreen
+/// [`IcedRenderTarget`].
+///
+/// Changes take effect on the next UI displayed, with the viewport rebuilt for a new
+/// scale factor, and the window falls back to the global settings once the component is
+/// removed. A theme or style given in [`DisplayOptions`] still wins over the window's,
+/// as does [`IcedAccessibility::high_contrast`].
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy::window::PrimaryWindow;
+/// # use bevy_iced::{iced, IcedWindowSettings};
+/// fn setup(mut commands: Commands, window: Query<Entity, With<PrimaryWindow>>) {
+///     commands.entity(window.single()).insert(IcedWindowSettings {
+///         scale_factor: Some(1.25),
+///         theme: Some(iced::Theme::Light),
+///         ..Default::default()
+///     });
+/// }
+/// # App::new().add_systems(Startup, setup);
+/// ```
+#[derive(Component, Clone, Debug, Default, PartialEq)]
+pub struct IcedWindowSettings {
+    /// The scale factor of the window's UIs, instead of [`IcedSettings::scale_factor`].
+    pub scale_factor: Option<f64>,
+    /// The theme of the window's UIs, instead of [`IcedSettings::theme`].
+    pub theme: Option<Theme>,
+    /// The style of the window's UIs, instead of [`IcedSettings::style`].
+    pub style: Option<iced::Style>,
+}
+
+/// The area of the window that isn't obscured by notches, rounded corners or system bars.
+///
+/// Bevy doesn't report safe-area insets yet, so they have to be supplied by the application
+/// (e.g. queried through the platform's native APIs) by updating this resource.
+#[derive(Clone, Copy, Debug, Resource)]
+pub struct IcedSafeArea {
+    /// The insets from each edge of the window, in logical pixels.
+    pub insets: iced::Padding,
+    /// Whether the root element should be laid out inside the insets, in a container
+    /// filling the window that's only added while there are any.
+    /// Setting this to `false` only exposes the insets for manual handling.
+    pub inset_root: bool,
+}
+
+impl IcedSafeArea {
+    fn has_insets(&self) -> bool {
+        let insets = self.insets;
+        [insets.top, insets.right, insets.bottom, insets.left] != [0.0; 4]
+    }
+}
+
+impl Default for IcedSafeArea {
+    fn default() -> Self {
+        Self {
+            insets: iced::Padding::ZERO,
+            inset_root: true,
         }
     }
 }
 
+/// A cursor position that replaces the OS cursor's, e.g. for a software cursor that is
+/// also moved with a gamepad.
+///
+/// While it's set, widgets are hovered and clicked at this position, in the window's
+/// logical coordinates like [`Window::cursor_position`] (or the texture's, with an
+/// offscreen [`IcedRenderTarget`]), and the OS cursor's moves are ignored. Set it back
+/// to `None` to follow the OS cursor again.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Resource, Deref, DerefMut)]
+pub struct IcedCursorOverride(pub Option<bevy_math::Vec2>);
+
+/// Whether `bevy_iced` is active, e.g. to hide the UI for screenshots.
+///
+/// While disabled, input isn't converted into Iced events, [`IcedContext::display`] does
+/// nothing and the UI isn't presented. The state of the widgets is kept, so the UI comes
+/// back as it was on the first frame after re-enabling it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Resource, Deref, DerefMut)]
+pub struct IcedEnabled(pub bool);
+
+impl Default for IcedEnabled {
+    fn default() -> Self {
+        Self(true)
+    }
+}
+
+// What the UIs need that isn't `Send`: their widget state, the system clipboard and the
+// widget operations waiting to be run on them.
+#[derive(SystemParam)]
+struct NonSendState<'w> {
+    cache: NonSendMut<'w, IcedCache>,
+    clipboard: NonSendMut<'w, clipboard::IcedClipboard>,
+    operations: NonSendMut<'w, command::Operations>,
+}
+
 // An atomic flag for updating the draw state.
 #[derive(Resource, Deref, DerefMut, Default)]
 pub(crate) struct DidDraw(std::sync::atomic::AtomicBool);
 
+/// How a UI shown with [`IcedContext::display_with`] is drawn, overriding the
+/// [`IcedSettings`] for that UI only.
+///
+/// The overrides don't apply while [`IcedAccessibility::high_contrast`] is on, so that
+/// every UI stays legible.
+#[derive(Clone, Debug, Default)]
+pub struct DisplayOptions {
+    /// The theme to draw with instead of [`IcedSettings::theme`].
+    pub theme: Option<Theme>,
+    /// The style to draw with instead of [`IcedSettings::style`].
+    pub style: Option<iced::Style>,
+    /// The bevy_ui node to display the UI in instead of the whole window, e.g. for an
+    /// Iced panel in a bevy_ui HUD. Requires the `bevy_ui` feature.
+    ///
+    /// The UI is laid out in the node's bounds as of bevy_ui's last layout, clipped to
+    /// them, and follows the node as its layout changes, e.g. when the window is resized.
+    /// It only sees the cursor while it's over the node. Nothing is displayed while the
+    /// entity isn't a node, e.g. once it's despawned. The safe area doesn't apply, as the
+    /// node is placed with bevy_ui already.
+    #[cfg(feature = "bevy_ui")]
+    pub node: Option<Entity>,
+    /// Where the UI is drawn among the UIs displayed in the same frame: UIs with a higher
+    /// index are drawn above it, whatever order the systems displaying them run in, and
+    /// UIs of the same index are drawn in the order they're displayed. Modal UIs are drawn
+    /// above all of them. Defaults to `0`.
+    ///
+    /// Input goes to the top UI first. A UI doesn't see the cursor where the widgets
+    /// handling the pointer of the UIs above it were last frame, so that an overlay takes
+    /// the clicks and the scrolling over it, and it isn't fed the events the UIs above it
+    /// displayed earlier in the frame captured. The keys go to the topmost UI with a
+    /// focused widget last frame, and to the ones above it, so that two focused text
+    /// inputs don't both type. After a click, the keys go to the UI clicked instead, so
+    /// that typing right after clicking into another UI's text input goes there.
+    pub z_index: i32,
+}
+
+impl DisplayOptions {
+    /// Draw with `theme` instead of [`IcedSettings::theme`].
+    #[must_use]
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = Some(theme);
+        self
+    }
+
+    /// Draw with `style` instead of [`IcedSettings::style`], e.g. for a different
+    /// default text color.
+    #[must_use]
+    pub fn style(mut self, style: iced::Style) -> Self {
+        self.style = Some(style);
+        self
+    }
+
+    /// Draw the UI above the UIs with a lower `z_index` and below the ones with a higher
+    /// one, see [`DisplayOptions::z_index`](#structfield.z_index).
+    #[must_use]
+    pub fn z_index(mut self, z_index: i32) -> Self {
+        self.z_index = z_index;
+        self
+    }
+
+    /// Display the UI in the bevy_ui `node` instead of the whole window, see
+    /// [`DisplayOptions::node`](#structfield.node).
+    #[cfg(feature = "bevy_ui")]
+    #[must_use]
+    pub fn node(mut self, node: Entity) -> Self {
+        self.node = Some(node);
+        self
+    }
+}
+
+/// Why [`IcedContext::display_in_window`] didn't display a UI.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IcedDisplayError {
+    /// The entity isn't a window, e.g. as the window was closed this frame.
+    WindowNotFound(Entity),
+    /// The window isn't the primary window. UIs can't be displayed in secondary windows.
+    WindowNotDriven(Entity),
+}
+
+impl std::fmt::Display for IcedDisplayError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::WindowNotFound(entity) => write!(f, "{entity:?} isn't a window"),
+            Self::WindowNotDriven(entity) => {
+                write!(
+                    f,
+                    "{entity:?} is a secondary window, UIs can't be displayed in it"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for IcedDisplayError {}
+
+/// Sends UI messages from systems that don't display a UI, e.g. to handle a network
+/// packet exactly like the user clicking "Accept". Messages sent this way are
+/// indistinguishable from those produced by widgets, see [`IcedContext::send`].
+#[derive(SystemParam)]
+pub struct IcedMessages<'w, Message: bevy_ecs::event::Event> {
+    // Optional, to name the message type when its events weren't added to the app,
+    // instead of bevy's panic about a missing resource.
+    events: Option<ResMut<'w, Events<Message>>>,
+}
+
+impl<M: bevy_ecs::event::Event> IcedMessages<'_, M> {
+    /// Send `message` as if a widget had produced it.
+    pub fn send(&mut self, message: M) {
+        self.events().send(message);
+    }
+
+    /// Send every message of `messages`, in order.
+    pub fn send_batch(&mut self, messages: impl IntoIterator<Item = M>) {
+        self.events().send_batch(messages);
+    }
+
+    fn events(&mut self) -> &mut Events<M> {
+        match &mut self.events {
+            Some(events) => events,
+            None => panic!(
+                "The UI messages `{0}` aren't registered, add them to the app with \
+                 `app.add_event::<{0}>()` to use `IcedContext<{0}>`",
+                std::any::type_name::<M>()
+            ),
+        }
+    }
+}
+
 /// The context for interacting with Iced. Add this as a parameter to your system.
-/// ```ignore
-/// fn ui_system(..., mut ctx: IcedContext<UiMessage>) {
-///     let element = ...; // Build your element
-///     ctx.display(element);
+///
+/// `IcedContext<T>` requires an event system to be defined in the [`App`].
+/// Do so by invoking `app.add_event::<T>()` when constructing your App; without it,
+/// displaying a UI panics.
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_iced::iced::widget::button;
+/// use bevy_iced::{IcedContext, IcedPlugin};
+///
+/// #[derive(Event, Clone)]
+/// enum UiMessage {
+///     Play,
+/// }
+///
+/// fn main() {
+///     App::new()
+///         .add_plugins((DefaultPlugins, IcedPlugin::default()))
+///         .add_event::<UiMessage>()
+///         .add_systems(Update, ui_system)
+///         .run();
+/// }
+///
+/// fn ui_system(mut ctx: IcedContext<UiMessage>) {
+///     ctx.display(button("Play").on_press(UiMessage::Play));
 /// }
 /// ```
 ///
-/// `IcedContext<T>` requires an event system to be defined in the [`App`].
-/// Do so by invoking `app.add_event::<T>()` when constructing your App.
+/// The widget state of a UI, like its shaped text and scroll offsets, is kept between
+/// frames by message type and the order UIs of that type are displayed in. Systems that
+/// display several UIs of the same type should run in a fixed order, e.g. with
+/// [`chain`](bevy_ecs::schedule::IntoSystemConfigs::chain).
 #[derive(SystemParam)]
 pub struct IcedContext<'w, 's, Message: bevy_ecs::event::Event> {
-    viewport: Res<'w, ViewportResource>,
+    viewport: render::PrimaryViewport<'w, 's>,
     props: Res<'w, IcedResource>,
-    settings: Res<'w, IcedSettings>,
-    windows: Query<'w, 's, &'static Window, With<PrimaryWindow>>,
+    appearance: accessibility::Appearance<'w, 's>,
+    safe_area: Res<'w, IcedSafeArea>,
+    enabled: Res<'w, IcedEnabled>,
+    cursor_override: Res<'w, IcedCursorOverride>,
+    windows: Query<'w, 's, (Entity, &'static Window), With<PrimaryWindow>>,
     events: ResMut<'w, IcedEventQueue>,
-    cache_map: NonSendMut<'w, IcedCache>,
-    messages: EventWriter<'w, Message>,
+    non_send: NonSendState<'w>,
+    messages: IcedMessages<'w, Message>,
     did_draw: ResMut<'w, DidDraw>,
     touches: Res<'w, Touches>,
+    iced_windows: window::IcedWindows<'w, 's>,
+    widget_events: interaction::WidgetEvents<'w>,
+    modal: ResMut<'w, IcedModal>,
+    announcements: ResMut<'w, announce::Announcements>,
 }
 
 impl<'w, 's, M: bevy_ecs::event::Event> IcedContext<'w, 's, M> {
+    /// Run an Iced [`Command`](iced::Command), e.g. one returned by `iced::window::resize`.
+    ///
+    /// Window actions apply to the primary window, whose Iced window id is
+    /// [`iced::window::Id::MAIN`]; actions for other ids are ignored with a warning. The
+    /// actions that bevy doesn't expose, like dragging the window or requesting the
+    /// user's attention, require the `winit` feature. Clipboard actions, like the ones of
+    /// [`iced::clipboard::read`] and [`iced::clipboard::write`], use the system clipboard
+    /// with the `clipboard` feature; without it, and on the web, reads produce `None`.
+    ///
+    /// Widget operations, like the ones of `text_input::focus` and `scrollable::snap_to`,
+    /// are run on the next UI of this message type displayed, this frame or a later one,
+    /// before it handles its events. Futures and streams, like the ones of
+    /// [`Command::perform`](iced::Command::perform), run on bevy's
+    /// [`AsyncComputeTaskPool`](bevy_tasks::AsyncComputeTaskPool), and their messages are
+    /// sent before `Update`, on the frame after they're produced at the earliest. Fonts
+    /// are loaded right away. System and custom actions aren't supported and are ignored.
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_iced::iced::{self, widget::text_input};
+    /// # use bevy_iced::IcedContext;
+    /// # #[derive(Event, Clone)]
+    /// # enum UiMessage {
+    /// #     Motd(String),
+    /// # }
+    /// # async fn fetch_motd() -> String {
+    /// #     "Welcome!".to_owned()
+    /// # }
+    /// # fn ui_system(mut ctx: IcedContext<UiMessage>) {
+    /// ctx.run_command(iced::Command::batch([
+    ///     text_input::focus(text_input::Id::new("chat")),
+    ///     iced::Command::perform(fetch_motd(), UiMessage::Motd),
+    /// ]));
+    /// # }
+    /// ```
+    pub fn run_command(&mut self, command: iced::Command<M>) {
+        use iced_core::Clipboard as _;
+        use iced_runtime::clipboard;
+
+        for action in command.actions() {
+            match action {
+                iced_runtime::command::Action::Widget(operation) => {
+                    self.non_send.operations.push(operation);
+                }
+                iced_runtime::command::Action::Future(future) => {
+                    self.props.lock().unwrap().tasks.spawn_future(future);
+                }
+                iced_runtime::command::Action::Stream(stream) => {
+                    self.props.lock().unwrap().tasks.spawn_stream(stream);
+                }
+                iced_runtime::command::Action::LoadFont { bytes, tagger } => {
+                    self.load_font(bytes);
+                    self.messages.send(tagger(Ok(())));
+                }
+                iced_runtime::command::Action::Window(action) => {
+                    self.iced_windows.perform(action, self.messages.events())
+                }
+                iced_runtime::command::Action::Clipboard(clipboard::Action::Read(read, kind)) => {
+                    let contents = self.non_send.clipboard.read(kind);
+                    self.messages.send(read(contents));
+                }
+                iced_runtime::command::Action::Clipboard(clipboard::Action::Write(
+                    contents,
+                    kind,
+                )) => self.non_send.clipboard.write(kind, contents),
+                action => warn!("Unsupported Iced command action: {action:?}"),
+            }
+        }
+    }
+
+    /// Load a font from the contents of a font file, e.g. one the user picked or
+    /// downloaded, for UIs to use by name with [`Font::with_name`](iced::Font::with_name).
+    /// See [`IcedFont`] to load fonts as assets instead.
+    pub fn load_font(&mut self, bytes: impl Into<Cow<'static, [u8]>>) {
+        self.props.lock().unwrap().renderer.load_font(bytes.into());
+    }
+
+    /// The Iced window id of a window entity, e.g. for the window events of
+    /// [`IcedRawEvent`]s. Only the primary window has one, [`iced::window::Id::MAIN`].
+    pub fn window_id(&self, window: Entity) -> Option<iced::window::Id> {
+        self.iced_windows.id(window)
+    }
+
+    /// The window entity with the given Iced window id, see [`IcedContext::window_id`].
+    pub fn window_entity(&self, id: iced::window::Id) -> Option<Entity> {
+        self.iced_windows.entity(id)
+    }
+
+    /// Send `message` as if a widget of the UI had produced it, e.g. to share the
+    /// handling of a button with a keyboard shortcut. See [`IcedMessages`] to send
+    /// messages without the rest of the context.
+    pub fn send(&mut self, message: M) {
+        self.messages.send(message);
+    }
+
+    /// Send every message of `messages`, in order, see [`IcedContext::send`].
+    pub fn send_batch(&mut self, messages: impl IntoIterator<Item = M>) {
+        self.messages.send_batch(messages);
+    }
+
+    /// Start moving the window with the mouse, e.g. when a [`widget::drag_region`] of a
+    /// custom titlebar is pressed. Requires the `winit` feature.
+    pub fn start_window_drag(&mut self) {
+        self.run_command(iced::window::drag(iced::window::Id::MAIN));
+    }
+
+    /// Start resizing the window from the given edge with the mouse, e.g. when a
+    /// [`widget::drag_region`] along the window border is pressed. Requires the `winit`
+    /// feature.
+    pub fn start_window_resize(&mut self, edge: ResizeEdge) {
+        self.iced_windows.start_resize(edge);
+    }
+
+    /// Announce `text` to screen readers, e.g. "3 new messages", without moving focus.
+    ///
+    /// Announcements are made through bevy's AccessKit integration and queued until an
+    /// assistive technology starts listening. Without accessibility support, e.g.
+    /// without bevy's `AccessibilityPlugin`, they are dropped.
+    pub fn announce(&mut self, text: impl Into<String>, priority: AnnouncePriority) {
+        self.announcements.push(text.into(), priority);
+    }
+
+    /// Blur the scene behind the UI displayed this frame, e.g. behind a translucent
+    /// panel, see [`BackdropBlur`].
+    ///
+    /// Like the UI itself, the blur only lasts for the frame it's requested in.
+    pub fn blur_backdrop(&mut self, blur: BackdropBlur) {
+        if self.enabled.0 {
+            self.props.lock().unwrap().backdrops.push(blur);
+        }
+    }
+
+    /// The scroll offset of the [`scrollable`](iced::widget::scrollable) with the given
+    /// id, as of the UIs displayed last frame, e.g. to keep a chat log stuck to the
+    /// bottom when it already was. `None` if it wasn't displayed, or its content isn't
+    /// laid out as a container, like a column or row.
+    pub fn scroll_offset(
+        &self,
+        id: impl Into<iced_core::widget::Id>,
+    ) -> Option<iced::widget::scrollable::RelativeOffset> {
+        self.props.lock().unwrap().scrolling.offset(&id.into())
+    }
+
+    /// Scroll the [`scrollable`](iced::widget::scrollable) with the given id to `offset`,
+    /// e.g. [`RelativeOffset::END`](iced::widget::scrollable::RelativeOffset::END), the
+    /// next time a UI holding it is displayed.
+    pub fn snap_to(
+        &mut self,
+        id: impl Into<iced_core::widget::Id>,
+        offset: iced::widget::scrollable::RelativeOffset,
+    ) {
+        self.props
+            .lock()
+            .unwrap()
+            .scrolling
+            .snap_to(id.into(), offset);
+    }
+
+    /// Move the selected key of the [`virtual_keyboard`](widget::virtual_keyboard) with
+    /// the given id, or press it, the next time a UI holding it is displayed, e.g. from
+    /// the d-pad and a face button of a gamepad. Requires the `virtual-keyboard` feature.
+    #[cfg(feature = "virtual-keyboard")]
+    pub fn navigate_virtual_keyboard(
+        &mut self,
+        id: impl Into<iced_core::widget::Id>,
+        navigation: widget::KeyboardNavigation,
+    ) {
+        self.props
+            .lock()
+            .unwrap()
+            .keyboard_navigations
+            .push(id.into(), navigation);
+    }
+
+    /// Rasterize the glyphs of `texts` in `font`, at each of `sizes`, e.g. during a
+    /// loading screen, so that a panel showing them for the first time doesn't hitch.
+    ///
+    /// The glyphs are drawn at the end of the frame and rasterized the next time the UI
+    /// is rendered, at the current scale factor. Glyphs that are already rasterized are
+    /// skipped. See [`IcedContext::prewarm_duration`] for the time it took.
+    pub fn prewarm_text(&mut self, texts: &[&str], font: iced::Font, sizes: &[f32]) {
+        let sizes: Vec<_> = sizes.iter().copied().map(iced::Pixels).collect();
+        self.props
+            .lock()
+            .unwrap()
+            .prewarm
+            .request(texts, font, &sizes);
+    }
+
+    /// The time the last [`IcedContext::prewarm_text`] took, or `None` while the glyphs
+    /// haven't been rasterized yet, e.g. to end a loading screen once they have.
+    pub fn prewarm_duration(&self) -> Option<std::time::Duration> {
+        let props = self.props.lock().unwrap();
+        props
+            .prewarm
+            .duration()
+            .filter(|_| !props.prewarm.is_pending())
+    }
+
+    /// Open a context menu of `items` at the cursor, e.g. when a
+    /// [`mouse_area`](iced::widget::mouse_area) is right-clicked. Picking an item sends
+    /// its message.
+    ///
+    /// The menu stays open, displayed with the first UI of this message type every frame
+    /// and drawn over the other UIs, until an item is picked, Escape is pressed or the
+    /// window is pressed outside of it. It's flipped to the left of or above the cursor
+    /// where it would cross the edge of the window. While it's open it's a modal UI, so
+    /// the other UIs receive no input and [`IcedModal::is_open`]; the events of this frame,
+    /// e.g. the click that opened it, don't reach the UIs displayed after it either.
+    /// Nothing opens while the cursor isn't over the window, and opening a menu replaces
+    /// the open one.
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_iced::IcedContext;
+    /// # #[derive(Event, Clone)]
+    /// # enum UiMessage {
+    /// #     Rename(u32),
+    /// #     Delete(u32),
+    /// # }
+    /// # fn ui_system(mut ctx: IcedContext<UiMessage>, id: u32) {
+    /// ctx.open_context_menu(vec![
+    ///     ("Rename", UiMessage::Rename(id)),
+    ///     ("Delete", UiMessage::Delete(id)),
+    /// ]);
+    /// # }
+    /// ```
+    pub fn open_context_menu<L: Into<String>>(&mut self, items: impl IntoIterator<Item = (L, M)>)
+    where
+        M: Clone,
+    {
+        if !self.enabled.0 {
+            return;
+        }
+        let window = self
+            .windows
+            .get_single()
+            .ok()
+            .filter(|_| !self.viewport.is_offscreen());
+        let viewport = self.viewport.get(window, &self.appearance).clone();
+        let replayed = self.props.lock().unwrap().replayed_cursor;
+        let Cursor::Available(position) = self.cursor(&viewport, window, replayed) else {
+            return;
+        };
+        let items = items
+            .into_iter()
+            .map(|(label, message)| (label.into(), message))
+            .collect();
+        self.props.lock().unwrap().context_menu = Some(menu::ContextMenu::new(items, position));
+        self.events.clear();
+        self.modal.show();
+        self.display_context_menu();
+    }
+
+    /// The topmost widget at `point` of `window`, in logical coordinates, as of the UIs
+    /// displayed last frame, e.g. to tell what a gamepad-driven cursor or a touch gesture
+    /// recognized by the app is over without displaying the UI again. `None` if there is
+    /// none, or the window isn't the primary window, the only one with UIs.
+    ///
+    /// Widgets scrolled out of view don't count, nor do UIs covered by a modal UI. The
+    /// widgets inside buttons and other widgets handling clicks are part of them, and
+    /// the overlays of pick lists aren't laid out with their UI.
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_iced::coordinates;
+    /// # use bevy_iced::iced::advanced::graphics::Viewport;
+    /// # use bevy_iced::iced::Size;
+    /// # use bevy_iced::IcedContext;
+    /// # #[derive(Event)]
+    /// # enum UiMessage {}
+    /// # fn cursor_system(
+    /// #     ctx: IcedContext<UiMessage>,
+    /// #     window: Entity,
+    /// #     gamepad_cursor: Vec2,
+    /// #     cursor_sprite: &mut UiImage,
+    /// #     pointer: Handle<Image>,
+    /// # ) {
+    /// # let viewport = Viewport::with_physical_size(Size::new(1280, 720), 1.0);
+    /// let position = coordinates::to_iced_logical(gamepad_cursor, &viewport);
+    /// if ctx.hit_test(window, position).is_some_and(|hit| hit.interactive) {
+    ///     cursor_sprite.texture = pointer.clone();
+    /// }
+    /// # }
+    /// ```
+    pub fn hit_test(&self, window: Entity, point: iced::Point) -> Option<HitInfo> {
+        self.iced_windows.id(window)?;
+        self.props.lock().unwrap().hit_targets.hit_test(point)
+    }
+
+    /// Whether the pointer is over a widget of `window`, the primary window, handling it,
+    /// like buttons and scrollables, as of the UIs displayed last frame. Unlike
+    /// [`IcedContext::hit_test`], it doesn't count text and containers. Unlike [`IcedEventCaptureStatus::pointer_captured`],
+    /// it doesn't follow presses held down or count an open modal UI as the pointer being
+    /// over it.
+    pub fn is_pointer_over_ui(&self, window: Entity) -> bool {
+        self.iced_windows.id(window).is_some() && self.props.lock().unwrap().capture.pointer_over
+    }
+
+    /// The interactive widget nearest to `point` of `window`, within `max_distance`
+    /// logical pixels of it, like [`IcedContext::hit_test`] does for the widget right at
+    /// it, e.g. to snap a gamepad cursor to buttons. Widgets containing the point are at
+    /// a distance of 0, and the topmost one of widgets as near wins.
+    pub fn nearest_interactive(
+        &self,
+        window: Entity,
+        point: iced::Point,
+        max_distance: f32,
+    ) -> Option<HitInfo> {
+        self.iced_windows.id(window)?;
+        self.props
+            .lock()
+            .unwrap()
+            .hit_targets
+            .nearest_interactive(point, max_distance)
+    }
+
+    /// A dump of the layout of the last UI of this message type displayed in `window`,
+    /// this frame or the one before, e.g. to find out why a widget isn't where it
+    /// should be. `None` when no UI of this type was displayed in the window, which is
+    /// only ever the primary window. Requires the `debug` feature.
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_iced::IcedContext;
+    /// # #[derive(Event)]
+    /// # enum UiMessage {}
+    /// # fn ui_system(ctx: IcedContext<UiMessage>, window: Entity) {
+    /// if let Some(layout) = ctx.debug_layout(window) {
+    ///     info!("{layout}");
+    /// }
+    /// # }
+    /// ```
+    ///
+    /// [`IcedDebugPanel::log_layouts`] logs the layouts of every UI instead.
+    #[cfg(feature = "debug")]
+    pub fn debug_layout(&self, window: Entity) -> Option<LayoutNode> {
+        self.iced_windows.id(window)?;
+        self.props.lock().unwrap().debug_stats.layout::<M>()
+    }
+
+    /// Close the open context menu, see [`IcedContext::open_context_menu`].
+    pub fn close_context_menu(&mut self) {
+        self.props.lock().unwrap().context_menu = None;
+    }
+
     /// Display an [`Element`] to the screen.
     pub fn display<'a>(
         &'a mut self,
         element: impl Into<iced_core::Element<'a, M, Theme, Renderer>>,
     ) {
+        self.display_layer(element.into(), None, &DisplayOptions::default(), false);
+    }
+
+    /// Display an [`Element`] to the screen with the given options, e.g. to draw an
+    /// overlay in a different text color than the other UIs.
+    pub fn display_with<'a>(
+        &'a mut self,
+        element: impl Into<iced_core::Element<'a, M, Theme, Renderer>>,
+        options: DisplayOptions,
+    ) {
+        self.display_layer(element.into(), None, &options, false);
+    }
+
+    /// Display the [`Element`] built by `view`, like [`IcedContext::display`] does.
+    ///
+    /// With [`IcedSettings::catch_panics`], a panic while building the element, e.g. in
+    /// view code formatting a stat that isn't there, is caught like the ones while
+    /// displaying it: the error banner is displayed instead, and an [`IcedPanic`] sent.
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_iced::iced::widget::text;
+    /// # use bevy_iced::IcedContext;
+    /// # #[derive(Event)]
+    /// # struct UiMessage;
+    /// # #[derive(Resource)]
+    /// # struct Stats {
+    /// #     levels: Vec<u32>,
+    /// # }
+    /// # #[derive(Component)]
+    /// # struct Player {
+    /// #     level: usize,
+    /// # }
+    /// fn hud_system(mut ctx: IcedContext<UiMessage>, stats: Res<Stats>, player: Query<&Player>) {
+    ///     let player = player.single();
+    ///     ctx.display_view(|| text(format!("Level {}", stats.levels[player.level])).into());
+    /// }
+    /// # App::new().add_systems(Update, hud_system);
+    /// ```
+    pub fn display_view<'a>(
+        &'a mut self,
+        view: impl FnOnce() -> iced_core::Element<'a, M, Theme, Renderer>,
+    ) {
+        let element = if self.appearance.settings().catch_panics {
+            let panics = &mut self.props.lock().unwrap().panics;
+            match panics.catch_view::<M, _>(view) {
+                Some(element) => element,
+                None => panics::banner(panics.failing_view::<M>().unwrap()),
+            }
+        } else {
+            view()
+        };
+        self.display_layer(element, None, &DisplayOptions::default(), false);
+    }
+
+    /// Display an [`Element`] in the window `window`, like [`IcedContext::display`], or
+    /// return why it can't be rather than panicking, e.g. as the window was closed earlier
+    /// in the frame. Nothing is displayed then, and the widget state of the UI is kept as
+    /// if it wasn't displayed.
+    ///
+    /// Secondary windows aren't supported: UIs are only displayed in the primary window,
+    /// and any other gets [`IcedDisplayError::WindowNotDriven`].
+    pub fn display_in_window<'a>(
+        &'a mut self,
+        element: impl Into<iced_core::Element<'a, M, Theme, Renderer>>,
+        window: Entity,
+    ) -> Result<(), IcedDisplayError> {
+        if self.windows.get(window).is_err() {
+            return Err(if self.iced_windows.is_window(window) {
+                IcedDisplayError::WindowNotDriven(window)
+            } else {
+                IcedDisplayError::WindowNotFound(window)
+            });
+        }
+        self.display_layer(element.into(), None, &DisplayOptions::default(), false);
+        Ok(())
+    }
+
+    /// Display the [`Element`] built by `view` from the primary window's entity and its
+    /// [`Window`], e.g. for the size or [`window_theme`](Window::window_theme). Nothing is
+    /// displayed without a primary window, or when `view` returns `None`, and the widget
+    /// state is kept then like any UI that isn't displayed.
+    ///
+    /// UIs are only displayed in the primary window, see
+    /// [`IcedContext::display_in_window`]. `view` isn't passed the window's other
+    /// components: capture a query of them in `view` and look the entity up, as below.
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_iced::iced::widget::text;
+    /// # use bevy_iced::IcedContext;
+    /// # #[derive(Event)]
+    /// # struct UiMessage;
+    /// # #[derive(Component)]
+    /// # struct Editor {
+    /// #     name: String,
+    /// # }
+    /// fn status_ui(mut ctx: IcedContext<UiMessage>, editors: Query<&Editor>) {
+    ///     ctx.display_from_window(|entity, window| {
+    ///         let editor = editors.get(entity).ok()?;
+    ///         Some(text(format!("{}: {}px wide", editor.name, window.width())).into())
+    ///     });
+    /// }
+    /// # App::new().add_systems(Update, status_ui);
+    /// ```
+    pub fn display_from_window<'a>(
+        &'a mut self,
+        view: impl FnOnce(Entity, &Window) -> Option<iced_core::Element<'a, M, Theme, Renderer>>,
+    ) {
+        let Ok((entity, window)) = self.windows.get_single() else {
+            return;
+        };
+        if let Some(element) = view(entity, window) {
+            self.display_layer(element, None, &DisplayOptions::default(), false);
+        }
+    }
+
+    /// Display an [`Element`] as a modal dialog, e.g. to confirm quitting, with the
+    /// default [`ModalOptions`].
+    ///
+    /// The dialog is centered over a dimmed backdrop and drawn above the other UIs,
+    /// whichever order they're displayed in. While it's displayed, and for the frame after
+    /// it's dismissed, the other UIs receive no input, so their focus and state are left
+    /// as they were. Keep displaying it every frame for as long as it should stay open.
+    pub fn display_modal<'a>(
+        &'a mut self,
+        element: impl Into<iced_core::Element<'a, M, Theme, Renderer>>,
+    ) {
+        self.display_modal_with(element, ModalOptions::default());
+    }
+
+    /// Display an [`Element`] as a modal dialog with the given options, see
+    /// [`IcedContext::display_modal`].
+    pub fn display_modal_with<'a>(
+        &'a mut self,
+        element: impl Into<iced_core::Element<'a, M, Theme, Renderer>>,
+        options: ModalOptions<M>,
+    ) {
+        self.display_layer(
+            element.into(),
+            Some(options),
+            &DisplayOptions::default(),
+            false,
+        );
+    }
+
+    /// Display an [`Element`] into the image of the [`IcedTextureTarget`] of `target`, e.g.
+    /// the screen of a computer in the game world, rather than the window.
+    ///
+    /// The UI sees the cursor of the target, and nothing otherwise, and the keys, like the
+    /// other UIs do. Nothing is displayed until the image is loaded, or when `target` has
+    /// no [`IcedTextureTarget`]. Displaying several UIs into the same image in a frame
+    /// only presents the last one.
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_iced::iced::widget::button;
+    /// # use bevy_iced::IcedContext;
+    /// # #[derive(Event, Clone)]
+    /// # enum UiMessage {
+    /// #     Reboot,
+    /// # }
+    /// # #[derive(Component)]
+    /// # struct Screen;
+    /// fn screen_ui(mut ctx: IcedContext<UiMessage>, screens: Query<Entity, With<Screen>>) {
+    ///     for screen in &screens {
+    ///         ctx.display_in_target(button("Reboot").on_press(UiMessage::Reboot), screen);
+    ///     }
+    /// }
+    /// # App::new().add_systems(Update, screen_ui);
+    /// ```
+    pub fn display_in_target<'a>(
+        &'a mut self,
+        element: impl Into<iced_core::Element<'a, M, Theme, Renderer>>,
+        target: Entity,
+    ) {
+        if !self.enabled.0 {
+            return;
+        }
+        // Fail early, naming the message type, when its events are missing.
+        self.messages.events();
+        let Some((image, viewport, cursor)) = self.viewport.texture(target) else {
+            return;
+        };
+        let start = std::time::Instant::now();
+        let bounds = viewport.logical_size();
+        // Like the other UIs, it's left alone under an open modal.
+        let blocked = self.modal.is_open();
+        let cursor = if blocked { Cursor::Unavailable } else { cursor };
+        let events = if blocked {
+            Vec::new()
+        } else {
+            texture::events(&self.events, cursor)
+        };
+
+        let props = &mut *self.props.lock().unwrap();
+        props.wake_renderer();
         let IcedProps {
             ref mut renderer,
-            ref mut clipboard,
+            ref mut keyboard_focus,
+            ref mut panics,
+            ref mut texture_layers,
             ..
-        } = &mut *self.props.lock().unwrap();
-        let bounds = self.viewport.logical_size();
+        } = *props;
+        let mut messages = Vec::<M>::new();
+        let cache_entry = self.non_send.cache.get::<M>();
+        let cache = cache_entry.take().unwrap();
+        let clipboard = &mut *self.non_send.clipboard;
+        // The UI is drawn on its own, leaving what the window's UIs drew as it was.
+        let window_layer = modal::Layer::take(renderer);
+        let display = || {
+            let mut ui = UserInterface::build(element.into(), bounds, cache, renderer);
+            let (state, _) = ui.update(&events, cursor, renderer, clipboard, &mut messages);
+            if let iced_runtime::user_interface::State::Updated {
+                redraw_request: Some(request),
+            } = state
+            {
+                self.iced_windows.request_redraw(request);
+            }
+            *keyboard_focus |= focus::has_focus(&mut ui, renderer);
+            ui.draw(
+                renderer,
+                self.appearance.theme(),
+                &self.appearance.style(),
+                cursor,
+            );
+            *cache_entry = Some(ui.into_cache());
+        };
+        let displayed = if self.appearance.settings().catch_panics {
+            panics.catch::<M, _>(display).is_some()
+        } else {
+            display();
+            true
+        };
+        let layer = modal::Layer::take(renderer);
+        window_layer.draw(renderer);
+        if displayed {
+            let scale_factor = viewport.scale_factor();
+            texture::push(
+                texture_layers,
+                texture::TextureLayer {
+                    image,
+                    scale_factor,
+                    layer,
+                },
+            );
+            messages.into_iter().for_each(|msg| {
+                self.messages.send(msg);
+            });
+        } else {
+            // The widget state may be half updated, so the UI starts from scratch.
+            *cache_entry = Some(Default::default());
+        }
+        self.non_send.cache.spend(start.elapsed());
+    }
 
-        let element = element.into();
+    // Display the open context menu of `M` over the other UIs, unless it already was this
+    // frame, and close it once it's dismissed.
+    fn display_context_menu(&mut self) {
+        let menu = {
+            let mut props = self.props.lock().unwrap();
+            match &props.context_menu {
+                Some(menu) if menu.is_due::<M>() => props.context_menu.take(),
+                _ => None,
+            }
+        };
+        let Some(mut menu) = menu else {
+            return;
+        };
+        let options = ModalOptions::default().scrim(iced::Color::TRANSPARENT);
+        let dismissed = self.display_layer(
+            menu.view::<M>(),
+            Some(options),
+            &DisplayOptions::default(),
+            true,
+        );
+        menu.shown = true;
+        let context_menu = &mut self.props.lock().unwrap().context_menu;
+        if !dismissed && context_menu.is_none() {
+            *context_menu = Some(menu);
+        }
+    }
 
-        let cursor = {
-            let window = self.windows.single();
-            match window.cursor_position() {
-                Some(position) => {
-                    Cursor::Available(utils::process_cursor_position(position, bounds, window))
-                }
-                None => utils::process_touch_input(self)
-                    .map(Cursor::Available)
-                    .unwrap_or(Cursor::Unavailable),
+    // Display a UI, returning whether it's a context menu that was dismissed.
+    fn display_layer<'a>(
+        &mut self,
+        mut element: iced_core::Element<'a, M, Theme, Renderer>,
+        modal_options: Option<ModalOptions<M>>,
+        options: &DisplayOptions,
+        context_menu: bool,
+    ) -> bool {
+        if !self.enabled.0 {
+            // Hand the cursor back to the app while the UI is hidden.
+            self.iced_windows
+                .request_interaction(iced_core::mouse::Interaction::Idle);
+            return false;
+        }
+        // Fail early, naming the message type, when its events are missing.
+        self.messages.events();
+        // While the window isn't focused, what its UIs drew last may be presented again.
+        let policy = self.update_policy();
+        let reuse =
+            policy != UnfocusedUpdatePolicy::Full && self.props.lock().unwrap().retained.is_some();
+        if reuse && policy == UnfocusedUpdatePolicy::Paused {
+            // The widget state is kept as if the UI was displayed.
+            if context_menu {
+                self.non_send.cache.get_context_menu::<M>();
+            } else {
+                self.non_send.cache.get::<M>();
+            }
+            self.props.lock().unwrap().present_retained(&self.did_draw);
+            return false;
+        }
+        if modal_options.is_none() {
+            self.display_context_menu();
+        }
+        let start = std::time::Instant::now();
+        element = probe::wrap(element);
+
+        let props = &mut *self.props.lock().unwrap();
+        props.wake_renderer();
+        if reuse {
+            props.present_retained(&self.did_draw);
+        } else {
+            props.retain = policy != UnfocusedUpdatePolicy::Full;
+            if !props.retain {
+                props.retained = None;
+            }
+        }
+        let IcedProps {
+            ref mut renderer,
+            ref mut modal_layer,
+            ref mut z_layers,
+            ref mut keyboard_navigation,
+            ref mut keyboard_focus,
+            ref mut scrolling,
+            ref mut hit_targets,
+            ref mut routing,
+            ref mut capture,
+            ref mut tracked_widgets,
+            ref mut panics,
+            ref mut drawn_scale_factor,
+            #[cfg(feature = "virtual-keyboard")]
+            ref mut keyboard_navigations,
+            #[cfg(feature = "test-utils")]
+            ref mut layouts,
+            #[cfg(feature = "debug")]
+            ref mut debug_stats,
+            ..
+        } = *props;
+        // The window's cursor doesn't point at a UI presented to a texture.
+        let window = self
+            .windows
+            .get_single()
+            .ok()
+            .filter(|_| !self.viewport.is_offscreen());
+        let viewport = self.viewport.get(window, &self.appearance).clone();
+        let bounds = viewport.logical_size();
+        #[cfg(feature = "bevy_ui")]
+        let node_bounds = match (options.node, window) {
+            (Some(node), Some((_, window))) => match self.viewport.node_bounds(node, window) {
+                Some(node_bounds) => Some(node_bounds),
+                None => return false,
+            },
+            _ => None,
+        };
+        #[cfg(not(feature = "bevy_ui"))]
+        let node_bounds: Option<iced_core::Rectangle> = None;
+
+        if let Some(node_bounds) = node_bounds {
+            element = node::wrap(element, node_bounds);
+        } else if self.safe_area.inset_root && self.safe_area.has_insets() {
+            // Padding a filling container keeps the cursor and event positions in window
+            // coordinates while the content is laid out within the safe area.
+            element = iced_widget::Container::new(element)
+                .width(iced::Length::Fill)
+                .height(iced::Length::Fill)
+                .padding(self.safe_area.insets)
+                .into();
+        }
+        if let Some(options) = &modal_options {
+            element = modal::wrap(element, options.scrim);
+            self.modal.show();
+        }
+        // The UIs under an open modal see neither the cursor nor any events.
+        let is_modal = modal_options.is_some();
+        let blocked = !is_modal && self.modal.is_open();
+
+        let cursor = match window {
+            _ if blocked => Cursor::Unavailable,
+            window => self.cursor(&viewport, window, props.replayed_cursor),
+        };
+        // Nor do UIs see the cursor over the UIs drawn above them last frame.
+        let z_index = if is_modal { 0 } else { options.z_index };
+        let order = routing.next(z_index, is_modal);
+        let pointer = cursor.position();
+        let cursor = match cursor {
+            Cursor::Available(position) if hit_targets.is_covered(position, order) => {
+                Cursor::Unavailable
+            }
+            cursor => cursor,
+        };
+        // A UI displayed in a node only sees the cursor over it.
+        let cursor = match (cursor, node_bounds) {
+            (Cursor::Available(position), Some(node_bounds)) if !node_bounds.contains(position) => {
+                Cursor::Unavailable
             }
+            (cursor, _) => cursor,
         };
+        // The UIs above take the events they captured, and the keys while focused.
+        let fed = if blocked {
+            Vec::new()
+        } else {
+            routing.route(&self.events, order, hit_targets, pointer)
+        };
+        let events: Vec<_> = fed
+            .iter()
+            .map(|&index| self.events[index].clone())
+            .collect();
+        let events = events.as_slice();
 
         let mut messages = Vec::<M>::new();
-        let cache_entry = self.cache_map.get::<M>();
+        let cache_entry = if context_menu {
+            self.non_send.cache.get_context_menu::<M>()
+        } else {
+            self.non_send.cache.get::<M>()
+        };
         let cache = cache_entry.take().unwrap();
-        let mut ui = UserInterface::build(element, bounds, cache, renderer);
-        let (_, _event_statuses) = ui.update(
-            self.events.as_slice(),
-            cursor,
-            renderer,
-            clipboard,
-            &mut messages,
-        );
+        let clipboard = &mut *self.non_send.clipboard;
+        // The operations of commands are for the app's UIs, not its context menus.
+        let operations = if context_menu {
+            Vec::new()
+        } else {
+            self.non_send.operations.take::<M>()
+        };
+        let display = || {
+            let mut ui = UserInterface::build(element, bounds, cache, renderer);
+            scrolling.apply_snaps(&mut ui, renderer);
+            #[cfg(feature = "virtual-keyboard")]
+            keyboard_navigations.apply(&mut ui, renderer, &mut messages);
+            command::apply(operations, &mut ui, renderer, &mut messages);
+            let (state, event_statuses) =
+                ui.update(events, cursor, renderer, clipboard, &mut messages);
+            capture.record(events, &event_statuses);
+            routing.record(order, &fed, &event_statuses);
+            #[cfg(feature = "virtual-keyboard")]
+            virtual_keyboard::type_into_targets(&mut ui, renderer, clipboard, &mut messages);
+            scrolling.collect(&mut ui, renderer);
+            let layout = probe::capture(&mut ui, renderer);
+            if let Some(layout) = &layout {
+                hit_targets.collect(layout, is_modal, order);
+            }
+            #[cfg(feature = "debug")]
+            debug_stats.record::<M>(
+                &event_statuses,
+                is_modal,
+                layout.as_ref().map(layout_dump::dump),
+            );
+            tracking::collect(tracked_widgets, &mut ui, renderer, &viewport, blocked);
+            if let iced_runtime::user_interface::State::Updated {
+                redraw_request: Some(request),
+            } = state
+            {
+                self.iced_windows.request_redraw(request);
+            }
+
+            if let Some(on_escape) = modal_options.and_then(|options| options.on_escape) {
+                let escaped = events.iter().zip(&event_statuses).any(|(event, status)| {
+                    *status == iced_core::event::Status::Ignored && modal::is_escape(event)
+                });
+                if escaped {
+                    messages.push(on_escape);
+                }
+            }
 
-        messages.into_iter().for_each(|msg| {
-            self.messages.send(msg);
-        });
+            // Picking an item dismisses a context menu, as does any press outside of it.
+            let dismissed = context_menu
+                && (!messages.is_empty()
+                    || events
+                        .iter()
+                        .zip(&event_statuses)
+                        .any(|(event, status)| menu::is_dismissal(event, *status)));
+            messages.into_iter().for_each(|msg| {
+                self.messages.send(msg);
+            });
+            if focus::has_focus(&mut ui, renderer) {
+                *keyboard_focus = true;
+                if !blocked {
+                    routing.focus(order);
+                }
+            }
 
-        ui.draw(renderer, &self.settings.theme, &self.settings.style, cursor);
+            // The UI is only drawn when its primitives aren't presented again.
+            let interaction = if reuse {
+                iced_core::mouse::Interaction::Idle
+            } else {
+                // A modal is drawn on its own, and kept aside to be drawn over every other UI
+                // at the end of the frame.
+                let below = (is_modal || z_index != 0).then(|| modal::Layer::take(renderer));
+                *drawn_scale_factor = viewport.scale_factor();
+                let interaction = ui.draw(
+                    renderer,
+                    self.appearance.theme_or(options.theme.as_ref()),
+                    &self
+                        .appearance
+                        .style_or(options.theme.as_ref(), options.style),
+                    cursor,
+                );
+                if let Some(ring) = &self.appearance.settings().focus_ring {
+                    *keyboard_navigation = focus::update_modality(*keyboard_navigation, events);
+                    if *keyboard_navigation || ring.always_visible {
+                        if let Some(focused) = focus::focused_bounds(&mut ui, renderer, bounds) {
+                            focus::draw(renderer, ring, focused);
+                        }
+                    }
+                }
+                if let Some(below) = below {
+                    let layer = modal::Layer::take(renderer);
+                    if is_modal {
+                        // Modals displayed later in the frame, like a context menu, are
+                        // drawn above.
+                        if let Some(previous) = modal_layer.take() {
+                            previous.draw(renderer);
+                        }
+                        layer.draw(renderer);
+                        *modal_layer = Some(modal::Layer::take(renderer));
+                    } else {
+                        z_layers.push((z_index, layer));
+                    }
+                    below.draw(renderer);
+                }
+                self.iced_windows.request_interaction(interaction);
+                interaction
+            };
 
-        self.events.clear();
-        *cache_entry = Some(ui.into_cache());
+            let widget_events = self.appearance.settings().widget_events;
+            if cfg!(feature = "test-utils") || widget_events {
+                let ui_layouts = layout::Layouts::collect(&mut ui, renderer);
+                if widget_events {
+                    self.widget_events.update::<M>(
+                        &ui_layouts,
+                        cursor,
+                        interaction,
+                        events,
+                        &event_statuses,
+                        self.appearance.settings().double_click_interval,
+                    );
+                }
+                #[cfg(feature = "test-utils")]
+                layouts.extend(ui_layouts);
+            }
+            *cache_entry = Some(ui.into_cache());
+            dismissed
+        };
+        let dismissed = if self.appearance.settings().catch_panics {
+            match panics.catch::<M, _>(display) {
+                Some(dismissed) => dismissed,
+                None => {
+                    // The widget state may be half updated, and the primitives half drawn,
+                    // so the banner starts from scratch. Its state takes the UI's place, as
+                    // the text it draws is kept there until it's presented.
+                    let banner = panics::banner::<M>(panics.failing::<M>().unwrap());
+                    let mut ui = UserInterface::build(banner, bounds, Default::default(), renderer);
+                    ui.draw(
+                        renderer,
+                        self.appearance.theme(),
+                        &self.appearance.style(),
+                        Cursor::Unavailable,
+                    );
+                    *cache_entry = Some(ui.into_cache());
+                    false
+                }
+            }
+        } else {
+            display()
+        };
+        self.non_send.cache.spend(start.elapsed());
         self.did_draw
             .store(true, std::sync::atomic::Ordering::Relaxed);
+        dismissed
     }
+
+    // What displaying a UI does this frame: the configured policy while the primary
+    // window isn't focused, and a full display otherwise.
+    fn update_policy(&self) -> UnfocusedUpdatePolicy {
+        match self.windows.get_single() {
+            Ok((_, window)) if !window.focused => {
+                self.appearance.settings().unfocused_update_policy
+            }
+            _ => UnfocusedUpdatePolicy::Full,
+        }
+    }
+
+    // Where the cursor is over the UIs, in the viewport's logical coordinates, or the
+    // one of the session being replayed.
+    fn cursor(
+        &self,
+        viewport: &Viewport,
+        window: Option<(Entity, &Window)>,
+        replayed: Option<Cursor>,
+    ) -> Cursor {
+        if let Some(cursor) = replayed {
+            return cursor;
+        }
+        let touch_input = self.appearance.settings().touch_input;
+        utils::cursor(
+            self.cursor_override.0,
+            touch_input.then_some(&*self.touches),
+            &self.events,
+            viewport,
+            window.map(|(_, window)| window),
+        )
+    }
+}
+
+// The system state of the `IcedContext` used by `display_from_world`, kept so that its
+// message events and change detection carry over between calls.
+#[derive(Resource)]
+struct WorldContextState<M: bevy_ecs::event::Event>(SystemState<IcedContext<'static, 'static, M>>);
+
+/// Display an [`Element`](iced::Element) from an exclusive system, like
+/// [`IcedContext::display`] does from a regular one.
+///
+/// This is useful when a UI needs more resources and queries than fit in one system. The
+/// UI shares its widget state with the [`IcedContext<M>`] of regular systems, so both can
+/// display it. The element can't borrow from the world, so read what it needs first.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_iced::iced::widget::text;
+/// # #[derive(Event)]
+/// # enum UiMessage {}
+/// # #[derive(Resource)]
+/// # struct Score(u32);
+/// fn ui_system(world: &mut World) {
+///     let score = world.resource::<Score>().0;
+///     bevy_iced::display_from_world::<UiMessage>(world, text(format!("Score: {score}")));
+/// }
+/// # App::new().add_systems(Update, ui_system);
+/// ```
+pub fn display_from_world<'a, M: bevy_ecs::event::Event>(
+    world: &mut World,
+    element: impl Into<iced_core::Element<'a, M, Theme, Renderer>>,
+) {
+    if !world.contains_resource::<WorldContextState<M>>() {
+        let state = SystemState::new(world);
+        world.insert_resource(WorldContextState::<M>(state));
+    }
+    world.resource_scope(|world, mut state: Mut<WorldContextState<M>>| {
+        state.0.get_mut(world).display_layer(
+            element.into(),
+            None,
+            &DisplayOptions::default(),
+            false,
+        );
+        state.0.apply(world);
+    });
 }
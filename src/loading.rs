@@ -0,0 +1,236 @@
+use std::fmt;
+
+use bevy_asset::{AssetLoadError, AssetPath, AssetServer, LoadState, RecursiveDependencyLoadState};
+use bevy_asset::{UntypedAssetId, UntypedAssetLoadFailedEvent};
+use bevy_ecs::event::EventReader;
+use bevy_ecs::system::{Res, ResMut, Resource, SystemParam};
+use bevy_utils::HashMap;
+use iced_core::{Color, Element, Length, Pixels};
+use iced_widget::style::{theme, Theme};
+use iced_widget::{column, progress_bar, text};
+
+use crate::Renderer;
+
+/// The loading progress of a set of assets, e.g. for a loading screen.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_iced::{IcedContext, IcedLoading};
+/// use bevy_iced::widget::loading_bar;
+/// # #[derive(Event)]
+/// # enum UiMessage {}
+/// # #[derive(Resource)]
+/// # struct GameAssets {
+/// #     handles: Vec<Handle<Image>>,
+/// # }
+///
+/// fn loading_screen(
+///     mut ctx: IcedContext<UiMessage>,
+///     loading: IcedLoading,
+///     assets: Res<GameAssets>,
+/// ) {
+///     let progress = loading.progress(assets.handles.iter().map(|handle| handle.id()));
+///     if progress.is_done() {
+///         // Move on to the game.
+///     }
+///     ctx.display(loading_bar(&progress));
+/// }
+/// # App::new().add_systems(Update, loading_screen);
+/// ```
+#[derive(SystemParam)]
+pub struct IcedLoading<'w> {
+    // Absent without `AssetPlugin`, in which case nothing ever loads.
+    server: Option<Res<'w, AssetServer>>,
+    failures: Res<'w, LoadFailures>,
+}
+
+impl IcedLoading<'_> {
+    /// The progress of the given assets, as of this frame. An asset counts as loaded
+    /// once its dependencies are too.
+    pub fn progress<I>(&self, ids: I) -> LoadingProgress
+    where
+        I: IntoIterator,
+        I::Item: Into<UntypedAssetId>,
+    {
+        let mut progress = LoadingProgress::default();
+        for id in ids.into_iter().map(Into::into) {
+            progress.total += 1;
+            let Some(server) = &self.server else {
+                continue;
+            };
+            let path = server.get_path(id).map(|path| path.into_owned());
+            match server.get_load_states(id) {
+                Some((LoadState::Loaded, _, RecursiveDependencyLoadState::Loaded)) => {
+                    progress.loaded += 1;
+                }
+                Some((LoadState::Failed, _, _) | (_, _, RecursiveDependencyLoadState::Failed)) => {
+                    // Only the asset's own failure is known, not which dependency failed.
+                    let error = self.failures.0.get(&id).cloned();
+                    progress.failed.push(LoadFailure { path, error });
+                }
+                _ => {
+                    if progress.current.is_none() {
+                        progress.current = path;
+                    }
+                }
+            }
+        }
+        progress
+    }
+}
+
+/// How far along loading a set of assets is, see [`IcedLoading::progress`].
+#[derive(Clone, Debug, Default)]
+pub struct LoadingProgress {
+    /// The number of assets loaded, with their dependencies.
+    pub loaded: usize,
+    /// The number of assets.
+    pub total: usize,
+    /// The path of the first asset still loading, e.g. to show what the game is waiting
+    /// for.
+    pub current: Option<AssetPath<'static>>,
+    /// The assets that failed to load. They're never counted as loaded.
+    pub failed: Vec<LoadFailure>,
+}
+
+impl LoadingProgress {
+    /// The fraction of the assets loaded, from `0.0` to `1.0`. An empty set is loaded.
+    pub fn fraction(&self) -> f32 {
+        if self.total == 0 {
+            1.0
+        } else {
+            self.loaded as f32 / self.total as f32
+        }
+    }
+
+    /// Whether every asset is loaded.
+    pub fn is_done(&self) -> bool {
+        self.loaded == self.total
+    }
+
+    /// Whether every asset either loaded or failed to, so that loading won't progress
+    /// any further.
+    pub fn is_settled(&self) -> bool {
+        self.loaded + self.failed.len() == self.total
+    }
+}
+
+/// An asset that failed to load, see [`LoadingProgress::failed`].
+#[derive(Clone, Debug)]
+pub struct LoadFailure {
+    /// The path of the asset, if it was loaded from one.
+    pub path: Option<AssetPath<'static>>,
+    /// Why the asset failed to load, or `None` if one of its dependencies did.
+    pub error: Option<AssetLoadError>,
+}
+
+impl fmt::Display for LoadFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.path {
+            Some(path) => write!(f, "Failed to load {path}")?,
+            None => write!(f, "Failed to load an asset")?,
+        }
+        match &self.error {
+            Some(error) => write!(f, ": {error}"),
+            None => write!(f, ": a dependency failed to load"),
+        }
+    }
+}
+
+/// A progress bar of a [`LoadingProgress`], with the asset being loaded above and the
+/// failures below, see [`loading_bar`].
+#[allow(missing_debug_implementations)]
+pub struct LoadingBar {
+    fraction: f32,
+    label: String,
+    failures: Vec<String>,
+    width: Length,
+    height: Pixels,
+    style: theme::ProgressBar,
+    failure_color: Option<Color>,
+}
+
+/// A progress bar of `progress`, labelled with the asset being loaded and listing the
+/// assets that failed to load.
+pub fn loading_bar(progress: &LoadingProgress) -> LoadingBar {
+    LoadingBar {
+        fraction: progress.fraction(),
+        label: progress
+            .current
+            .as_ref()
+            .map(ToString::to_string)
+            .unwrap_or_default(),
+        failures: progress.failed.iter().map(ToString::to_string).collect(),
+        width: Length::Fill,
+        height: Pixels(12.0),
+        style: theme::ProgressBar::default(),
+        failure_color: None,
+    }
+}
+
+impl LoadingBar {
+    /// Set the width of the bar, `Fill` by default.
+    #[must_use]
+    pub fn width(mut self, width: impl Into<Length>) -> Self {
+        self.width = width.into();
+        self
+    }
+
+    /// Set the height of the bar itself, without the labels.
+    #[must_use]
+    pub fn height(mut self, height: impl Into<Pixels>) -> Self {
+        self.height = height.into();
+        self
+    }
+
+    /// Set the style of the bar.
+    #[must_use]
+    pub fn style(mut self, style: impl Into<theme::ProgressBar>) -> Self {
+        self.style = style.into();
+        self
+    }
+
+    /// Set the color of the failures, e.g. the theme's danger color. They're drawn in the
+    /// default text color otherwise.
+    #[must_use]
+    pub fn failure_color(mut self, color: Color) -> Self {
+        self.failure_color = Some(color);
+        self
+    }
+}
+
+impl<'a, Message: 'a> From<LoadingBar> for Element<'a, Message, Theme, Renderer> {
+    fn from(bar: LoadingBar) -> Self {
+        let style = bar
+            .failure_color
+            .map_or(theme::Text::Default, theme::Text::Color);
+        let failures = bar
+            .failures
+            .into_iter()
+            .map(|failure| text(failure).style(style).into());
+        column![
+            text(bar.label),
+            progress_bar(0.0..=1.0, bar.fraction)
+                .width(bar.width)
+                .height(bar.height)
+                .style(bar.style),
+        ]
+        .extend(failures)
+        .spacing(4)
+        .width(bar.width)
+        .into()
+    }
+}
+
+// Why the assets that failed to load did, kept past the frame of their event.
+#[derive(Resource, Default)]
+pub struct LoadFailures(HashMap<UntypedAssetId, AssetLoadError>);
+
+pub fn record_load_failures(
+    mut events: EventReader<UntypedAssetLoadFailedEvent>,
+    mut failures: ResMut<LoadFailures>,
+) {
+    for event in events.read() {
+        failures.0.insert(event.id, event.error.clone());
+    }
+}
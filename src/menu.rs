@@ -0,0 +1,346 @@
+use std::any::{Any, TypeId};
+
+use iced_core::event::{self, Event};
+use iced_core::layout::{self, Layout};
+use iced_core::mouse;
+use iced_core::overlay;
+use iced_core::renderer;
+use iced_core::widget::{tree, Operation, Tree};
+use iced_core::{
+    touch, Background, Border, Clipboard, Color, Element, Length, Point, Rectangle, Shell, Size,
+    Vector, Widget,
+};
+use iced_widget::style::Theme;
+use iced_widget::{button, container, text, Column};
+
+use crate::{modal, Renderer};
+
+// The padding around the items, and within each item.
+const PADDING: u16 = 4;
+const ITEM_PADDING: [u16; 2] = [4, 12];
+
+// The items of an open context menu, and how to view them, which requires the messages
+// to be `Clone`.
+pub struct Items<M> {
+    items: Vec<(String, M)>,
+    view: View<M>,
+}
+
+type View<M> = for<'a> fn(&'a [(String, M)], Point) -> Element<'a, M, Theme, Renderer>;
+
+// The context menu opened by `IcedContext::open_context_menu`, displayed with the first
+// UI of its message type every frame until it's dismissed.
+pub struct ContextMenu {
+    message: TypeId,
+    position: Point,
+    items: Box<dyn Any + Send>,
+    // Whether it was displayed this frame.
+    pub shown: bool,
+}
+
+impl ContextMenu {
+    pub fn new<M: Clone + Send + 'static>(items: Vec<(String, M)>, position: Point) -> Self {
+        Self {
+            message: TypeId::of::<M>(),
+            position,
+            items: Box::new(Items {
+                items,
+                view: view::<M>,
+            }),
+            shown: false,
+        }
+    }
+
+    // Whether the menu is still to be displayed this frame, by the UIs of `M`.
+    pub fn is_due<M: 'static>(&self) -> bool {
+        self.message == TypeId::of::<M>() && !self.shown
+    }
+
+    pub fn view<M: 'static>(&self) -> Element<'_, M, Theme, Renderer> {
+        let items = self.items.downcast_ref::<Items<M>>().unwrap();
+        (items.view)(&items.items, self.position)
+    }
+}
+
+fn view<M: Clone>(items: &[(String, M)], position: Point) -> Element<'_, M, Theme, Renderer> {
+    // The items fill the width of the widest label, which a column only finds out from
+    // children that don't fill it, so the labels are laid out once more, hidden.
+    let sizer = Element::new(Sizer(
+        Column::with_children(
+            items
+                .iter()
+                .map(|(label, _)| container(text(label)).padding([0, ITEM_PADDING[1]]).into()),
+        )
+        .into(),
+    ));
+    let items = std::iter::once(sizer).chain(items.iter().map(|(label, message)| {
+        button(text(label))
+            .on_press(message.clone())
+            .width(Length::Fill)
+            .padding(ITEM_PADDING)
+            .style(iced_widget::style::theme::Button::custom(Item))
+            .into()
+    }));
+    let menu = container(Column::with_children(items).width(Length::Shrink))
+        .padding(PADDING)
+        .style(iced_widget::style::theme::Container::Custom(Box::new(
+            Panel,
+        )));
+    Element::new(Placed {
+        position,
+        content: menu.into(),
+    })
+}
+
+// Whether the event dismisses the menu when it reaches no item, like a press outside of
+// the menu or Escape.
+pub fn is_dismissal(event: &Event, status: event::Status) -> bool {
+    status == event::Status::Ignored
+        && (modal::is_escape(event)
+            || matches!(
+                event,
+                Event::Mouse(mouse::Event::ButtonPressed(_))
+                    | Event::Touch(touch::Event::FingerPressed { .. })
+            ))
+}
+
+struct Panel;
+
+impl container::StyleSheet for Panel {
+    type Style = Theme;
+
+    fn appearance(&self, style: &Theme) -> container::Appearance {
+        let palette = style.extended_palette();
+        container::Appearance {
+            background: Some(palette.background.weak.color.into()),
+            border: Border {
+                color: palette.background.strong.color,
+                width: 1.0,
+                radius: 4.0.into(),
+            },
+            ..Default::default()
+        }
+    }
+}
+
+struct Item;
+
+impl button::StyleSheet for Item {
+    type Style = Theme;
+
+    fn active(&self, style: &Theme) -> button::Appearance {
+        button::Appearance {
+            background: None,
+            text_color: style.extended_palette().background.weak.text,
+            border: Border::with_radius(2),
+            ..Default::default()
+        }
+    }
+
+    fn hovered(&self, style: &Theme) -> button::Appearance {
+        let primary = style.extended_palette().primary.base;
+        button::Appearance {
+            background: Some(Background::Color(primary.color)),
+            text_color: primary.text,
+            ..self.active(style)
+        }
+    }
+
+    fn pressed(&self, style: &Theme) -> button::Appearance {
+        self.hovered(style)
+    }
+
+    fn disabled(&self, style: &Theme) -> button::Appearance {
+        button::Appearance {
+            text_color: Color {
+                a: 0.5,
+                ..self.active(style).text_color
+            },
+            ..self.active(style)
+        }
+    }
+}
+
+// Takes the width of its content, without drawing it or taking any height.
+struct Sizer<'a, M>(Element<'a, M, Theme, Renderer>);
+
+impl<'a, M> Widget<M, Theme, Renderer> for Sizer<'a, M> {
+    fn children(&self) -> Vec<Tree> {
+        vec![Tree::new(&self.0)]
+    }
+
+    fn diff(&self, tree: &mut Tree) {
+        tree.diff_children(std::slice::from_ref(&self.0));
+    }
+
+    fn size(&self) -> Size<Length> {
+        Size::new(Length::Shrink, Length::Shrink)
+    }
+
+    fn layout(
+        &self,
+        tree: &mut Tree,
+        renderer: &Renderer,
+        limits: &layout::Limits,
+    ) -> layout::Node {
+        let content = self
+            .0
+            .as_widget()
+            .layout(&mut tree.children[0], renderer, limits);
+        layout::Node::new(Size::new(content.size().width, 0.0))
+    }
+
+    fn draw(
+        &self,
+        _tree: &Tree,
+        _renderer: &mut Renderer,
+        _theme: &Theme,
+        _style: &renderer::Style,
+        _layout: Layout<'_>,
+        _cursor: mouse::Cursor,
+        _viewport: &Rectangle,
+    ) {
+    }
+}
+
+// Fills the window and places the menu at the cursor, flipped to its left or above it
+// where it would cross the edge of the window.
+struct Placed<'a, M> {
+    position: Point,
+    content: Element<'a, M, Theme, Renderer>,
+}
+
+impl<'a, M> Widget<M, Theme, Renderer> for Placed<'a, M> {
+    fn children(&self) -> Vec<Tree> {
+        vec![Tree::new(&self.content)]
+    }
+
+    fn diff(&self, tree: &mut Tree) {
+        tree.diff_children(std::slice::from_ref(&self.content));
+    }
+
+    fn tag(&self) -> tree::Tag {
+        tree::Tag::stateless()
+    }
+
+    fn size(&self) -> Size<Length> {
+        Size::new(Length::Fill, Length::Fill)
+    }
+
+    fn layout(
+        &self,
+        tree: &mut Tree,
+        renderer: &Renderer,
+        limits: &layout::Limits,
+    ) -> layout::Node {
+        let bounds = limits.max();
+        let content = self.content.as_widget().layout(
+            &mut tree.children[0],
+            renderer,
+            &layout::Limits::new(Size::ZERO, bounds),
+        );
+        let size = content.size();
+        let flip = |position: f32, length: f32, max: f32| {
+            if position + length > max {
+                (position - length).max(0.0)
+            } else {
+                position
+            }
+        };
+        let position = Point::new(
+            flip(self.position.x, size.width, bounds.width),
+            flip(self.position.y, size.height, bounds.height),
+        );
+        layout::Node::with_children(bounds, vec![content.move_to(position)])
+    }
+
+    fn operate(
+        &self,
+        tree: &mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        operation: &mut dyn Operation<M>,
+    ) {
+        let layout = layout.children().next().unwrap();
+        self.content
+            .as_widget()
+            .operate(&mut tree.children[0], layout, renderer, operation);
+    }
+
+    fn on_event(
+        &mut self,
+        tree: &mut Tree,
+        event: Event,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, M>,
+        viewport: &Rectangle,
+    ) -> event::Status {
+        let layout = layout.children().next().unwrap();
+        self.content.as_widget_mut().on_event(
+            &mut tree.children[0],
+            event,
+            layout,
+            cursor,
+            renderer,
+            clipboard,
+            shell,
+            viewport,
+        )
+    }
+
+    fn mouse_interaction(
+        &self,
+        tree: &Tree,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+        renderer: &Renderer,
+    ) -> mouse::Interaction {
+        let layout = layout.children().next().unwrap();
+        self.content.as_widget().mouse_interaction(
+            &tree.children[0],
+            layout,
+            cursor,
+            viewport,
+            renderer,
+        )
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+    ) {
+        let layout = layout.children().next().unwrap();
+        self.content.as_widget().draw(
+            &tree.children[0],
+            renderer,
+            theme,
+            style,
+            layout,
+            cursor,
+            viewport,
+        );
+    }
+
+    fn overlay<'b>(
+        &'b mut self,
+        tree: &'b mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        translation: Vector,
+    ) -> Option<overlay::Element<'b, M, Theme, Renderer>> {
+        let layout = layout.children().next().unwrap();
+        self.content
+            .as_widget_mut()
+            .overlay(&mut tree.children[0], layout, renderer, translation)
+    }
+}
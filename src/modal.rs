@@ -0,0 +1,221 @@
+use bevy_ecs::event::Events;
+use bevy_ecs::system::{Res, ResMut, Resource};
+use bevy_input::keyboard::KeyCode;
+use bevy_input::mouse::{MouseButton, MouseMotion, MouseWheel};
+use bevy_input::ButtonInput;
+use iced_core::{keyboard, Color, Element, Event as IcedEvent, Length};
+use iced_widget::container;
+use iced_widget::graphics::backend::Backend;
+use iced_widget::graphics::Primitive;
+use iced_widget::style::Theme;
+
+use crate::retain::Retained;
+use crate::{DidDraw, IcedResource, IcedSettings, Renderer};
+
+/// How a UI shown with [`IcedContext::display_modal_with`](crate::IcedContext::display_modal_with)
+/// is presented and dismissed.
+#[derive(Clone, Debug)]
+pub struct ModalOptions<M> {
+    /// The color of the backdrop covering the window behind the dialog.
+    pub scrim: Color,
+    /// The message to produce when Escape is pressed and the dialog doesn't handle it.
+    pub on_escape: Option<M>,
+}
+
+impl<M> Default for ModalOptions<M> {
+    fn default() -> Self {
+        Self {
+            scrim: Color::from_rgba(0.0, 0.0, 0.0, 0.5),
+            on_escape: None,
+        }
+    }
+}
+
+impl<M> ModalOptions<M> {
+    /// Set the color of the backdrop.
+    #[must_use]
+    pub fn scrim(mut self, color: Color) -> Self {
+        self.scrim = color;
+        self
+    }
+
+    /// Produce `message` when Escape is pressed, e.g. to close the dialog.
+    #[must_use]
+    pub fn on_escape(mut self, message: M) -> Self {
+        self.on_escape = Some(message);
+        self
+    }
+}
+
+/// Whether a modal UI is open. While one is, the other UIs receive no input; check
+/// [`IcedModal::is_open`] to keep game input from reacting as well, e.g. with
+/// `.run_if(|modal: Res<IcedModal>| !modal.is_open())`, or set
+/// [`IcedSettings::modal_blocks_game_input`] to clear the game's input altogether.
+#[derive(Resource, Debug, Default)]
+pub struct IcedModal {
+    // Whether a modal was displayed this frame, and whether one was displayed last frame.
+    shown: bool,
+    open: bool,
+}
+
+impl IcedModal {
+    /// Whether a modal UI was displayed this frame or the last one.
+    pub fn is_open(&self) -> bool {
+        self.shown || self.open
+    }
+
+    pub(crate) fn show(&mut self) {
+        self.shown = true;
+    }
+}
+
+type WgpuPrimitive = Primitive<<iced_wgpu::Backend as Backend>::Primitive>;
+type TinySkiaPrimitive = Primitive<<iced_tiny_skia::Backend as Backend>::Primitive>;
+
+// The primitives of a modal UI, drawn over the other UIs at the end of the frame.
+#[derive(Clone)]
+pub enum Layer {
+    Wgpu(Vec<WgpuPrimitive>),
+    TinySkia(Vec<TinySkiaPrimitive>),
+}
+
+impl Layer {
+    // Take the primitives recorded so far, leaving the renderer empty.
+    pub fn take(renderer: &mut Renderer) -> Self {
+        match renderer {
+            Renderer::Wgpu(renderer) => Self::Wgpu(renderer.start_layer()),
+            Renderer::TinySkia(renderer) => Self::TinySkia(renderer.start_layer()),
+        }
+    }
+
+    // The primitives recorded so far, leaving them in the renderer.
+    pub fn copy(renderer: &mut Renderer) -> Self {
+        match renderer {
+            Renderer::Wgpu(renderer) => Self::Wgpu(renderer.with_primitives(|_, p| p.to_vec())),
+            Renderer::TinySkia(renderer) => {
+                Self::TinySkia(renderer.with_primitives(|_, p| p.to_vec()))
+            }
+        }
+    }
+
+    // The number of primitives, not counting those within groups and clips.
+    #[cfg(feature = "debug")]
+    pub fn len(&self) -> usize {
+        match self {
+            Self::Wgpu(primitives) => primitives.len(),
+            Self::TinySkia(primitives) => primitives.len(),
+        }
+    }
+
+    // Append the primitives to the renderer.
+    pub fn draw(self, renderer: &mut Renderer) {
+        match (self, renderer) {
+            (Self::Wgpu(primitives), Renderer::Wgpu(renderer)) => {
+                primitives
+                    .into_iter()
+                    .for_each(|p| renderer.draw_primitive(p));
+            }
+            (Self::TinySkia(primitives), Renderer::TinySkia(renderer)) => {
+                primitives
+                    .into_iter()
+                    .for_each(|p| renderer.draw_primitive(p));
+            }
+            _ => {}
+        }
+    }
+}
+
+// Center the dialog over a backdrop filling the window.
+pub fn wrap<'a, M: 'a>(
+    content: Element<'a, M, Theme, Renderer>,
+    scrim: Color,
+) -> Element<'a, M, Theme, Renderer> {
+    container(content)
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .center_x()
+        .center_y()
+        .style(iced_widget::style::theme::Container::Custom(Box::new(
+            Scrim(scrim),
+        )))
+        .into()
+}
+
+struct Scrim(Color);
+
+impl container::StyleSheet for Scrim {
+    type Style = Theme;
+
+    fn appearance(&self, _style: &Theme) -> container::Appearance {
+        container::Appearance {
+            background: Some(self.0.into()),
+            ..Default::default()
+        }
+    }
+}
+
+pub fn is_escape(event: &IcedEvent) -> bool {
+    matches!(
+        event,
+        IcedEvent::Keyboard(keyboard::Event::KeyPressed {
+            key: keyboard::Key::Named(keyboard::key::Named::Escape),
+            ..
+        })
+    )
+}
+
+// Draw the UIs in the order of their z-index and the modal over them, and track whether
+// it's still open.
+pub fn finish_frame(
+    props: Res<IcedResource>,
+    mut modal: ResMut<IcedModal>,
+    did_draw: Res<DidDraw>,
+) {
+    modal.open = std::mem::take(&mut modal.shown);
+    let props = &mut *props.lock().unwrap();
+    if let Some(menu) = &mut props.context_menu {
+        menu.shown = false;
+    }
+    // The UIs displayed with a z-index are drawn with the others, the ones with the same
+    // index in the order they were displayed.
+    if !props.z_layers.is_empty() {
+        let mut layers = std::mem::take(&mut props.z_layers);
+        layers.push((0, Layer::take(&mut props.renderer)));
+        layers.sort_by_key(|(z_index, _)| *z_index);
+        for (_, layer) in layers {
+            layer.draw(&mut props.renderer);
+        }
+    }
+    if let Some(layer) = props.modal_layer.take() {
+        layer.draw(&mut props.renderer);
+        did_draw.store(true, std::sync::atomic::Ordering::Relaxed);
+    }
+    // Without the toasts and the debug panel, which are drawn over it every frame.
+    if std::mem::take(&mut props.retain) {
+        props.retained = Some(Retained::copy(&mut props.renderer));
+    }
+}
+
+// Release the buttons and keys and drop the mouse motion while a modal is open, once
+// Iced has taken its events. The UI follows the modifiers held from the keyboard events,
+// in `HeldKeys`, so text inputs of the modal still see them.
+pub fn block_game_input(
+    modal: Res<IcedModal>,
+    settings: Res<IcedSettings>,
+    mouse_buttons: Option<ResMut<ButtonInput<MouseButton>>>,
+    mut keys: ResMut<ButtonInput<KeyCode>>,
+    motion: Option<ResMut<Events<MouseMotion>>>,
+    mut wheel: ResMut<Events<MouseWheel>>,
+) {
+    if !settings.modal_blocks_game_input || !modal.is_open() {
+        return;
+    }
+    if let Some(mut mouse_buttons) = mouse_buttons {
+        mouse_buttons.reset_all();
+    }
+    keys.reset_all();
+    if let Some(mut motion) = motion {
+        motion.clear();
+    }
+    wheel.clear();
+}
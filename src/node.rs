@@ -0,0 +1,64 @@
+#[cfg(feature = "bevy_ui")]
+use bevy_ecs::prelude::Entity;
+#[cfg(feature = "bevy_ui")]
+use bevy_ecs::system::{Query, Res, SystemParam};
+#[cfg(feature = "bevy_ui")]
+use bevy_math::Rect;
+#[cfg(feature = "bevy_ui")]
+use bevy_transform::components::GlobalTransform;
+#[cfg(feature = "bevy_ui")]
+use bevy_ui::{Node, UiScale};
+#[cfg(feature = "bevy_ui")]
+use bevy_window::Window;
+use iced_core::{Element, Length, Padding, Rectangle};
+use iced_widget::container;
+use iced_widget::style::Theme;
+
+use crate::Renderer;
+
+// The bevy_ui nodes UIs can be displayed in, see `DisplayOptions::node`.
+#[cfg(feature = "bevy_ui")]
+#[derive(SystemParam)]
+pub struct UiNodes<'w, 's> {
+    nodes: Query<'w, 's, (&'static Node, &'static GlobalTransform)>,
+    // Absent without `UiPlugin`, in which case nodes aren't scaled.
+    scale: Option<Res<'w, UiScale>>,
+}
+
+// There's nothing to read without bevy_ui, the `Local` only uses the lifetime.
+#[cfg(not(feature = "bevy_ui"))]
+pub type UiNodes<'w, 's> = bevy_ecs::system::Local<'s, ()>;
+
+#[cfg(feature = "bevy_ui")]
+impl UiNodes<'_, '_> {
+    // The layout of a node as of the last time bevy_ui laid it out, in the window's
+    // physical pixels, or `None` if the entity isn't a node.
+    pub fn physical_rect(&self, node: Entity, window: &Window) -> Option<Rect> {
+        let (node, transform) = self.nodes.get(node).ok()?;
+        let ui_scale = self.scale.as_ref().map_or(1.0, |scale| scale.0);
+        Some(node.physical_rect(transform, window.scale_factor(), ui_scale))
+    }
+}
+
+// Lay the UI out within the node's bounds and clip it to them. Like the safe area's
+// padding, this keeps the cursor and event positions in window coordinates. The padding
+// can't be negative, so a node reaching past the top left of the window is laid out from
+// the window's edge.
+pub fn wrap<'a, M: 'a>(
+    content: Element<'a, M, Theme, Renderer>,
+    bounds: Rectangle,
+) -> Element<'a, M, Theme, Renderer> {
+    let node = container(content)
+        .width(bounds.width)
+        .height(bounds.height)
+        .clip(true);
+    container(node)
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .padding(Padding {
+            top: bounds.y.max(0.0),
+            left: bounds.x.max(0.0),
+            ..Padding::ZERO
+        })
+        .into()
+}
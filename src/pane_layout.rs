@@ -0,0 +1,248 @@
+//! Save the arrangement of a `pane_grid` and restore it later, e.g. across restarts of an
+//! editor with dockable panels.
+//!
+//! Panes are identified by keys of your own, like the name of the panel they show, since
+//! `pane_grid` only identifies them until it's dropped:
+//!
+//! ```
+//! # use bevy_iced::iced::widget::pane_grid;
+//! # use bevy_iced::pane_layout::PaneLayout;
+//! # #[derive(Clone, Copy, Debug, PartialEq)]
+//! # enum Panel {
+//! #     Outliner,
+//! #     Viewport,
+//! # }
+//! # impl Panel {
+//! #     fn name(&self) -> String {
+//! #         format!("{self:?}")
+//! #     }
+//! #     fn from_name(name: String) -> Option<Self> {
+//! #         [Self::Outliner, Self::Viewport].into_iter().find(|panel| panel.name() == name)
+//! #     }
+//! # }
+//! # fn default_grid() -> pane_grid::State<Panel> {
+//! #     pane_grid::State::new(Panel::Viewport).0
+//! # }
+//! # struct Panels {
+//! #     grid: pane_grid::State<Panel>,
+//! # }
+//! # let panels = Panels { grid: default_grid() };
+//! # let path = std::env::temp_dir().join("bevy_iced_layout.ron");
+//! // On exit:
+//! PaneLayout::from_state(&panels.grid, |panel| panel.name()).save(&path)?;
+//!
+//! // On startup, keeping the panels that still exist:
+//! let grid = PaneLayout::load(&path)
+//!     .ok()
+//!     .and_then(|layout| layout.into_state(Panel::from_name))
+//!     .unwrap_or_else(default_grid);
+//! # assert_eq!(grid.len(), 1);
+//! # Ok::<(), bevy_iced::pane_layout::PaneLayoutError>(())
+//! ```
+//!
+//! Layouts are stored as [RON](https://github.com/ron-rs/ron) files.
+
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+use iced_widget::pane_grid::{self, Axis, Configuration, Node};
+use serde::{Deserialize, Serialize};
+
+/// The arrangement of the panes of a `pane_grid`, as a tree of splits with the key of
+/// every pane.
+///
+/// ```
+/// use bevy_iced::iced::widget::pane_grid;
+/// use bevy_iced::pane_layout::{PaneAxis, PaneLayout};
+///
+/// // A sidebar, and a viewport above a console.
+/// let layout = PaneLayout::Split {
+///     axis: PaneAxis::Vertical,
+///     ratio: 0.25,
+///     a: Box::new(PaneLayout::Pane("outliner")),
+///     b: Box::new(PaneLayout::Split {
+///         axis: PaneAxis::Horizontal,
+///         ratio: 0.7,
+///         a: Box::new(PaneLayout::Pane("viewport")),
+///         b: Box::new(PaneLayout::Pane("console")),
+///     }),
+/// };
+/// let ron = ron::to_string(&layout).unwrap();
+/// let restored: PaneLayout<String> = ron::from_str(&ron).unwrap();
+///
+/// let state: pane_grid::State<String> = restored.clone().into_state(Some).unwrap();
+/// assert_eq!(PaneLayout::from_state(&state, String::clone), restored);
+///
+/// // Without a console anymore, the viewport takes the whole right side.
+/// let state = restored.into_state(|key| (key != "console").then_some(key)).unwrap();
+/// assert_eq!(
+///     PaneLayout::from_state(&state, String::clone),
+///     PaneLayout::Split {
+///         axis: PaneAxis::Vertical,
+///         ratio: 0.25,
+///         a: Box::new(PaneLayout::Pane("outliner".to_owned())),
+///         b: Box::new(PaneLayout::Pane("viewport".to_owned())),
+///     },
+/// );
+/// ```
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub enum PaneLayout<K> {
+    /// The space split in two.
+    Split {
+        /// The direction of the split.
+        axis: PaneAxis,
+        /// The share of the space taken by `a`, from 0 to 1.
+        ratio: f32,
+        /// The left or top side of the split.
+        a: Box<PaneLayout<K>>,
+        /// The right or bottom side of the split.
+        b: Box<PaneLayout<K>>,
+    },
+    /// A pane, with its key.
+    Pane(K),
+}
+
+/// The direction of a [`PaneLayout::Split`], like `pane_grid::Axis`.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PaneAxis {
+    /// Split by a horizontal line, into a top and a bottom side.
+    Horizontal,
+    /// Split by a vertical line, into a left and a right side.
+    Vertical,
+}
+
+impl From<Axis> for PaneAxis {
+    fn from(axis: Axis) -> Self {
+        match axis {
+            Axis::Horizontal => Self::Horizontal,
+            Axis::Vertical => Self::Vertical,
+        }
+    }
+}
+
+impl From<PaneAxis> for Axis {
+    fn from(axis: PaneAxis) -> Self {
+        match axis {
+            PaneAxis::Horizontal => Self::Horizontal,
+            PaneAxis::Vertical => Self::Vertical,
+        }
+    }
+}
+
+/// An error that occurred while loading or saving a [`PaneLayout`].
+#[derive(Debug)]
+pub enum PaneLayoutError {
+    /// The file couldn't be read or written.
+    Io(std::io::Error),
+    /// The file isn't a valid layout.
+    Parsing(ron::error::SpannedError),
+    /// The layout couldn't be serialized.
+    Serializing(ron::Error),
+}
+
+impl fmt::Display for PaneLayoutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "pane layout I/O error: {err}"),
+            Self::Parsing(err) => write!(f, "failed to parse pane layout: {err}"),
+            Self::Serializing(err) => write!(f, "failed to serialize pane layout: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for PaneLayoutError {}
+
+impl From<std::io::Error> for PaneLayoutError {
+    fn from(err: std::io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl From<ron::error::SpannedError> for PaneLayoutError {
+    fn from(err: ron::error::SpannedError) -> Self {
+        Self::Parsing(err)
+    }
+}
+
+impl From<ron::Error> for PaneLayoutError {
+    fn from(err: ron::Error) -> Self {
+        Self::Serializing(err)
+    }
+}
+
+impl<K> PaneLayout<K> {
+    /// The layout of `state`, with the key of every pane taken from its state. A
+    /// maximized pane is saved within the full layout.
+    pub fn from_state<T>(state: &pane_grid::State<T>, mut key: impl FnMut(&T) -> K) -> Self {
+        fn convert<T, K>(
+            node: &Node,
+            state: &pane_grid::State<T>,
+            key: &mut impl FnMut(&T) -> K,
+        ) -> PaneLayout<K> {
+            match node {
+                Node::Split {
+                    axis, ratio, a, b, ..
+                } => PaneLayout::Split {
+                    axis: (*axis).into(),
+                    ratio: *ratio,
+                    a: Box::new(convert(a, state, key)),
+                    b: Box::new(convert(b, state, key)),
+                },
+                // Every pane of the layout has a state.
+                Node::Pane(pane) => PaneLayout::Pane(key(state.get(*pane).unwrap())),
+            }
+        }
+        convert(state.layout(), state, &mut key)
+    }
+
+    /// A `pane_grid` state with this layout, with the state of every pane made from its
+    /// key by `pane`.
+    ///
+    /// Panes for which `pane` returns `None`, e.g. because the panel they showed no
+    /// longer exists, are left out and the other side of their split takes its space.
+    /// Returns `None` when no pane is left.
+    pub fn into_state<T>(
+        self,
+        mut pane: impl FnMut(K) -> Option<T>,
+    ) -> Option<pane_grid::State<T>> {
+        self.into_configuration(&mut pane)
+            .map(pane_grid::State::with_configuration)
+    }
+
+    fn into_configuration<T>(
+        self,
+        pane: &mut impl FnMut(K) -> Option<T>,
+    ) -> Option<Configuration<T>> {
+        match self {
+            Self::Split { axis, ratio, a, b } => {
+                match (a.into_configuration(pane), b.into_configuration(pane)) {
+                    (Some(a), Some(b)) => Some(Configuration::Split {
+                        axis: axis.into(),
+                        // Ratios are clamped, since the file may have been edited.
+                        ratio: ratio.clamp(0.0, 1.0),
+                        a: Box::new(a),
+                        b: Box::new(b),
+                    }),
+                    (a, b) => a.or(b),
+                }
+            }
+            Self::Pane(key) => pane(key).map(Configuration::Pane),
+        }
+    }
+}
+
+impl<K: Serialize> PaneLayout<K> {
+    /// Save the layout to a RON file.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), PaneLayoutError> {
+        let contents = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())?;
+        Ok(fs::write(path, contents)?)
+    }
+}
+
+impl<K: for<'de> Deserialize<'de>> PaneLayout<K> {
+    /// Load a layout from a RON file.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, PaneLayoutError> {
+        Ok(ron::from_str(&fs::read_to_string(path)?)?)
+    }
+}
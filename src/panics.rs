@@ -0,0 +1,174 @@
+use std::any::{type_name, Any, TypeId};
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Once;
+
+use bevy_ecs::event::{Event, EventWriter};
+use bevy_ecs::system::Res;
+use bevy_utils::tracing::warn;
+use iced_core::{Element, Length};
+use iced_widget::style::Theme;
+use iced_widget::{column, container, text};
+
+use crate::{IcedResource, Renderer};
+
+/// A panic caught while displaying a UI, sent in [`PostUpdate`](bevy_app::PostUpdate)
+/// when [`IcedSettings::catch_panics`](crate::IcedSettings::catch_panics) is enabled.
+///
+/// A UI that keeps panicking the same way every frame is only reported once, until it's
+/// displayed successfully again.
+#[derive(Event, Clone, Debug, PartialEq, Eq)]
+pub struct IcedPanic {
+    /// The message type of the UI, as named by [`std::any::type_name`].
+    pub ui: &'static str,
+    /// The panic message, if the panic had one, as `panic!`, `expect` and out of bounds
+    /// indexing do.
+    pub message: Option<String>,
+    /// Where the panic was raised, as `file:line:column`.
+    pub location: Option<String>,
+}
+
+impl fmt::Display for IcedPanic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "the UI of {} panicked", self.ui)?;
+        if let Some(location) = &self.location {
+            write!(f, " at {location}")?;
+        }
+        if let Some(message) = &self.message {
+            write!(f, ": {message}")?;
+        }
+        Ok(())
+    }
+}
+
+// The last panic of each message type's UI, and of building its view, until it's displayed
+// successfully, and the panics to send as events.
+#[derive(Default)]
+pub struct Panics {
+    failing: HashMap<(TypeId, bool), IcedPanic>,
+    pending: Vec<IcedPanic>,
+}
+
+impl Panics {
+    // Run the display of a UI of `M`, returning `None` if it panicked. The caller resets
+    // what the display left half done.
+    pub fn catch<M: Any, T>(&mut self, display: impl FnOnce() -> T) -> Option<T> {
+        self.catch_as::<M, T>(false, display)
+    }
+
+    // Build the view of a UI of `M`, returning `None` if it panicked. It's failing apart
+    // from the display, which succeeds with the banner shown instead, so that the panic is
+    // only reported once.
+    pub fn catch_view<M: Any, T>(&mut self, view: impl FnOnce() -> T) -> Option<T> {
+        self.catch_as::<M, T>(true, view)
+    }
+
+    fn catch_as<M: Any, T>(&mut self, view: bool, display: impl FnOnce() -> T) -> Option<T> {
+        let key = (TypeId::of::<M>(), view);
+        install_hook();
+        let caught = CAUGHT.replace(true);
+        let result = panic::catch_unwind(AssertUnwindSafe(display));
+        CAUGHT.set(caught);
+        let payload = match result {
+            Ok(value) => {
+                self.failing.remove(&key);
+                return Some(value);
+            }
+            Err(payload) => payload,
+        };
+        let panic = IcedPanic {
+            ui: type_name::<M>(),
+            message: message(payload.as_ref()),
+            location: LOCATION.take(),
+        };
+        if self.failing.get(&key) != Some(&panic) {
+            warn!("Caught a panic, showing an error banner instead: {panic}");
+            self.pending.push(panic.clone());
+            self.failing.insert(key, panic);
+        }
+        None
+    }
+
+    // The panic the UI of `M` keeps failing with.
+    pub fn failing<M: Any>(&self) -> Option<&IcedPanic> {
+        self.failing.get(&(TypeId::of::<M>(), false))
+    }
+
+    // The panic building the view of the UI of `M` keeps failing with.
+    pub fn failing_view<M: Any>(&self) -> Option<&IcedPanic> {
+        self.failing.get(&(TypeId::of::<M>(), true))
+    }
+}
+
+fn message(payload: &(dyn Any + Send)) -> Option<String> {
+    payload
+        .downcast_ref::<&str>()
+        .map(|message| message.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+}
+
+thread_local! {
+    // Whether a panic is caught by `Panics::catch`, and where the last one was raised.
+    static CAUGHT: Cell<bool> = const { Cell::new(false) };
+    static LOCATION: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+// Panics caught while displaying a UI are logged once by `Panics::catch`, rather than by
+// the default hook every frame; the others go to the hook set before.
+fn install_hook() {
+    static HOOK: Once = Once::new();
+    HOOK.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if CAUGHT.get() {
+                LOCATION.set(info.location().map(ToString::to_string));
+            } else {
+                previous(info);
+            }
+        }));
+    });
+}
+
+// A banner across the top of the window, in place of the UI that panicked.
+pub fn banner<'a, M: 'a>(panic: &IcedPanic) -> Element<'a, M, Theme, Renderer> {
+    let ui = panic.ui.rsplit("::").next().unwrap_or(panic.ui);
+    let mut details = column![text(format!("The UI of {ui} panicked")).size(16)];
+    if let Some(message) = &panic.message {
+        details = details.push(text(message).size(14));
+    }
+    if let Some(location) = &panic.location {
+        details = details.push(text(location).size(12));
+    }
+    container(details.spacing(4))
+        .width(Length::Fill)
+        .padding(12)
+        .style(iced_widget::style::theme::Container::Custom(Box::new(
+            Banner,
+        )))
+        .into()
+}
+
+struct Banner;
+
+impl container::StyleSheet for Banner {
+    type Style = Theme;
+
+    fn appearance(&self, style: &Theme) -> container::Appearance {
+        let danger = style.extended_palette().danger.base;
+        container::Appearance {
+            text_color: Some(danger.text),
+            background: Some(danger.color.into()),
+            ..Default::default()
+        }
+    }
+}
+
+// Send the panics caught this frame.
+pub fn publish_panics(props: Res<IcedResource>, mut events: EventWriter<IcedPanic>) {
+    let pending = std::mem::take(&mut props.lock().unwrap().panics.pending);
+    if !pending.is_empty() {
+        events.send_batch(pending);
+    }
+}
@@ -0,0 +1,178 @@
+use std::collections::HashSet;
+use std::time::{Duration, Instant};
+
+use bevy_ecs::system::Res;
+use iced_core::text::{LineHeight, Renderer as _, Shaping, Text};
+use iced_core::{alignment, Color, Font, Pixels, Point, Rectangle, Size};
+use iced_wgpu::wgpu;
+use iced_widget::graphics::Viewport;
+
+use crate::modal::Layer;
+use crate::render::ViewportResource;
+use crate::IcedResource;
+
+// The size of the target the glyphs are rasterized into, in physical pixels. Every word
+// is drawn at its top left corner, so only longer words are clipped.
+const TARGET_SIZE: Size<u32> = Size::new(2048, 256);
+
+// The glyphs waiting to be rasterized, and how long the last batch took.
+#[derive(Default)]
+pub struct Prewarm {
+    requests: Vec<Request>,
+    // Drawn in the main world, waiting to be rasterized by the render node.
+    pending: Option<Pending>,
+    duration: Option<Duration>,
+}
+
+struct Request {
+    words: Vec<String>,
+    font: Font,
+    sizes: Vec<Pixels>,
+}
+
+pub struct Pending {
+    layer: Layer,
+    scale_factor: f64,
+    // The time spent in the main world.
+    spent: Duration,
+}
+
+impl Prewarm {
+    pub fn request(&mut self, texts: &[&str], font: Font, sizes: &[Pixels]) {
+        // Glyphs are rasterized per word, which keeps the shaping of ligatures and scripts
+        // that join letters while fitting every word in the target.
+        let words: HashSet<_> = texts
+            .iter()
+            .flat_map(|text| text.split_whitespace())
+            .collect();
+        self.requests.push(Request {
+            words: words.into_iter().map(str::to_owned).collect(),
+            font,
+            sizes: sizes.to_vec(),
+        });
+    }
+
+    // Request the printable ASCII characters of the default font.
+    pub fn request_ascii(&mut self, font: Font, size: Pixels) {
+        let ascii: String = ('!'..='~').collect();
+        self.request(&[&ascii], font, &[size]);
+    }
+
+    pub fn is_pending(&self) -> bool {
+        !self.requests.is_empty() || self.pending.is_some()
+    }
+
+    pub fn duration(&self) -> Option<Duration> {
+        self.duration
+    }
+
+    pub fn take_pending(&mut self) -> Option<Pending> {
+        self.pending.take()
+    }
+
+    // Record the time a batch took, in both worlds.
+    pub fn finish(&mut self, duration: Duration) {
+        self.duration = Some(duration);
+    }
+}
+
+impl Pending {
+    // Rasterize the glyphs into the backend's atlas, by presenting them to a throwaway
+    // target.
+    pub fn rasterize(
+        &self,
+        backend: &mut iced_wgpu::Backend,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        encoder: &mut wgpu::CommandEncoder,
+        format: wgpu::TextureFormat,
+    ) {
+        let Layer::Wgpu(primitives) = &self.layer else {
+            return;
+        };
+        let target = device
+            .create_texture(&wgpu::TextureDescriptor {
+                label: Some("bevy_iced.prewarm.texture"),
+                size: wgpu::Extent3d {
+                    width: TARGET_SIZE.width,
+                    height: TARGET_SIZE.height,
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format,
+                usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+                view_formats: &[],
+            })
+            .create_view(&wgpu::TextureViewDescriptor::default());
+        let viewport = Viewport::with_physical_size(TARGET_SIZE, self.scale_factor);
+        backend.present::<&str>(
+            device,
+            queue,
+            encoder,
+            Some(Color::TRANSPARENT),
+            format,
+            &target,
+            primitives,
+            &viewport,
+            &[],
+        );
+    }
+
+    pub fn spent(&self) -> Duration {
+        self.spent
+    }
+}
+
+// Draw the requested words into a layer of their own, leaving the primitives of this
+// frame's UIs as they were.
+pub fn draw_prewarm(props: Res<IcedResource>, viewport: Res<ViewportResource>) {
+    let props = &mut *props.lock().unwrap();
+    // Requests made while the last batch is rasterized wait for the next frame.
+    if props.prewarm.requests.is_empty() || props.prewarm.pending.is_some() {
+        return;
+    }
+    let start = Instant::now();
+    props.wake_renderer();
+    let renderer = &mut props.renderer;
+    let below = Layer::take(renderer);
+    let clip = Rectangle::with_size(Size::new(
+        TARGET_SIZE.width as f32,
+        TARGET_SIZE.height as f32,
+    ));
+    for request in std::mem::take(&mut props.prewarm.requests) {
+        for size in &request.sizes {
+            for word in &request.words {
+                renderer.fill_text(
+                    Text {
+                        content: word,
+                        bounds: Size::INFINITY,
+                        size: *size,
+                        line_height: LineHeight::default(),
+                        font: request.font,
+                        horizontal_alignment: alignment::Horizontal::Left,
+                        vertical_alignment: alignment::Vertical::Top,
+                        shaping: Shaping::Advanced,
+                    },
+                    Point::ORIGIN,
+                    Color::WHITE,
+                    clip,
+                );
+            }
+        }
+    }
+    let layer = Layer::take(renderer);
+    below.draw(renderer);
+
+    if matches!(layer, Layer::Wgpu(_)) {
+        props.prewarm.pending = Some(Pending {
+            layer,
+            scale_factor: viewport.scale_factor(),
+            spent: start.elapsed(),
+        });
+    } else {
+        // Without a GPU, there's no atlas to rasterize into.
+        props.prewarm.finish(start.elapsed());
+    }
+}
@@ -0,0 +1,327 @@
+use std::any::Any;
+use std::collections::VecDeque;
+
+use iced_core::event::{self, Event};
+use iced_core::layout::{self, Layout};
+use iced_core::mouse;
+use iced_core::overlay;
+use iced_core::renderer;
+use iced_core::widget::{self, operation, tree, Operation, Tree};
+use iced_core::{Clipboard, Element, Length, Rectangle, Shell, Size, Vector, Widget};
+use iced_runtime::user_interface::UserInterface;
+use iced_widget::style::Theme;
+
+use crate::Renderer;
+
+type Paragraph = <Renderer as iced_core::text::Renderer>::Paragraph;
+
+// A widget of a UI as it was laid out, with what can be told about it: the kind of the
+// built-in widgets with their own state, the ids of containers and scrollables, and the
+// scroll offset of scrollables.
+pub struct Node {
+    pub kind: Option<&'static str>,
+    pub id: Option<widget::Id>,
+    pub bounds: Rectangle,
+    // The translation of the content of a scrollable.
+    pub translation: Option<Vector>,
+    pub children: Vec<Node>,
+}
+
+impl Node {
+    fn new(tree: Option<&Tree>, layout: Layout<'_>) -> Self {
+        let mut children_layouts = layout.children();
+        let only_child = children_layouts
+            .next()
+            .filter(|_| children_layouts.next().is_none());
+        // Containers hand their own tree to their content, so the tree belongs to the
+        // only child whose layout it fits; a text input's only child is its text.
+        // Otherwise, widgets whose children are laid out one to one with their trees,
+        // like most, have the kinds of those known too.
+        let forwarded = tree.zip(only_child).filter(|(tree, child)| {
+            let text = kind(tree, false) == Some("text_input") && child.children().count() == 0;
+            !text && fits(tree, *child)
+        });
+        let (kind, children) = match forwarded {
+            Some((tree, child)) => (None, vec![Self::new(Some(tree), child)]),
+            None => {
+                let trees = tree
+                    .map(|tree| &tree.children)
+                    .filter(|trees| trees.len() == layout.children().count());
+                let children: Vec<_> = layout
+                    .children()
+                    .enumerate()
+                    .map(|(i, child)| Self::new(trees.map(|trees| &trees[i]), child))
+                    .collect();
+                (
+                    tree.and_then(|tree| kind(tree, children.is_empty())),
+                    children,
+                )
+            }
+        };
+        Self {
+            kind,
+            id: None,
+            bounds: layout.bounds(),
+            translation: None,
+            children,
+        }
+    }
+
+    // Give the outermost widget with the bounds of each id that id, text inputs theirs in
+    // order, and scrollables the translation reported with their bounds.
+    fn assign(&mut self, capture: &mut Capture) {
+        if let Some(i) = capture
+            .ids
+            .iter()
+            .position(|(bounds, _)| *bounds == self.bounds)
+        {
+            self.id = Some(capture.ids.remove(i).1);
+        }
+        if self.kind == Some("text_input") {
+            self.id = capture.text_inputs.pop_front().flatten();
+        }
+        if self.kind == Some("scrollable") {
+            if let Some(i) = capture
+                .translations
+                .iter()
+                .position(|(bounds, _)| *bounds == self.bounds)
+            {
+                self.translation = Some(capture.translations.remove(i).1);
+            }
+        }
+        for child in &mut self.children {
+            child.assign(capture);
+        }
+    }
+
+    fn count(&self, kind: &str) -> usize {
+        let count: usize = self.children.iter().map(|child| child.count(kind)).sum();
+        count + usize::from(self.kind == Some(kind))
+    }
+}
+
+// Whether the tree can be the one of the widget laid out as `layout`. Widgets without
+// child trees may lay out any children.
+fn fits(tree: &Tree, layout: Layout<'_>) -> bool {
+    let mut children = layout.children();
+    let zipped = tree.children.len() == layout.children().count()
+        && tree
+            .children
+            .iter()
+            .zip(layout.children())
+            .all(|(tree, child)| fits(tree, child));
+    let only_child = children.next().filter(|_| children.next().is_none());
+    tree.children.is_empty() || zipped || only_child.is_some_and(|child| fits(tree, child))
+}
+
+// The kind of the built-in widgets, told apart by the type of their state.
+fn kind(tree: &Tree, leaf: bool) -> Option<&'static str> {
+    use iced_widget::vertical_slider;
+    use iced_widget::{button, pane_grid, pick_list, scrollable, slider, text_input};
+
+    let tag = tree.tag;
+    let kinds = [
+        (tree::Tag::of::<button::State>(), "button"),
+        (tree::Tag::of::<scrollable::State>(), "scrollable"),
+        (tree::Tag::of::<slider::State>(), "slider"),
+        (tree::Tag::of::<vertical_slider::State>(), "vertical_slider"),
+        (
+            tree::Tag::of::<text_input::State<Paragraph>>(),
+            "text_input",
+        ),
+        (tree::Tag::of::<pick_list::State<Paragraph>>(), "pick_list"),
+        (tree::Tag::of::<pane_grid::state::Action>(), "pane_grid"),
+    ];
+    if let Some((_, kind)) = kinds.iter().find(|(kind, _)| *kind == tag) {
+        return Some(kind);
+    }
+    // Checkboxes, radios and togglers keep the state of their label, like text.
+    let text = tree::Tag::of::<iced_core::widget::text::State<Paragraph>>();
+    (tag == text).then_some(if leaf { "text" } else { "toggle" })
+}
+
+// The layout of the UI, captured by the `Probe` at its root.
+pub fn capture<M>(
+    ui: &mut UserInterface<'_, M, Theme, Renderer>,
+    renderer: &Renderer,
+) -> Option<Node> {
+    let mut capture = Capture::default();
+    ui.operate(renderer, &mut capture);
+    let mut root = capture.root.take()?;
+    // Text inputs report their ids without their bounds, in the order they're laid out.
+    if root.count("text_input") != capture.text_inputs.len() {
+        capture.text_inputs.clear();
+    }
+    root.assign(&mut capture);
+    Some(root)
+}
+
+// Asks the probe for the layout, which it only builds when asked.
+struct Wanted(bool);
+
+struct Captured(Option<Node>);
+
+#[derive(Default)]
+struct Capture {
+    root: Option<Node>,
+    ids: Vec<(Rectangle, widget::Id)>,
+    translations: Vec<(Rectangle, Vector)>,
+    text_inputs: VecDeque<Option<widget::Id>>,
+}
+
+impl<T> Operation<T> for Capture {
+    fn container(
+        &mut self,
+        id: Option<&widget::Id>,
+        bounds: Rectangle,
+        operate_on_children: &mut dyn FnMut(&mut dyn Operation<T>),
+    ) {
+        if let Some(id) = id {
+            self.ids.push((bounds, id.clone()));
+        }
+        operate_on_children(self);
+    }
+
+    fn scrollable(
+        &mut self,
+        _state: &mut dyn operation::Scrollable,
+        _id: Option<&widget::Id>,
+        bounds: Rectangle,
+        translation: Vector,
+    ) {
+        self.translations.push((bounds, translation));
+    }
+
+    fn text_input(&mut self, _state: &mut dyn operation::TextInput, id: Option<&widget::Id>) {
+        self.text_inputs.push_back(id.cloned());
+    }
+
+    fn custom(&mut self, state: &mut dyn Any, _id: Option<&widget::Id>) {
+        if let Some(wanted) = state.downcast_mut::<Wanted>() {
+            wanted.0 = true;
+        } else if let Some(captured) = state.downcast_mut::<Captured>() {
+            self.root = captured.0.take();
+        }
+    }
+}
+
+// Wraps the root of every UI, sharing its content's widget state, to hand the layout
+// to a capture.
+pub fn wrap<'a, M: 'a>(
+    content: Element<'a, M, Theme, Renderer>,
+) -> Element<'a, M, Theme, Renderer> {
+    Element::new(Probe { content })
+}
+
+struct Probe<'a, M> {
+    content: Element<'a, M, Theme, Renderer>,
+}
+
+impl<'a, M> Widget<M, Theme, Renderer> for Probe<'a, M> {
+    fn tag(&self) -> tree::Tag {
+        self.content.as_widget().tag()
+    }
+
+    fn state(&self) -> tree::State {
+        self.content.as_widget().state()
+    }
+
+    fn children(&self) -> Vec<Tree> {
+        self.content.as_widget().children()
+    }
+
+    fn diff(&self, tree: &mut Tree) {
+        self.content.as_widget().diff(tree);
+    }
+
+    fn size(&self) -> Size<Length> {
+        self.content.as_widget().size()
+    }
+
+    fn size_hint(&self) -> Size<Length> {
+        self.content.as_widget().size_hint()
+    }
+
+    fn layout(
+        &self,
+        tree: &mut Tree,
+        renderer: &Renderer,
+        limits: &layout::Limits,
+    ) -> layout::Node {
+        self.content.as_widget().layout(tree, renderer, limits)
+    }
+
+    fn operate(
+        &self,
+        tree: &mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        operation: &mut dyn Operation<M>,
+    ) {
+        let mut wanted = Wanted(false);
+        operation.custom(&mut wanted, None);
+        if wanted.0 {
+            let mut captured = Captured(Some(Node::new(Some(tree), layout)));
+            operation.custom(&mut captured, None);
+        }
+        self.content
+            .as_widget()
+            .operate(tree, layout, renderer, operation);
+    }
+
+    fn on_event(
+        &mut self,
+        tree: &mut Tree,
+        event: Event,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, M>,
+        viewport: &Rectangle,
+    ) -> event::Status {
+        self.content.as_widget_mut().on_event(
+            tree, event, layout, cursor, renderer, clipboard, shell, viewport,
+        )
+    }
+
+    fn mouse_interaction(
+        &self,
+        tree: &Tree,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+        renderer: &Renderer,
+    ) -> mouse::Interaction {
+        self.content
+            .as_widget()
+            .mouse_interaction(tree, layout, cursor, viewport, renderer)
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+    ) {
+        self.content
+            .as_widget()
+            .draw(tree, renderer, theme, style, layout, cursor, viewport);
+    }
+
+    fn overlay<'b>(
+        &'b mut self,
+        tree: &'b mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        translation: Vector,
+    ) -> Option<overlay::Element<'b, M, Theme, Renderer>> {
+        self.content
+            .as_widget_mut()
+            .overlay(tree, layout, renderer, translation)
+    }
+}
@@ -0,0 +1,553 @@
+//! Record the events fed to Iced UIs and replay them later, e.g. to reproduce a bug report.
+//!
+//! ```
+//! # use bevy::prelude::*;
+//! # use bevy_iced::record::{IcedEventRecorder, IcedEventReplayer, IcedSession};
+//! # use bevy_iced::IcedPlugin;
+//! # let mut app = App::new();
+//! # app.add_plugins(IcedPlugin::default());
+//! # app.finish();
+//! # app.cleanup();
+//! # let path = std::env::temp_dir().join("bevy_iced_bug.ron");
+//! // While the user reproduces the bug:
+//! app.world.resource_mut::<IcedEventRecorder>().start();
+//! app.update();
+//! let session = app.world.resource_mut::<IcedEventRecorder>().stop();
+//! session.save(&path)?;
+//!
+//! // Later, in a debug build or a test:
+//! let session = IcedSession::load(&path)?;
+//! app.world.resource_mut::<IcedEventReplayer>().play(session);
+//! app.update();
+//! # Ok::<(), bevy_iced::record::SessionError>(())
+//! ```
+//!
+//! Sessions are stored as [RON](https://github.com/ron-rs/ron) files.
+
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+use bevy_app::{App, PreUpdate};
+use bevy_ecs::prelude::IntoSystemConfigs;
+use bevy_ecs::system::{Res, ResMut, Resource};
+use iced_core::keyboard::{self, Key, Location, Modifiers};
+use iced_core::mouse::{self, Button, Cursor, ScrollDelta};
+use iced_core::{touch, Event as IcedEvent, Point, Size, SmolStr};
+use iced_widget::graphics::Viewport;
+use serde::{Deserialize, Serialize};
+
+use crate::render::ViewportResource;
+use crate::{capture, conversions, systems, IcedEventQueue, IcedResource, IcedSet};
+
+pub(crate) fn setup(app: &mut App) {
+    app.init_resource::<IcedEventRecorder>()
+        .init_resource::<IcedEventReplayer>()
+        .add_systems(
+            PreUpdate,
+            (
+                // Before the capture status is judged from the cursor it replays.
+                replay_events
+                    .after(systems::process_input)
+                    .before(capture::update_status)
+                    .in_set(IcedSet::ProcessInput),
+                record_events
+                    .after(replay_events)
+                    .after(IcedSet::ProcessInput),
+            ),
+        );
+}
+
+/// A recorded interaction with the UI.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct IcedSession {
+    /// The frames in which something happened, in order.
+    pub frames: Vec<RecordedFrame>,
+}
+
+/// The changes recorded during a single frame.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct RecordedFrame {
+    /// The index of the frame, counted from the start of the recording.
+    pub frame: u64,
+    /// The new viewport, if it changed during this frame.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub viewport: Option<RecordedViewport>,
+    /// The events fed to the UI during this frame.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub events: Vec<RecordedEvent>,
+}
+
+/// The physical size and scale factor of the viewport.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct RecordedViewport {
+    /// The physical width, in pixels.
+    pub width: u32,
+    /// The physical height, in pixels.
+    pub height: u32,
+    /// The scale factor of the viewport.
+    pub scale_factor: f64,
+}
+
+impl RecordedViewport {
+    fn new(viewport: &Viewport) -> Self {
+        let size = viewport.physical_size();
+        Self {
+            width: size.width,
+            height: size.height,
+            scale_factor: viewport.scale_factor(),
+        }
+    }
+
+    fn to_viewport(self) -> Viewport {
+        Viewport::with_physical_size(Size::new(self.width, self.height), self.scale_factor)
+    }
+}
+
+/// A serializable mirror of the Iced events produced by `bevy_iced`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[allow(missing_docs)]
+pub enum RecordedEvent {
+    CursorEntered,
+    CursorLeft,
+    CursorMoved {
+        #[serde(with = "PointDef")]
+        position: Point,
+    },
+    ButtonPressed(#[serde(with = "ButtonDef")] Button),
+    ButtonReleased(#[serde(with = "ButtonDef")] Button),
+    WheelScrolled {
+        #[serde(with = "ScrollDeltaDef")]
+        delta: ScrollDelta,
+    },
+    KeyPressed {
+        key: RecordedKey,
+        #[serde(with = "LocationDef")]
+        location: Location,
+        #[serde(with = "modifiers")]
+        modifiers: Modifiers,
+        text: Option<String>,
+    },
+    KeyReleased {
+        key: RecordedKey,
+        #[serde(with = "LocationDef")]
+        location: Location,
+        #[serde(with = "modifiers")]
+        modifiers: Modifiers,
+    },
+    ModifiersChanged(#[serde(with = "modifiers")] Modifiers),
+    FingerPressed {
+        id: u64,
+        #[serde(with = "PointDef")]
+        position: Point,
+    },
+    FingerMoved {
+        id: u64,
+        #[serde(with = "PointDef")]
+        position: Point,
+    },
+    FingerLifted {
+        id: u64,
+        #[serde(with = "PointDef")]
+        position: Point,
+    },
+    FingerLost {
+        id: u64,
+        #[serde(with = "PointDef")]
+        position: Point,
+    },
+}
+
+/// A serializable mirror of [`Key`]. Named keys are stored by name.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[allow(missing_docs)]
+pub enum RecordedKey {
+    Named(String),
+    Character(String),
+    Unidentified,
+}
+
+impl RecordedKey {
+    fn new(key: &Key) -> Self {
+        match key {
+            Key::Named(named) => conversions::named_key_name(*named)
+                .map_or(Self::Unidentified, |name| Self::Named(name.to_owned())),
+            Key::Character(char) => Self::Character(char.to_string()),
+            Key::Unidentified => Self::Unidentified,
+        }
+    }
+
+    fn to_key(&self) -> Key {
+        match self {
+            Self::Named(name) => {
+                conversions::named_key_from_name(name).map_or(Key::Unidentified, Key::Named)
+            }
+            Self::Character(char) => Key::Character(SmolStr::new(char)),
+            Self::Unidentified => Key::Unidentified,
+        }
+    }
+}
+
+impl RecordedEvent {
+    /// Mirror an Iced event. Returns `None` for events `bevy_iced` never produces.
+    pub fn new(event: &IcedEvent) -> Option<Self> {
+        Some(match event {
+            IcedEvent::Mouse(event) => match *event {
+                mouse::Event::CursorEntered => Self::CursorEntered,
+                mouse::Event::CursorLeft => Self::CursorLeft,
+                mouse::Event::CursorMoved { position } => Self::CursorMoved { position },
+                mouse::Event::ButtonPressed(button) => Self::ButtonPressed(button),
+                mouse::Event::ButtonReleased(button) => Self::ButtonReleased(button),
+                mouse::Event::WheelScrolled { delta } => Self::WheelScrolled { delta },
+            },
+            IcedEvent::Keyboard(event) => match event {
+                keyboard::Event::KeyPressed {
+                    key,
+                    location,
+                    modifiers,
+                    text,
+                } => Self::KeyPressed {
+                    key: RecordedKey::new(key),
+                    location: *location,
+                    modifiers: *modifiers,
+                    text: text.as_ref().map(ToString::to_string),
+                },
+                keyboard::Event::KeyReleased {
+                    key,
+                    location,
+                    modifiers,
+                } => Self::KeyReleased {
+                    key: RecordedKey::new(key),
+                    location: *location,
+                    modifiers: *modifiers,
+                },
+                keyboard::Event::ModifiersChanged(modifiers) => Self::ModifiersChanged(*modifiers),
+            },
+            IcedEvent::Touch(event) => match *event {
+                touch::Event::FingerPressed { id, position } => {
+                    Self::FingerPressed { id: id.0, position }
+                }
+                touch::Event::FingerMoved { id, position } => {
+                    Self::FingerMoved { id: id.0, position }
+                }
+                touch::Event::FingerLifted { id, position } => {
+                    Self::FingerLifted { id: id.0, position }
+                }
+                touch::Event::FingerLost { id, position } => {
+                    Self::FingerLost { id: id.0, position }
+                }
+            },
+            IcedEvent::Window(..) => return None,
+        })
+    }
+
+    /// Convert back to the Iced event.
+    pub fn to_event(&self) -> IcedEvent {
+        match self {
+            Self::CursorEntered => IcedEvent::Mouse(mouse::Event::CursorEntered),
+            Self::CursorLeft => IcedEvent::Mouse(mouse::Event::CursorLeft),
+            Self::CursorMoved { position } => IcedEvent::Mouse(mouse::Event::CursorMoved {
+                position: *position,
+            }),
+            Self::ButtonPressed(button) => IcedEvent::Mouse(mouse::Event::ButtonPressed(*button)),
+            Self::ButtonReleased(button) => IcedEvent::Mouse(mouse::Event::ButtonReleased(*button)),
+            Self::WheelScrolled { delta } => {
+                IcedEvent::Mouse(mouse::Event::WheelScrolled { delta: *delta })
+            }
+            Self::KeyPressed {
+                key,
+                location,
+                modifiers,
+                text,
+            } => IcedEvent::Keyboard(keyboard::Event::KeyPressed {
+                key: key.to_key(),
+                location: *location,
+                modifiers: *modifiers,
+                text: text.as_deref().map(SmolStr::new),
+            }),
+            Self::KeyReleased {
+                key,
+                location,
+                modifiers,
+            } => IcedEvent::Keyboard(keyboard::Event::KeyReleased {
+                key: key.to_key(),
+                location: *location,
+                modifiers: *modifiers,
+            }),
+            Self::ModifiersChanged(modifiers) => {
+                IcedEvent::Keyboard(keyboard::Event::ModifiersChanged(*modifiers))
+            }
+            Self::FingerPressed { id, position } => IcedEvent::Touch(touch::Event::FingerPressed {
+                id: touch::Finger(*id),
+                position: *position,
+            }),
+            Self::FingerMoved { id, position } => IcedEvent::Touch(touch::Event::FingerMoved {
+                id: touch::Finger(*id),
+                position: *position,
+            }),
+            Self::FingerLifted { id, position } => IcedEvent::Touch(touch::Event::FingerLifted {
+                id: touch::Finger(*id),
+                position: *position,
+            }),
+            Self::FingerLost { id, position } => IcedEvent::Touch(touch::Event::FingerLost {
+                id: touch::Finger(*id),
+                position: *position,
+            }),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "Point")]
+struct PointDef {
+    x: f32,
+    y: f32,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "Button")]
+enum ButtonDef {
+    Left,
+    Right,
+    Middle,
+    Back,
+    Forward,
+    Other(u16),
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "ScrollDelta")]
+enum ScrollDeltaDef {
+    Lines { x: f32, y: f32 },
+    Pixels { x: f32, y: f32 },
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "Location")]
+enum LocationDef {
+    Standard,
+    Left,
+    Right,
+    Numpad,
+}
+
+mod modifiers {
+    use iced_core::keyboard::Modifiers;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(
+        modifiers: &Modifiers,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        modifiers.bits().serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Modifiers, D::Error> {
+        u32::deserialize(deserializer).map(Modifiers::from_bits_truncate)
+    }
+}
+
+/// An error that occurred while loading or saving an [`IcedSession`].
+#[derive(Debug)]
+pub enum SessionError {
+    /// The file couldn't be read or written.
+    Io(std::io::Error),
+    /// The file isn't a valid session.
+    Parsing(ron::error::SpannedError),
+    /// The session couldn't be serialized.
+    Serializing(ron::Error),
+}
+
+impl fmt::Display for SessionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "session I/O error: {err}"),
+            Self::Parsing(err) => write!(f, "failed to parse session: {err}"),
+            Self::Serializing(err) => write!(f, "failed to serialize session: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for SessionError {}
+
+impl From<std::io::Error> for SessionError {
+    fn from(err: std::io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl From<ron::error::SpannedError> for SessionError {
+    fn from(err: ron::error::SpannedError) -> Self {
+        Self::Parsing(err)
+    }
+}
+
+impl From<ron::Error> for SessionError {
+    fn from(err: ron::Error) -> Self {
+        Self::Serializing(err)
+    }
+}
+
+impl IcedSession {
+    /// Load a session from a RON file.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, SessionError> {
+        Ok(ron::from_str(&fs::read_to_string(path)?)?)
+    }
+
+    /// Save the session to a RON file.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), SessionError> {
+        let contents = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())?;
+        Ok(fs::write(path, contents)?)
+    }
+}
+
+/// Records every event fed to the UI, along with viewport changes.
+///
+/// The events are captured from [`IcedEventQueue`] during [`PreUpdate`], so events
+/// injected during `Update` aren't recorded.
+/// Recording is off until [`IcedEventRecorder::start`] is called.
+#[derive(Resource, Default)]
+pub struct IcedEventRecorder {
+    recording: bool,
+    frame: u64,
+    viewport: Option<RecordedViewport>,
+    session: IcedSession,
+}
+
+impl IcedEventRecorder {
+    /// Start a new recording, discarding the current one.
+    pub fn start(&mut self) {
+        *self = Self {
+            recording: true,
+            ..Default::default()
+        };
+    }
+
+    /// Stop recording and take the recorded session.
+    pub fn stop(&mut self) -> IcedSession {
+        self.recording = false;
+        std::mem::take(&mut self.session)
+    }
+
+    /// Whether a recording is in progress.
+    pub fn is_recording(&self) -> bool {
+        self.recording
+    }
+
+    /// The session recorded so far.
+    pub fn session(&self) -> &IcedSession {
+        &self.session
+    }
+}
+
+fn record_events(
+    mut recorder: ResMut<IcedEventRecorder>,
+    event_queue: Res<IcedEventQueue>,
+    viewport: Res<ViewportResource>,
+) {
+    if !recorder.recording {
+        return;
+    }
+    let frame = recorder.frame;
+    recorder.frame += 1;
+
+    let current = RecordedViewport::new(&viewport);
+    let viewport = (recorder.viewport != Some(current)).then_some(current);
+    recorder.viewport = Some(current);
+    let events: Vec<_> = event_queue.iter().filter_map(RecordedEvent::new).collect();
+
+    if viewport.is_some() || !events.is_empty() {
+        recorder.session.frames.push(RecordedFrame {
+            frame,
+            viewport,
+            events,
+        });
+    }
+}
+
+/// Replays a recorded [`IcedSession`].
+///
+/// While playing, the live input is discarded and the recorded events are fed to the UI
+/// at the frames they were recorded in, counted from the call to [`IcedEventReplayer::play`].
+/// The UIs see the cursor where the recorded events left it rather than the window's, so
+/// replayed clicks land where they were recorded whatever the mouse does meanwhile.
+#[derive(Resource, Default)]
+pub struct IcedEventReplayer {
+    session: Option<IcedSession>,
+    frame: u64,
+    next: usize,
+    viewport: Option<RecordedViewport>,
+    cursor: Cursor,
+}
+
+impl IcedEventReplayer {
+    /// Start replaying the given session, starting with the next frame.
+    pub fn play(&mut self, session: IcedSession) {
+        *self = Self {
+            session: Some(session),
+            ..Default::default()
+        };
+    }
+
+    /// Stop replaying and return to the live input.
+    pub fn stop(&mut self) {
+        self.session = None;
+    }
+
+    /// Whether a session is being replayed.
+    pub fn is_playing(&self) -> bool {
+        self.session.is_some()
+    }
+}
+
+fn replay_events(
+    mut replayer: ResMut<IcedEventReplayer>,
+    mut event_queue: ResMut<IcedEventQueue>,
+    mut viewport: ResMut<ViewportResource>,
+    props: Res<IcedResource>,
+) {
+    let replayer = &mut *replayer;
+    let Some(session) = &replayer.session else {
+        // Back to the window's cursor once the replay is over.
+        props.lock().unwrap().replayed_cursor = None;
+        return;
+    };
+    // Window events, like the redraws that animate widgets, aren't recorded: keep them.
+    event_queue.retain(|event| matches!(event, iced_core::Event::Window(..)));
+
+    if let Some(recorded) = session
+        .frames
+        .get(replayer.next)
+        .filter(|recorded| recorded.frame == replayer.frame)
+    {
+        if recorded.viewport.is_some() {
+            replayer.viewport = recorded.viewport;
+        }
+        event_queue.extend(recorded.events.iter().map(RecordedEvent::to_event));
+        replayer.next += 1;
+    }
+    replayer.cursor = replayed_cursor(replayer.cursor, &event_queue);
+    props.lock().unwrap().replayed_cursor = Some(replayer.cursor);
+    // The viewport is refreshed from the window every frame, so keep overriding it.
+    if let Some(recorded) = replayer.viewport {
+        viewport.0 = recorded.to_viewport();
+    }
+
+    replayer.frame += 1;
+    if replayer.next == session.frames.len() {
+        replayer.session = None;
+    }
+}
+
+// Where the replayed events leave the cursor.
+fn replayed_cursor(cursor: Cursor, events: &[IcedEvent]) -> Cursor {
+    events.iter().fold(cursor, |cursor, event| match event {
+        IcedEvent::Mouse(mouse::Event::CursorMoved { position })
+        | IcedEvent::Touch(
+            touch::Event::FingerPressed { position, .. }
+            | touch::Event::FingerMoved { position, .. },
+        ) => Cursor::Available(*position),
+        IcedEvent::Mouse(mouse::Event::CursorLeft) => Cursor::Unavailable,
+        _ => cursor,
+    })
+}
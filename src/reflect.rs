@@ -0,0 +1,587 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::ops::RangeInclusive;
+
+use bevy_ecs::event::Event;
+use bevy_reflect::{
+    DynamicEnum, DynamicVariant, Enum, GetPath, Reflect, ReflectRef, TypeInfo, VariantInfo,
+};
+use iced_core::event::{self, Event as IcedEvent};
+use iced_core::layout::{self, Layout};
+use iced_core::mouse;
+use iced_core::overlay;
+use iced_core::renderer;
+use iced_core::widget::{tree, Operation, Tree};
+use iced_core::{
+    Alignment, Clipboard, Element, Length, Point, Rectangle, Shell, Size, Vector, Widget,
+};
+use iced_widget::style::Theme;
+use iced_widget::{checkbox, column, pick_list, row, slider, text, text_input, Column};
+
+use crate::Renderer;
+
+// The width of the field names, and the indentation of nested sections.
+const LABEL_WIDTH: f32 = 140.0;
+const INDENT: f32 = 16.0;
+const SPACING: f32 = 4.0;
+
+/// A change made in a [`reflect_editor`](crate::widget::reflect_editor): the new value of
+/// the field at `path` within the edited value. Requires the `reflect-ui` feature.
+///
+/// The editor only shows the value, so [`apply`](ReflectEdit::apply) the edits to it,
+/// e.g. in the system handling the UI's messages:
+///
+/// ```
+/// use bevy_iced::ReflectEdit;
+/// use bevy::reflect::Reflect;
+///
+/// #[derive(Reflect, Debug, PartialEq)]
+/// enum Quality {
+///     Low,
+///     High,
+/// }
+///
+/// #[derive(Reflect, Debug, PartialEq)]
+/// struct Tuning {
+///     speed: f32,
+///     name: String,
+///     quality: Quality,
+///     offset: (i32, bool),
+/// }
+///
+/// let mut tuning = Tuning {
+///     speed: 1.0,
+///     name: "player".to_owned(),
+///     quality: Quality::Low,
+///     offset: (0, false),
+/// };
+/// assert!(ReflectEdit::new(".speed", 2.5f32).apply(&mut tuning));
+/// assert!(ReflectEdit::new(".name", "enemy".to_owned()).apply(&mut tuning));
+/// assert!(ReflectEdit::variant(".quality", "High").apply(&mut tuning));
+/// assert!(ReflectEdit::new(".offset.1", true).apply(&mut tuning));
+/// // Values of another type and unknown fields and variants are ignored.
+/// assert!(!ReflectEdit::new(".speed", 2.5f64).apply(&mut tuning));
+/// assert!(!ReflectEdit::new(".missing", 1.0f32).apply(&mut tuning));
+/// assert!(!ReflectEdit::variant(".quality", "Ultra").apply(&mut tuning));
+///
+/// let expected = Tuning {
+///     speed: 2.5,
+///     name: "enemy".to_owned(),
+///     quality: Quality::High,
+///     offset: (0, true),
+/// };
+/// assert_eq!(tuning, expected);
+/// ```
+#[derive(Event)]
+pub struct ReflectEdit {
+    /// The path of the field, as understood by [`GetPath`].
+    pub path: String,
+    /// The new value of the field, of the field's type, or a [`DynamicEnum`] of a unit
+    /// variant for enums.
+    pub value: Box<dyn Reflect>,
+}
+
+impl ReflectEdit {
+    /// Set the field at `path` to `value`.
+    pub fn new(path: impl Into<String>, value: impl Reflect) -> Self {
+        Self {
+            path: path.into(),
+            value: Box::new(value),
+        }
+    }
+
+    /// Switch the enum at `path` to its unit variant `variant`.
+    pub fn variant(path: impl Into<String>, variant: impl Into<String>) -> Self {
+        Self::new(path, DynamicEnum::new(variant, DynamicVariant::Unit))
+    }
+
+    /// Apply the edit to `target`, the value the editor showed. Returns whether the field
+    /// was changed, which it isn't if it doesn't exist anymore or has another type.
+    pub fn apply(&self, target: &mut dyn Reflect) -> bool {
+        let Ok(field) = target.reflect_path_mut(self.path.as_str()) else {
+            return false;
+        };
+        let Some(variant) = self.value.downcast_ref::<DynamicEnum>() else {
+            return field.set(self.value.clone_value()).is_ok();
+        };
+        // Applying an unknown variant would panic.
+        let known = match field.get_represented_type_info() {
+            Some(TypeInfo::Enum(info)) => matches!(
+                info.variant(variant.variant_name()),
+                Some(VariantInfo::Unit(_))
+            ),
+            _ => false,
+        };
+        if known {
+            field.apply(variant);
+        }
+        known
+    }
+}
+
+impl Clone for ReflectEdit {
+    fn clone(&self) -> Self {
+        Self {
+            path: self.path.clone(),
+            value: self.value.clone_value(),
+        }
+    }
+}
+
+impl fmt::Debug for ReflectEdit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReflectEdit")
+            .field("path", &self.path)
+            .field("value", &self.value)
+            .finish()
+    }
+}
+
+/// An editor of a [`Reflect`] value, see [`reflect_editor`].
+#[allow(missing_debug_implementations)]
+pub struct ReflectEditor<'a> {
+    value: &'a dyn Reflect,
+    ranges: HashMap<String, RangeInclusive<f64>>,
+}
+
+/// An editor of `value`, e.g. for a debug or tuning panel, producing a [`ReflectEdit`] for
+/// every change. Requires the `reflect-ui` feature.
+///
+/// Booleans get a checkbox, strings a text input, numbers a text input or a slider with a
+/// [`range`](ReflectEditor::range), and enums a dropdown of their unit variants. Structs,
+/// tuples and lists are shown as collapsible sections of their fields. Other values, like
+/// maps, are shown but can't be edited.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_iced::widget::reflect_editor;
+/// # use bevy_iced::{IcedContext, ReflectEdit};
+/// # #[derive(Resource, Reflect)]
+/// # struct Tuning {
+/// #     speed: f32,
+/// # }
+/// fn tuning_panel(mut ctx: IcedContext<ReflectEdit>, tuning: Res<Tuning>) {
+///     ctx.display(reflect_editor(&*tuning).range(".speed", 0.0..=10.0));
+/// }
+///
+/// fn apply_tuning(mut edits: EventReader<ReflectEdit>, mut tuning: ResMut<Tuning>) {
+///     for edit in edits.read() {
+///         edit.apply(&mut *tuning);
+///     }
+/// }
+/// # App::new().add_systems(Update, (tuning_panel, apply_tuning));
+/// ```
+pub fn reflect_editor(value: &dyn Reflect) -> ReflectEditor<'_> {
+    ReflectEditor {
+        value,
+        ranges: HashMap::new(),
+    }
+}
+
+impl ReflectEditor<'_> {
+    /// Edit the number at `path` with a slider over `range`, instead of a text input.
+    #[must_use]
+    pub fn range(mut self, path: impl Into<String>, range: RangeInclusive<f64>) -> Self {
+        self.ranges.insert(path.into(), range);
+        self
+    }
+}
+
+impl<'a> From<ReflectEditor<'a>> for Element<'a, ReflectEdit, Theme, Renderer> {
+    fn from(editor: ReflectEditor<'a>) -> Self {
+        match fields(editor.value, "") {
+            Some(fields) => column(
+                fields
+                    .into_iter()
+                    .map(|(label, path, value)| field(&editor.ranges, label, path, value)),
+            )
+            .spacing(SPACING)
+            .into(),
+            None => editor_of(&editor.ranges, String::new(), editor.value),
+        }
+    }
+}
+
+// The fields of a value shown as a section, with their labels and paths.
+fn fields<'a>(
+    value: &'a dyn Reflect,
+    path: &str,
+) -> Option<Vec<(String, String, &'a dyn Reflect)>> {
+    let named = |name: &str| format!("{path}.{name}");
+    let fields = match value.reflect_ref() {
+        ReflectRef::Struct(value) => (0..value.field_len())
+            .filter_map(|i| Some((value.name_at(i)?.to_owned(), value.field_at(i)?)))
+            .map(|(name, field)| (name.clone(), named(&name), field))
+            .collect(),
+        ReflectRef::TupleStruct(value) => (0..value.field_len())
+            .filter_map(|i| Some((i.to_string(), value.field(i)?)))
+            .map(|(name, field)| (name.clone(), named(&name), field))
+            .collect(),
+        ReflectRef::Tuple(value) => (0..value.field_len())
+            .filter_map(|i| Some((i.to_string(), value.field(i)?)))
+            .map(|(name, field)| (name.clone(), named(&name), field))
+            .collect(),
+        ReflectRef::List(value) => value
+            .iter()
+            .enumerate()
+            .map(|(i, item)| (format!("[{i}]"), format!("{path}[{i}]"), item))
+            .collect(),
+        ReflectRef::Array(value) => value
+            .iter()
+            .enumerate()
+            .map(|(i, item)| (format!("[{i}]"), format!("{path}[{i}]"), item))
+            .collect(),
+        _ => return None,
+    };
+    Some(fields)
+}
+
+// A labelled field, or a collapsible section of its own fields.
+fn field<'a>(
+    ranges: &HashMap<String, RangeInclusive<f64>>,
+    label: String,
+    path: String,
+    value: &'a dyn Reflect,
+) -> Element<'a, ReflectEdit, Theme, Renderer> {
+    if let Some(fields) = fields(value, &path) {
+        let content = column(
+            fields
+                .into_iter()
+                .map(|(label, path, value)| field(ranges, label, path, value)),
+        )
+        .spacing(SPACING);
+        return Section::new(label, content).into();
+    }
+    let editor = editor_of(ranges, path.clone(), value);
+    match value.reflect_ref() {
+        // The fields of the current variant go below its dropdown.
+        ReflectRef::Enum(variant) if variant.field_len() > 0 => {
+            let fields = (0..variant.field_len()).filter_map(|i| {
+                let field_value = variant.field_at(i)?;
+                let name = variant
+                    .name_at(i)
+                    .map_or_else(|| i.to_string(), str::to_owned);
+                Some(field(
+                    ranges,
+                    name.clone(),
+                    format!("{path}.{name}"),
+                    field_value,
+                ))
+            });
+            Section::new(
+                label,
+                Column::with_children(std::iter::once(editor).chain(fields)).spacing(SPACING),
+            )
+            .into()
+        }
+        _ => row![text(label).width(LABEL_WIDTH), editor]
+            .spacing(SPACING)
+            .align_items(Alignment::Center)
+            .into(),
+    }
+}
+
+// Edit a number with a text input, keeping the value while the text doesn't parse.
+macro_rules! number_input {
+    ($value:expr, $path:expr, $($ty:ty),*) => {
+        $(if let Some(&current) = $value.downcast_ref::<$ty>() {
+            let path = $path;
+            return text_input("", &current.to_string())
+                .on_input(move |input| {
+                    ReflectEdit::new(path.clone(), input.trim().parse::<$ty>().unwrap_or(current))
+                })
+                .into();
+        })*
+    };
+}
+
+// The widget editing a value that isn't shown as a section.
+fn editor_of<'a>(
+    ranges: &HashMap<String, RangeInclusive<f64>>,
+    path: String,
+    value: &'a dyn Reflect,
+) -> Element<'a, ReflectEdit, Theme, Renderer> {
+    if let ReflectRef::Enum(current) = value.reflect_ref() {
+        let variants: Vec<String> = match value.get_represented_type_info() {
+            Some(TypeInfo::Enum(info)) => info
+                .iter()
+                .filter(|variant| matches!(variant, VariantInfo::Unit(_)))
+                .map(|variant| variant.name().to_owned())
+                .collect(),
+            _ => Vec::new(),
+        };
+        return pick_list(
+            variants,
+            Some(current.variant_name().to_owned()),
+            move |variant| ReflectEdit::variant(path.clone(), variant),
+        )
+        .into();
+    }
+    if let Some(&checked) = value.downcast_ref::<bool>() {
+        return checkbox("", checked)
+            .on_toggle(move |checked| ReflectEdit::new(path.clone(), checked))
+            .into();
+    }
+    if let Some(current) = value.downcast_ref::<String>() {
+        return text_input("", current)
+            .on_input(move |input| ReflectEdit::new(path.clone(), input))
+            .into();
+    }
+    if let Some(range) = ranges.get(&path) {
+        let step = (range.end() - range.start()) / 100.0;
+        if let Some(&current) = value.downcast_ref::<f32>() {
+            let range = *range.start() as f32..=*range.end() as f32;
+            return row![
+                slider(range, current, move |value| ReflectEdit::new(
+                    path.clone(),
+                    value
+                ))
+                .step(step as f32),
+                text(format!("{current:.2}")),
+            ]
+            .spacing(SPACING)
+            .into();
+        }
+        if let Some(&current) = value.downcast_ref::<f64>() {
+            return row![
+                slider(range.clone(), current, move |value| ReflectEdit::new(
+                    path.clone(),
+                    value
+                ))
+                .step(step),
+                text(format!("{current:.2}")),
+            ]
+            .spacing(SPACING)
+            .into();
+        }
+    }
+    number_input!(value, path, f32, f64, i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+    text(format!("{value:?}")).into()
+}
+
+#[derive(Clone, Copy)]
+struct SectionState {
+    open: bool,
+}
+
+// A header that shows or hides the content below it when clicked. The widget state
+// keeps whether it's open, so sections stay as the user left them.
+struct Section<'a> {
+    // The header while open and while closed, followed by the content.
+    children: [Element<'a, ReflectEdit, Theme, Renderer>; 3],
+}
+
+impl<'a> Section<'a> {
+    fn new(label: String, content: impl Into<Element<'a, ReflectEdit, Theme, Renderer>>) -> Self {
+        Self {
+            children: [
+                text(format!("- {label}")).into(),
+                text(format!("+ {label}")).into(),
+                content.into(),
+            ],
+        }
+    }
+
+    // The children shown, in the order of the layout's children.
+    fn shown(tree: &Tree) -> &'static [usize] {
+        if tree.state.downcast_ref::<SectionState>().open {
+            &[0, 2]
+        } else {
+            &[1]
+        }
+    }
+}
+
+impl Widget<ReflectEdit, Theme, Renderer> for Section<'_> {
+    fn tag(&self) -> tree::Tag {
+        tree::Tag::of::<SectionState>()
+    }
+
+    fn state(&self) -> tree::State {
+        tree::State::new(SectionState { open: true })
+    }
+
+    fn children(&self) -> Vec<Tree> {
+        self.children.iter().map(Tree::new).collect()
+    }
+
+    fn diff(&self, tree: &mut Tree) {
+        tree.diff_children(&self.children);
+    }
+
+    fn size(&self) -> Size<Length> {
+        Size::new(Length::Shrink, Length::Shrink)
+    }
+
+    fn layout(
+        &self,
+        tree: &mut Tree,
+        renderer: &Renderer,
+        limits: &layout::Limits,
+    ) -> layout::Node {
+        let shown = Self::shown(tree);
+        let header = self.children[shown[0]].as_widget().layout(
+            &mut tree.children[shown[0]],
+            renderer,
+            limits,
+        );
+        let header_size = header.size();
+        let Some(&content) = shown.get(1) else {
+            return layout::Node::with_children(
+                limits.resolve(Length::Shrink, Length::Shrink, header_size),
+                vec![header],
+            );
+        };
+        let offset = Vector::new(INDENT, header_size.height + SPACING);
+        let content = self.children[content]
+            .as_widget()
+            .layout(
+                &mut tree.children[content],
+                renderer,
+                &limits.shrink(Size::new(offset.x, offset.y)),
+            )
+            .move_to(Point::new(offset.x, offset.y));
+        let size = Size::new(
+            header_size.width.max(offset.x + content.size().width),
+            offset.y + content.size().height,
+        );
+        layout::Node::with_children(
+            limits.resolve(Length::Shrink, Length::Shrink, size),
+            vec![header, content],
+        )
+    }
+
+    fn operate(
+        &self,
+        tree: &mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        operation: &mut dyn Operation<ReflectEdit>,
+    ) {
+        for (&i, layout) in Self::shown(tree).iter().zip(layout.children()) {
+            self.children[i].as_widget().operate(
+                &mut tree.children[i],
+                layout,
+                renderer,
+                operation,
+            );
+        }
+    }
+
+    fn on_event(
+        &mut self,
+        tree: &mut Tree,
+        event: IcedEvent,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, ReflectEdit>,
+        viewport: &Rectangle,
+    ) -> event::Status {
+        let shown = Self::shown(tree);
+        let mut status = event::Status::Ignored;
+        for (&i, layout) in shown.iter().zip(layout.children()) {
+            status = status.merge(self.children[i].as_widget_mut().on_event(
+                &mut tree.children[i],
+                event.clone(),
+                layout,
+                cursor,
+                renderer,
+                clipboard,
+                shell,
+                viewport,
+            ));
+        }
+        let header = layout.children().next().map(|header| header.bounds());
+        let toggled = status == event::Status::Ignored
+            && matches!(
+                event,
+                IcedEvent::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left))
+            )
+            && header.is_some_and(|header| cursor.is_over(header));
+        if toggled {
+            let state = tree.state.downcast_mut::<SectionState>();
+            state.open = !state.open;
+            shell.invalidate_layout();
+            status = event::Status::Captured;
+        }
+        status
+    }
+
+    fn mouse_interaction(
+        &self,
+        tree: &Tree,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+        renderer: &Renderer,
+    ) -> mouse::Interaction {
+        let mut children = layout.children();
+        if children
+            .next()
+            .is_some_and(|header| cursor.is_over(header.bounds()))
+        {
+            return mouse::Interaction::Pointer;
+        }
+        let shown = Self::shown(tree);
+        shown
+            .iter()
+            .zip(layout.children())
+            .map(|(&i, layout)| {
+                self.children[i].as_widget().mouse_interaction(
+                    &tree.children[i],
+                    layout,
+                    cursor,
+                    viewport,
+                    renderer,
+                )
+            })
+            .max()
+            .unwrap_or_default()
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+    ) {
+        for (&i, layout) in Self::shown(tree).iter().zip(layout.children()) {
+            self.children[i].as_widget().draw(
+                &tree.children[i],
+                renderer,
+                theme,
+                style,
+                layout,
+                cursor,
+                viewport,
+            );
+        }
+    }
+
+    fn overlay<'b>(
+        &'b mut self,
+        tree: &'b mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        translation: Vector,
+    ) -> Option<overlay::Element<'b, ReflectEdit, Theme, Renderer>> {
+        // Only the content has overlays, e.g. the menus of enum dropdowns.
+        let content = layout.children().nth(1)?;
+        let [_, _, child] = &mut self.children;
+        child
+            .as_widget_mut()
+            .overlay(&mut tree.children[2], content, renderer, translation)
+    }
+}
+
+impl<'a> From<Section<'a>> for Element<'a, ReflectEdit, Theme, Renderer> {
+    fn from(section: Section<'a>) -> Self {
+        Element::new(section)
+    }
+}
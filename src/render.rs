@@ -1,29 +1,52 @@
+use bevy_asset::{AssetId, Assets, Handle};
 use bevy_derive::{Deref, DerefMut};
-use bevy_ecs::prelude::Query;
+use bevy_diagnostic::{DiagnosticPath, Diagnostics};
+use bevy_ecs::prelude::{Entity, EventReader, Query, QueryState, With};
 use bevy_ecs::{
-    system::{Commands, Res, Resource},
+    system::{Commands, Res, ResMut, Resource, SystemParam},
     world::World,
 };
+use bevy_math::UVec2;
+use bevy_render::camera::{
+    ExtractedCamera, ManualTextureViewHandle, ManualTextureViews, NormalizedRenderTarget,
+    RenderTarget,
+};
+use bevy_render::render_asset::RenderAssets;
 use bevy_render::render_graph::RenderLabel;
 use bevy_render::renderer::{RenderDevice, RenderQueue};
+use bevy_render::texture::Image;
 use bevy_render::{
     render_graph::{Node, NodeRunError, RenderGraphContext},
     renderer::RenderContext,
-    view::ExtractedWindows,
+    view::{ExtractedWindows, ViewTarget},
     Extract,
 };
-use bevy_window::Window;
+use bevy_utils::warn_once;
+use bevy_window::{ApplicationLifetime, PrimaryWindow, Window, WindowRef};
+use iced_core::mouse::Cursor;
 use iced_core::Size;
-use iced_wgpu::wgpu::util::StagingBelt;
-use iced_wgpu::wgpu::TextureFormat;
+use iced_wgpu::wgpu::{
+    self, CommandEncoder, LoadOp, Operations, RenderPassColorAttachment, RenderPassDescriptor,
+    StoreOp, TextureFormat, TextureUsages, TextureView,
+};
 use iced_widget::graphics::Viewport;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Mutex;
 
-use crate::{DidDraw, IcedProps, IcedResource, IcedSettings};
+use crate::accessibility::Appearance;
+use crate::backdrop::{Backdrop, ExtractedBackdrops, Scene};
+use crate::fade::Fade;
+use crate::node::UiNodes;
+use crate::software::Software;
+use crate::texture::{ExtractedTextures, IcedTextureTarget, TextureBackends};
+use crate::zoom::IcedZoom;
+use crate::{DidDraw, IcedEnabled, IcedPlugin, IcedProps, IcedResource, IcedSettings};
 
 #[derive(Clone, Hash, Debug, Eq, PartialEq, RenderLabel)]
 pub struct IcedPass;
 
+// The format the backend is initially created with. The actual surface format is only
+// known once the window has been configured, at which point the backend is rebuilt if needed.
 #[cfg(target_arch = "wasm32")]
 pub const TEXTURE_FMT: TextureFormat = TextureFormat::Rgba8UnormSrgb;
 #[cfg(not(target_arch = "wasm32"))]
@@ -32,52 +55,352 @@ pub const TEXTURE_FMT: TextureFormat = TextureFormat::Bgra8UnormSrgb;
 #[derive(Resource, Deref, DerefMut, Clone)]
 pub struct ViewportResource(pub Viewport);
 
+/// Drops the backend's GPU state when the application is suspended (the surface is
+/// destroyed on mobile platforms) and rebuilds it on resume. Fonts live in iced's global
+/// font system and widget state in [`crate::cache::IcedCache`], so both survive the rebuild.
+pub fn handle_application_lifetime(
+    mut events: EventReader<ApplicationLifetime>,
+    props: Res<IcedResource>,
+    device: Option<Res<RenderDevice>>,
+    queue: Option<Res<RenderQueue>>,
+) {
+    let (Some(device), Some(queue)) = (device, queue) else {
+        return;
+    };
+    for event in events.read() {
+        let props = &mut *props.lock().unwrap();
+        match event {
+            ApplicationLifetime::Suspended => props.suspended = true,
+            ApplicationLifetime::Resumed if props.suspended => {
+                let format = props.format;
+                props.rebuild_backend(Some((device.wgpu_device(), &queue)), format);
+                props.suspended = false;
+            }
+            _ => {}
+        }
+    }
+}
+
+impl IcedPlugin {
+    /// The diagnostic counting the renderers holding GPU resources: `1` while the wgpu
+    /// renderer is alive, `0` once it's released after
+    /// [`IcedSettings::renderer_idle_timeout`] or when rendering on the CPU.
+    pub const LIVE_RENDERERS: DiagnosticPath = DiagnosticPath::const_new("iced/live_renderers");
+}
+
+/// Drops the wgpu renderer once no UI was drawn for [`IcedSettings::renderer_idle_timeout`].
+/// It's recreated by the next UI displayed, before drawing it.
+pub fn release_idle_renderer(
+    props: Res<IcedResource>,
+    settings: Res<IcedSettings>,
+    (device, queue): (Option<Res<RenderDevice>>, Option<Res<RenderQueue>>),
+    mut diagnostics: Diagnostics,
+) {
+    let props = &mut *props.lock().unwrap();
+    if let (Some(timeout), Some(device), Some(queue)) =
+        (settings.renderer_idle_timeout, device, queue)
+    {
+        if props.last_drawn.elapsed() >= timeout {
+            props.release_renderer(&device, &queue);
+        }
+    }
+    let live = matches!(props.renderer, crate::Renderer::Wgpu(_));
+    diagnostics.add_measurement(&IcedPlugin::LIVE_RENDERERS, || f64::from(u8::from(live)));
+}
+
+/// Where the UI is presented, e.g. a texture for a screen in the game world or an XR
+/// quad layer.
+///
+/// Offscreen targets get a viewport of their own size, at the
+/// [`IcedSettings::scale_factor`] or `1.0`. They're redrawn whenever a UI is, over what the
+/// cameras rendering to the same target drew, and cleared first when no camera does.
+/// Images need the [`RENDER_ATTACHMENT`](bevy_render::render_resource::TextureUsages)
+/// usage.
+///
+/// The integration with the target is left to the app or XR plugin: showing the texture,
+/// e.g. as an OpenXR quad layer, and mapping its input to the UI, e.g. by setting
+/// [`IcedCursorOverride`](crate::IcedCursorOverride) to where a controller points on it,
+/// in the texture's logical pixels. The UI is rendered once, so it isn't composited into
+/// each eye view of an XR camera.
+///
+/// Every UI is presented to the same target. See
+/// [`IcedTextureTarget`](crate::IcedTextureTarget) to display some of them into images,
+/// like the screens of the game world, while the others stay in the window.
+#[derive(Resource, Clone, Debug, Default, PartialEq)]
+pub enum IcedRenderTarget {
+    /// The primary window, over the cameras rendering to it.
+    #[default]
+    PrimaryWindow,
+    /// An image, e.g. used as the texture of a material.
+    Image(Handle<Image>),
+    /// A texture view managed outside of bevy, like the swapchain images of an XR runtime.
+    TextureView(ManualTextureViewHandle),
+}
+
+impl IcedRenderTarget {
+    fn is_offscreen(&self) -> bool {
+        *self != Self::PrimaryWindow
+    }
+
+    fn normalize(&self, primary_window: Option<Entity>) -> Option<NormalizedRenderTarget> {
+        match self {
+            Self::PrimaryWindow => RenderTarget::Window(WindowRef::Primary),
+            Self::Image(image) => RenderTarget::Image(image.clone()),
+            Self::TextureView(view) => RenderTarget::TextureView(*view),
+        }
+        .normalize(primary_window)
+    }
+}
+
+// The window the viewport was last computed from, as it was then.
+#[derive(Resource, Default)]
+pub struct ViewportWindow(Option<WindowState>);
+
+#[derive(Clone, Copy, PartialEq)]
+struct WindowState {
+    entity: Entity,
+    size: UVec2,
+    scale_factor: f64,
+}
+
+impl WindowState {
+    fn new(entity: Entity, window: &Window, scale_factor: Option<f64>, zoom: &IcedZoom) -> Self {
+        let scale_factor = scale_factor.unwrap_or_else(|| window.scale_factor().into());
+        Self {
+            entity,
+            size: UVec2::new(window.physical_width(), window.physical_height()),
+            scale_factor: scale_factor * zoom.0,
+        }
+    }
+
+    fn viewport(&self) -> Viewport {
+        Viewport::with_physical_size(Size::new(self.size.x, self.size.y), self.scale_factor)
+    }
+}
+
 pub fn update_viewport(
-    windows: Query<&Window>,
-    iced_settings: Res<IcedSettings>,
-    mut commands: Commands,
+    windows: Query<(Entity, &Window), With<PrimaryWindow>>,
+    (appearance, zoom): (Appearance, Res<IcedZoom>),
+    target: Res<IcedRenderTarget>,
+    images: Option<Res<Assets<Image>>>,
+    texture_views: Option<Res<ManualTextureViews>>,
+    mut viewport: ResMut<ViewportResource>,
+    mut viewport_window: ResMut<ViewportWindow>,
 ) {
-    let window = windows.single();
-    let scale_factor = iced_settings
-        .scale_factor
-        .unwrap_or_else(|| window.scale_factor().into());
-    let viewport = Viewport::with_physical_size(
-        Size::new(window.physical_width(), window.physical_height()),
-        scale_factor,
-    );
-    commands.insert_resource(ViewportResource(viewport));
+    if target.is_offscreen() {
+        let size = match &*target {
+            IcedRenderTarget::Image(image) => {
+                images.and_then(|images| Some(images.get(image)?.size()))
+            }
+            IcedRenderTarget::TextureView(view) => {
+                texture_views.and_then(|views| Some(views.get(view)?.size))
+            }
+            IcedRenderTarget::PrimaryWindow => None,
+        };
+        // The viewport is kept until the texture is loaded.
+        if let Some(size) = size {
+            viewport.0 = Viewport::with_physical_size(
+                Size::new(size.x, size.y),
+                appearance.settings().scale_factor.unwrap_or(1.0),
+            );
+        }
+        return;
+    }
+    let Ok((entity, window)) = windows.get_single() else {
+        return;
+    };
+    let state = WindowState::new(entity, window, appearance.scale_factor(), &zoom);
+    viewport.0 = state.viewport();
+    viewport_window.0 = Some(state);
+}
+
+/// The viewport as seen by UI systems.
+#[derive(SystemParam)]
+pub struct PrimaryViewport<'w, 's> {
+    viewport: ResMut<'w, ViewportResource>,
+    window: ResMut<'w, ViewportWindow>,
+    target: Res<'w, IcedRenderTarget>,
+    zoom: Res<'w, IcedZoom>,
+    #[cfg_attr(not(feature = "bevy_ui"), allow(dead_code))]
+    nodes: UiNodes<'w, 's>,
+    images: Option<Res<'w, Assets<Image>>>,
+    textures: Query<'w, 's, &'static IcedTextureTarget>,
 }
 
-// Same as DidDraw, but as a regular bool instead of an atomic.
-#[derive(Resource, Deref, DerefMut)]
-struct DidDrawBasic(bool);
+impl PrimaryViewport<'_, '_> {
+    /// Whether the UI is presented to a texture rather than the window.
+    pub fn is_offscreen(&self) -> bool {
+        self.target.is_offscreen()
+    }
+
+    // The window may have been resized, or replaced by another primary window, since
+    // `update_viewport` ran, e.g. by a system earlier in `Update`. The UI is then laid out
+    // for the window it's presented to rather than a frame late.
+    pub fn get(&mut self, window: Option<(Entity, &Window)>, appearance: &Appearance) -> &Viewport {
+        let window = window.filter(|_| !self.is_offscreen());
+        if let Some((entity, window)) = window {
+            let state = WindowState::new(entity, window, appearance.scale_factor(), &self.zoom);
+            if self.window.0 != Some(state) {
+                self.viewport.0 = state.viewport();
+                self.window.0 = Some(state);
+            }
+        }
+        &self.viewport
+    }
+
+    // The image of a texture target, with a viewport of its size and the cursor over it.
+    // `None` until the image is loaded.
+    pub fn texture(&self, target: Entity) -> Option<(AssetId<Image>, Viewport, Cursor)> {
+        let target = self.textures.get(target).ok()?;
+        let size = self.images.as_ref()?.get(&target.image)?.size();
+        if size.x == 0 || size.y == 0 {
+            return None;
+        }
+        let viewport = Viewport::with_physical_size(Size::new(size.x, size.y), target.scale_factor);
+        let cursor = target.cursor.map_or(Cursor::Unavailable, |cursor| {
+            Cursor::Available(iced_core::Point::new(cursor.x, cursor.y))
+        });
+        Some((target.image.id(), viewport, cursor))
+    }
 
+    // The bounds of a bevy_ui node in the viewport's logical pixels, see
+    // `DisplayOptions::node`.
+    #[cfg(feature = "bevy_ui")]
+    pub fn node_bounds(&self, node: Entity, window: &Window) -> Option<iced_core::Rectangle> {
+        let rect = self.nodes.physical_rect(node, window)?;
+        Some(crate::coordinates::to_iced_logical_rect(
+            rect,
+            &self.viewport,
+        ))
+    }
+}
+
+// Whether the UI drawn in the main world still has to be presented. Unlike DidDraw, it's
+// only cleared once the pass presented it, so a frame the pass skips doesn't lose the UI.
+#[derive(Resource, Deref, Default)]
+pub struct PendingPresent(AtomicBool);
+
+#[derive(Resource)]
+struct ExtractedComposite {
+    opacity: f32,
+    render_scale: f32,
+    target: IcedRenderTarget,
+}
+
+// The texture the UI is presented to.
+struct Surface<'a> {
+    // The target of the cameras drawing under the UI.
+    target: NormalizedRenderTarget,
+    view: Option<&'a TextureView>,
+    size: UVec2,
+    format: Option<TextureFormat>,
+}
+
+impl<'a> Surface<'a> {
+    fn find(world: &'a World, target: &IcedRenderTarget) -> Option<Self> {
+        let windows = world.get_resource::<ExtractedWindows>();
+        let target = target.normalize(windows.and_then(|windows| windows.primary))?;
+        let (view, size, format) = match &target {
+            NormalizedRenderTarget::Window(window) => {
+                let window = windows?.get(&window.entity())?;
+                (
+                    window.swap_chain_texture_view.as_deref(),
+                    UVec2::new(window.physical_width, window.physical_height),
+                    window.swap_chain_texture_format,
+                )
+            }
+            NormalizedRenderTarget::Image(image) => {
+                let image = world.get_resource::<RenderAssets<Image>>()?.get(image)?;
+                if !image
+                    .texture
+                    .usage()
+                    .contains(TextureUsages::RENDER_ATTACHMENT)
+                {
+                    warn_once!("The image Iced is rendered to lacks the RENDER_ATTACHMENT usage");
+                    return None;
+                }
+                (
+                    Some(&*image.texture_view),
+                    image.size.as_uvec2(),
+                    Some(image.texture_format),
+                )
+            }
+            NormalizedRenderTarget::TextureView(view) => {
+                let view = world.get_resource::<ManualTextureViews>()?.get(view)?;
+                (Some(&*view.texture_view), view.size, Some(view.format))
+            }
+        };
+        Some(Self {
+            target,
+            view,
+            size,
+            format,
+        })
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn extract_iced_data(
     mut commands: Commands,
-    viewport: Extract<Res<ViewportResource>>,
     did_draw: Extract<Res<DidDraw>>,
+    settings: Extract<Res<IcedSettings>>,
+    (enabled, target): (Extract<Res<IcedEnabled>>, Extract<Res<IcedRenderTarget>>),
+    pending: Res<PendingPresent>,
+    props: Res<IcedResource>,
+    (mut backdrops, textures): (ResMut<ExtractedBackdrops>, Res<ExtractedTextures>),
+    images: Extract<Option<Res<Assets<Image>>>>,
 ) {
-    commands.insert_resource(viewport.clone());
-    commands.insert_resource(DidDrawBasic(
-        did_draw.swap(false, std::sync::atomic::Ordering::Relaxed),
-    ));
+    // A disabled UI is skipped like a fully transparent one.
+    let opacity = if enabled.0 { settings.opacity } else { 0.0 };
+    commands.insert_resource(ExtractedComposite {
+        opacity: opacity.clamp(0.0, 1.0),
+        render_scale: settings.render_scale.clamp(0.0, 1.0),
+        target: target.clone(),
+    });
+    // The blurs belong to the UI they were requested with, so they're kept along with its
+    // primitives until the next UI is drawn.
+    let props = &mut *props.lock().unwrap();
+    let blurs = std::mem::take(&mut props.backdrops);
+    let extracted = &mut *textures.0.lock().unwrap();
+    for texture in std::mem::take(&mut props.texture_layers) {
+        crate::texture::push(extracted, texture);
+    }
+    // The layers waiting for an image to get to the GPU are dropped with the image.
+    if let Some(images) = &*images {
+        extracted.retain(|texture| images.contains(texture.image));
+    }
+    if did_draw.swap(false, Ordering::Relaxed) {
+        pending.store(true, Ordering::Relaxed);
+        backdrops.blurs = blurs;
+        backdrops.scale_factor = props.drawn_scale_factor;
+    }
 }
 
 pub struct IcedNode {
-    staging_belt: Mutex<StagingBelt>,
+    fade: Mutex<Option<Fade>>,
+    backdrop: Mutex<Option<Backdrop>>,
+    software: Mutex<Software>,
+    textures: Mutex<TextureBackends>,
+    cameras: Option<QueryState<(&'static ExtractedCamera, &'static ViewTarget)>>,
 }
 
 impl IcedNode {
     pub fn new() -> Self {
         Self {
-            staging_belt: Mutex::new(StagingBelt::new(5 * 1024)),
+            fade: Mutex::new(None),
+            backdrop: Mutex::new(None),
+            software: Mutex::new(Software::default()),
+            textures: Mutex::new(TextureBackends::default()),
+            cameras: None,
         }
     }
 }
 
 impl Node for IcedNode {
-    fn update(&mut self, _world: &mut World) {
-        self.staging_belt.lock().unwrap().recall();
+    fn update(&mut self, world: &mut World) {
+        self.cameras
+            .get_or_insert_with(|| world.query())
+            .update_archetypes(world);
     }
 
     fn run(
@@ -86,48 +409,223 @@ impl Node for IcedNode {
         render_context: &mut RenderContext,
         world: &World,
     ) -> Result<(), NodeRunError> {
-        let Some(extracted_window) = world
-            .get_resource::<ExtractedWindows>()
-            .unwrap()
-            .windows
-            .values()
-            .next()
-        else {
+        let props = &mut *world.resource::<IcedResource>().lock().unwrap();
+        if props.suspended {
             return Ok(());
-        };
+        }
+        let render_device = world.resource::<RenderDevice>().wgpu_device();
+        let render_queue = world.resource::<RenderQueue>();
+        // The UIs displayed into images are presented whether or not a window is there.
+        if let Some((textures, images)) = world
+            .get_resource::<ExtractedTextures>()
+            .zip(world.get_resource::<RenderAssets<Image>>())
+        {
+            self.textures.lock().unwrap().present(
+                props,
+                textures,
+                images,
+                (render_device, render_queue),
+                render_context.command_encoder(),
+            );
+        }
 
-        let IcedProps {
-            renderer, debug, ..
-        } = &mut *world.resource::<IcedResource>().lock().unwrap();
-        let crate::Renderer::Wgpu(renderer) = renderer else {
+        let composite = world.get_resource::<ExtractedComposite>();
+        let target = composite.map_or(&IcedRenderTarget::PrimaryWindow, |x| &x.target);
+        // The primary window may have been closed since the UI was drawn, or other windows,
+        // like short-lived popups, spawned and closed around it. Images may still load.
+        let Some(surface) = Surface::find(world, target) else {
             return Ok(());
         };
-        let render_device = world.resource::<RenderDevice>().wgpu_device();
-        let render_queue = world.resource::<RenderQueue>();
-        let viewport = world.resource::<ViewportResource>();
+        // Present at the scale the primitives were drawn at: when the scale factor changes,
+        // e.g. while moving the window to another monitor, the last UI may have been drawn
+        // before the change.
+        let viewport = &Viewport::with_physical_size(
+            Size::new(surface.size.x, surface.size.y),
+            props.drawn_scale_factor,
+        );
+
+        let surface_format = surface.format.unwrap_or(props.format);
+        props.adopt_surface_format(Some((render_device, render_queue)), surface_format);
+
+        // Glyphs requested with `IcedContext::prewarm_text` are rasterized whether or not
+        // a UI is presented.
+        if let Some(prewarm) = props.prewarm.take_pending() {
+            let start = std::time::Instant::now();
+            if let crate::Renderer::Wgpu(renderer) = &mut props.renderer {
+                let format = props.format;
+                renderer.with_primitives(|backend, _| {
+                    prewarm.rasterize(
+                        backend,
+                        render_device,
+                        render_queue,
+                        render_context.command_encoder(),
+                        format,
+                    );
+                });
+            }
+            props.prewarm.finish(prewarm.spent() + start.elapsed());
+        }
 
-        if !world.get_resource::<DidDrawBasic>().is_some_and(|x| x.0) {
+        let Some(pending) = world
+            .get_resource::<PendingPresent>()
+            .filter(|pending| pending.load(Ordering::Relaxed))
+        else {
+            return Ok(());
+        };
+        // Minimized windows may have no swap chain texture, or one without any pixels.
+        let Some(view) = surface
+            .view
+            .filter(|_| surface.size.cmpgt(UVec2::ZERO).all())
+        else {
             return Ok(());
+        };
+        // The renderer keeps the primitives of the last UI drawn, so they can still be
+        // presented on a later frame, even if no UI is drawn in between.
+        pending.store(false, Ordering::Relaxed);
+        let (opacity, render_scale) = composite.map_or((1.0, 1.0), |x| (x.opacity, x.render_scale));
+        let cameras: Vec<_> = self.cameras.as_ref().map_or(Vec::new(), |cameras| {
+            cameras
+                .iter_manual(world)
+                .filter(|(camera, _)| camera.target.as_ref() == Some(&surface.target))
+                .collect()
+        });
+        let encoder = render_context.command_encoder();
+        // A texture no camera renders to still holds the last frame's UI.
+        if cameras.is_empty() && target.is_offscreen() {
+            clear(encoder, view);
         }
-        let view = extracted_window.swap_chain_texture_view.as_ref().unwrap();
-        let staging_belt = &mut *self.staging_belt.lock().unwrap();
+        if opacity <= 0.0 {
+            return Ok(());
+        }
+        let IcedProps {
+            renderer,
+            debug,
+            format,
+            ..
+        } = props;
 
-        renderer.with_primitives(|backend, primitives| {
-            backend.present(
+        // The scene is read from the last camera rendering to the surface, as the surface
+        // itself can't be sampled.
+        let backdrops = world
+            .get_resource::<ExtractedBackdrops>()
+            .filter(|backdrops| !backdrops.blurs.is_empty());
+        let scene = backdrops.and_then(|_| {
+            cameras
+                .iter()
+                .max_by_key(|(camera, _)| camera.order)
+                .map(|(camera, target)| {
+                    let (position, size) = camera
+                        .viewport
+                        .as_ref()
+                        .map_or((UVec2::ZERO, surface.size), |viewport| {
+                            (viewport.physical_position, viewport.physical_size)
+                        });
+                    Scene {
+                        view: target.main_texture_view(),
+                        position,
+                        size,
+                    }
+                })
+        });
+
+        if let Some((backdrops, scene)) = backdrops.zip(scene) {
+            let backdrop = &mut *self.backdrop.lock().unwrap();
+            if backdrop.as_ref().map(Backdrop::format) != Some(*format) {
+                *backdrop = Some(Backdrop::new(render_device, *format));
+            }
+            backdrop.as_mut().unwrap().draw(
                 render_device,
                 render_queue,
-                render_context.command_encoder(),
-                None,
-                TEXTURE_FMT,
+                encoder,
                 view,
-                primitives,
-                viewport,
-                &debug.overlay(),
+                Size::new(surface.size.x, surface.size.y),
+                scene,
+                backdrops,
             );
-        });
+        }
 
-        staging_belt.finish();
+        match renderer {
+            crate::Renderer::Wgpu(renderer) if opacity >= 1.0 && render_scale >= 1.0 => {
+                // iced_wgpu uploads the primitives with `Queue::write_buffer`, which wgpu
+                // submits ahead of the frame's command buffers, so the node only records the
+                // draws. Its backend doesn't expose the uploads separately from `present`.
+                renderer.with_primitives(|backend, primitives| {
+                    backend.present(
+                        render_device,
+                        render_queue,
+                        encoder,
+                        None,
+                        *format,
+                        view,
+                        primitives,
+                        viewport,
+                        &debug.overlay(),
+                    );
+                });
+            }
+            renderer => {
+                let fade = &mut *self.fade.lock().unwrap();
+                if fade.as_ref().map(Fade::format) != Some(*format) {
+                    *fade = Some(Fade::new(render_device, *format));
+                }
+                let fade = fade.as_mut().unwrap();
+                // A reduced resolution rasterizes the same layout at a smaller scale, and
+                // the composite stretches it back over the surface.
+                let size = viewport.physical_size();
+                let scaled = |length: u32| ((length as f32 * render_scale).round() as u32).max(1);
+                let size = Size::new(scaled(size.width), scaled(size.height));
+                let scaled_viewport = Viewport::with_physical_size(
+                    size,
+                    viewport.scale_factor() * size.width as f64 / viewport.physical_width() as f64,
+                );
+                match renderer {
+                    crate::Renderer::Wgpu(renderer) => {
+                        let target = fade.target(render_device, size);
+                        renderer.with_primitives(|backend, primitives| {
+                            backend.present(
+                                render_device,
+                                render_queue,
+                                encoder,
+                                Some(iced_core::Color::TRANSPARENT),
+                                *format,
+                                target,
+                                primitives,
+                                &scaled_viewport,
+                                &debug.overlay(),
+                            );
+                        });
+                    }
+                    // Without a working wgpu renderer, the UI is rendered on the CPU.
+                    crate::Renderer::TinySkia(renderer) => {
+                        let software = &mut *self.software.lock().unwrap();
+                        let pixels = software.draw(renderer, &scaled_viewport, &debug.overlay());
+                        if pixels.is_empty() {
+                            return Ok(());
+                        }
+                        fade.upload(render_device, render_queue, size, pixels);
+                    }
+                }
+                fade.composite(render_queue, encoder, view, opacity);
+            }
+        }
 
         Ok(())
     }
 }
+
+fn clear(encoder: &mut CommandEncoder, view: &TextureView) {
+    encoder.begin_render_pass(&RenderPassDescriptor {
+        label: Some("bevy_iced.clear_pass"),
+        color_attachments: &[Some(RenderPassColorAttachment {
+            view,
+            resolve_target: None,
+            ops: Operations {
+                load: LoadOp::Clear(wgpu::Color::TRANSPARENT),
+                store: StoreOp::Store,
+            },
+        })],
+        depth_stencil_attachment: None,
+        timestamp_writes: None,
+        occlusion_query_set: None,
+    });
+}
@@ -0,0 +1,56 @@
+use std::any::Any;
+
+use iced_widget::graphics::Primitive;
+
+use crate::modal::Layer;
+use crate::Renderer;
+
+// The primitives of the UIs of a frame, presented again while the window isn't focused.
+pub struct Retained {
+    layer: Layer,
+    // Text primitives only refer to the text shaped by their widgets, which may reshape
+    // it in the meantime, so it's kept alive along with them.
+    _text: Vec<Box<dyn Any + Send + Sync>>,
+}
+
+impl Retained {
+    // Keep the primitives recorded so far, leaving them in the renderer.
+    pub fn copy(renderer: &mut Renderer) -> Self {
+        let layer = Layer::copy(renderer);
+        let mut text = Vec::new();
+        match &layer {
+            Layer::Wgpu(primitives) => primitives.iter().for_each(|p| keep_text(p, &mut text)),
+            Layer::TinySkia(primitives) => {
+                primitives.iter().for_each(|p| keep_text(p, &mut text));
+            }
+        }
+        Self { layer, _text: text }
+    }
+
+    // Replace the primitives recorded so far with the retained ones.
+    pub fn present(&self, renderer: &mut Renderer) {
+        Layer::take(renderer);
+        self.layer.clone().draw(renderer);
+    }
+}
+
+fn keep_text<T>(primitive: &Primitive<T>, text: &mut Vec<Box<dyn Any + Send + Sync>>) {
+    match primitive {
+        Primitive::Paragraph { paragraph, .. } => {
+            if let Some(paragraph) = paragraph.upgrade() {
+                text.push(Box::new(paragraph));
+            }
+        }
+        Primitive::Editor { editor, .. } => {
+            if let Some(editor) = editor.upgrade() {
+                text.push(Box::new(editor));
+            }
+        }
+        Primitive::Group { primitives } => primitives.iter().for_each(|p| keep_text(p, text)),
+        Primitive::Clip { content, .. } | Primitive::Transform { content, .. } => {
+            keep_text(content, text);
+        }
+        Primitive::Cache { content } => keep_text(content, text),
+        _ => {}
+    }
+}
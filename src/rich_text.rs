@@ -0,0 +1,493 @@
+use std::borrow::Cow;
+
+use bevy_utils::HashMap;
+use iced_core::alignment;
+use iced_core::event::{self, Event};
+use iced_core::layout::{self, Layout};
+use iced_core::mouse;
+use iced_core::renderer;
+use iced_core::text::{self, LineHeight, Paragraph as _, Shaping};
+use iced_core::touch;
+use iced_core::widget::{tree, Tree};
+use iced_core::{
+    Clipboard, Color, Element, Font, Length, Pixels, Point, Rectangle, Shell, Size, Vector, Widget,
+};
+use iced_widget::style::Theme;
+use unicode_linebreak::BreakOpportunity;
+
+/// A run of text within a [`RichText`], see [`span`].
+#[allow(missing_debug_implementations)]
+pub struct Span<'a, Message> {
+    text: Cow<'a, str>,
+    font: Option<Font>,
+    size: Option<Pixels>,
+    color: Option<Color>,
+    link: Option<Message>,
+}
+
+/// A run of `text` to lay out with others in a [`rich_text`], in the paragraph's font,
+/// size and color unless the span sets its own.
+pub fn span<'a, Message>(text: impl Into<Cow<'a, str>>) -> Span<'a, Message> {
+    Span {
+        text: text.into(),
+        font: None,
+        size: None,
+        color: None,
+        link: None,
+    }
+}
+
+impl<'a, Message> Span<'a, Message> {
+    /// Set the font of the span, e.g. a bold weight or an icon font.
+    #[must_use]
+    pub fn font(mut self, font: impl Into<Font>) -> Self {
+        self.font = Some(font.into());
+        self
+    }
+
+    /// Set the text size of the span.
+    #[must_use]
+    pub fn size(mut self, size: impl Into<Pixels>) -> Self {
+        self.size = Some(size.into());
+        self
+    }
+
+    /// Set the color of the span.
+    #[must_use]
+    pub fn color(mut self, color: impl Into<Color>) -> Self {
+        self.color = Some(color.into());
+        self
+    }
+
+    /// Make the span a link, which is underlined, shows a pointer cursor and produces
+    /// `message` when clicked or tapped. Links are drawn in the theme's primary color
+    /// unless the span has its own.
+    #[must_use]
+    pub fn link(mut self, message: Message) -> Self {
+        self.link = Some(message);
+        self
+    }
+}
+
+impl<'a, Message> From<&'a str> for Span<'a, Message> {
+    fn from(text: &'a str) -> Self {
+        span(text)
+    }
+}
+
+impl<'a, Message> From<String> for Span<'a, Message> {
+    fn from(text: String) -> Self {
+        span(text)
+    }
+}
+
+/// A paragraph of differently styled spans, see [`rich_text`].
+#[allow(missing_debug_implementations)]
+pub struct RichText<'a, Message> {
+    spans: Vec<Span<'a, Message>>,
+    font: Option<Font>,
+    size: Option<Pixels>,
+    line_height: LineHeight,
+    width: Length,
+}
+
+/// A paragraph of `spans`, wrapped as one text, e.g. for chat lines with colored player
+/// names or dialogue with bold keywords and inline icons from an icon font.
+///
+/// Lines only break where the whole text allows it, so a word split across spans stays
+/// together, and at every newline. Each fragment is shaped on its own, which keeps the
+/// renderer's font fallback for emoji and CJK within spans but not ligatures across
+/// span boundaries. It's as wide as its longest line, up to the width it's given, so a
+/// chat bubble fits its message.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_iced::iced::Color;
+/// # use bevy_iced::widget::{rich_text, span};
+/// # use bevy_iced::IcedContext;
+/// # #[derive(Event, Clone)]
+/// # enum UiMessage {
+/// #     Whisper(u64),
+/// # }
+/// # struct Player {
+/// #     id: u64,
+/// #     name: String,
+/// #     color: Color,
+/// # }
+/// # struct Line {
+/// #     text: String,
+/// # }
+/// # fn ui_system(mut ctx: IcedContext<UiMessage>, player: Player, line: Line) {
+/// ctx.display(rich_text([
+///     span(&player.name).color(player.color).link(UiMessage::Whisper(player.id)),
+///     span(": "),
+///     span(&line.text),
+/// ]));
+/// # }
+/// ```
+pub fn rich_text<'a, Message>(
+    spans: impl IntoIterator<Item = Span<'a, Message>>,
+) -> RichText<'a, Message> {
+    RichText {
+        spans: spans.into_iter().collect(),
+        font: None,
+        size: None,
+        line_height: LineHeight::default(),
+        width: Length::Shrink,
+    }
+}
+
+impl<'a, Message> RichText<'a, Message> {
+    /// Set the font of the spans that don't set their own.
+    #[must_use]
+    pub fn font(mut self, font: impl Into<Font>) -> Self {
+        self.font = Some(font.into());
+        self
+    }
+
+    /// Set the text size of the spans that don't set their own.
+    #[must_use]
+    pub fn size(mut self, size: impl Into<Pixels>) -> Self {
+        self.size = Some(size.into());
+        self
+    }
+
+    /// Set the line height, relative to the text size of every span.
+    #[must_use]
+    pub fn line_height(mut self, line_height: impl Into<LineHeight>) -> Self {
+        self.line_height = line_height.into();
+        self
+    }
+
+    /// Set the width of the paragraph, which it wraps within.
+    #[must_use]
+    pub fn width(mut self, width: impl Into<Length>) -> Self {
+        self.width = width.into();
+        self
+    }
+
+    // The fragments of the spans between the places where lines may break, and where
+    // one span ends and the next begins.
+    fn fragments<Renderer>(&self, renderer: &Renderer) -> Vec<Fragment>
+    where
+        Renderer: text::Renderer<Font = Font>,
+    {
+        let text: String = self.spans.iter().map(|span| &*span.text).collect();
+        let mut span_ends = self.spans.iter().scan(0, |end, span| {
+            *end += span.text.len();
+            Some(*end)
+        });
+        let mut span_end = span_ends.next().unwrap_or(0);
+        let mut span = 0;
+        let mut fragments = Vec::new();
+        let mut start = 0;
+        for (end, opportunity) in unicode_linebreak::linebreaks(&text) {
+            while start < end {
+                while span_end <= start {
+                    span += 1;
+                    span_end = span_ends.next().unwrap_or(text.len());
+                }
+                let fragment_end = end.min(span_end);
+                let style = &self.spans[span];
+                fragments.push(Fragment {
+                    span,
+                    text: text[start..fragment_end].to_owned(),
+                    font: style.font.or(self.font).unwrap_or(renderer.default_font()),
+                    size: style.size.or(self.size).unwrap_or(renderer.default_size()),
+                    breaks: (fragment_end == end).then_some(opportunity),
+                });
+                start = fragment_end;
+            }
+        }
+        fragments
+    }
+}
+
+#[derive(Clone, PartialEq)]
+struct Fragment {
+    span: usize,
+    text: String,
+    font: Font,
+    size: Pixels,
+    // Whether a line may or must break after it.
+    breaks: Option<BreakOpportunity>,
+}
+
+// A fragment shaped without its trailing whitespace, which only takes space within lines.
+struct Shaped<P> {
+    fragment: Fragment,
+    paragraph: P,
+    trailing: f32,
+    position: Point,
+}
+
+struct State<P> {
+    shaped: Vec<Shaped<P>>,
+    line_height: LineHeight,
+}
+
+impl<P> Default for State<P> {
+    fn default() -> Self {
+        Self {
+            shaped: Vec::new(),
+            line_height: LineHeight::default(),
+        }
+    }
+}
+
+impl<P: text::Paragraph<Font = Font>> State<P> {
+    // Shape the fragments again if they changed.
+    fn update(&mut self, fragments: Vec<Fragment>, line_height: LineHeight) {
+        let unchanged = self.line_height == line_height
+            && self.shaped.len() == fragments.len()
+            && self
+                .shaped
+                .iter()
+                .zip(&fragments)
+                .all(|(shaped, fragment)| shaped.fragment == *fragment);
+        if unchanged {
+            return;
+        }
+        let mut whitespace = HashMap::new();
+        self.line_height = line_height;
+        self.shaped = fragments
+            .into_iter()
+            .map(|fragment| {
+                let content = fragment.text.trim_end();
+                let spaces: String = fragment.text[content.len()..]
+                    .chars()
+                    .filter(|c| !matches!(c, '\n' | '\r'))
+                    .collect();
+                let shape = |content: &str| {
+                    P::with_text(text::Text {
+                        content,
+                        bounds: Size::INFINITY,
+                        size: fragment.size,
+                        line_height,
+                        font: fragment.font,
+                        horizontal_alignment: alignment::Horizontal::Left,
+                        vertical_alignment: alignment::Vertical::Top,
+                        shaping: Shaping::Advanced,
+                    })
+                };
+                let trailing = if spaces.is_empty() {
+                    0.0
+                } else {
+                    let key = (spaces, fragment.font, fragment.size.0.to_bits());
+                    *whitespace
+                        .entry(key)
+                        .or_insert_with_key(|(spaces, ..)| shape(spaces).min_bounds().width)
+                };
+                Shaped {
+                    paragraph: shape(content),
+                    fragment,
+                    trailing,
+                    position: Point::ORIGIN,
+                }
+            })
+            .collect();
+    }
+
+    // Place the fragments in lines no wider than `max_width`, returning the size they
+    // take.
+    fn flow(&mut self, max_width: f32) -> Size {
+        let line_height = self.line_height;
+        let mut size = Size::ZERO;
+        let mut line = 0;
+        let mut x: f32 = 0.0;
+        let finish = |shaped: &mut [Shaped<P>], x: f32, size: &mut Size| {
+            let height = shaped
+                .iter()
+                .map(|shaped| line_height.to_absolute(shaped.fragment.size).0)
+                .fold(0.0, f32::max);
+            // Fragments of different sizes share their bottom, close to a baseline.
+            for shaped in shaped.iter_mut() {
+                let own = line_height.to_absolute(shaped.fragment.size).0;
+                shaped.position.y = size.height + height - own;
+            }
+            let trailing = shaped.last().map_or(0.0, |shaped| shaped.trailing);
+            size.width = size.width.max(x - trailing);
+            size.height += height;
+        };
+        let mut start = 0;
+        while start < self.shaped.len() {
+            // The fragments up to the next place a line may break stay together.
+            let end = self.shaped[start..]
+                .iter()
+                .position(|shaped| shaped.fragment.breaks.is_some())
+                .map_or(self.shaped.len(), |i| start + i + 1);
+            let unit = &self.shaped[start..end];
+            let width: f32 = unit.iter().map(Shaped::advance).sum();
+            let fits = width - unit.last().map_or(0.0, |shaped| shaped.trailing);
+            if x > 0.0 && x + fits > max_width {
+                finish(&mut self.shaped[line..start], x, &mut size);
+                line = start;
+                x = 0.0;
+            }
+            for shaped in &mut self.shaped[start..end] {
+                shaped.position.x = x;
+                x += shaped.advance();
+            }
+            if self.shaped[end - 1].fragment.breaks == Some(BreakOpportunity::Mandatory) {
+                finish(&mut self.shaped[line..end], x, &mut size);
+                line = end;
+                x = 0.0;
+            }
+            start = end;
+        }
+        if line < self.shaped.len() {
+            finish(&mut self.shaped[line..], x, &mut size);
+        }
+        size
+    }
+
+    // The fragment under the cursor that links somewhere.
+    fn link_at(&self, bounds: Rectangle, cursor: mouse::Cursor) -> Option<usize> {
+        let position = cursor.position_in(bounds)?;
+        self.shaped
+            .iter()
+            .find(|shaped| shaped.bounds().contains(position))
+            .map(|shaped| shaped.fragment.span)
+    }
+}
+
+impl<P: text::Paragraph> Shaped<P> {
+    fn advance(&self) -> f32 {
+        self.paragraph.min_bounds().width + self.trailing
+    }
+
+    fn bounds(&self) -> Rectangle {
+        Rectangle::new(self.position, self.paragraph.min_bounds())
+    }
+}
+
+impl<'a, Message, Renderer> Widget<Message, Theme, Renderer> for RichText<'a, Message>
+where
+    Message: Clone,
+    Renderer: text::Renderer<Font = Font>,
+{
+    fn tag(&self) -> tree::Tag {
+        tree::Tag::of::<State<Renderer::Paragraph>>()
+    }
+
+    fn state(&self) -> tree::State {
+        tree::State::new(State::<Renderer::Paragraph>::default())
+    }
+
+    fn size(&self) -> Size<Length> {
+        Size::new(self.width, Length::Shrink)
+    }
+
+    fn layout(
+        &self,
+        tree: &mut Tree,
+        renderer: &Renderer,
+        limits: &layout::Limits,
+    ) -> layout::Node {
+        let state = tree.state.downcast_mut::<State<Renderer::Paragraph>>();
+        state.update(self.fragments(renderer), self.line_height);
+        let limits = limits.width(self.width);
+        let size = state.flow(limits.max().width);
+        layout::Node::new(limits.resolve(self.width, Length::Shrink, size))
+    }
+
+    fn on_event(
+        &mut self,
+        tree: &mut Tree,
+        event: Event,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        _renderer: &Renderer,
+        _clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+        _viewport: &Rectangle,
+    ) -> event::Status {
+        let cursor = match event {
+            Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) => cursor,
+            Event::Touch(touch::Event::FingerPressed { position, .. }) => {
+                mouse::Cursor::Available(position)
+            }
+            _ => return event::Status::Ignored,
+        };
+        let state = tree.state.downcast_ref::<State<Renderer::Paragraph>>();
+        let link = state
+            .link_at(layout.bounds(), cursor)
+            .and_then(|span| self.spans[span].link.clone());
+        match link {
+            Some(message) => {
+                shell.publish(message);
+                event::Status::Captured
+            }
+            None => event::Status::Ignored,
+        }
+    }
+
+    fn mouse_interaction(
+        &self,
+        tree: &Tree,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        _viewport: &Rectangle,
+        _renderer: &Renderer,
+    ) -> mouse::Interaction {
+        let state = tree.state.downcast_ref::<State<Renderer::Paragraph>>();
+        match state.link_at(layout.bounds(), cursor) {
+            Some(span) if self.spans[span].link.is_some() => mouse::Interaction::Pointer,
+            _ => mouse::Interaction::Idle,
+        }
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        _cursor: mouse::Cursor,
+        viewport: &Rectangle,
+    ) {
+        let state = tree.state.downcast_ref::<State<Renderer::Paragraph>>();
+        let bounds = layout.bounds();
+        let Some(clip) = bounds.intersection(viewport) else {
+            return;
+        };
+        let offset = Vector::new(bounds.x, bounds.y);
+        for shaped in &state.shaped {
+            let span = &self.spans[shaped.fragment.span];
+            let default = if span.link.is_some() {
+                theme.palette().primary
+            } else {
+                style.text_color
+            };
+            let color = span.color.unwrap_or(default);
+            let position = shaped.position + offset;
+            renderer.fill_paragraph(&shaped.paragraph, position, color, clip);
+            if span.link.is_some() {
+                let size = shaped.paragraph.min_bounds();
+                renderer.fill_quad(
+                    renderer::Quad {
+                        bounds: Rectangle {
+                            x: position.x,
+                            y: position.y + size.height - 2.0,
+                            width: size.width + shaped.trailing,
+                            height: 1.0,
+                        },
+                        ..Default::default()
+                    },
+                    color,
+                );
+            }
+        }
+    }
+}
+
+impl<'a, Message, Renderer> From<RichText<'a, Message>> for Element<'a, Message, Theme, Renderer>
+where
+    Message: 'a + Clone,
+    Renderer: 'a + text::Renderer<Font = Font>,
+{
+    fn from(rich_text: RichText<'a, Message>) -> Self {
+        Element::new(rich_text)
+    }
+}
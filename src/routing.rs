@@ -0,0 +1,120 @@
+use bevy_ecs::system::Res;
+use iced_core::event::Status;
+use iced_core::{mouse, touch, Event as IcedEvent, Point};
+
+use crate::hit::HitTargets;
+use crate::IcedResource;
+
+// Where a UI is in the frame's stack of UIs: by z-index, then in the order UIs are
+// displayed in, which is the order UIs of the same z-index are drawn in. Modal UIs are
+// above all the others.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Order {
+    z_index: i32,
+    ordinal: usize,
+}
+
+// Which of the frame's events the UIs see, from the top one down: an event captured by a
+// UI isn't fed to the UIs below it displayed later in the frame, and the keys only go to
+// the topmost UI with a focused widget last frame, and to the ones above it. After a
+// press, which moves the focus, the keys go to the UI pressed instead, as of last frame's
+// hit targets, or to every UI when none was. The pointer is routed with the hit targets
+// of last frame too, see `HitTargets::is_covered`.
+#[derive(Default)]
+pub struct Routing {
+    displayed: usize,
+    keyboard: Option<Order>,
+    next_keyboard: Option<Order>,
+    // The topmost UI that captured each event of `IcedEventQueue` so far, by index.
+    captured: Vec<Option<Order>>,
+}
+
+impl Routing {
+    // The place of the next UI displayed this frame.
+    pub fn next(&mut self, z_index: i32, modal: bool) -> Order {
+        let ordinal = self.displayed;
+        self.displayed += 1;
+        let z_index = if modal { i32::MAX } else { z_index };
+        Order { z_index, ordinal }
+    }
+
+    // The indices of the events in `events` the UI at `order` is fed, `cursor` being
+    // where the pointer is when the events don't say.
+    pub fn route(
+        &self,
+        events: &[IcedEvent],
+        order: Order,
+        hit_targets: &HitTargets,
+        mut cursor: Option<Point>,
+    ) -> Vec<usize> {
+        let mut keyboard = self.keyboard;
+        (0..events.len())
+            .filter(|&index| {
+                let event = &events[index];
+                match event {
+                    IcedEvent::Mouse(mouse::Event::CursorMoved { position }) => {
+                        cursor = Some(*position);
+                    }
+                    IcedEvent::Mouse(mouse::Event::ButtonPressed(_)) => {
+                        keyboard = cursor.and_then(|cursor| hit_targets.captor(cursor));
+                    }
+                    IcedEvent::Touch(touch::Event::FingerPressed { position, .. }) => {
+                        keyboard = hit_targets.captor(*position);
+                    }
+                    _ => {}
+                }
+                let captured_above = self
+                    .captured
+                    .get(index)
+                    .copied()
+                    .flatten()
+                    .is_some_and(|captor| captor > order);
+                let keys_above = keyboard.is_some_and(|owner| owner > order);
+                let is_key = matches!(event, IcedEvent::Keyboard(_));
+                !(is_routed(event) && captured_above || is_key && keys_above)
+            })
+            .collect()
+    }
+
+    // Record the events the UI at `order` captured, given the indices it was fed.
+    pub fn record(&mut self, order: Order, fed: &[usize], statuses: &[Status]) {
+        for (&index, status) in fed.iter().zip(statuses) {
+            if *status != Status::Captured {
+                continue;
+            }
+            if self.captured.len() <= index {
+                self.captured.resize(index + 1, None);
+            }
+            let captor = &mut self.captured[index];
+            *captor = (*captor).max(Some(order));
+        }
+    }
+
+    // A widget of the UI at `order` has the keyboard focus.
+    pub fn focus(&mut self, order: Order) {
+        self.next_keyboard = self.next_keyboard.max(Some(order));
+    }
+}
+
+// The events a UI capturing them takes from the UIs below. Cursor and window events,
+// like the redraws animating widgets, go to every UI.
+fn is_routed(event: &IcedEvent) -> bool {
+    match event {
+        IcedEvent::Keyboard(_) | IcedEvent::Touch(_) => true,
+        IcedEvent::Mouse(event) => matches!(
+            event,
+            mouse::Event::ButtonPressed(_)
+                | mouse::Event::ButtonReleased(_)
+                | mouse::Event::WheelScrolled { .. }
+        ),
+        IcedEvent::Window(..) => false,
+    }
+}
+
+// Start routing the new frame's events, the keys going to last frame's focused UI.
+pub fn begin_frame(props: Res<IcedResource>) {
+    let routing = &mut props.lock().unwrap().routing;
+    routing.displayed = 0;
+    routing.captured.clear();
+    routing.keyboard = routing.next_keyboard.take();
+}
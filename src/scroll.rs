@@ -0,0 +1,144 @@
+use std::collections::HashMap;
+
+use bevy_ecs::system::Res;
+use iced_core::widget::{self, operation, Operation};
+use iced_core::{Rectangle, Vector};
+use iced_runtime::user_interface::UserInterface;
+use iced_widget::scrollable::RelativeOffset;
+use iced_widget::style::Theme;
+
+use crate::{IcedResource, Renderer};
+
+// The offsets of the scrollables with an id, and the snaps waiting for their scrollable
+// to be displayed.
+#[derive(Default)]
+pub struct Scrolling {
+    // As of the UIs displayed last frame, and the ones displayed so far this frame.
+    offsets: HashMap<widget::Id, RelativeOffset>,
+    next_offsets: HashMap<widget::Id, RelativeOffset>,
+    snaps: Vec<(widget::Id, RelativeOffset)>,
+}
+
+impl Scrolling {
+    pub fn offset(&self, id: &widget::Id) -> Option<RelativeOffset> {
+        self.offsets.get(id).copied()
+    }
+
+    pub fn snap_to(&mut self, id: widget::Id, offset: RelativeOffset) {
+        self.snaps.retain(|(snap, _)| *snap != id);
+        self.snaps.push((id, offset));
+    }
+
+    // Apply the snaps to the scrollables of the UI, before it handles this frame's events.
+    pub fn apply_snaps<M>(
+        &mut self,
+        ui: &mut UserInterface<'_, M, Theme, Renderer>,
+        renderer: &Renderer,
+    ) {
+        if self.snaps.is_empty() {
+            return;
+        }
+        let mut snap = Snap(&mut self.snaps);
+        ui.operate(renderer, &mut snap);
+    }
+
+    // Record the offsets of the UI's scrollables, once it handled this frame's events.
+    pub fn collect<M>(
+        &mut self,
+        ui: &mut UserInterface<'_, M, Theme, Renderer>,
+        renderer: &Renderer,
+    ) {
+        let mut collect = CollectOffsets {
+            offsets: &mut self.next_offsets,
+            scrollable: None,
+            content: None,
+        };
+        ui.operate(renderer, &mut collect);
+    }
+}
+
+// Publish the offsets of this frame's UIs, once they've all been displayed.
+pub fn finish_frame(props: Res<IcedResource>) {
+    let scrolling = &mut props.lock().unwrap().scrolling;
+    scrolling.offsets = std::mem::take(&mut scrolling.next_offsets);
+}
+
+struct Snap<'a>(&'a mut Vec<(widget::Id, RelativeOffset)>);
+
+impl<T> Operation<T> for Snap<'_> {
+    fn container(
+        &mut self,
+        _id: Option<&widget::Id>,
+        _bounds: Rectangle,
+        operate_on_children: &mut dyn FnMut(&mut dyn Operation<T>),
+    ) {
+        if !self.0.is_empty() {
+            operate_on_children(self);
+        }
+    }
+
+    fn scrollable(
+        &mut self,
+        state: &mut dyn operation::Scrollable,
+        id: Option<&widget::Id>,
+        _bounds: Rectangle,
+        _translation: Vector,
+    ) {
+        let Some(id) = id else {
+            return;
+        };
+        if let Some(index) = self.0.iter().position(|(snap, _)| snap == id) {
+            state.snap_to(self.0.remove(index).1);
+        }
+    }
+}
+
+// Scrollables only report their visible bounds and absolute offset, then operate on
+// themselves as a container; the first container inside holds the bounds of the content.
+struct CollectOffsets<'a> {
+    offsets: &'a mut HashMap<widget::Id, RelativeOffset>,
+    // The scrollable that operates on itself next, and the one whose content does.
+    scrollable: Option<(widget::Id, Rectangle, Vector)>,
+    content: Option<(widget::Id, Rectangle, Vector)>,
+}
+
+impl<T> Operation<T> for CollectOffsets<'_> {
+    fn container(
+        &mut self,
+        _id: Option<&widget::Id>,
+        bounds: Rectangle,
+        operate_on_children: &mut dyn FnMut(&mut dyn Operation<T>),
+    ) {
+        if let Some((id, viewport, translation)) = self.content.take() {
+            let relative = |offset: f32, content: f32, viewport: f32| {
+                let range = content - viewport;
+                if range > 0.0 {
+                    (offset / range).clamp(0.0, 1.0)
+                } else {
+                    0.0
+                }
+            };
+            self.offsets.insert(
+                id,
+                RelativeOffset {
+                    x: relative(translation.x, bounds.width, viewport.width),
+                    y: relative(translation.y, bounds.height, viewport.height),
+                },
+            );
+        }
+        self.content = self.scrollable.take();
+        operate_on_children(self);
+        // Content that isn't laid out as a container can't be measured.
+        self.content = None;
+    }
+
+    fn scrollable(
+        &mut self,
+        _state: &mut dyn operation::Scrollable,
+        id: Option<&widget::Id>,
+        bounds: Rectangle,
+        translation: Vector,
+    ) {
+        self.scrollable = id.map(|id| (id.clone(), bounds, translation));
+    }
+}
@@ -0,0 +1,483 @@
+//! Capture the Iced layer as an image, for golden-image tests of UI screens.
+//!
+//! ```
+//! # use bevy::prelude::*;
+//! # use bevy_iced::harness::IcedTestHarness;
+//! # use bevy_iced::iced::widget::text;
+//! # use bevy_iced::snapshot::Snapshot;
+//! # use bevy_iced::IcedContext;
+//! # #[derive(Event)]
+//! # struct UiMessage;
+//! # let mut harness = IcedTestHarness::new(320.0, 240.0);
+//! # harness.app.add_event::<UiMessage>().add_systems(
+//! #     Update,
+//! #     |mut ctx: IcedContext<UiMessage>| ctx.display(text("Main menu")),
+//! # );
+//! # harness.run_frames(0);
+//! # let app = &mut harness.app;
+//! # let path = std::env::temp_dir().join("bevy_iced_menu.png");
+//! app.update(); // Run the UI system at least once
+//! let snapshot = Snapshot::capture(&app.world).unwrap();
+//! # snapshot.save_png(&path).unwrap();
+//! let expected = Snapshot::load_png(&path).unwrap();
+//! assert!(snapshot.matches(&expected, 8, 0.001));
+//! ```
+//!
+//! [`assert_ui_matches!`](crate::assert_ui_matches) wraps this for golden files, writing
+//! the actual and diff images next to the golden file when the comparison fails.
+//! Platforms rasterize text slightly differently, so pick tolerances per platform:
+//!
+//! ```
+//! # use bevy::prelude::*;
+//! # use bevy_iced::assert_ui_matches;
+//! # use bevy_iced::harness::IcedTestHarness;
+//! # use bevy_iced::iced::widget::text;
+//! # use bevy_iced::iced::Rectangle;
+//! # use bevy_iced::snapshot::{Snapshot, Tolerance};
+//! # use bevy_iced::IcedContext;
+//! # #[derive(Event)]
+//! # struct UiMessage;
+//! # let mut harness = IcedTestHarness::new(320.0, 240.0);
+//! # harness.app.add_event::<UiMessage>().add_systems(
+//! #     Update,
+//! #     |mut ctx: IcedContext<UiMessage>| ctx.display(text("Main menu")),
+//! # );
+//! # harness.run_frames(1);
+//! # let snapshot = Snapshot::capture(&harness.app.world).unwrap();
+//! # let golden = std::env::temp_dir().join("bevy_iced_golden_menu.png");
+//! # snapshot.save_png(&golden).unwrap();
+//! let tolerance = Tolerance {
+//!     max_diff_pixels: if cfg!(target_os = "linux") { 0 } else { 200 },
+//!     per_channel: 16,
+//!     // Ignore the FPS counter in the corner
+//!     masks: vec![Rectangle { x: 0, y: 0, width: 120, height: 30 }],
+//! };
+//! assert_ui_matches!(snapshot, &golden, tolerance);
+//! ```
+
+use std::fmt;
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+use std::path::Path;
+
+use bevy_ecs::world::World;
+use bevy_render::renderer::{RenderDevice, RenderQueue};
+use iced_core::{Color, Rectangle, Size};
+use iced_wgpu::wgpu;
+use iced_widget::graphics::Viewport;
+
+use crate::render::ViewportResource;
+use crate::software::Software;
+use crate::{IcedResource, Renderer};
+
+/// An image of the Iced layer, stored as RGBA8 rows with the alpha premultiplied
+/// the same way the renderer blends it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Snapshot {
+    /// The width of the image, in physical pixels.
+    pub width: u32,
+    /// The height of the image, in physical pixels.
+    pub height: u32,
+    /// The RGBA8 pixels of the image, row by row.
+    pub pixels: Vec<u8>,
+}
+
+/// How much two snapshots may differ while still being considered equal.
+///
+/// A mask may reach past the edges of the image, e.g. to ignore everything right of a
+/// column. Differences outside the masks still count:
+///
+/// ```
+/// # use bevy_iced::iced::Rectangle;
+/// # use bevy_iced::snapshot::{Snapshot, Tolerance};
+/// let blank = Snapshot { width: 40, height: 10, pixels: vec![0; 40 * 10 * 4] };
+/// let mut changed = blank.clone();
+/// // A pixel changed in a column at x = 30, and another at x = 5.
+/// changed.pixels[(3 * 40 + 30) * 4] = u8::MAX;
+/// changed.pixels[(3 * 40 + 5) * 4] = u8::MAX;
+///
+/// let tolerance = Tolerance {
+///     masks: vec![Rectangle { x: 10, y: 0, width: u32::MAX, height: u32::MAX }],
+///     ..Default::default()
+/// };
+/// let comparison = changed.compare(&blank, &tolerance).unwrap();
+/// assert_eq!(comparison.diff_pixels, 1);
+/// ```
+///
+/// So a golden-image assertion passing with changes in the masks fails on them:
+///
+/// ```should_panic
+/// # use bevy_iced::assert_ui_matches;
+/// # use bevy_iced::iced::Rectangle;
+/// # use bevy_iced::snapshot::{Snapshot, Tolerance};
+/// # let golden = std::env::temp_dir().join("bevy_iced_golden_masked.png");
+/// let blank = Snapshot { width: 40, height: 10, pixels: vec![0; 40 * 10 * 4] };
+/// blank.save_png(&golden).unwrap();
+/// let tolerance = Tolerance {
+///     masks: vec![Rectangle { x: 10, y: 0, width: u32::MAX, height: u32::MAX }],
+///     ..Default::default()
+/// };
+///
+/// let mut changed = blank.clone();
+/// changed.pixels[(3 * 40 + 30) * 4] = u8::MAX;
+/// assert_ui_matches!(changed, &golden, tolerance.clone());
+///
+/// changed.pixels[(3 * 40 + 5) * 4] = u8::MAX;
+/// assert_ui_matches!(changed, &golden, tolerance);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Tolerance {
+    /// The number of differing pixels allowed.
+    pub max_diff_pixels: usize,
+    /// How much a channel may differ before its pixel counts as differing.
+    pub per_channel: u8,
+    /// Regions to ignore, in physical pixels.
+    pub masks: Vec<Rectangle<u32>>,
+}
+
+/// The outcome of comparing a [`Snapshot`] with an expected one.
+#[derive(Clone, Debug)]
+pub struct Comparison {
+    /// The number of pixels differing beyond the per-channel tolerance.
+    pub diff_pixels: usize,
+    /// The expected image dimmed, with the differing pixels highlighted in red.
+    pub diff_image: Snapshot,
+}
+
+/// An error that occurred while loading or saving a [`Snapshot`].
+#[derive(Debug)]
+pub enum SnapshotError {
+    /// The file couldn't be read or written.
+    Io(std::io::Error),
+    /// The file isn't a PNG image this module can read.
+    Decoding(png::DecodingError),
+    /// The image couldn't be encoded as a PNG.
+    Encoding(png::EncodingError),
+}
+
+impl fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "snapshot I/O error: {err}"),
+            Self::Decoding(err) => write!(f, "failed to decode snapshot: {err}"),
+            Self::Encoding(err) => write!(f, "failed to encode snapshot: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for SnapshotError {}
+
+impl From<std::io::Error> for SnapshotError {
+    fn from(err: std::io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl From<png::DecodingError> for SnapshotError {
+    fn from(err: png::DecodingError) -> Self {
+        Self::Decoding(err)
+    }
+}
+
+impl From<png::EncodingError> for SnapshotError {
+    fn from(err: png::EncodingError) -> Self {
+        Self::Encoding(err)
+    }
+}
+
+impl Snapshot {
+    /// Render the primitives of the last displayed UI and read them back.
+    ///
+    /// Works both with the wgpu renderer, which renders into an offscreen texture,
+    /// and in headless mode, where the UI is rasterized on the CPU.
+    /// Returns `None` if the plugin hasn't been set up yet.
+    pub fn capture(world: &World) -> Option<Self> {
+        let viewport = world.get_resource::<ViewportResource>()?;
+        let props = &mut *world.get_resource::<IcedResource>()?.lock().unwrap();
+
+        match &mut props.renderer {
+            Renderer::Wgpu(renderer) => {
+                let device = world.get_resource::<RenderDevice>()?.wgpu_device();
+                let queue = world.get_resource::<RenderQueue>()?;
+                let format = props.format;
+                let overlay = props.debug.overlay();
+                let bgra = renderer.with_primitives(|backend, primitives| {
+                    read_back(device, queue, viewport, format, |encoder, view| {
+                        backend.present(
+                            device,
+                            queue,
+                            encoder,
+                            Some(Color::TRANSPARENT),
+                            format,
+                            view,
+                            primitives,
+                            viewport,
+                            &overlay,
+                        );
+                    })
+                });
+                Some(Self::from_raw(
+                    viewport.physical_size(),
+                    bgra,
+                    is_bgra(format),
+                ))
+            }
+            Renderer::TinySkia(renderer) => {
+                let overlay = props.debug.overlay();
+                let pixels = Software::default()
+                    .draw(renderer, viewport, &overlay)
+                    .to_vec();
+                // An empty viewport has no pixels to compare.
+                if pixels.is_empty() {
+                    return None;
+                }
+                Some(Self::from_raw(viewport.physical_size(), pixels, true))
+            }
+        }
+    }
+
+    /// Load a snapshot from a PNG file.
+    pub fn load_png(path: impl AsRef<Path>) -> Result<Self, SnapshotError> {
+        let mut decoder = png::Decoder::new(BufReader::new(File::open(path)?));
+        decoder.set_transformations(png::Transformations::normalize_to_color8());
+        let mut reader = decoder.read_info()?;
+        let mut buffer = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut buffer)?;
+        buffer.truncate(info.buffer_size());
+
+        let pixels = match info.color_type {
+            png::ColorType::Rgba => buffer,
+            png::ColorType::Rgb => buffer
+                .chunks_exact(3)
+                .flat_map(|rgb| [rgb[0], rgb[1], rgb[2], u8::MAX])
+                .collect(),
+            png::ColorType::GrayscaleAlpha => buffer
+                .chunks_exact(2)
+                .flat_map(|ga| [ga[0], ga[0], ga[0], ga[1]])
+                .collect(),
+            _ => buffer
+                .into_iter()
+                .flat_map(|g| [g, g, g, u8::MAX])
+                .collect(),
+        };
+
+        Ok(Self {
+            width: info.width,
+            height: info.height,
+            pixels,
+        })
+    }
+
+    /// Save the snapshot as a PNG file.
+    pub fn save_png(&self, path: impl AsRef<Path>) -> Result<(), SnapshotError> {
+        let mut encoder =
+            png::Encoder::new(BufWriter::new(File::create(path)?), self.width, self.height);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder.write_header()?.write_image_data(&self.pixels)?;
+        Ok(())
+    }
+
+    /// The fraction of pixels having a channel that differs by more than `per_channel`.
+    ///
+    /// Small per-channel tolerances absorb the antialiasing differences of text rendering
+    /// across platforms. Returns `None` if the snapshots have different sizes.
+    pub fn diff_ratio(&self, other: &Self, per_channel: u8) -> Option<f32> {
+        let tolerance = Tolerance {
+            per_channel,
+            ..Default::default()
+        };
+        let comparison = self.compare(other, &tolerance)?;
+        let total = (self.width as usize * self.height as usize).max(1);
+        Some(comparison.diff_pixels as f32 / total as f32)
+    }
+
+    /// Compare against an expected snapshot, ignoring the masked regions.
+    ///
+    /// Returns `None` if the snapshots have different sizes.
+    pub fn compare(&self, expected: &Self, tolerance: &Tolerance) -> Option<Comparison> {
+        if (self.width, self.height) != (expected.width, expected.height) {
+            return None;
+        }
+        let mut diff_pixels = 0;
+        let mut diff = Vec::with_capacity(expected.pixels.len());
+        let pixels = self
+            .pixels
+            .chunks_exact(4)
+            .zip(expected.pixels.chunks_exact(4));
+        for (i, (actual, expected)) in pixels.enumerate() {
+            let (x, y) = (i as u32 % self.width, i as u32 / self.width);
+            let masked = tolerance.masks.iter().any(|mask| {
+                (mask.x..mask.x.saturating_add(mask.width)).contains(&x)
+                    && (mask.y..mask.y.saturating_add(mask.height)).contains(&y)
+            });
+            let differs = actual
+                .iter()
+                .zip(expected)
+                .any(|(a, b)| a.abs_diff(*b) > tolerance.per_channel);
+            if differs && !masked {
+                diff_pixels += 1;
+                diff.extend([u8::MAX, 0, 0, u8::MAX]);
+            } else {
+                let luma = (expected[0] as u32 + expected[1] as u32 + expected[2] as u32) / 12;
+                diff.extend([luma as u8, luma as u8, luma as u8, u8::MAX]);
+            }
+        }
+        Some(Comparison {
+            diff_pixels,
+            diff_image: Self {
+                width: self.width,
+                height: self.height,
+                pixels: diff,
+            },
+        })
+    }
+
+    /// Whether at most `max_diff_ratio` of the pixels differ by more than `per_channel`.
+    pub fn matches(&self, other: &Self, per_channel: u8, max_diff_ratio: f32) -> bool {
+        self.diff_ratio(other, per_channel)
+            .is_some_and(|ratio| ratio <= max_diff_ratio)
+    }
+
+    fn from_raw(size: Size<u32>, mut pixels: Vec<u8>, bgra: bool) -> Self {
+        if bgra {
+            pixels
+                .chunks_exact_mut(4)
+                .for_each(|pixel| pixel.swap(0, 2));
+        }
+        Self {
+            width: size.width,
+            height: size.height,
+            pixels,
+        }
+    }
+}
+
+fn is_bgra(format: wgpu::TextureFormat) -> bool {
+    matches!(
+        format,
+        wgpu::TextureFormat::Bgra8Unorm | wgpu::TextureFormat::Bgra8UnormSrgb
+    )
+}
+
+// Renders into an offscreen texture and copies it back into tightly packed rows.
+fn read_back(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    viewport: &Viewport,
+    format: wgpu::TextureFormat,
+    draw: impl FnOnce(&mut wgpu::CommandEncoder, &wgpu::TextureView),
+) -> Vec<u8> {
+    let size = viewport.physical_size();
+    let extent = wgpu::Extent3d {
+        width: size.width,
+        height: size.height,
+        depth_or_array_layers: 1,
+    };
+    let texture = device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("bevy_iced.snapshot.texture"),
+        size: extent,
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format,
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+        view_formats: &[],
+    });
+    let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+    let bytes_per_row = size.width as usize * 4;
+    let padded_bytes_per_row =
+        bytes_per_row.next_multiple_of(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT as usize);
+    let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("bevy_iced.snapshot.buffer"),
+        size: (padded_bytes_per_row * size.height as usize) as u64,
+        usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    });
+
+    let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+        label: Some("bevy_iced.snapshot.encoder"),
+    });
+    draw(&mut encoder, &view);
+    encoder.copy_texture_to_buffer(
+        texture.as_image_copy(),
+        wgpu::ImageCopyBuffer {
+            buffer: &buffer,
+            layout: wgpu::ImageDataLayout {
+                offset: 0,
+                bytes_per_row: Some(padded_bytes_per_row as u32),
+                rows_per_image: None,
+            },
+        },
+        extent,
+    );
+    queue.submit(Some(encoder.finish()));
+
+    let slice = buffer.slice(..);
+    slice.map_async(wgpu::MapMode::Read, |_| {});
+    device.poll(wgpu::Maintain::Wait);
+    let pixels = slice
+        .get_mapped_range()
+        .chunks_exact(padded_bytes_per_row)
+        .flat_map(|row| &row[..bytes_per_row])
+        .copied()
+        .collect();
+    buffer.unmap();
+    pixels
+}
+
+/// Assert that a snapshot matches the golden image at `path`.
+///
+/// On failure, the actual image is written to `<path>.actual.png` and, if the sizes match,
+/// the highlighted differences to `<path>.diff.png`. A missing golden image also fails,
+/// after writing the actual image so it can be reviewed and moved into place.
+#[track_caller]
+pub fn assert_matches(actual: &Snapshot, path: impl AsRef<Path>, tolerance: &Tolerance) {
+    let path = path.as_ref();
+    let artifact = |suffix: &str| path.with_extension(format!("{suffix}.png"));
+
+    let expected = match Snapshot::load_png(path) {
+        Ok(expected) => expected,
+        Err(err) => {
+            let _ = actual.save_png(artifact("actual"));
+            panic!("failed to load golden image {}: {err}", path.display());
+        }
+    };
+    let Some(comparison) = actual.compare(&expected, tolerance) else {
+        let _ = actual.save_png(artifact("actual"));
+        panic!(
+            "UI snapshot is {}x{}, but golden image {} is {}x{}",
+            actual.width,
+            actual.height,
+            path.display(),
+            expected.width,
+            expected.height
+        );
+    };
+    if comparison.diff_pixels > tolerance.max_diff_pixels {
+        let _ = actual.save_png(artifact("actual"));
+        let _ = comparison.diff_image.save_png(artifact("diff"));
+        panic!(
+            "UI snapshot differs from golden image {} in {} pixels (tolerance: {}), see {}",
+            path.display(),
+            comparison.diff_pixels,
+            tolerance.max_diff_pixels,
+            artifact("diff").display()
+        );
+    }
+}
+
+/// Assert that a [`Snapshot`](crate::snapshot::Snapshot) matches a golden PNG image,
+/// optionally within a [`Tolerance`](crate::snapshot::Tolerance).
+///
+/// See [`assert_matches`](crate::snapshot::assert_matches) for the artifacts written on failure.
+#[macro_export]
+macro_rules! assert_ui_matches {
+    ($snapshot:expr, $path:expr $(,)?) => {
+        $crate::snapshot::assert_matches(&$snapshot, $path, &$crate::snapshot::Tolerance::default())
+    };
+    ($snapshot:expr, $path:expr, $tolerance:expr $(,)?) => {
+        $crate::snapshot::assert_matches(&$snapshot, $path, &$tolerance)
+    };
+}
@@ -0,0 +1,50 @@
+use iced_core::{Color, Rectangle, Size};
+use iced_widget::graphics::Viewport;
+
+/// Renders the UI on the CPU with tiny-skia, for GPUs that can't run iced's wgpu
+/// renderer. The pixels are then uploaded and composited like a faded UI, see
+/// [`crate::fade::Fade::upload`].
+#[derive(Default)]
+pub struct Software {
+    size: Option<Size<u32>>,
+    pixels: Vec<u8>,
+    clip_mask: Option<tiny_skia::Mask>,
+}
+
+impl Software {
+    /// Draw the renderer's primitives over a transparent background, returning the
+    /// premultiplied pixels in BGRA order, as iced_tiny_skia swaps the red and blue
+    /// channels for softbuffer.
+    pub fn draw(
+        &mut self,
+        renderer: &mut iced_tiny_skia::Renderer,
+        viewport: &Viewport,
+        overlay: &[String],
+    ) -> &[u8] {
+        let size = viewport.physical_size();
+        if self.size != Some(size) {
+            self.size = Some(size);
+            self.pixels = vec![0; size.width as usize * size.height as usize * 4];
+            self.clip_mask = tiny_skia::Mask::new(size.width, size.height);
+        }
+        let pixmap = tiny_skia::PixmapMut::from_bytes(&mut self.pixels, size.width, size.height);
+        let (Some(mut pixmap), Some(clip_mask)) = (pixmap, &mut self.clip_mask) else {
+            return &[];
+        };
+        renderer.with_primitives(|backend, primitives| {
+            backend.draw(
+                &mut pixmap,
+                clip_mask,
+                primitives,
+                viewport,
+                &[Rectangle::with_size(Size::new(
+                    size.width as f32,
+                    size.height as f32,
+                ))],
+                Color::TRANSPARENT,
+                overlay,
+            );
+        });
+        &self.pixels
+    }
+}
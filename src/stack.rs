@@ -0,0 +1,353 @@
+use iced_core::event::{self, Event};
+use iced_core::layout;
+use iced_core::mouse;
+use iced_core::overlay;
+use iced_core::renderer;
+use iced_core::widget::{Operation, Tree};
+use iced_core::{
+    alignment, Clipboard, Element, Layout, Length, Point, Rectangle, Shell, Size, Vector, Widget,
+};
+
+use crate::widget::Anchor;
+
+/// A container laying its children out over each other, at absolute positions or
+/// anchored to its corners and edges, see [`stack`].
+#[allow(missing_debug_implementations)]
+pub struct Stack<'a, Message, Theme, Renderer> {
+    width: Length,
+    height: Length,
+    children: Vec<Element<'a, Message, Theme, Renderer>>,
+    placements: Vec<Placement>,
+}
+
+#[derive(Clone, Copy)]
+enum Placement {
+    Fill,
+    At(Point),
+    Anchored(Anchor, Vector),
+}
+
+/// A container for HUDs, laying its children out over each other rather than next to
+/// each other: each is placed at a position, anchored to a corner or an edge, or over
+/// the whole stack. Later children are drawn on top and get the cursor first; where an
+/// interactive child is hovered, the children below it aren't.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_iced::iced::widget::text;
+/// # use bevy_iced::iced::{Point, Vector};
+/// # use bevy_iced::widget::{stack, Anchor};
+/// # use bevy_iced::IcedContext;
+/// # #[derive(Event)]
+/// # enum UiMessage {}
+/// # fn ui_system(mut ctx: IcedContext<UiMessage>) {
+/// # let (world_markers, minimap, hotbar) = (text("Markers"), text("Minimap"), text("Hotbar"));
+/// ctx.display(
+///     stack()
+///         .push(world_markers)
+///         .anchor(Anchor::TopRight, Vector::new(16.0, 16.0), minimap)
+///         .anchor(Anchor::Bottom, Vector::new(0.0, 24.0), hotbar)
+///         .float_at(Point::new(320.0, 180.0), text("-12")),
+/// );
+/// # }
+/// ```
+///
+/// A stack fills the space it's given by default, like the whole viewport at the root of
+/// a UI. Positions are relative to its top left corner, in logical pixels.
+pub fn stack<'a, Message, Theme, Renderer>() -> Stack<'a, Message, Theme, Renderer> {
+    Stack {
+        width: Length::Fill,
+        height: Length::Fill,
+        children: Vec::new(),
+        placements: Vec::new(),
+    }
+}
+
+impl<'a, Message, Theme, Renderer> Stack<'a, Message, Theme, Renderer> {
+    /// Sets the width of the stack.
+    #[must_use]
+    pub fn width(mut self, width: impl Into<Length>) -> Self {
+        self.width = width.into();
+        self
+    }
+
+    /// Sets the height of the stack.
+    #[must_use]
+    pub fn height(mut self, height: impl Into<Length>) -> Self {
+        self.height = height.into();
+        self
+    }
+
+    /// Add a child laid out over the whole stack.
+    #[must_use]
+    pub fn push(self, child: impl Into<Element<'a, Message, Theme, Renderer>>) -> Self {
+        self.place(Placement::Fill, child.into())
+    }
+
+    /// Add a child with its top left corner at `position`. It's shrunk to fit the stack
+    /// to its right and bottom, but isn't moved to stay within it.
+    #[must_use]
+    pub fn float_at(
+        self,
+        position: Point,
+        child: impl Into<Element<'a, Message, Theme, Renderer>>,
+    ) -> Self {
+        self.place(Placement::At(position), child.into())
+    }
+
+    /// Add a child at `anchor`, with `offset` as the margin to the anchored edges, like
+    /// [`anchored`](crate::widget::anchored) places a single element: along a centered
+    /// axis, it moves the child right or down from the center instead. Children larger
+    /// than the space left by the margin are shrunk to fit it.
+    #[must_use]
+    pub fn anchor(
+        self,
+        anchor: Anchor,
+        offset: Vector,
+        child: impl Into<Element<'a, Message, Theme, Renderer>>,
+    ) -> Self {
+        self.place(Placement::Anchored(anchor, offset), child.into())
+    }
+
+    fn place(mut self, placement: Placement, child: Element<'a, Message, Theme, Renderer>) -> Self {
+        self.children.push(child);
+        self.placements.push(placement);
+        self
+    }
+}
+
+impl<'a, Message, Theme, Renderer> Widget<Message, Theme, Renderer>
+    for Stack<'a, Message, Theme, Renderer>
+where
+    Renderer: renderer::Renderer,
+{
+    fn children(&self) -> Vec<Tree> {
+        self.children.iter().map(Tree::new).collect()
+    }
+
+    fn diff(&self, tree: &mut Tree) {
+        tree.diff_children(&self.children);
+    }
+
+    fn size(&self) -> Size<Length> {
+        Size::new(self.width, self.height)
+    }
+
+    fn layout(
+        &self,
+        tree: &mut Tree,
+        renderer: &Renderer,
+        limits: &layout::Limits,
+    ) -> layout::Node {
+        let size = limits.resolve(self.width, self.height, Size::ZERO);
+        let children = self
+            .children
+            .iter()
+            .zip(&self.placements)
+            .zip(&mut tree.children)
+            .map(|((child, placement), tree)| {
+                let mut within = |space: Size| {
+                    let space = Size::new(space.width.max(0.0), space.height.max(0.0));
+                    child.as_widget().layout(
+                        tree,
+                        renderer,
+                        &layout::Limits::new(Size::ZERO, space),
+                    )
+                };
+                match *placement {
+                    Placement::Fill => within(size),
+                    Placement::At(position) => {
+                        within(Size::new(size.width - position.x, size.height - position.y))
+                            .move_to(position)
+                    }
+                    Placement::Anchored(anchor, offset) => {
+                        let (horizontal, vertical) = anchor.alignment();
+                        let node = within(Size::new(
+                            size.width - offset.x.abs(),
+                            size.height - offset.y.abs(),
+                        ));
+                        let child = node.size();
+                        let x = match horizontal {
+                            alignment::Horizontal::Left => offset.x,
+                            alignment::Horizontal::Center => {
+                                (size.width - child.width) / 2.0 + offset.x
+                            }
+                            alignment::Horizontal::Right => size.width - child.width - offset.x,
+                        };
+                        let y = match vertical {
+                            alignment::Vertical::Top => offset.y,
+                            alignment::Vertical::Center => {
+                                (size.height - child.height) / 2.0 + offset.y
+                            }
+                            alignment::Vertical::Bottom => size.height - child.height - offset.y,
+                        };
+                        node.move_to(Point::new(x, y))
+                    }
+                }
+            })
+            .collect();
+        layout::Node::with_children(size, children)
+    }
+
+    fn operate(
+        &self,
+        tree: &mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        operation: &mut dyn Operation<Message>,
+    ) {
+        operation.container(None, layout.bounds(), &mut |operation| {
+            self.children
+                .iter()
+                .zip(&mut tree.children)
+                .zip(layout.children())
+                .for_each(|((child, tree), layout)| {
+                    child.as_widget().operate(tree, layout, renderer, operation);
+                });
+        });
+    }
+
+    fn on_event(
+        &mut self,
+        tree: &mut Tree,
+        event: Event,
+        layout: Layout<'_>,
+        mut cursor: mouse::Cursor,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+        viewport: &Rectangle,
+    ) -> event::Status {
+        // The topmost children get the event first, and hide the cursor from those below
+        // where they're interactive.
+        let layouts: Vec<_> = layout.children().collect();
+        for ((child, tree), layout) in self
+            .children
+            .iter_mut()
+            .zip(&mut tree.children)
+            .zip(layouts)
+            .rev()
+        {
+            let status = child.as_widget_mut().on_event(
+                tree,
+                event.clone(),
+                layout,
+                cursor,
+                renderer,
+                clipboard,
+                shell,
+                viewport,
+            );
+            if status == event::Status::Captured {
+                return status;
+            }
+            let interaction = child
+                .as_widget()
+                .mouse_interaction(tree, layout, cursor, viewport, renderer);
+            if interaction != mouse::Interaction::Idle {
+                cursor = mouse::Cursor::Unavailable;
+            }
+        }
+        event::Status::Ignored
+    }
+
+    fn mouse_interaction(
+        &self,
+        tree: &Tree,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+        renderer: &Renderer,
+    ) -> mouse::Interaction {
+        let layouts: Vec<_> = layout.children().collect();
+        self.children
+            .iter()
+            .zip(&tree.children)
+            .zip(layouts)
+            .rev()
+            .map(|((child, tree), layout)| {
+                child
+                    .as_widget()
+                    .mouse_interaction(tree, layout, cursor, viewport, renderer)
+            })
+            .find(|interaction| *interaction != mouse::Interaction::Idle)
+            .unwrap_or_default()
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+    ) {
+        // Only the cursor over the child that gets it shows as hovering.
+        let layouts: Vec<_> = layout.children().collect();
+        let mut covered = false;
+        let cursors: Vec<_> = self
+            .children
+            .iter()
+            .zip(&tree.children)
+            .zip(layouts.iter().copied())
+            .rev()
+            .map(|((child, tree), layout)| {
+                let cursor = if covered {
+                    mouse::Cursor::Unavailable
+                } else {
+                    cursor
+                };
+                covered |= child
+                    .as_widget()
+                    .mouse_interaction(tree, layout, cursor, viewport, renderer)
+                    != mouse::Interaction::Idle;
+                cursor
+            })
+            .collect();
+        for (i, (((child, tree), layout), cursor)) in self
+            .children
+            .iter()
+            .zip(&tree.children)
+            .zip(layouts)
+            .zip(cursors.into_iter().rev())
+            .enumerate()
+        {
+            // Iced draws the quads of a layer before its text, so each child above the
+            // first gets a layer of its own to cover the text below it.
+            let draw = |renderer: &mut Renderer| {
+                child
+                    .as_widget()
+                    .draw(tree, renderer, theme, style, layout, cursor, viewport);
+            };
+            if i == 0 {
+                draw(renderer);
+            } else {
+                renderer.with_layer(*viewport, draw);
+            }
+        }
+    }
+
+    fn overlay<'b>(
+        &'b mut self,
+        tree: &'b mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        translation: Vector,
+    ) -> Option<overlay::Element<'b, Message, Theme, Renderer>> {
+        overlay::from_children(&mut self.children, tree, layout, renderer, translation)
+    }
+}
+
+impl<'a, Message, Theme, Renderer> From<Stack<'a, Message, Theme, Renderer>>
+    for Element<'a, Message, Theme, Renderer>
+where
+    Message: 'a,
+    Theme: 'a,
+    Renderer: 'a + renderer::Renderer,
+{
+    fn from(stack: Stack<'a, Message, Theme, Renderer>) -> Self {
+        Element::new(stack)
+    }
+}
@@ -1,7 +1,9 @@
-use crate::conversions;
+use crate::render::ViewportResource;
+use crate::{conversions, zoom, IcedCursorOverride, IcedEnabled, IcedSettings};
 use bevy_derive::{Deref, DerefMut};
+use bevy_ecs::change_detection::DetectChanges;
 use bevy_ecs::{
-    prelude::EventReader,
+    prelude::{Entity, Event, EventReader, EventWriter, Query, With},
     system::{Res, ResMut, Resource, SystemParam},
 };
 use bevy_input::keyboard::KeyCode;
@@ -11,13 +13,42 @@ use bevy_input::{
     mouse::{MouseButtonInput, MouseWheel},
     ButtonInput, ButtonState,
 };
-use bevy_window::{CursorEntered, CursorLeft, CursorMoved, ReceivedCharacter};
-use iced_core::SmolStr;
-use iced_core::{keyboard, mouse, Event as IcedEvent, Point};
+use bevy_math::Vec2;
+use bevy_time::{Real, Time};
+use bevy_window::{
+    CursorEntered, CursorLeft, CursorMoved, PrimaryWindow, ReceivedCharacter, Window, WindowMoved,
+};
+use iced_core::time::Instant;
+use iced_core::{keyboard, mouse, window, Event as IcedEvent, Point};
 
+/// The Iced events of the current frame, fed to the UIs displayed this frame.
+///
+/// The built-in input processing refills it in [`IcedSet::ProcessInput`](crate::IcedSet).
+/// To inject events of your own, push them after that set and before your UI systems run.
+///
+/// The events are routed from the top UI down: the keys, mouse buttons, wheel and touches
+/// a UI captures, like a click on one of its buttons, aren't fed to the UIs below it, see
+/// [`DisplayOptions::z_index`](crate::DisplayOptions::z_index), and the keys only go to
+/// the topmost UI with a focused widget and the ones above it. The queue itself keeps
+/// every event of the frame.
 #[derive(Resource, Deref, DerefMut, Default)]
 pub struct IcedEventQueue(Vec<iced_core::Event>);
 
+/// An Iced event converted from this frame's input, sent when
+/// [`IcedPlugin::raw_events`](crate::IcedPlugin::raw_events) is enabled.
+///
+/// They are sent in [`IcedSet::ProcessInput`](crate::IcedSet), so systems in `Update`
+/// can read them in the same frame, whether or not a UI consumes them. Events pushed to
+/// the [`IcedEventQueue`] by other systems aren't included, and none are sent while
+/// there is no primary window.
+#[derive(Event, Clone, Debug, PartialEq)]
+pub struct IcedRawEvent {
+    /// The window the event happened in.
+    pub window: Entity,
+    /// The converted event.
+    pub event: iced_core::Event,
+}
+
 #[derive(SystemParam)]
 pub struct InputEvents<'w, 's> {
     cursor_entered: EventReader<'w, 's, CursorEntered>,
@@ -28,44 +59,114 @@ pub struct InputEvents<'w, 's> {
     received_character: EventReader<'w, 's, ReceivedCharacter>,
     keyboard_input: EventReader<'w, 's, KeyboardInput>,
     touch_input: EventReader<'w, 's, TouchInput>,
+    window_moved: EventReader<'w, 's, WindowMoved>,
 }
 
-fn compute_modifiers(input_map: &ButtonInput<KeyCode>) -> keyboard::Modifiers {
-    let mut modifiers = keyboard::Modifiers::default();
-    if input_map.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]) {
-        modifiers |= keyboard::Modifiers::CTRL;
+impl InputEvents<'_, '_> {
+    // Skip the unread events, so that they aren't delivered once the UI is re-enabled.
+    fn clear(&mut self) {
+        self.cursor_entered.clear();
+        self.cursor_left.clear();
+        self.cursor.clear();
+        self.mouse_button.clear();
+        self.mouse_wheel.clear();
+        self.received_character.clear();
+        self.keyboard_input.clear();
+        self.touch_input.clear();
+        self.window_moved.clear();
     }
-    if input_map.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]) {
-        modifiers |= keyboard::Modifiers::SHIFT;
-    }
-    if input_map.any_pressed([KeyCode::AltLeft, KeyCode::AltRight]) {
-        modifiers |= keyboard::Modifiers::ALT;
+}
+
+// Start the frame's queue, with the redraw that advances animated widgets, like the
+// blinking caret of text inputs.
+pub fn begin_frame(
+    mut event_queue: ResMut<IcedEventQueue>,
+    enabled: Res<IcedEnabled>,
+    time: Option<Res<Time<Real>>>,
+) {
+    event_queue.clear();
+    if !enabled.0 {
+        return;
     }
-    if input_map.any_pressed([KeyCode::SuperLeft, KeyCode::SuperRight]) {
-        modifiers |= keyboard::Modifiers::LOGO;
+    let now = time
+        .and_then(|time| time.last_update())
+        .unwrap_or_else(Instant::now);
+    event_queue.push(IcedEvent::Window(
+        window::Id::MAIN,
+        window::Event::RedrawRequested(now),
+    ));
+}
+
+// The keys held down, followed from the keyboard events rather than read from
+// `ButtonInput<KeyCode>`, which is reset while the UI keeps its input from the game: a
+// modifier held since an earlier frame still applies to the UI then.
+#[derive(Resource, Deref, Default)]
+pub struct HeldKeys(ButtonInput<KeyCode>);
+
+pub fn track_held_keys(mut events: EventReader<KeyboardInput>, mut held: ResMut<HeldKeys>) {
+    held.0.clear();
+    for ev in events.read() {
+        match ev.state {
+            ButtonState::Pressed => held.0.press(ev.key_code),
+            ButtonState::Released => held.0.release(ev.key_code),
+        }
     }
-    modifiers
 }
 
 pub fn process_input(
     mut events: InputEvents,
     mut event_queue: ResMut<IcedEventQueue>,
-    input_map: Res<ButtonInput<KeyCode>>,
+    held_keys: Res<HeldKeys>,
+    viewport: Res<ViewportResource>,
+    windows: Query<(Entity, &Window), With<PrimaryWindow>>,
+    (enabled, settings, cursor_override): (
+        Res<IcedEnabled>,
+        Res<IcedSettings>,
+        Res<IcedCursorOverride>,
+    ),
 ) {
-    event_queue.clear();
+    if !enabled.0 {
+        events.clear();
+        return;
+    }
 
-    for ev in events.cursor.read() {
-        event_queue.push(IcedEvent::Mouse(mouse::Event::CursorMoved {
-            position: Point::new(ev.position.x, ev.position.y),
-        }));
+    // Positions are reported in window coordinates, which differ from the viewport's
+    // logical coordinates when the scale factor is overridden.
+    let (primary, window) = windows.get_single().ok().unzip();
+    let to_viewport = |position: Vec2| match window {
+        Some(window) => conversions::cursor_position(position, &viewport, window),
+        None => Point::new(position.x, position.y),
+    };
+
+    match cursor_override.0 {
+        Some(position) => {
+            events.cursor.clear();
+            if cursor_override.is_changed() {
+                event_queue.push(IcedEvent::Mouse(mouse::Event::CursorMoved {
+                    position: to_viewport(position),
+                }));
+            }
+        }
+        None => {
+            // Back to the OS cursor, which may have moved while it was overridden.
+            let os_position = window.and_then(Window::cursor_position);
+            if let Some(position) = os_position.filter(|_| cursor_override.is_changed()) {
+                event_queue.push(IcedEvent::Mouse(mouse::Event::CursorMoved {
+                    position: to_viewport(position),
+                }));
+            }
+            for ev in events.cursor.read() {
+                event_queue.push(IcedEvent::Mouse(mouse::Event::CursorMoved {
+                    position: to_viewport(ev.position),
+                }));
+            }
+        }
     }
 
+    // Bevy queues each kind of input separately, so each kind stays in order, but the
+    // order between kinds is lost: a frame's presses land at its last cursor position.
     for ev in events.mouse_button.read() {
-        let button = conversions::mouse_button(ev.button);
-        event_queue.push(IcedEvent::Mouse(match ev.state {
-            ButtonState::Pressed => iced_core::mouse::Event::ButtonPressed(button),
-            ButtonState::Released => iced_core::mouse::Event::ButtonReleased(button),
-        }));
+        event_queue.push(IcedEvent::Mouse(conversions::mouse_button_event(ev)));
     }
 
     for _ev in events.cursor_entered.read() {
@@ -76,64 +177,76 @@ pub fn process_input(
         event_queue.push(IcedEvent::Mouse(iced_core::mouse::Event::CursorLeft));
     }
 
+    let modifiers = conversions::modifiers(&held_keys);
+    // Ctrl+wheel and Ctrl+0 zoom the UI instead, so scrollables don't scroll along.
+    let zooming = zoom::is_zooming(&settings, modifiers);
+    if zooming {
+        events.mouse_wheel.clear();
+    }
     for ev in events.mouse_wheel.read() {
-        event_queue.push(IcedEvent::Mouse(iced_core::mouse::Event::WheelScrolled {
-            delta: mouse::ScrollDelta::Pixels { x: ev.x, y: ev.y },
-        }));
+        event_queue.push(IcedEvent::Mouse(conversions::mouse_wheel(ev)));
     }
 
-    let modifiers = compute_modifiers(&input_map);
+    // Widgets only learn of the modifiers from the events, so the ones appearing while a
+    // modifier is held, like the text input of a modal opened with Ctrl held, are told
+    // again every frame.
+    if !modifiers.is_empty() {
+        event_queue.push(IcedEvent::Keyboard(keyboard::Event::ModifiersChanged(
+            modifiers,
+        )));
+    }
 
     for ev in events.received_character.read() {
-        for char in ev.char.chars() {
-            let smol_str = SmolStr::new(char.to_string());
-            let event = keyboard::Event::KeyPressed {
-                key: keyboard::Key::Character(smol_str.clone()),
-                modifiers,
-                // NOTE: This is a winit thing we don't get from bevy events
-                location: keyboard::Location::Standard,
-                text: Some(smol_str),
-            };
-            event_queue.push(IcedEvent::Keyboard(event));
+        if zooming && ev.char == "0" {
+            continue;
         }
+        event_queue.extend(conversions::character_events(ev, modifiers).map(IcedEvent::Keyboard));
     }
 
     for ev in events.keyboard_input.read() {
-        use keyboard::Event::*;
-        let event = match ev.key_code {
-            KeyCode::ControlLeft
-            | KeyCode::ControlRight
-            | KeyCode::ShiftLeft
-            | KeyCode::ShiftRight
-            | KeyCode::AltLeft
-            | KeyCode::AltRight
-            | KeyCode::SuperLeft
-            | KeyCode::SuperRight => ModifiersChanged(modifiers),
-            _ => {
-                let key = conversions::key_code(&ev.logical_key);
-                if ev.state.is_pressed() {
-                    KeyPressed {
-                        key,
-                        modifiers,
-                        // NOTE: This is a winit thing we don't get from bevy events
-                        location: keyboard::Location::Standard,
-                        text: None,
-                    }
-                } else {
-                    KeyReleased {
-                        key,
-                        modifiers,
-                        // NOTE: This is a winit thing we don't get from bevy events
-                        location: keyboard::Location::Standard,
-                    }
-                }
-            }
-        };
-
-        event_queue.push(IcedEvent::Keyboard(event));
+        if zooming && zoom::is_reset(ev.key_code) {
+            continue;
+        }
+        event_queue.push(IcedEvent::Keyboard(conversions::key_event(ev, modifiers)));
     }
 
+    if !settings.touch_input {
+        events.touch_input.clear();
+    }
     for ev in events.touch_input.read() {
-        event_queue.push(IcedEvent::Touch(conversions::touch_event(ev)));
+        event_queue.push(IcedEvent::Touch(conversions::touch_event(
+            ev,
+            to_viewport(ev.position),
+        )));
+    }
+
+    // Bevy reports the window's position in physical pixels, Iced in logical ones, as
+    // winit does.
+    for ev in events.window_moved.read() {
+        let Some(window) = window.filter(|_| Some(ev.window) == primary) else {
+            continue;
+        };
+        let position = ev.position.as_vec2() / window.scale_factor();
+        event_queue.push(IcedEvent::Window(
+            window::Id::MAIN,
+            window::Event::Moved {
+                x: position.x.round() as i32,
+                y: position.y.round() as i32,
+            },
+        ));
     }
 }
+
+pub fn send_raw_events(
+    event_queue: Res<IcedEventQueue>,
+    windows: Query<Entity, With<PrimaryWindow>>,
+    mut raw_events: EventWriter<IcedRawEvent>,
+) {
+    let Ok(window) = windows.get_single() else {
+        return;
+    };
+    raw_events.send_batch(event_queue.iter().map(|event| IcedRawEvent {
+        window,
+        event: event.clone(),
+    }));
+}
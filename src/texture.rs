@@ -0,0 +1,174 @@
+use std::sync::Mutex;
+
+use bevy_asset::{AssetId, Handle};
+use bevy_ecs::component::Component;
+use bevy_ecs::system::Resource;
+use bevy_math::Vec2;
+use bevy_render::render_asset::RenderAssets;
+use bevy_render::texture::Image;
+use bevy_utils::{warn_once, HashMap};
+use iced_core::mouse::{self, Cursor};
+use iced_core::{Event as IcedEvent, Size};
+use iced_wgpu::wgpu::{self, CommandEncoder, TextureFormat, TextureUsages};
+use iced_widget::graphics::Viewport;
+
+use crate::modal::Layer;
+use crate::IcedProps;
+
+/// An image UIs are displayed into with
+/// [`IcedContext::display_in_target`](crate::IcedContext::display_in_target), e.g. the
+/// texture of a screen in the game world, rather than the window.
+///
+/// The UI is laid out for the image's size at `scale_factor`, and laid out again when the
+/// image is resized. The image is cleared before the UI is presented to it, and then holds
+/// the UI until it's displayed again. The image needs the
+/// [`RENDER_ATTACHMENT`](bevy_render::render_resource::TextureUsages) usage, and UIs are
+/// only presented to it with the wgpu renderer.
+///
+/// Each image is presented by a renderer of its own, a full `iced_wgpu` backend with its
+/// own pipelines, buffers and glyph atlas, as the primitives of every present are uploaded
+/// into the backend's buffers. It's created when a UI is first presented to the image, and
+/// dropped along with the image, so prefer a few targets over many small ones, e.g. one
+/// image for a wall of screens.
+///
+/// The UI doesn't see the window's cursor and touches, which don't point at the image.
+/// Set `cursor` to where the pointer is on it instead, e.g. from a ray cast against the
+/// screen, for the UI to see it and the mouse buttons and wheel. Keys are sent to the UI
+/// as they are to the others.
+#[derive(Component, Clone, Debug, PartialEq)]
+pub struct IcedTextureTarget {
+    /// The image the UI is presented to.
+    pub image: Handle<Image>,
+    /// The number of the image's pixels per logical pixel of the UI.
+    pub scale_factor: f64,
+    /// Where the pointer is over the image, in the UI's logical pixels from its top left
+    /// corner.
+    pub cursor: Option<Vec2>,
+}
+
+impl IcedTextureTarget {
+    /// A target presenting to `image` at a scale factor of `1.0`, without a cursor.
+    pub fn new(image: Handle<Image>) -> Self {
+        Self {
+            image,
+            scale_factor: 1.0,
+            cursor: None,
+        }
+    }
+
+    /// Set the scale factor of the UI.
+    pub fn scale_factor(mut self, scale_factor: f64) -> Self {
+        self.scale_factor = scale_factor;
+        self
+    }
+}
+
+// The primitives of a UI displayed into an image, drawn at the given scale factor.
+pub struct TextureLayer {
+    pub image: AssetId<Image>,
+    pub scale_factor: f64,
+    pub layer: Layer,
+}
+
+// Keep the layer of the last UI displayed into each image.
+pub fn push(layers: &mut Vec<TextureLayer>, layer: TextureLayer) {
+    layers.retain(|pending| pending.image != layer.image);
+    layers.push(layer);
+}
+
+// The events of the window a UI displayed into an image sees: the keys, and the mouse
+// buttons and wheel at the cursor it was given.
+pub fn events(events: &[IcedEvent], cursor: Cursor) -> Vec<IcedEvent> {
+    let Cursor::Available(position) = cursor else {
+        return events
+            .iter()
+            .filter(|event| matches!(event, IcedEvent::Keyboard(_)))
+            .cloned()
+            .collect();
+    };
+    let moved = IcedEvent::Mouse(mouse::Event::CursorMoved { position });
+    let events = events.iter().filter(|event| match event {
+        IcedEvent::Keyboard(_) => true,
+        IcedEvent::Mouse(event) => matches!(
+            event,
+            mouse::Event::ButtonPressed(_)
+                | mouse::Event::ButtonReleased(_)
+                | mouse::Event::WheelScrolled { .. }
+        ),
+        IcedEvent::Touch(_) | IcedEvent::Window(..) => false,
+    });
+    std::iter::once(moved).chain(events.cloned()).collect()
+}
+
+// The layers extracted from the main world, until their images are ready to present them
+// or are removed.
+#[derive(Resource, Default)]
+pub struct ExtractedTextures(pub Mutex<Vec<TextureLayer>>);
+
+// The wgpu backends presenting to images, one per image, as iced_wgpu uploads the
+// primitives of each present into the backend's own buffers.
+#[derive(Default)]
+pub struct TextureBackends(HashMap<AssetId<Image>, (TextureFormat, iced_wgpu::Backend)>);
+
+impl TextureBackends {
+    // Present the layers whose images are on the GPU, keeping the others until they are.
+    pub fn present(
+        &mut self,
+        props: &IcedProps,
+        textures: &ExtractedTextures,
+        images: &RenderAssets<Image>,
+        (device, queue): (&wgpu::Device, &wgpu::Queue),
+        encoder: &mut CommandEncoder,
+    ) {
+        // The backends of images that are gone are dropped with them.
+        self.0.retain(|image, _| images.get(*image).is_some());
+        let pending = &mut *textures.0.lock().unwrap();
+        for texture in std::mem::take(pending) {
+            let Some(image) = images.get(texture.image) else {
+                pending.push(texture);
+                continue;
+            };
+            if !image
+                .texture
+                .usage()
+                .contains(TextureUsages::RENDER_ATTACHMENT)
+            {
+                warn_once!("An image Iced is displayed into lacks the RENDER_ATTACHMENT usage");
+                continue;
+            }
+            let Layer::Wgpu(primitives) = &texture.layer else {
+                warn_once!("UIs are only displayed into images with the wgpu renderer");
+                continue;
+            };
+            // The image may have been resized since the UI was laid out, in which case it's
+            // presented at its new size until the UI is laid out again.
+            let size = image.size.as_uvec2();
+            if size.x == 0 || size.y == 0 {
+                continue;
+            }
+            let format = image.texture_format;
+            if self.0.get(&texture.image).map(|(current, _)| *current) != Some(format) {
+                let Some(backend) =
+                    IcedProps::create_backend(device, queue, props.settings, format)
+                else {
+                    continue;
+                };
+                self.0.insert(texture.image, (format, backend));
+            }
+            let (_, backend) = self.0.get_mut(&texture.image).unwrap();
+            let viewport =
+                Viewport::with_physical_size(Size::new(size.x, size.y), texture.scale_factor);
+            backend.present(
+                device,
+                queue,
+                encoder,
+                Some(iced_core::Color::TRANSPARENT),
+                format,
+                &image.texture_view,
+                primitives,
+                &viewport,
+                &props.debug.overlay(),
+            );
+        }
+    }
+}
@@ -0,0 +1,77 @@
+use bevy_ecs::change_detection::DetectChangesMut;
+use bevy_ecs::system::{ResMut, Resource};
+use bevy_render::color::Color as BevyColor;
+use iced_widget::style::theme::Palette;
+use iced_widget::style::Theme;
+
+use crate::accessibility::Appearance;
+use crate::conversions;
+
+/// The theme UIs are currently drawn with, after applying
+/// [`IcedAccessibility::high_contrast`](crate::IcedAccessibility::high_contrast) to
+/// [`IcedSettings::theme`](crate::IcedSettings::theme) or the primary window's
+/// [`IcedWindowSettings::theme`](crate::IcedWindowSettings::theme), e.g. to color world-space
+/// healthbars to match the UI.
+///
+/// It's updated in [`PreUpdate`](bevy_app::PreUpdate), so a theme change made during
+/// `Update` shows up here on the next frame, along with the UIs drawn with it.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_iced::ActiveIcedTheme;
+/// # #[derive(Component)]
+/// # struct Healthbar;
+/// fn color_healthbars(theme: Res<ActiveIcedTheme>, mut bars: Query<&mut Sprite, With<Healthbar>>) {
+///     if theme.is_changed() {
+///         bars.iter_mut().for_each(|mut bar| bar.color = theme.danger());
+///     }
+/// }
+/// ```
+#[derive(Resource, Clone, Debug, Default, PartialEq)]
+pub struct ActiveIcedTheme {
+    theme: Theme,
+}
+
+impl ActiveIcedTheme {
+    /// The theme.
+    pub fn theme(&self) -> &Theme {
+        &self.theme
+    }
+
+    /// The base colors of the theme.
+    pub fn palette(&self) -> Palette {
+        self.theme.palette()
+    }
+
+    /// The background color of the theme.
+    pub fn background(&self) -> BevyColor {
+        conversions::bevy_color(self.palette().background)
+    }
+
+    /// The text color of the theme.
+    pub fn text(&self) -> BevyColor {
+        conversions::bevy_color(self.palette().text)
+    }
+
+    /// The primary color of the theme.
+    pub fn primary(&self) -> BevyColor {
+        conversions::bevy_color(self.palette().primary)
+    }
+
+    /// The color of the theme for successes.
+    pub fn success(&self) -> BevyColor {
+        conversions::bevy_color(self.palette().success)
+    }
+
+    /// The color of the theme for dangers and errors.
+    pub fn danger(&self) -> BevyColor {
+        conversions::bevy_color(self.palette().danger)
+    }
+}
+
+// The theme is compared every frame, as the window's overrides may have been removed.
+pub fn update_active_theme(appearance: Appearance, mut active: ResMut<ActiveIcedTheme>) {
+    active.set_if_neq(ActiveIcedTheme {
+        theme: appearance.theme().clone(),
+    });
+}
@@ -0,0 +1,237 @@
+use std::time::Duration;
+
+use bevy_ecs::system::{NonSendMut, Res, ResMut, Resource};
+use bevy_time::Time;
+use bevy_utils::HashMap;
+use iced_core::mouse::Cursor;
+use iced_core::{alignment, Color, Element, Length, Padding};
+use iced_runtime::user_interface::{self, UserInterface};
+use iced_widget::style::Theme;
+use iced_widget::{column, container, text};
+
+use crate::accessibility::Appearance;
+use crate::modal::Layer;
+use crate::render::ViewportResource;
+use crate::{DidDraw, IcedResource, IcedSafeArea, Renderer};
+
+/// How long toasts take to fade in and out.
+const FADE: Duration = Duration::from_millis(250);
+
+/// The corner of the window a toast is shown in.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ToastPosition {
+    /// The top left corner.
+    TopLeft,
+    /// The top right corner.
+    TopRight,
+    /// The bottom left corner.
+    BottomLeft,
+    /// The bottom right corner.
+    #[default]
+    BottomRight,
+}
+
+impl ToastPosition {
+    const ALL: [Self; 4] = [
+        Self::TopLeft,
+        Self::TopRight,
+        Self::BottomLeft,
+        Self::BottomRight,
+    ];
+}
+
+struct Toast {
+    text: String,
+    position: ToastPosition,
+    duration: Duration,
+    elapsed: Duration,
+}
+
+impl Toast {
+    fn opacity(&self) -> f32 {
+        let fade_in = self.elapsed.as_secs_f32() / FADE.as_secs_f32();
+        let fade_out =
+            self.duration.saturating_sub(self.elapsed).as_secs_f32() / FADE.as_secs_f32();
+        fade_in.min(fade_out).clamp(0.0, 1.0)
+    }
+}
+
+/// Transient notifications, like "Saved" or "Connection lost".
+///
+/// Toasts are drawn over every UI, stacked in the corner they were pushed to, and fade
+/// out once their time is up. They are drawn even on frames where no UI is displayed,
+/// and never receive input, so the UIs below them stay clickable.
+///
+/// ```
+/// # use std::time::Duration;
+/// # use bevy::prelude::*;
+/// # use bevy_iced::{IcedToasts, ToastPosition};
+/// fn save(mut toasts: ResMut<IcedToasts>) {
+///     toasts.push("Saved", Duration::from_secs(2), ToastPosition::BottomRight);
+/// }
+/// ```
+#[derive(Resource, Default)]
+pub struct IcedToasts {
+    toasts: Vec<Toast>,
+}
+
+impl IcedToasts {
+    /// Show `text` for the given duration, fading in and out included.
+    pub fn push(&mut self, text: impl Into<String>, duration: Duration, position: ToastPosition) {
+        self.toasts.push(Toast {
+            text: text.into(),
+            position,
+            duration,
+            elapsed: Duration::ZERO,
+        });
+    }
+
+    /// Remove every toast.
+    pub fn clear(&mut self) {
+        self.toasts.clear();
+    }
+
+    /// The number of toasts being shown.
+    pub fn len(&self) -> usize {
+        self.toasts.len()
+    }
+
+    /// Whether no toast is being shown.
+    pub fn is_empty(&self) -> bool {
+        self.toasts.is_empty()
+    }
+}
+
+fn stack<'a>(
+    toasts: &'a [Toast],
+    position: ToastPosition,
+    theme: &Theme,
+    padding: Padding,
+    reduced_motion: bool,
+) -> Element<'a, (), Theme, Renderer> {
+    let palette = theme.extended_palette().background.strong;
+    let toasts = toasts
+        .iter()
+        .filter(|toast| toast.position == position)
+        .map(|toast| {
+            let opacity = if reduced_motion { 1.0 } else { toast.opacity() };
+            let text_color = Color {
+                a: palette.text.a * opacity,
+                ..palette.text
+            };
+            let background = Color {
+                a: palette.color.a * opacity,
+                ..palette.color
+            };
+            container(text(&toast.text).style(text_color))
+                .padding([8, 12])
+                .style(iced_widget::style::theme::Container::Custom(Box::new(
+                    Background(background),
+                )))
+                .into()
+        });
+
+    let (horizontal, vertical) = match position {
+        ToastPosition::TopLeft => (alignment::Horizontal::Left, alignment::Vertical::Top),
+        ToastPosition::TopRight => (alignment::Horizontal::Right, alignment::Vertical::Top),
+        ToastPosition::BottomLeft => (alignment::Horizontal::Left, alignment::Vertical::Bottom),
+        ToastPosition::BottomRight => (alignment::Horizontal::Right, alignment::Vertical::Bottom),
+    };
+    let align_items = match horizontal {
+        alignment::Horizontal::Left => iced_core::Alignment::Start,
+        _ => iced_core::Alignment::End,
+    };
+    container(column(toasts).spacing(8).align_items(align_items))
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .padding(padding)
+        .align_x(horizontal)
+        .align_y(vertical)
+        .into()
+}
+
+pub struct Background(pub Color);
+
+impl container::StyleSheet for Background {
+    type Style = Theme;
+
+    fn appearance(&self, _style: &Theme) -> container::Appearance {
+        container::Appearance {
+            background: Some(self.0.into()),
+            border: iced_core::Border::with_radius(4),
+            ..Default::default()
+        }
+    }
+}
+
+// The widget state of every corner's toasts. It owns the text that the drawn primitives
+// refer to, so it must outlive the frame.
+#[derive(Default)]
+pub struct ToastCaches(HashMap<ToastPosition, user_interface::Cache>);
+
+// Draw the toasts over the UIs of this frame, after the modal layer.
+pub fn draw_toasts(
+    mut toasts: ResMut<IcedToasts>,
+    time: Option<Res<Time>>,
+    props: Res<IcedResource>,
+    (viewport, appearance, safe_area): (Res<ViewportResource>, Appearance, Res<IcedSafeArea>),
+    did_draw: Res<DidDraw>,
+    mut caches: NonSendMut<ToastCaches>,
+) {
+    let delta = time.map_or(Duration::ZERO, |time| time.delta());
+    for toast in &mut toasts.toasts {
+        toast.elapsed += delta;
+    }
+    toasts.toasts.retain(|toast| toast.elapsed < toast.duration);
+    if toasts.toasts.is_empty() {
+        caches.0.clear();
+        return;
+    }
+
+    let props = &mut *props.lock().unwrap();
+    props.wake_renderer();
+    props.drawn_scale_factor = viewport.scale_factor();
+    let renderer = &mut props.renderer;
+    let bounds = viewport.logical_size();
+    let padding = Padding {
+        top: safe_area.insets.top + 16.0,
+        right: safe_area.insets.right + 16.0,
+        bottom: safe_area.insets.bottom + 16.0,
+        left: safe_area.insets.left + 16.0,
+    };
+
+    // Without a UI this frame, the renderer still holds the last one's primitives.
+    let drew = did_draw.load(std::sync::atomic::Ordering::Relaxed);
+    let below = Layer::take(renderer);
+    let mut layers = Vec::new();
+    if drew {
+        layers.push(below);
+    }
+    for position in ToastPosition::ALL {
+        if !toasts.toasts.iter().any(|toast| toast.position == position) {
+            caches.0.remove(&position);
+            continue;
+        }
+        let element = stack(
+            &toasts.toasts,
+            position,
+            appearance.theme(),
+            padding,
+            appearance.reduced_motion(),
+        );
+        let cache = caches.0.remove(&position).unwrap_or_default();
+        let mut ui = UserInterface::build(element, bounds, cache, renderer);
+        ui.draw(
+            renderer,
+            appearance.theme(),
+            &appearance.style(),
+            Cursor::Unavailable,
+        );
+        caches.0.insert(position, ui.into_cache());
+        layers.push(Layer::take(renderer));
+    }
+    for layer in layers {
+        layer.draw(renderer);
+    }
+    did_draw.store(true, std::sync::atomic::Ordering::Relaxed);
+}
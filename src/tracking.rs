@@ -0,0 +1,159 @@
+use std::any::Any;
+use std::collections::HashMap;
+
+use bevy_ecs::system::{Res, ResMut, Resource};
+use bevy_math::Rect;
+use iced_core::widget::{self, operation, Operation};
+use iced_core::{Rectangle, Size, Vector};
+use iced_runtime::user_interface::UserInterface;
+use iced_widget::graphics::Viewport;
+use iced_widget::style::Theme;
+
+use crate::coordinates::to_bevy_physical_rect;
+use crate::{IcedResource, Renderer};
+
+/// Where a widget wrapped in [`tracked`](crate::widget::tracked) was laid out, see
+/// [`IcedWidgetInfo`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TrackedWidget {
+    /// The bounds of the widget, in the window's physical pixels, e.g. to point at it
+    /// with gizmos. Parts of it may be scrolled out of view.
+    pub bounds: Rect,
+    /// The part of the widget on screen, in the window's physical pixels: within the
+    /// window and the scrollables around it, and not covered by a modal UI.
+    pub visible_bounds: Option<Rect>,
+}
+
+impl TrackedWidget {
+    /// Whether any part of the widget is on screen.
+    pub fn is_visible(&self) -> bool {
+        self.visible_bounds.is_some()
+    }
+}
+
+/// The layout of the widgets wrapped in [`tracked`](crate::widget::tracked), e.g. to
+/// point a tutorial arrow at a button only while it's visible.
+///
+/// It's updated in [`PostUpdate`](bevy_app::PostUpdate) with the UIs displayed that
+/// frame. Widgets that weren't displayed are absent.
+#[derive(Resource, Clone, Debug, Default)]
+pub struct IcedWidgetInfo {
+    widgets: HashMap<widget::Id, TrackedWidget>,
+}
+
+impl IcedWidgetInfo {
+    /// The layout of the tracked widget with the given id.
+    pub fn get(&self, id: impl Into<widget::Id>) -> Option<&TrackedWidget> {
+        self.widgets.get(&id.into())
+    }
+
+    /// Whether the tracked widget with the given id is on screen.
+    pub fn is_visible(&self, id: impl Into<widget::Id>) -> bool {
+        self.get(id).is_some_and(TrackedWidget::is_visible)
+    }
+
+    /// The layout of every tracked widget.
+    pub fn iter(&self) -> impl Iterator<Item = (&widget::Id, &TrackedWidget)> {
+        self.widgets.iter()
+    }
+}
+
+// The marker a tracked widget operates with, holding its bounds.
+pub struct Marker(pub Rectangle);
+
+// Record the tracked widgets of a UI. Those of a UI under a modal aren't visible.
+pub fn collect<M>(
+    widgets: &mut HashMap<widget::Id, TrackedWidget>,
+    ui: &mut UserInterface<'_, M, Theme, Renderer>,
+    renderer: &Renderer,
+    viewport: &Viewport,
+    covered: bool,
+) {
+    let window = Rectangle::with_size(viewport.logical_size());
+    let mut collect = Collect {
+        widgets,
+        viewport,
+        covered,
+        frame: Frame {
+            translation: Vector::ZERO,
+            clip: window,
+        },
+        scroll: None,
+    };
+    ui.operate(renderer, &mut collect);
+}
+
+// Publish the tracked widgets of this frame's UIs, once they've all been displayed.
+pub fn publish_widget_info(props: Res<IcedResource>, mut info: ResMut<IcedWidgetInfo>) {
+    let widgets = std::mem::take(&mut props.lock().unwrap().tracked_widgets);
+    if !(info.widgets.is_empty() && widgets.is_empty()) {
+        info.widgets = widgets;
+    }
+}
+
+#[derive(Clone, Copy)]
+struct Frame {
+    // The scroll offset of the content, and the visible area of scrollables around it.
+    translation: Vector,
+    clip: Rectangle,
+}
+
+struct Collect<'a> {
+    widgets: &'a mut HashMap<widget::Id, TrackedWidget>,
+    viewport: &'a Viewport,
+    covered: bool,
+    frame: Frame,
+    // The translation of the scrollable whose content is operated on next.
+    scroll: Option<Vector>,
+}
+
+impl<T> Operation<T> for Collect<'_> {
+    fn container(
+        &mut self,
+        _id: Option<&widget::Id>,
+        bounds: Rectangle,
+        operate_on_children: &mut dyn FnMut(&mut dyn Operation<T>),
+    ) {
+        let parent = self.frame;
+        // Scrollables report their offset, then operate on their content as a container.
+        if let Some(translation) = self.scroll.take() {
+            let visible = bounds - parent.translation;
+            self.frame.translation = parent.translation + translation;
+            self.frame.clip = parent
+                .clip
+                .intersection(&visible)
+                .unwrap_or(Rectangle::new(visible.position(), Size::ZERO));
+        }
+        operate_on_children(self);
+        self.frame = parent;
+    }
+
+    fn scrollable(
+        &mut self,
+        _state: &mut dyn operation::Scrollable,
+        _id: Option<&widget::Id>,
+        _bounds: Rectangle,
+        translation: Vector,
+    ) {
+        self.scroll = Some(translation);
+    }
+
+    fn custom(&mut self, state: &mut dyn Any, id: Option<&widget::Id>) {
+        let (Some(Marker(bounds)), Some(id)) = (state.downcast_ref::<Marker>(), id) else {
+            return;
+        };
+        let bounds = *bounds - self.frame.translation;
+        let visible = (!self.covered)
+            .then(|| bounds.intersection(&self.frame.clip))
+            .flatten()
+            .filter(|visible| visible.width > 0.0 && visible.height > 0.0);
+        self.widgets.insert(
+            id.clone(),
+            TrackedWidget {
+                bounds: to_bevy_physical_rect(bounds, self.viewport),
+                visible_bounds: visible
+                    .map(|visible| to_bevy_physical_rect(visible, self.viewport)),
+            },
+        );
+    }
+}
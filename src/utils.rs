@@ -1,37 +1,74 @@
-use crate::iced;
-use crate::IcedContext;
+use crate::{coordinates, iced};
+use bevy_input::touch::Touches;
 use bevy_math::Vec2;
 use bevy_window::Window;
+use iced_core::mouse::Cursor;
+use iced_widget::graphics::Viewport;
 
+/// Convert a position in window coordinates to the viewport's logical coordinates.
+///
+/// Both go through the physical position, like the viewport's logical size is derived
+/// from its physical size, so that they agree at fractional and overridden scale factors.
 pub fn process_cursor_position(
     position: Vec2,
-    bounds: iced_core::Size,
+    viewport: &Viewport,
     window: &Window,
 ) -> iced_core::Point {
-    iced_core::Point {
-        x: position.x * bounds.width / window.width(),
-        y: position.y * bounds.height / window.height(),
+    let physical = position * window.scale_factor();
+    coordinates::to_iced_logical(physical, viewport)
+}
+
+/// The cursor the UIs see: the override, a finger while one is down, or else the mouse.
+pub fn cursor(
+    cursor_override: Option<Vec2>,
+    touches: Option<&Touches>,
+    events: &[iced::Event],
+    viewport: &Viewport,
+    window: Option<&Window>,
+) -> Cursor {
+    match window {
+        // While a finger is down, it takes over from the mouse: Iced hit-tests touch
+        // events against the cursor, e.g. for scrollables to pan with the finger.
+        Some(window) => cursor_override
+            .map(|position| process_cursor_position(position, viewport, window))
+            .or_else(|| {
+                touches.and_then(|touches| process_touch_input(touches, events, viewport, window))
+            })
+            .or_else(|| {
+                window
+                    .cursor_position()
+                    .map(|position| process_cursor_position(position, viewport, window))
+            })
+            .map(Cursor::Available)
+            .unwrap_or(Cursor::Unavailable),
+        // Headless, the cursor can only be known from the override, in logical
+        // coordinates, or the events fed to Iced.
+        None => cursor_override
+            .map(|position| iced_core::Point::new(position.x, position.y))
+            .or_else(|| last_cursor_position(events))
+            .map(Cursor::Available)
+            .unwrap_or(Cursor::Unavailable),
     }
 }
 
 /// To correctly process input as last resort events are used
-pub fn process_touch_input<M: bevy_ecs::event::Event>(
-    context: &IcedContext<M>,
+pub fn process_touch_input(
+    touches: &Touches,
+    events: &[iced::Event],
+    viewport: &Viewport,
+    window: &Window,
 ) -> Option<iced::Point> {
-    context
-        .touches
+    touches
         .first_pressed_position()
         .or_else(|| {
-            context
-                .touches
+            touches
                 .iter_just_released()
                 .map(bevy_input::touch::Touch::position)
                 .next()
         })
-        .map(|Vec2 { x, y }| iced::Point { x, y })
+        .map(|position| process_cursor_position(position, viewport, window))
         .or_else(|| {
-            context
-                .events
+            events
                 .iter()
                 .find_map(|ev| {
                     if let iced::Event::Touch(
@@ -49,3 +86,36 @@ pub fn process_touch_input<M: bevy_ecs::event::Event>(
                 .copied()
         })
 }
+
+/// The last position the mouse cursor moved to within the given events
+pub fn last_cursor_position(events: &[iced::Event]) -> Option<iced::Point> {
+    events.iter().rev().find_map(|ev| {
+        if let iced::Event::Mouse(iced::mouse::Event::CursorMoved { position }) = ev {
+            Some(*position)
+        } else {
+            None
+        }
+    })
+}
+
+/// Run `f` with a shell of its own, e.g. for a widget publishing its own kind of
+/// messages, passing on its redraw requests and invalidations to `shell`. Returns what
+/// `f` returned and the messages it published.
+pub fn capture<A, B, R>(
+    shell: &mut iced_core::Shell<'_, B>,
+    f: impl FnOnce(&mut iced_core::Shell<'_, A>) -> R,
+) -> (R, Vec<A>) {
+    let mut messages = Vec::new();
+    let mut own = iced_core::Shell::new(&mut messages);
+    let result = f(&mut own);
+    if let Some(request) = own.redraw_request() {
+        shell.request_redraw(request);
+    }
+    if own.is_layout_invalid() {
+        shell.invalidate_layout();
+    }
+    if own.are_widgets_invalid() {
+        shell.invalidate_widgets();
+    }
+    (result, messages)
+}
@@ -0,0 +1,852 @@
+use std::any::Any;
+
+use iced_core::alignment;
+use iced_core::event::{self, Event};
+use iced_core::keyboard::{self, key};
+use iced_core::layout::{self, Layout};
+use iced_core::mouse;
+use iced_core::renderer::{self, Quad, Renderer as _};
+use iced_core::text::{self, LineHeight, Renderer as _, Shaping};
+use iced_core::touch;
+use iced_core::widget::{operation, tree, Id, Operation, Tree};
+use iced_core::{
+    Background, Clipboard, Color, Element, Length, Pixels, Point, Rectangle, Shell, Size, SmolStr,
+    Widget,
+};
+use iced_runtime::user_interface::UserInterface;
+use iced_widget::button::StyleSheet;
+use iced_widget::style::{theme, Theme};
+use iced_widget::text_input;
+
+use crate::Renderer;
+
+/// The keys of a [`virtual_keyboard`], row by row, on each of its pages, and the labels
+/// of its special keys.
+///
+/// Each character of a row is a key, so a layout is a few strings: the letters page, the
+/// shifted page, which defaults to the uppercase letters, and the symbols page.
+///
+/// ```
+/// # use bevy_iced::widget::{KeyLabels, VirtualKeys};
+/// let keys = VirtualKeys::new(["1234567890", "azertyuiop", "qsdfghjklm", "wxcvbn"])
+///     .labels(KeyLabels {
+///         shift: "Maj".into(),
+///         space: "Espace".into(),
+///         backspace: "Effacer".into(),
+///         done: "OK".into(),
+///         ..Default::default()
+///     });
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct VirtualKeys {
+    letters: Vec<Vec<String>>,
+    shifted: Vec<Vec<String>>,
+    symbols: Vec<Vec<String>>,
+    labels: KeyLabels,
+}
+
+impl VirtualKeys {
+    /// The letters page, with its uppercase as the shifted page and the default symbols.
+    pub fn new<R: AsRef<str>>(letters: impl IntoIterator<Item = R>) -> Self {
+        let letters = rows(letters);
+        let shifted = letters
+            .iter()
+            .map(|row| row.iter().map(|key| key.to_uppercase()).collect())
+            .collect();
+        Self {
+            letters,
+            shifted,
+            symbols: rows(["1234567890", "@#$%&*-+()", "!\"':;/?,.", "_~=<>[]{}"]),
+            labels: KeyLabels::default(),
+        }
+    }
+
+    /// Set the page shown after shift is pressed, for letters whose uppercase isn't what
+    /// their key should type.
+    #[must_use]
+    pub fn shifted<R: AsRef<str>>(mut self, shifted: impl IntoIterator<Item = R>) -> Self {
+        self.shifted = rows(shifted);
+        self
+    }
+
+    /// Set the symbols page.
+    #[must_use]
+    pub fn symbols<R: AsRef<str>>(mut self, symbols: impl IntoIterator<Item = R>) -> Self {
+        self.symbols = rows(symbols);
+        self
+    }
+
+    /// Set the labels of the special keys, e.g. to translate them.
+    #[must_use]
+    pub fn labels(mut self, labels: KeyLabels) -> Self {
+        self.labels = labels;
+        self
+    }
+
+    fn page(&self, page: Page) -> &[Vec<String>] {
+        match page {
+            Page::Letters => &self.letters,
+            Page::Shifted => &self.shifted,
+            Page::Symbols => &self.symbols,
+        }
+    }
+}
+
+impl Default for VirtualKeys {
+    /// A QWERTY layout.
+    fn default() -> Self {
+        Self::new(["1234567890", "qwertyuiop", "asdfghjkl", "zxcvbnm"])
+    }
+}
+
+fn rows<R: AsRef<str>>(rows: impl IntoIterator<Item = R>) -> Vec<Vec<String>> {
+    rows.into_iter()
+        .map(|row| row.as_ref().chars().map(String::from).collect())
+        .collect()
+}
+
+/// The labels of the special keys of a [`virtual_keyboard`], see [`VirtualKeys::labels`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KeyLabels {
+    /// The key switching to the shifted page for the next key, or back to the letters.
+    pub shift: String,
+    /// The key switching to the symbols page.
+    pub symbols: String,
+    /// The key switching from the symbols page back to the letters.
+    pub letters: String,
+    /// The space bar.
+    pub space: String,
+    /// The key erasing the character before the caret.
+    pub backspace: String,
+    /// The key submitting the text.
+    pub done: String,
+}
+
+impl Default for KeyLabels {
+    fn default() -> Self {
+        Self {
+            shift: "Shift".into(),
+            symbols: "?123".into(),
+            letters: "ABC".into(),
+            space: "Space".into(),
+            backspace: "Delete".into(),
+            done: "Done".into(),
+        }
+    }
+}
+
+/// A move of the selected key of a [`virtual_keyboard`], or a press of it, see
+/// [`IcedContext::navigate_virtual_keyboard`](crate::IcedContext::navigate_virtual_keyboard).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum KeyboardNavigation {
+    /// Select the nearest key of the row above.
+    Up,
+    /// Select the nearest key of the row below.
+    Down,
+    /// Select the key to the left.
+    Left,
+    /// Select the key to the right.
+    Right,
+    /// Press the selected key, or select the first key if none is.
+    Press,
+}
+
+/// An on-screen keyboard, see [`virtual_keyboard`]. Requires the `virtual-keyboard`
+/// feature.
+#[allow(missing_debug_implementations)]
+pub struct VirtualKeyboard<'a, Message> {
+    keys: &'a VirtualKeys,
+    id: Option<Id>,
+    target: Option<text_input::Id>,
+    on_char: Option<Box<dyn Fn(String) -> Message + 'a>>,
+    on_backspace: Option<Message>,
+    on_done: Option<Message>,
+    key_size: f32,
+    spacing: f32,
+    text_size: Option<Pixels>,
+    style: theme::Button,
+    done_style: theme::Button,
+}
+
+/// An on-screen keyboard with `keys`, e.g. to enter a name with a gamepad, pressed with
+/// the mouse, by touch, or by moving a selection over its keys with
+/// [`IcedContext::navigate_virtual_keyboard`](crate::IcedContext::navigate_virtual_keyboard).
+/// Requires the `virtual-keyboard` feature.
+///
+/// Bound to a [`text_input`](crate::iced::widget::text_input()), the keyboard types into it
+/// as a physical keyboard would: the characters are inserted at its caret, and it
+/// produces its own `on_input` and `on_submit` messages. Pressing a key with the mouse or
+/// by touch unfocuses the text input like any other press outside of it does, so the
+/// caret is then moved to the end. Without a binding, the keyboard only produces the
+/// messages of [`VirtualKeyboard::on_char`], [`on_backspace`](VirtualKeyboard::on_backspace)
+/// and [`on_done`](VirtualKeyboard::on_done).
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_iced::iced::advanced::widget::Id;
+/// # use bevy_iced::iced::widget::{column, text_input};
+/// # use bevy_iced::widget::{virtual_keyboard, VirtualKeys};
+/// # use bevy_iced::IcedContext;
+/// # #[derive(Event, Clone)]
+/// # enum UiMessage {
+/// #     Name(String),
+/// #     Confirm,
+/// # }
+/// # fn ui_system(mut ctx: IcedContext<UiMessage>, name: String, keys: VirtualKeys) {
+/// let name_input = text_input::Id::new("name");
+/// ctx.display(column![
+///     text_input("Name", &name).id(name_input.clone()).on_input(UiMessage::Name),
+///     virtual_keyboard(&keys)
+///         .id(Id::new("keyboard"))
+///         .bind(name_input)
+///         .on_done(UiMessage::Confirm),
+/// ]);
+/// # }
+/// ```
+///
+/// Shift applies to the next key only. The keys are styled as buttons, the secondary
+/// ones by default and the done key as a primary one, and the selected key as a hovered
+/// one.
+pub fn virtual_keyboard<'a, Message>(keys: &'a VirtualKeys) -> VirtualKeyboard<'a, Message> {
+    VirtualKeyboard {
+        keys,
+        id: None,
+        target: None,
+        on_char: None,
+        on_backspace: None,
+        on_done: None,
+        key_size: 40.0,
+        spacing: 4.0,
+        text_size: None,
+        style: theme::Button::Secondary,
+        done_style: theme::Button::Primary,
+    }
+}
+
+impl<'a, Message> VirtualKeyboard<'a, Message> {
+    /// Set the id of the keyboard, to navigate it with
+    /// [`IcedContext::navigate_virtual_keyboard`](crate::IcedContext::navigate_virtual_keyboard).
+    #[must_use]
+    pub fn id(mut self, id: impl Into<Id>) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
+    /// Type into the text input with the given id, focusing it when a key is pressed.
+    #[must_use]
+    pub fn bind(mut self, target: text_input::Id) -> Self {
+        self.target = Some(target);
+        self
+    }
+
+    /// Produce the message of `on_char` with the text of each character key, and a
+    /// space for the space bar.
+    #[must_use]
+    pub fn on_char(mut self, on_char: impl Fn(String) -> Message + 'a) -> Self {
+        self.on_char = Some(Box::new(on_char));
+        self
+    }
+
+    /// Produce `message` when the backspace key is pressed.
+    #[must_use]
+    pub fn on_backspace(mut self, message: Message) -> Self {
+        self.on_backspace = Some(message);
+        self
+    }
+
+    /// Produce `message` when the done key is pressed.
+    #[must_use]
+    pub fn on_done(mut self, message: Message) -> Self {
+        self.on_done = Some(message);
+        self
+    }
+
+    /// Set the width and height of a character key, 40 by default. The special keys are
+    /// wider.
+    #[must_use]
+    pub fn key_size(mut self, size: f32) -> Self {
+        self.key_size = size;
+        self
+    }
+
+    /// Set the gap between keys, 4 by default.
+    #[must_use]
+    pub fn spacing(mut self, spacing: f32) -> Self {
+        self.spacing = spacing;
+        self
+    }
+
+    /// Set the text size of the labels of the keys, the renderer's default by default.
+    #[must_use]
+    pub fn text_size(mut self, size: impl Into<Pixels>) -> Self {
+        self.text_size = Some(size.into());
+        self
+    }
+
+    /// Set the style of the keys.
+    #[must_use]
+    pub fn style(mut self, style: impl Into<theme::Button>) -> Self {
+        self.style = style.into();
+        self
+    }
+
+    /// Set the style of the done key.
+    #[must_use]
+    pub fn done_style(mut self, style: impl Into<theme::Button>) -> Self {
+        self.done_style = style.into();
+        self
+    }
+
+    // The keys of a page, row by row, with their width in character keys.
+    fn rows(&self, page: Page) -> Vec<Vec<(Key<'_>, f32)>> {
+        let mut rows: Vec<Vec<_>> = self
+            .keys
+            .page(page)
+            .iter()
+            .map(|row| row.iter().map(|key| (Key::Text(key), 1.0)).collect())
+            .collect();
+        rows.push(vec![
+            (Key::Shift, 1.5),
+            (Key::Page, 1.5),
+            (Key::Space, 5.0),
+            (Key::Backspace, 1.5),
+            (Key::Done, 1.5),
+        ]);
+        rows
+    }
+
+    fn label<'b>(&'b self, key: Key<'b>, page: Page) -> &'b str {
+        let labels = &self.keys.labels;
+        match key {
+            Key::Text(text) => text,
+            Key::Shift => &labels.shift,
+            Key::Page if page == Page::Symbols => &labels.letters,
+            Key::Page => &labels.symbols,
+            Key::Space => &labels.space,
+            Key::Backspace => &labels.backspace,
+            Key::Done => &labels.done,
+        }
+    }
+
+    fn press(&self, state: &mut State, (row, column): (usize, usize), messages: &mut Vec<Message>)
+    where
+        Message: Clone,
+    {
+        let rows = self.rows(state.page);
+        let Some(&(key, _)) = rows.get(row).and_then(|row| row.get(column)) else {
+            return;
+        };
+        let typed = match key {
+            Key::Text(text) => {
+                if state.page == Page::Shifted {
+                    state.page = Page::Letters;
+                }
+                Typed::Text(text.into())
+            }
+            Key::Space => Typed::Text(" ".into()),
+            Key::Backspace => Typed::Backspace,
+            Key::Done => Typed::Done,
+            Key::Shift => {
+                state.page = match state.page {
+                    Page::Shifted => Page::Letters,
+                    _ => Page::Shifted,
+                };
+                return;
+            }
+            Key::Page => {
+                state.page = match state.page {
+                    Page::Symbols => Page::Letters,
+                    _ => Page::Symbols,
+                };
+                return;
+            }
+        };
+        let message = match &typed {
+            Typed::Text(text) => self.on_char.as_ref().map(|on_char| on_char(text.clone())),
+            Typed::Backspace => self.on_backspace.clone(),
+            Typed::Done => self.on_done.clone(),
+        };
+        messages.extend(message);
+        if self.target.is_some() {
+            state.typed.push(typed);
+        }
+    }
+
+    fn navigate(
+        &self,
+        state: &mut State,
+        navigation: KeyboardNavigation,
+    ) -> Option<(usize, usize)> {
+        let Some((row, column)) = state.selected else {
+            state.selected = Some((0, 0));
+            return None;
+        };
+        let last_row = state.centers.len().saturating_sub(1);
+        let x = state
+            .centers
+            .get(row)
+            .and_then(|row| row.get(column).copied());
+        let nearest = |row: usize| {
+            let (x, centers) = x.zip(state.centers.get(row))?;
+            (0..centers.len())
+                .min_by(|a, b| (centers[*a] - x).abs().total_cmp(&(centers[*b] - x).abs()))
+        };
+        let selected = match navigation {
+            KeyboardNavigation::Up => {
+                nearest(row.saturating_sub(1)).map(|column| (row.saturating_sub(1), column))
+            }
+            KeyboardNavigation::Down => {
+                nearest((row + 1).min(last_row)).map(|column| ((row + 1).min(last_row), column))
+            }
+            KeyboardNavigation::Left => Some((row, column.saturating_sub(1))),
+            KeyboardNavigation::Right => {
+                let last = state
+                    .centers
+                    .get(row)
+                    .map_or(0, |row| row.len().saturating_sub(1));
+                Some((row, (column + 1).min(last)))
+            }
+            KeyboardNavigation::Press => return Some((row, column)),
+        };
+        state.selected = selected.or(state.selected);
+        None
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Key<'a> {
+    Text(&'a str),
+    Shift,
+    Page,
+    Space,
+    Backspace,
+    Done,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum Page {
+    #[default]
+    Letters,
+    Shifted,
+    Symbols,
+}
+
+// What a key typed into the bound text input, until it's handed to it.
+#[derive(Clone, Debug)]
+enum Typed {
+    Text(String),
+    Backspace,
+    Done,
+}
+
+impl Typed {
+    fn event(self) -> Event {
+        let (key, text) = match self {
+            Typed::Text(text) if text == " " => {
+                (keyboard::Key::Named(key::Named::Space), Some(text))
+            }
+            Typed::Text(text) => (keyboard::Key::Character(SmolStr::new(&text)), Some(text)),
+            Typed::Backspace => (keyboard::Key::Named(key::Named::Backspace), None),
+            Typed::Done => (keyboard::Key::Named(key::Named::Enter), None),
+        };
+        Event::Keyboard(keyboard::Event::KeyPressed {
+            key,
+            location: keyboard::Location::Standard,
+            modifiers: keyboard::Modifiers::empty(),
+            text: text.map(SmolStr::from),
+        })
+    }
+}
+
+#[derive(Default)]
+struct State {
+    page: Page,
+    // The key selected by navigating, and the one pressed with the mouse or by touch.
+    selected: Option<(usize, usize)>,
+    pressed: Option<(usize, usize)>,
+    // The horizontal centers of the keys, row by row, to navigate between rows.
+    centers: Vec<Vec<f32>>,
+    typed: Vec<Typed>,
+}
+
+impl<'a, Message> Widget<Message, Theme, Renderer> for VirtualKeyboard<'a, Message>
+where
+    Message: Clone + 'static,
+{
+    fn tag(&self) -> tree::Tag {
+        tree::Tag::of::<State>()
+    }
+
+    fn state(&self) -> tree::State {
+        tree::State::new(State::default())
+    }
+
+    fn size(&self) -> Size<Length> {
+        Size::new(Length::Shrink, Length::Shrink)
+    }
+
+    fn layout(
+        &self,
+        tree: &mut Tree,
+        _renderer: &Renderer,
+        limits: &layout::Limits,
+    ) -> layout::Node {
+        let state = tree.state.downcast_mut::<State>();
+        let rows = self.rows(state.page);
+        let row_width = |row: &[(Key<'_>, f32)]| {
+            let units: f32 = row.iter().map(|(_, units)| units).sum();
+            units * self.key_size + row.len().saturating_sub(1) as f32 * self.spacing
+        };
+        let width = rows.iter().map(|row| row_width(row)).fold(0.0, f32::max);
+        let mut y = 0.0;
+        let mut centers = Vec::with_capacity(rows.len());
+        let nodes = rows
+            .iter()
+            .map(|row| {
+                let mut x = (width - row_width(row)) / 2.0;
+                let mut row_centers = Vec::with_capacity(row.len());
+                let keys = row
+                    .iter()
+                    .map(|(_, units)| {
+                        let key_width = units * self.key_size + (units - 1.0) * self.spacing;
+                        let node = layout::Node::new(Size::new(key_width, self.key_size))
+                            .move_to(Point::new(x, 0.0));
+                        row_centers.push(x + key_width / 2.0);
+                        x += key_width + self.spacing;
+                        node
+                    })
+                    .collect();
+                centers.push(row_centers);
+                let node = layout::Node::with_children(Size::new(width, self.key_size), keys)
+                    .move_to(Point::new(0.0, y));
+                y += self.key_size + self.spacing;
+                node
+            })
+            .collect();
+        state.centers = centers;
+        let height = (y - self.spacing).max(0.0);
+        let size = limits.resolve(Length::Shrink, Length::Shrink, Size::new(width, height));
+        layout::Node::with_children(size, nodes)
+    }
+
+    fn operate(
+        &self,
+        tree: &mut Tree,
+        _layout: Layout<'_>,
+        _renderer: &Renderer,
+        operation: &mut dyn Operation<Message>,
+    ) {
+        let state = tree.state.downcast_mut::<State>();
+        if let Some(id) = &self.id {
+            let mut navigation = Navigation(Vec::new());
+            operation.custom(&mut navigation, Some(id));
+            let mut pressed = Pressed(Vec::new());
+            for navigation in navigation.0 {
+                if let Some(key) = self.navigate(state, navigation) {
+                    self.press(state, key, &mut pressed.0);
+                }
+            }
+            if !pressed.0.is_empty() {
+                operation.custom(&mut pressed, None);
+            }
+        }
+        if let Some(target) = &self.target {
+            if !state.typed.is_empty() {
+                let mut typed = Typing(target.clone(), std::mem::take(&mut state.typed));
+                operation.custom(&mut typed, None);
+                state.typed = typed.1;
+            }
+        }
+    }
+
+    fn on_event(
+        &mut self,
+        tree: &mut Tree,
+        event: Event,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        _renderer: &Renderer,
+        _clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+        _viewport: &Rectangle,
+    ) -> event::Status {
+        let state = tree.state.downcast_mut::<State>();
+        let position = match event {
+            Event::Touch(
+                touch::Event::FingerPressed { position, .. }
+                | touch::Event::FingerLifted { position, .. },
+            ) => Some(position),
+            _ => cursor.position(),
+        };
+        let over = position.and_then(|position| key_at(layout, position));
+        match event {
+            Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left))
+            | Event::Touch(touch::Event::FingerPressed { .. }) => {
+                let Some(key) = over else {
+                    return event::Status::Ignored;
+                };
+                state.pressed = Some(key);
+                // Navigating goes on from the key pressed last.
+                if state.selected.is_some() {
+                    state.selected = Some(key);
+                }
+                event::Status::Captured
+            }
+            Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left))
+            | Event::Touch(touch::Event::FingerLifted { .. }) => {
+                let Some(pressed) = state.pressed.take() else {
+                    return event::Status::Ignored;
+                };
+                if over == Some(pressed) {
+                    let mut messages = Vec::new();
+                    self.press(state, pressed, &mut messages);
+                    messages
+                        .into_iter()
+                        .for_each(|message| shell.publish(message));
+                    shell.invalidate_layout();
+                }
+                event::Status::Captured
+            }
+            Event::Touch(touch::Event::FingerLost { .. }) => {
+                state.pressed = None;
+                event::Status::Ignored
+            }
+            _ => event::Status::Ignored,
+        }
+    }
+
+    fn mouse_interaction(
+        &self,
+        _tree: &Tree,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        _viewport: &Rectangle,
+        _renderer: &Renderer,
+    ) -> mouse::Interaction {
+        match cursor
+            .position()
+            .and_then(|position| key_at(layout, position))
+        {
+            Some(_) => mouse::Interaction::Pointer,
+            None => mouse::Interaction::Idle,
+        }
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        _style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+    ) {
+        let state = tree.state.downcast_ref::<State>();
+        let over = cursor
+            .position()
+            .and_then(|position| key_at(layout, position));
+        let text_size = self.text_size.unwrap_or_else(|| renderer.default_size());
+        for ((r, row), row_layout) in self
+            .rows(state.page)
+            .into_iter()
+            .enumerate()
+            .zip(layout.children())
+        {
+            for ((c, (key, _)), key_layout) in
+                row.into_iter().enumerate().zip(row_layout.children())
+            {
+                let style = match key {
+                    Key::Done => &self.done_style,
+                    _ => &self.style,
+                };
+                let appearance = if state.pressed == Some((r, c)) && over == Some((r, c)) {
+                    theme.pressed(style)
+                } else if over == Some((r, c)) || state.selected == Some((r, c)) {
+                    theme.hovered(style)
+                } else {
+                    theme.active(style)
+                };
+                let bounds = key_layout.bounds() + appearance.shadow_offset;
+                renderer.fill_quad(
+                    Quad {
+                        bounds,
+                        border: appearance.border,
+                        shadow: appearance.shadow,
+                    },
+                    appearance
+                        .background
+                        .unwrap_or(Background::Color(Color::TRANSPARENT)),
+                );
+                renderer.fill_text(
+                    text::Text {
+                        content: self.label(key, state.page),
+                        bounds: bounds.size(),
+                        size: text_size,
+                        line_height: LineHeight::default(),
+                        font: renderer.default_font(),
+                        horizontal_alignment: alignment::Horizontal::Center,
+                        vertical_alignment: alignment::Vertical::Center,
+                        shaping: Shaping::Advanced,
+                    },
+                    bounds.center(),
+                    appearance.text_color,
+                    *viewport,
+                );
+            }
+        }
+    }
+}
+
+impl<'a, Message> From<VirtualKeyboard<'a, Message>> for Element<'a, Message, Theme, Renderer>
+where
+    Message: Clone + 'static,
+{
+    fn from(keyboard: VirtualKeyboard<'a, Message>) -> Self {
+        Element::new(keyboard)
+    }
+}
+
+// The row and column of the key under `position`.
+fn key_at(layout: Layout<'_>, position: Point) -> Option<(usize, usize)> {
+    layout.children().enumerate().find_map(|(r, row)| {
+        row.children()
+            .position(|key| key.bounds().contains(position))
+            .map(|c| (r, c))
+    })
+}
+
+// The navigation sent to a keyboard, handed to it by `Navigate`.
+struct Navigation(Vec<KeyboardNavigation>);
+
+// The messages of the keys pressed by navigating, taken by `Navigate`.
+struct Pressed<M>(Vec<M>);
+
+// What a keyboard typed into the text input with the id, taken by `collect_typing`.
+struct Typing(text_input::Id, Vec<Typed>);
+
+// The navigation sent to keyboards with `IcedContext::navigate_virtual_keyboard`, until
+// they're displayed.
+#[derive(Default)]
+pub struct Navigations(Vec<(Id, KeyboardNavigation)>);
+
+impl Navigations {
+    pub fn push(&mut self, id: Id, navigation: KeyboardNavigation) {
+        self.0.push((id, navigation));
+    }
+
+    // Hand the navigation to the keyboards of the UI, before it handles this frame's
+    // events, collecting the messages of the keys it pressed.
+    pub fn apply<M: 'static>(
+        &mut self,
+        ui: &mut UserInterface<'_, M, Theme, Renderer>,
+        renderer: &Renderer,
+        messages: &mut Vec<M>,
+    ) {
+        if self.0.is_empty() {
+            return;
+        }
+        ui.operate(
+            renderer,
+            &mut Navigate {
+                navigations: &mut self.0,
+                messages,
+            },
+        );
+    }
+}
+
+struct Navigate<'a, M> {
+    navigations: &'a mut Vec<(Id, KeyboardNavigation)>,
+    messages: &'a mut Vec<M>,
+}
+
+impl<M: 'static> Operation<M> for Navigate<'_, M> {
+    fn container(
+        &mut self,
+        _id: Option<&Id>,
+        _bounds: Rectangle,
+        operate_on_children: &mut dyn FnMut(&mut dyn Operation<M>),
+    ) {
+        operate_on_children(self);
+    }
+
+    fn custom(&mut self, state: &mut dyn Any, id: Option<&Id>) {
+        if let (Some(navigation), Some(id)) = (state.downcast_mut::<Navigation>(), id) {
+            self.navigations.retain(|(target, pressed)| {
+                let matches = target == id;
+                if matches {
+                    navigation.0.push(*pressed);
+                }
+                !matches
+            });
+        } else if let Some(pressed) = state.downcast_mut::<Pressed<M>>() {
+            self.messages.append(&mut pressed.0);
+        }
+    }
+}
+
+// Type what the keyboards of the UI typed into the text inputs they're bound to, once it
+// handled this frame's events, by focusing each and handing it the keys as key presses.
+pub fn type_into_targets<M: 'static>(
+    ui: &mut UserInterface<'_, M, Theme, Renderer>,
+    renderer: &mut Renderer,
+    clipboard: &mut dyn Clipboard,
+    messages: &mut Vec<M>,
+) {
+    let mut collect = CollectTyping(Vec::new());
+    ui.operate(renderer, &mut collect);
+    for (target, typed) in collect.0 {
+        ui.operate(renderer, &mut Focus(target.into()));
+        let events: Vec<_> = typed.into_iter().map(Typed::event).collect();
+        ui.update(
+            &events,
+            mouse::Cursor::Unavailable,
+            renderer,
+            clipboard,
+            messages,
+        );
+    }
+}
+
+struct CollectTyping(Vec<(text_input::Id, Vec<Typed>)>);
+
+impl<M> Operation<M> for CollectTyping {
+    fn container(
+        &mut self,
+        _id: Option<&Id>,
+        _bounds: Rectangle,
+        operate_on_children: &mut dyn FnMut(&mut dyn Operation<M>),
+    ) {
+        operate_on_children(self);
+    }
+
+    fn custom(&mut self, state: &mut dyn Any, _id: Option<&Id>) {
+        if let Some(Typing(target, typed)) = state.downcast_mut::<Typing>() {
+            self.0.push((target.clone(), std::mem::take(typed)));
+        }
+    }
+}
+
+// Focus the text input with the id, unless it already is, as focusing it moves its caret
+// to the end.
+struct Focus(Id);
+
+impl<M> Operation<M> for Focus {
+    fn container(
+        &mut self,
+        _id: Option<&Id>,
+        _bounds: Rectangle,
+        operate_on_children: &mut dyn FnMut(&mut dyn Operation<M>),
+    ) {
+        operate_on_children(self);
+    }
+
+    fn focusable(&mut self, state: &mut dyn operation::Focusable, id: Option<&Id>) {
+        if id != Some(&self.0) {
+            state.unfocus();
+        } else if !state.is_focused() {
+            state.focus();
+        }
+    }
+}
@@ -0,0 +1,1319 @@
+//! Iced's own widgets live in [`crate::iced::widget`].
+
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+use iced_core::alignment;
+use iced_core::event::{self, Event};
+use iced_core::layout;
+use iced_core::mouse::{self, click};
+use iced_core::overlay;
+use iced_core::renderer;
+use iced_core::text;
+use iced_core::widget::text::Text;
+use iced_core::widget::{tree, Id, Operation, Tree};
+use iced_core::{
+    Clipboard, Element, Layout, Length, Padding, Pixels, Point, Rectangle, Shell, Size, Vector,
+    Widget,
+};
+use iced_widget::container::{self, Container};
+use iced_widget::scrollable::{self, Scrollable};
+use iced_widget::style::Theme;
+
+#[cfg(feature = "color-picker")]
+pub use crate::color_picker::{color_picker, ColorPicker};
+pub use crate::loading::{loading_bar, LoadingBar};
+#[cfg(feature = "reflect-ui")]
+pub use crate::reflect::{reflect_editor, ReflectEditor};
+pub use crate::rich_text::{rich_text, span, RichText, Span};
+pub use crate::stack::{stack, Stack};
+#[cfg(feature = "virtual-keyboard")]
+pub use crate::virtual_keyboard::{
+    virtual_keyboard, KeyLabels, KeyboardNavigation, VirtualKeyboard, VirtualKeys,
+};
+
+/// A region of a custom titlebar or window border, see [`drag_region`].
+#[allow(missing_debug_implementations)]
+pub struct DragRegion<'a, Message, Theme, Renderer> {
+    content: Element<'a, Message, Theme, Renderer>,
+    on_drag: Option<Message>,
+    on_double_click: Option<Message>,
+    interaction: Option<mouse::Interaction>,
+}
+
+/// Wrap `content` in a region that produces a message when pressed, to start dragging
+/// or resizing the window from it with [`IcedContext::start_window_drag`] or
+/// [`IcedContext::start_window_resize`].
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_iced::iced::widget::text;
+/// # use bevy_iced::widget::drag_region;
+/// # use bevy_iced::IcedContext;
+/// # #[derive(Event, Clone)]
+/// # enum UiMessage {
+/// #     MoveWindow,
+/// #     ToggleMaximized,
+/// # }
+/// # fn ui_system(mut ctx: IcedContext<UiMessage>) {
+/// # let titlebar = text("Editor");
+/// ctx.display(
+///     drag_region(titlebar)
+///         .on_drag(UiMessage::MoveWindow)
+///         .on_double_click(UiMessage::ToggleMaximized),
+/// );
+/// # }
+/// ```
+///
+/// [`IcedContext::start_window_drag`]: crate::IcedContext::start_window_drag
+/// [`IcedContext::start_window_resize`]: crate::IcedContext::start_window_resize
+pub fn drag_region<'a, Message, Theme, Renderer>(
+    content: impl Into<Element<'a, Message, Theme, Renderer>>,
+) -> DragRegion<'a, Message, Theme, Renderer> {
+    DragRegion {
+        content: content.into(),
+        on_drag: None,
+        on_double_click: None,
+        interaction: None,
+    }
+}
+
+impl<'a, Message, Theme, Renderer> DragRegion<'a, Message, Theme, Renderer> {
+    /// The message produced when the left mouse button is pressed over the region.
+    #[must_use]
+    pub fn on_drag(mut self, message: Message) -> Self {
+        self.on_drag = Some(message);
+        self
+    }
+
+    /// The message produced when the region is double-clicked, e.g. to maximize the window.
+    #[must_use]
+    pub fn on_double_click(mut self, message: Message) -> Self {
+        self.on_double_click = Some(message);
+        self
+    }
+
+    /// The cursor to show over the region, e.g. a resize cursor on window edges.
+    #[must_use]
+    pub fn interaction(mut self, interaction: mouse::Interaction) -> Self {
+        self.interaction = Some(interaction);
+        self
+    }
+}
+
+#[derive(Default)]
+struct State {
+    last_click: Option<mouse::Click>,
+}
+
+impl<'a, Message, Theme, Renderer> Widget<Message, Theme, Renderer>
+    for DragRegion<'a, Message, Theme, Renderer>
+where
+    Renderer: renderer::Renderer,
+    Message: Clone,
+{
+    fn tag(&self) -> tree::Tag {
+        tree::Tag::of::<State>()
+    }
+
+    fn state(&self) -> tree::State {
+        tree::State::new(State::default())
+    }
+
+    fn children(&self) -> Vec<Tree> {
+        vec![Tree::new(&self.content)]
+    }
+
+    fn diff(&self, tree: &mut Tree) {
+        tree.diff_children(std::slice::from_ref(&self.content));
+    }
+
+    fn size(&self) -> Size<Length> {
+        self.content.as_widget().size()
+    }
+
+    fn layout(
+        &self,
+        tree: &mut Tree,
+        renderer: &Renderer,
+        limits: &layout::Limits,
+    ) -> layout::Node {
+        self.content
+            .as_widget()
+            .layout(&mut tree.children[0], renderer, limits)
+    }
+
+    fn operate(
+        &self,
+        tree: &mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        operation: &mut dyn Operation<Message>,
+    ) {
+        self.content
+            .as_widget()
+            .operate(&mut tree.children[0], layout, renderer, operation);
+    }
+
+    fn on_event(
+        &mut self,
+        tree: &mut Tree,
+        event: Event,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+        viewport: &Rectangle,
+    ) -> event::Status {
+        // Buttons and other interactive content take precedence over dragging.
+        if let event::Status::Captured = self.content.as_widget_mut().on_event(
+            &mut tree.children[0],
+            event.clone(),
+            layout,
+            cursor,
+            renderer,
+            clipboard,
+            shell,
+            viewport,
+        ) {
+            return event::Status::Captured;
+        }
+
+        let Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) = event else {
+            return event::Status::Ignored;
+        };
+        let Some(position) = cursor.position_over(layout.bounds()) else {
+            return event::Status::Ignored;
+        };
+
+        let state: &mut State = tree.state.downcast_mut();
+        let click = mouse::Click::new(position, state.last_click);
+        state.last_click = Some(click);
+
+        let message = match click.kind() {
+            click::Kind::Double => self.on_double_click.as_ref(),
+            _ => self.on_drag.as_ref(),
+        };
+        match message {
+            Some(message) => {
+                shell.publish(message.clone());
+                event::Status::Captured
+            }
+            None => event::Status::Ignored,
+        }
+    }
+
+    fn mouse_interaction(
+        &self,
+        tree: &Tree,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+        renderer: &Renderer,
+    ) -> mouse::Interaction {
+        let content_interaction = self.content.as_widget().mouse_interaction(
+            &tree.children[0],
+            layout,
+            cursor,
+            viewport,
+            renderer,
+        );
+
+        match (self.interaction, content_interaction) {
+            (Some(interaction), mouse::Interaction::Idle) if cursor.is_over(layout.bounds()) => {
+                interaction
+            }
+            _ => content_interaction,
+        }
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+    ) {
+        self.content.as_widget().draw(
+            &tree.children[0],
+            renderer,
+            theme,
+            style,
+            layout,
+            cursor,
+            viewport,
+        );
+    }
+
+    fn overlay<'b>(
+        &'b mut self,
+        tree: &'b mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        translation: Vector,
+    ) -> Option<overlay::Element<'b, Message, Theme, Renderer>> {
+        self.content
+            .as_widget_mut()
+            .overlay(&mut tree.children[0], layout, renderer, translation)
+    }
+}
+
+impl<'a, Message, Theme, Renderer> From<DragRegion<'a, Message, Theme, Renderer>>
+    for Element<'a, Message, Theme, Renderer>
+where
+    Message: 'a + Clone,
+    Theme: 'a,
+    Renderer: 'a + renderer::Renderer,
+{
+    fn from(region: DragRegion<'a, Message, Theme, Renderer>) -> Self {
+        Element::new(region)
+    }
+}
+
+/// A widget whose layout is published in [`IcedWidgetInfo`](crate::IcedWidgetInfo),
+/// see [`tracked`].
+#[allow(missing_debug_implementations)]
+pub struct Tracked<'a, Message, Theme, Renderer> {
+    id: Id,
+    content: Element<'a, Message, Theme, Renderer>,
+}
+
+/// Publish where `content` is laid out, and whether it's on screen, in the
+/// [`IcedWidgetInfo`](crate::IcedWidgetInfo) resource under `id`, e.g. for a tutorial
+/// to point at a button.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_iced::iced::advanced::widget::Id;
+/// # use bevy_iced::iced::widget::button;
+/// # use bevy_iced::widget::tracked;
+/// # use bevy_iced::IcedContext;
+/// # #[derive(Event, Clone)]
+/// # enum UiMessage {
+/// #     Craft,
+/// # }
+/// # fn ui_system(mut ctx: IcedContext<UiMessage>) {
+/// ctx.display(tracked(Id::new("craft"), button("Craft").on_press(UiMessage::Craft)));
+/// # }
+/// ```
+pub fn tracked<'a, Message, Theme, Renderer>(
+    id: impl Into<Id>,
+    content: impl Into<Element<'a, Message, Theme, Renderer>>,
+) -> Tracked<'a, Message, Theme, Renderer> {
+    Tracked {
+        id: id.into(),
+        content: content.into(),
+    }
+}
+
+impl<'a, Message, Theme, Renderer> Widget<Message, Theme, Renderer>
+    for Tracked<'a, Message, Theme, Renderer>
+where
+    Renderer: renderer::Renderer,
+{
+    fn tag(&self) -> tree::Tag {
+        self.content.as_widget().tag()
+    }
+
+    fn state(&self) -> tree::State {
+        self.content.as_widget().state()
+    }
+
+    fn children(&self) -> Vec<Tree> {
+        self.content.as_widget().children()
+    }
+
+    fn diff(&self, tree: &mut Tree) {
+        self.content.as_widget().diff(tree);
+    }
+
+    fn size(&self) -> Size<Length> {
+        self.content.as_widget().size()
+    }
+
+    fn layout(
+        &self,
+        tree: &mut Tree,
+        renderer: &Renderer,
+        limits: &layout::Limits,
+    ) -> layout::Node {
+        self.content.as_widget().layout(tree, renderer, limits)
+    }
+
+    fn operate(
+        &self,
+        tree: &mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        operation: &mut dyn Operation<Message>,
+    ) {
+        operation.custom(
+            &mut crate::tracking::Marker(layout.bounds()),
+            Some(&self.id),
+        );
+        self.content
+            .as_widget()
+            .operate(tree, layout, renderer, operation);
+    }
+
+    fn on_event(
+        &mut self,
+        tree: &mut Tree,
+        event: Event,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+        viewport: &Rectangle,
+    ) -> event::Status {
+        self.content.as_widget_mut().on_event(
+            tree, event, layout, cursor, renderer, clipboard, shell, viewport,
+        )
+    }
+
+    fn mouse_interaction(
+        &self,
+        tree: &Tree,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+        renderer: &Renderer,
+    ) -> mouse::Interaction {
+        self.content
+            .as_widget()
+            .mouse_interaction(tree, layout, cursor, viewport, renderer)
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+    ) {
+        self.content
+            .as_widget()
+            .draw(tree, renderer, theme, style, layout, cursor, viewport);
+    }
+
+    fn overlay<'b>(
+        &'b mut self,
+        tree: &'b mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        translation: Vector,
+    ) -> Option<overlay::Element<'b, Message, Theme, Renderer>> {
+        self.content
+            .as_widget_mut()
+            .overlay(tree, layout, renderer, translation)
+    }
+}
+
+impl<'a, Message, Theme, Renderer> From<Tracked<'a, Message, Theme, Renderer>>
+    for Element<'a, Message, Theme, Renderer>
+where
+    Message: 'a,
+    Theme: 'a,
+    Renderer: 'a + renderer::Renderer,
+{
+    fn from(tracked: Tracked<'a, Message, Theme, Renderer>) -> Self {
+        Element::new(tracked)
+    }
+}
+
+/// A scrollable list that only builds the rows on screen, see [`virtual_list`].
+#[allow(missing_debug_implementations)]
+pub struct VirtualList<'a, Message, Theme, Renderer>
+where
+    Theme: scrollable::StyleSheet,
+{
+    row_height: f32,
+    len: usize,
+    view: Box<dyn Fn(usize) -> Element<'a, Message, Theme, Renderer> + 'a>,
+    overscan: usize,
+    id: Option<scrollable::Id>,
+    width: Length,
+    height: Length,
+    style: Option<Theme::Style>,
+}
+
+/// A vertical [`scrollable`](iced_widget::scrollable) of `len` rows, each `row_height`
+/// tall, that only calls `view` for the rows on screen and a few around them, e.g. for
+/// an inventory of thousands of items. It fills the space it's given by default.
+///
+/// The list keeps its scroll position in pixels as `len` changes. Give it an
+/// [`id`](VirtualList::id) to use [`IcedContext::scroll_offset`] and
+/// [`IcedContext::snap_to`] with it.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_iced::iced::widget::text;
+/// # use bevy_iced::widget::virtual_list;
+/// # use bevy_iced::IcedContext;
+/// # #[derive(Event)]
+/// # enum UiMessage {}
+/// # struct Item {
+/// #     name: String,
+/// # }
+/// # fn ui_system(mut ctx: IcedContext<UiMessage>, inventory: Vec<Item>) {
+/// ctx.display(virtual_list(32.0, inventory.len(), |index| {
+///     text(&inventory[index].name).into()
+/// }));
+/// # }
+/// ```
+///
+/// [`IcedContext::scroll_offset`]: crate::IcedContext::scroll_offset
+/// [`IcedContext::snap_to`]: crate::IcedContext::snap_to
+pub fn virtual_list<'a, Message, Theme, Renderer>(
+    row_height: impl Into<Pixels>,
+    len: usize,
+    view: impl Fn(usize) -> Element<'a, Message, Theme, Renderer> + 'a,
+) -> VirtualList<'a, Message, Theme, Renderer>
+where
+    Theme: scrollable::StyleSheet,
+{
+    VirtualList {
+        row_height: row_height.into().0,
+        len,
+        view: Box::new(view),
+        overscan: 4,
+        id: None,
+        width: Length::Fill,
+        height: Length::Fill,
+        style: None,
+    }
+}
+
+impl<'a, Message, Theme, Renderer> VirtualList<'a, Message, Theme, Renderer>
+where
+    Theme: scrollable::StyleSheet,
+{
+    /// The number of rows built above and below the ones on screen, `4` by default.
+    #[must_use]
+    pub fn overscan(mut self, rows: usize) -> Self {
+        self.overscan = rows;
+        self
+    }
+
+    /// The id of the list's scrollable.
+    #[must_use]
+    pub fn id(mut self, id: scrollable::Id) -> Self {
+        self.id = Some(id);
+        self
+    }
+
+    /// Set the width of the list.
+    #[must_use]
+    pub fn width(mut self, width: impl Into<Length>) -> Self {
+        self.width = width.into();
+        self
+    }
+
+    /// Set the height of the list.
+    #[must_use]
+    pub fn height(mut self, height: impl Into<Length>) -> Self {
+        self.height = height.into();
+        self
+    }
+
+    /// Set the style of the list's scrollable.
+    #[must_use]
+    pub fn style(mut self, style: impl Into<Theme::Style>) -> Self {
+        self.style = Some(style.into());
+        self
+    }
+}
+
+// The rows to build, as a range of indices shared between the list and its content.
+type Window = Rc<Cell<(usize, usize)>>;
+
+// The list around the scrollable, which picks the rows to build from its offset.
+struct List<'a, Message, Theme, Renderer>
+where
+    Theme: scrollable::StyleSheet,
+    Renderer: renderer::Renderer,
+{
+    scrollable: Scrollable<'a, Message, Theme, Renderer>,
+    row_height: f32,
+    len: usize,
+    overscan: usize,
+    window: Window,
+}
+
+#[derive(Default)]
+struct ListState {
+    // The offset and height of the scrollable when it was last laid out.
+    offset: f32,
+    height: f32,
+}
+
+impl<'a, Message, Theme, Renderer> List<'a, Message, Theme, Renderer>
+where
+    Theme: scrollable::StyleSheet,
+    Renderer: renderer::Renderer,
+{
+    fn window_at(&self, offset: f32, height: f32) -> (usize, usize) {
+        let row_height = self.row_height.max(1.0);
+        let first = (offset / row_height).floor() as usize;
+        let last = ((offset + height) / row_height).ceil() as usize;
+        (
+            first.saturating_sub(self.overscan).min(self.len),
+            last.saturating_add(self.overscan).min(self.len),
+        )
+    }
+
+    // The offset of the scrollable, which only operations are told.
+    fn offset(&self, tree: &mut Tree, layout: Layout<'_>, renderer: &Renderer) -> f32 {
+        let mut translation = ReadTranslation(Vector::ZERO);
+        self.scrollable
+            .operate(tree, layout, renderer, &mut translation);
+        translation.0.y
+    }
+}
+
+struct ReadTranslation(Vector);
+
+impl<T> Operation<T> for ReadTranslation {
+    fn container(
+        &mut self,
+        _id: Option<&Id>,
+        _bounds: Rectangle,
+        _operate_on_children: &mut dyn FnMut(&mut dyn Operation<T>),
+    ) {
+    }
+
+    fn scrollable(
+        &mut self,
+        _state: &mut dyn iced_core::widget::operation::Scrollable,
+        _id: Option<&Id>,
+        _bounds: Rectangle,
+        translation: Vector,
+    ) {
+        self.0 = translation;
+    }
+}
+
+impl<'a, Message, Theme, Renderer> Widget<Message, Theme, Renderer>
+    for List<'a, Message, Theme, Renderer>
+where
+    Theme: scrollable::StyleSheet,
+    Renderer: renderer::Renderer,
+{
+    fn tag(&self) -> tree::Tag {
+        tree::Tag::of::<ListState>()
+    }
+
+    fn state(&self) -> tree::State {
+        tree::State::new(ListState::default())
+    }
+
+    fn children(&self) -> Vec<Tree> {
+        self.window.set(self.window_at(0.0, 0.0));
+        vec![Tree::new(&self.scrollable as &dyn Widget<_, _, _>)]
+    }
+
+    fn diff(&self, tree: &mut Tree) {
+        // Build the rows that were on screen last frame.
+        let state = tree.state.downcast_ref::<ListState>();
+        self.window.set(self.window_at(state.offset, state.height));
+        tree.children[0].diff(&self.scrollable as &dyn Widget<_, _, _>);
+    }
+
+    fn size(&self) -> Size<Length> {
+        self.scrollable.size()
+    }
+
+    fn layout(
+        &self,
+        tree: &mut Tree,
+        renderer: &Renderer,
+        limits: &layout::Limits,
+    ) -> layout::Node {
+        let mut node = self
+            .scrollable
+            .layout(&mut tree.children[0], renderer, limits);
+        let offset = self.offset(&mut tree.children[0], Layout::new(&node), renderer);
+        let height = node.size().height;
+        // The height of the content doesn't depend on the rows that are built, so the
+        // rows are right after laying out again once.
+        let window = self.window_at(offset, height);
+        if window != self.window.get() {
+            self.window.set(window);
+            node = self
+                .scrollable
+                .layout(&mut tree.children[0], renderer, limits);
+        }
+        *tree.state.downcast_mut::<ListState>() = ListState { offset, height };
+        node
+    }
+
+    fn operate(
+        &self,
+        tree: &mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        operation: &mut dyn Operation<Message>,
+    ) {
+        self.scrollable
+            .operate(&mut tree.children[0], layout, renderer, operation);
+    }
+
+    fn on_event(
+        &mut self,
+        tree: &mut Tree,
+        event: Event,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+        viewport: &Rectangle,
+    ) -> event::Status {
+        let status = self.scrollable.on_event(
+            &mut tree.children[0],
+            event,
+            layout,
+            cursor,
+            renderer,
+            clipboard,
+            shell,
+            viewport,
+        );
+        // Scrolling doesn't invalidate the layout, but building other rows does.
+        let offset = self.offset(&mut tree.children[0], layout, renderer);
+        if self.window_at(offset, layout.bounds().height) != self.window.get() {
+            shell.invalidate_layout();
+        }
+        status
+    }
+
+    fn mouse_interaction(
+        &self,
+        tree: &Tree,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+        renderer: &Renderer,
+    ) -> mouse::Interaction {
+        self.scrollable
+            .mouse_interaction(&tree.children[0], layout, cursor, viewport, renderer)
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+    ) {
+        self.scrollable.draw(
+            &tree.children[0],
+            renderer,
+            theme,
+            style,
+            layout,
+            cursor,
+            viewport,
+        );
+    }
+
+    fn overlay<'b>(
+        &'b mut self,
+        tree: &'b mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        translation: Vector,
+    ) -> Option<overlay::Element<'b, Message, Theme, Renderer>> {
+        self.scrollable
+            .overlay(&mut tree.children[0], layout, renderer, translation)
+    }
+}
+
+// The content of the list's scrollable, holding the rows built so far this frame.
+struct Rows<'a, Message, Theme, Renderer> {
+    row_height: f32,
+    len: usize,
+    view: Box<dyn Fn(usize) -> Element<'a, Message, Theme, Renderer> + 'a>,
+    window: Window,
+    built: RefCell<Built<'a, Message, Theme, Renderer>>,
+}
+
+struct Built<'a, Message, Theme, Renderer> {
+    first: usize,
+    rows: Vec<Element<'a, Message, Theme, Renderer>>,
+}
+
+// The index of the row whose state is the first child of the tree.
+struct RowsState {
+    first: usize,
+}
+
+impl<'a, Message, Theme, Renderer> Rows<'a, Message, Theme, Renderer>
+where
+    Renderer: renderer::Renderer,
+{
+    // Build the rows of the window, keeping those already built this frame, and move the
+    // state of every row along with it.
+    fn build(&self, tree: &mut Tree) {
+        let (first, last) = self.window.get();
+        let built = &mut *self.built.borrow_mut();
+        let state = tree.state.downcast_mut::<RowsState>();
+        let is_built = built.first == first && built.rows.len() == last - first;
+        if is_built && state.first == first && tree.children.len() == built.rows.len() {
+            return;
+        }
+
+        let mut rows: Vec<_> = std::mem::take(&mut built.rows)
+            .into_iter()
+            .map(Some)
+            .collect();
+        let mut trees: Vec<_> = std::mem::take(&mut tree.children)
+            .into_iter()
+            .map(Some)
+            .collect();
+        for index in first..last {
+            let kept = index
+                .checked_sub(built.first)
+                .and_then(|i| rows.get_mut(i)?.take());
+            let is_new = kept.is_none();
+            let row = kept.unwrap_or_else(|| (self.view)(index));
+            let reused = index
+                .checked_sub(state.first)
+                .and_then(|i| trees.get_mut(i)?.take());
+            tree.children.push(match reused {
+                Some(mut tree) => {
+                    if is_new {
+                        tree.diff(&row);
+                    }
+                    tree
+                }
+                None => Tree::new(&row),
+            });
+            built.rows.push(row);
+        }
+        built.first = first;
+        state.first = first;
+    }
+}
+
+impl<'a, Message, Theme, Renderer> Widget<Message, Theme, Renderer>
+    for Rows<'a, Message, Theme, Renderer>
+where
+    Renderer: renderer::Renderer,
+{
+    fn tag(&self) -> tree::Tag {
+        tree::Tag::of::<RowsState>()
+    }
+
+    fn state(&self) -> tree::State {
+        tree::State::new(RowsState {
+            first: self.window.get().0,
+        })
+    }
+
+    fn children(&self) -> Vec<Tree> {
+        let mut tree = Tree {
+            tag: self.tag(),
+            state: self.state(),
+            children: Vec::new(),
+        };
+        self.build(&mut tree);
+        tree.children
+    }
+
+    fn diff(&self, tree: &mut Tree) {
+        self.build(tree);
+    }
+
+    fn size(&self) -> Size<Length> {
+        Size::new(Length::Fill, Length::Shrink)
+    }
+
+    fn layout(
+        &self,
+        tree: &mut Tree,
+        renderer: &Renderer,
+        limits: &layout::Limits,
+    ) -> layout::Node {
+        self.build(tree);
+        let width = limits.max().width;
+        let row_limits = layout::Limits::new(Size::ZERO, Size::new(width, self.row_height));
+        let built = self.built.borrow();
+        let children = built
+            .rows
+            .iter()
+            .zip(&mut tree.children)
+            .enumerate()
+            .map(|(i, (row, tree))| {
+                row.as_widget()
+                    .layout(tree, renderer, &row_limits)
+                    .move_to(Point::new(0.0, (built.first + i) as f32 * self.row_height))
+            })
+            .collect();
+        layout::Node::with_children(
+            Size::new(width, self.len as f32 * self.row_height),
+            children,
+        )
+    }
+
+    fn operate(
+        &self,
+        tree: &mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        operation: &mut dyn Operation<Message>,
+    ) {
+        operation.container(None, layout.bounds(), &mut |operation| {
+            let built = self.built.borrow();
+            for ((row, tree), layout) in built
+                .rows
+                .iter()
+                .zip(&mut tree.children)
+                .zip(layout.children())
+            {
+                row.as_widget().operate(tree, layout, renderer, operation);
+            }
+        });
+    }
+
+    fn on_event(
+        &mut self,
+        tree: &mut Tree,
+        event: Event,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+        viewport: &Rectangle,
+    ) -> event::Status {
+        self.built
+            .get_mut()
+            .rows
+            .iter_mut()
+            .zip(&mut tree.children)
+            .zip(layout.children())
+            .map(|((row, tree), layout)| {
+                row.as_widget_mut().on_event(
+                    tree,
+                    event.clone(),
+                    layout,
+                    cursor,
+                    renderer,
+                    clipboard,
+                    shell,
+                    viewport,
+                )
+            })
+            .fold(event::Status::Ignored, event::Status::merge)
+    }
+
+    fn mouse_interaction(
+        &self,
+        tree: &Tree,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+        renderer: &Renderer,
+    ) -> mouse::Interaction {
+        self.built
+            .borrow()
+            .rows
+            .iter()
+            .zip(&tree.children)
+            .zip(layout.children())
+            .map(|((row, tree), layout)| {
+                row.as_widget()
+                    .mouse_interaction(tree, layout, cursor, viewport, renderer)
+            })
+            .max()
+            .unwrap_or_default()
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+    ) {
+        let built = self.built.borrow();
+        for ((row, tree), layout) in built.rows.iter().zip(&tree.children).zip(layout.children()) {
+            // The overscan is only built, to be ready when it's scrolled into view.
+            if layout.bounds().intersects(viewport) {
+                row.as_widget()
+                    .draw(tree, renderer, theme, style, layout, cursor, viewport);
+            }
+        }
+    }
+
+    fn overlay<'b>(
+        &'b mut self,
+        tree: &'b mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        translation: Vector,
+    ) -> Option<overlay::Element<'b, Message, Theme, Renderer>> {
+        overlay::from_children(
+            &mut self.built.get_mut().rows,
+            tree,
+            layout,
+            renderer,
+            translation,
+        )
+    }
+}
+
+impl<'a, Message, Theme, Renderer> From<VirtualList<'a, Message, Theme, Renderer>>
+    for Element<'a, Message, Theme, Renderer>
+where
+    Message: 'a,
+    Theme: 'a + scrollable::StyleSheet,
+    Renderer: 'a + renderer::Renderer,
+{
+    fn from(list: VirtualList<'a, Message, Theme, Renderer>) -> Self {
+        let window = Window::default();
+        let rows = Rows {
+            row_height: list.row_height,
+            len: list.len,
+            view: list.view,
+            window: window.clone(),
+            built: RefCell::new(Built {
+                first: 0,
+                rows: Vec::new(),
+            }),
+        };
+        let mut scrollable = Scrollable::new(rows).width(list.width).height(list.height);
+        if let Some(id) = list.id {
+            scrollable = scrollable.id(id);
+        }
+        if let Some(style) = list.style {
+            scrollable = scrollable.style(style);
+        }
+        Element::new(List {
+            scrollable,
+            row_height: list.row_height,
+            len: list.len,
+            overscan: list.overscan,
+            window,
+        })
+    }
+}
+
+impl<'a, Message, Theme, Renderer> From<Rows<'a, Message, Theme, Renderer>>
+    for Element<'a, Message, Theme, Renderer>
+where
+    Message: 'a,
+    Theme: 'a,
+    Renderer: 'a + renderer::Renderer,
+{
+    fn from(rows: Rows<'a, Message, Theme, Renderer>) -> Self {
+        Element::new(rows)
+    }
+}
+
+/// A hyperlink, see [`link`].
+#[allow(missing_debug_implementations)]
+pub struct Link<'a, Message, Renderer>
+where
+    Renderer: text::Renderer,
+{
+    label: Text<'a, Theme, Renderer>,
+    url: String,
+    on_press: Option<Message>,
+}
+
+/// An underlined label that shows a pointer cursor and follows `url` when clicked.
+///
+/// With the `links` feature, the URL is opened directly, in the system browser or in a
+/// new tab on the web. Use [`Link::on_press`] to handle the click yourself instead, e.g.
+/// to ask for confirmation before leaving the game.
+pub fn link<'a, Message, Renderer>(
+    label: impl Into<std::borrow::Cow<'a, str>>,
+    url: impl Into<String>,
+) -> Link<'a, Message, Renderer>
+where
+    Renderer: text::Renderer,
+{
+    Link {
+        label: Text::new(label),
+        url: url.into(),
+        on_press: None,
+    }
+}
+
+impl<'a, Message, Renderer> Link<'a, Message, Renderer>
+where
+    Renderer: text::Renderer,
+{
+    /// Produce `message` when the link is clicked instead of opening its URL.
+    #[must_use]
+    pub fn on_press(mut self, message: Message) -> Self {
+        self.on_press = Some(message);
+        self
+    }
+
+    /// Set the text size of the label.
+    #[must_use]
+    pub fn size(mut self, size: impl Into<iced_core::Pixels>) -> Self {
+        self.label = self.label.size(size);
+        self
+    }
+
+    /// The URL the link points to.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+}
+
+impl<'a, Message, Renderer> Widget<Message, Theme, Renderer> for Link<'a, Message, Renderer>
+where
+    Renderer: text::Renderer,
+    Message: Clone,
+{
+    fn tag(&self) -> tree::Tag {
+        Widget::<Message, Theme, Renderer>::tag(&self.label)
+    }
+
+    fn state(&self) -> tree::State {
+        Widget::<Message, Theme, Renderer>::state(&self.label)
+    }
+
+    fn size(&self) -> Size<Length> {
+        Widget::<Message, Theme, Renderer>::size(&self.label)
+    }
+
+    fn layout(
+        &self,
+        tree: &mut Tree,
+        renderer: &Renderer,
+        limits: &layout::Limits,
+    ) -> layout::Node {
+        Widget::<Message, Theme, Renderer>::layout(&self.label, tree, renderer, limits)
+    }
+
+    fn on_event(
+        &mut self,
+        _tree: &mut Tree,
+        event: Event,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        _renderer: &Renderer,
+        _clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+        _viewport: &Rectangle,
+    ) -> event::Status {
+        let Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) = event else {
+            return event::Status::Ignored;
+        };
+        if !cursor.is_over(layout.bounds()) {
+            return event::Status::Ignored;
+        }
+        match &self.on_press {
+            Some(message) => shell.publish(message.clone()),
+            None => open_url(&self.url),
+        }
+        event::Status::Captured
+    }
+
+    fn mouse_interaction(
+        &self,
+        _tree: &Tree,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        _viewport: &Rectangle,
+        _renderer: &Renderer,
+    ) -> mouse::Interaction {
+        if cursor.is_over(layout.bounds()) {
+            mouse::Interaction::Pointer
+        } else {
+            mouse::Interaction::Idle
+        }
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        _style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+    ) {
+        let color = theme.palette().primary;
+        let style = renderer::Style { text_color: color };
+        Widget::<Message, Theme, Renderer>::draw(
+            &self.label,
+            tree,
+            renderer,
+            theme,
+            &style,
+            layout,
+            cursor,
+            viewport,
+        );
+
+        let bounds = layout.bounds();
+        renderer.fill_quad(
+            renderer::Quad {
+                bounds: Rectangle {
+                    x: bounds.x,
+                    y: bounds.y + bounds.height - 2.0,
+                    width: bounds.width,
+                    height: 1.0,
+                },
+                ..Default::default()
+            },
+            color,
+        );
+    }
+}
+
+impl<'a, Message, Renderer> From<Link<'a, Message, Renderer>>
+    for Element<'a, Message, Theme, Renderer>
+where
+    Message: 'a + Clone,
+    Renderer: 'a + text::Renderer,
+{
+    fn from(link: Link<'a, Message, Renderer>) -> Self {
+        Element::new(link)
+    }
+}
+
+#[cfg(all(feature = "links", not(target_arch = "wasm32")))]
+fn open_url(url: &str) {
+    if let Err(err) = open::that_detached(url) {
+        bevy_utils::tracing::warn!("Failed to open {url}: {err}");
+    }
+}
+
+#[cfg(all(feature = "links", target_arch = "wasm32"))]
+fn open_url(url: &str) {
+    let opened = web_sys::window().and_then(|window| {
+        window
+            .open_with_url_and_target(url, "_blank")
+            .ok()
+            .flatten()
+    });
+    if opened.is_none() {
+        bevy_utils::tracing::warn!("Failed to open {url}");
+    }
+}
+
+#[cfg(not(feature = "links"))]
+fn open_url(url: &str) {
+    bevy_utils::tracing::warn!("Enable the `links` feature of `bevy_iced` to open {url}, or handle it with `Link::on_press`");
+}
+
+/// Where [`anchored`] places its content within the space it's given.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Anchor {
+    /// The top left corner.
+    TopLeft,
+    /// The middle of the top edge.
+    Top,
+    /// The top right corner.
+    TopRight,
+    /// The middle of the left edge.
+    Left,
+    /// The center.
+    #[default]
+    Center,
+    /// The middle of the right edge.
+    Right,
+    /// The bottom left corner.
+    BottomLeft,
+    /// The middle of the bottom edge.
+    Bottom,
+    /// The bottom right corner.
+    BottomRight,
+}
+
+impl Anchor {
+    pub(crate) fn alignment(self) -> (alignment::Horizontal, alignment::Vertical) {
+        use alignment::{Horizontal, Vertical};
+        match self {
+            Self::TopLeft => (Horizontal::Left, Vertical::Top),
+            Self::Top => (Horizontal::Center, Vertical::Top),
+            Self::TopRight => (Horizontal::Right, Vertical::Top),
+            Self::Left => (Horizontal::Left, Vertical::Center),
+            Self::Center => (Horizontal::Center, Vertical::Center),
+            Self::Right => (Horizontal::Right, Vertical::Center),
+            Self::BottomLeft => (Horizontal::Left, Vertical::Bottom),
+            Self::Bottom => (Horizontal::Center, Vertical::Bottom),
+            Self::BottomRight => (Horizontal::Right, Vertical::Bottom),
+        }
+    }
+}
+
+/// Place `content` at `anchor` within the space it's given, e.g. the whole viewport for
+/// the root of a UI. `offset` is the margin to the anchored edges, in logical pixels;
+/// along a centered axis, it moves the content right or down from the center instead.
+///
+/// Content larger than the space left by the margin is shrunk to fit it, so it never
+/// reaches past the viewport.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_iced::iced::widget::text;
+/// # use bevy_iced::iced::Vector;
+/// # use bevy_iced::widget::{anchored, Anchor};
+/// # use bevy_iced::IcedContext;
+/// # #[derive(Event)]
+/// # enum UiMessage {}
+/// # fn ui_system(mut ctx: IcedContext<UiMessage>) {
+/// # let minimap = text("Minimap");
+/// ctx.display(anchored(Anchor::BottomRight, Vector::new(16.0, 16.0), minimap));
+/// # }
+/// ```
+pub fn anchored<'a, Message, Theme, Renderer>(
+    anchor: Anchor,
+    offset: Vector,
+    content: impl Into<Element<'a, Message, Theme, Renderer>>,
+) -> Element<'a, Message, Theme, Renderer>
+where
+    Message: 'a,
+    Theme: container::StyleSheet + 'a,
+    Renderer: renderer::Renderer + 'a,
+{
+    use alignment::{Horizontal, Vertical};
+    let (horizontal, vertical) = anchor.alignment();
+    // Padding twice the offset on one side of a centered axis moves the content by it.
+    let (left, right) = match horizontal {
+        Horizontal::Left => (offset.x, 0.0),
+        Horizontal::Right => (0.0, offset.x),
+        Horizontal::Center => ((2.0 * offset.x).max(0.0), (-2.0 * offset.x).max(0.0)),
+    };
+    let (top, bottom) = match vertical {
+        Vertical::Top => (offset.y, 0.0),
+        Vertical::Bottom => (0.0, offset.y),
+        Vertical::Center => ((2.0 * offset.y).max(0.0), (-2.0 * offset.y).max(0.0)),
+    };
+    Container::new(content)
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .padding(Padding {
+            top,
+            right,
+            bottom,
+            left,
+        })
+        .align_x(horizontal)
+        .align_y(vertical)
+        .into()
+}
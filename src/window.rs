@@ -0,0 +1,367 @@
+use bevy_ecs::event::Events;
+use bevy_ecs::prelude::{Entity, EventWriter, Query, With};
+use bevy_ecs::system::{Commands, Res, ResMut, Resource, SystemParam};
+use bevy_math::Vec2;
+use bevy_utils::tracing::warn;
+use bevy_window::{
+    CursorIcon, PrimaryWindow, RequestRedraw, Window, WindowLevel, WindowMode, WindowPosition,
+};
+use iced_core::mouse::Interaction;
+use iced_core::window::{Id, Level, Mode, RedrawRequest};
+use iced_core::{Point, Size};
+use iced_runtime::window::Action;
+
+use crate::{conversions, IcedSettings};
+
+#[cfg(feature = "winit")]
+use bevy_ecs::system::NonSend;
+#[cfg(feature = "winit")]
+use bevy_winit::WinitWindows;
+
+/// An edge or corner of the window, to resize it from with
+/// [`IcedContext::start_window_resize`](crate::IcedContext::start_window_resize).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ResizeEdge {
+    /// The top edge.
+    North,
+    /// The bottom edge.
+    South,
+    /// The right edge.
+    East,
+    /// The left edge.
+    West,
+    /// The top right corner.
+    NorthEast,
+    /// The top left corner.
+    NorthWest,
+    /// The bottom right corner.
+    SouthEast,
+    /// The bottom left corner.
+    SouthWest,
+}
+
+// A change to the window's component, applied in `PostUpdate` as UI systems only
+// have read access to the window.
+enum WindowCommand {
+    Resize(Size),
+    Move(Point),
+    Maximize(bool),
+    Minimize(bool),
+    ChangeMode(Mode),
+    ToggleDecorations,
+    ChangeLevel(Level),
+    GainFocus,
+    Close,
+}
+
+#[derive(Resource, Default)]
+pub struct WindowCommandQueue {
+    commands: Vec<WindowCommand>,
+    // The cursor requested by this frame's UIs, and the one last applied to the window.
+    interaction: Option<Interaction>,
+    applied_interaction: Interaction,
+    // The app's own cursor, restored once the UI doesn't request one anymore.
+    app_cursor: Option<CursorIcon>,
+}
+
+/// Access to the window that Iced window actions apply to. With a single window, the
+/// primary window is [`Id::MAIN`], and no other Iced window id refers to a window.
+#[derive(SystemParam)]
+pub struct IcedWindows<'w, 's> {
+    windows: Query<'w, 's, (Entity, &'static Window), With<PrimaryWindow>>,
+    all_windows: Query<'w, 's, (), With<Window>>,
+    queue: ResMut<'w, WindowCommandQueue>,
+    redraws: EventWriter<'w, RequestRedraw>,
+    #[cfg(feature = "winit")]
+    winit_windows: Option<NonSend<'w, WinitWindows>>,
+}
+
+impl IcedWindows<'_, '_> {
+    // The Iced window id of the window entity.
+    pub fn id(&self, entity: Entity) -> Option<Id> {
+        let (primary, _) = self.windows.get_single().ok()?;
+        (entity == primary).then_some(Id::MAIN)
+    }
+
+    // Whether the entity is a window, driven or not.
+    pub fn is_window(&self, entity: Entity) -> bool {
+        self.all_windows.contains(entity)
+    }
+
+    // The window entity with the Iced window id.
+    pub fn entity(&self, id: Id) -> Option<Entity> {
+        let (primary, _) = self.windows.get_single().ok()?;
+        (id == Id::MAIN).then_some(primary)
+    }
+
+    pub fn perform<M: bevy_ecs::event::Event>(
+        &mut self,
+        action: Action<M>,
+        messages: &mut Events<M>,
+    ) {
+        if let Some(id) = target(&action).filter(|id| self.entity(*id).is_none()) {
+            warn!("Ignoring an Iced window action for {id:?}, which isn't a window");
+            return;
+        }
+        let command = match action {
+            Action::Resize(_, size) => WindowCommand::Resize(size),
+            Action::Move(_, position) => WindowCommand::Move(position),
+            Action::Maximize(_, maximized) => WindowCommand::Maximize(maximized),
+            Action::Minimize(_, minimized) => WindowCommand::Minimize(minimized),
+            Action::ChangeMode(_, mode) => WindowCommand::ChangeMode(mode),
+            Action::ToggleDecorations(_) => WindowCommand::ToggleDecorations,
+            Action::ChangeLevel(_, level) => WindowCommand::ChangeLevel(level),
+            Action::GainFocus(_) => WindowCommand::GainFocus,
+            Action::Close(_) => WindowCommand::Close,
+            Action::FetchSize(_, tagger) => {
+                if let Ok((_, window)) = self.windows.get_single() {
+                    messages.send(tagger(Size::new(window.width(), window.height())));
+                }
+                return;
+            }
+            Action::FetchMode(_, tagger) => {
+                if let Ok((_, window)) = self.windows.get_single() {
+                    messages.send(tagger(mode(window)));
+                }
+                return;
+            }
+            Action::Spawn(..) => {
+                warn!(
+                    "Spawning windows from Iced isn't supported, spawn a `Window` entity instead"
+                );
+                return;
+            }
+            Action::Screenshot(..) => {
+                warn!("Iced window screenshots aren't supported");
+                return;
+            }
+            action => {
+                self.perform_native(action, messages);
+                return;
+            }
+        };
+        self.queue.commands.push(command);
+    }
+
+    // The cursor is only changed while Iced requests one, so that apps can still set
+    // their own while the mouse isn't over the UI.
+    pub fn request_interaction(&mut self, interaction: Interaction) {
+        let requested = self.queue.interaction.get_or_insert(Interaction::Idle);
+        if interaction != Interaction::Idle {
+            *requested = interaction;
+        }
+    }
+
+    // Keep updating while widgets animate, when bevy only updates on input. Redraws
+    // requested at a later instant are left to the update mode's wait timeout.
+    pub fn request_redraw(&mut self, request: RedrawRequest) {
+        if request == RedrawRequest::NextFrame {
+            self.redraws.send(RequestRedraw);
+        }
+    }
+
+    // The actions that bevy doesn't expose, performed on the winit window directly.
+    #[cfg(feature = "winit")]
+    fn perform_native<M: bevy_ecs::event::Event>(
+        &self,
+        action: Action<M>,
+        messages: &mut Events<M>,
+    ) {
+        use iced_core::window::UserAttention;
+        use winit::raw_window_handle::HasWindowHandle;
+        use winit::window::UserAttentionType;
+
+        let Some(window) = self.winit_window() else {
+            return;
+        };
+        match action {
+            Action::Drag(_) => {
+                if let Err(err) = window.drag_window() {
+                    warn!("Failed to drag the window: {err}");
+                }
+            }
+            Action::ToggleMaximize(_) => window.set_maximized(!window.is_maximized()),
+            Action::FetchMaximized(_, tagger) => {
+                messages.send(tagger(window.is_maximized()));
+            }
+            Action::FetchMinimized(_, tagger) => {
+                messages.send(tagger(window.is_minimized()));
+            }
+            Action::RequestUserAttention(_, attention) => {
+                window.request_user_attention(attention.map(|attention| match attention {
+                    UserAttention::Critical => UserAttentionType::Critical,
+                    UserAttention::Informational => UserAttentionType::Informational,
+                }));
+            }
+            Action::ShowSystemMenu(_) => {
+                if let Ok((_, bevy_window)) = self.windows.get_single() {
+                    if let Some(position) = bevy_window.physical_cursor_position() {
+                        window.show_window_menu(winit::dpi::PhysicalPosition::new(
+                            position.x, position.y,
+                        ));
+                    }
+                }
+            }
+            Action::FetchId(_, tagger) => {
+                messages.send(tagger(u64::from(window.id())));
+            }
+            Action::ChangeIcon(_, icon) => {
+                let (rgba, size) = icon.into_raw();
+                match winit::window::Icon::from_rgba(rgba, size.width, size.height) {
+                    Ok(icon) => window.set_window_icon(Some(icon)),
+                    Err(err) => warn!("Invalid window icon: {err}"),
+                }
+            }
+            Action::RunWithHandle(_, tagger) => {
+                if let Ok(handle) = window.window_handle() {
+                    messages.send(tagger(&handle));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    #[cfg(not(feature = "winit"))]
+    fn perform_native<M: bevy_ecs::event::Event>(
+        &self,
+        _action: Action<M>,
+        _messages: &mut Events<M>,
+    ) {
+        warn!("This Iced window action requires the `winit` feature of `bevy_iced`");
+    }
+
+    #[cfg(feature = "winit")]
+    pub fn start_resize(&self, edge: ResizeEdge) {
+        use winit::window::ResizeDirection;
+
+        let Some(window) = self.winit_window() else {
+            return;
+        };
+        let direction = match edge {
+            ResizeEdge::North => ResizeDirection::North,
+            ResizeEdge::South => ResizeDirection::South,
+            ResizeEdge::East => ResizeDirection::East,
+            ResizeEdge::West => ResizeDirection::West,
+            ResizeEdge::NorthEast => ResizeDirection::NorthEast,
+            ResizeEdge::NorthWest => ResizeDirection::NorthWest,
+            ResizeEdge::SouthEast => ResizeDirection::SouthEast,
+            ResizeEdge::SouthWest => ResizeDirection::SouthWest,
+        };
+        if let Err(err) = window.drag_resize_window(direction) {
+            warn!("Failed to resize the window: {err}");
+        }
+    }
+
+    #[cfg(not(feature = "winit"))]
+    pub fn start_resize(&self, _edge: ResizeEdge) {
+        warn!("Resizing the window from Iced requires the `winit` feature of `bevy_iced`");
+    }
+
+    #[cfg(feature = "winit")]
+    fn winit_window(&self) -> Option<&winit::window::Window> {
+        let (entity, _) = self.windows.get_single().ok()?;
+        self.winit_windows.as_ref()?.get_window(entity)
+    }
+}
+
+// The window an action applies to, or `None` for the one it spawns.
+fn target<M>(action: &Action<M>) -> Option<Id> {
+    match action {
+        Action::Spawn(..) => None,
+        Action::Close(id)
+        | Action::Drag(id)
+        | Action::Resize(id, _)
+        | Action::FetchSize(id, _)
+        | Action::FetchMaximized(id, _)
+        | Action::Maximize(id, _)
+        | Action::FetchMinimized(id, _)
+        | Action::Minimize(id, _)
+        | Action::Move(id, _)
+        | Action::ChangeMode(id, _)
+        | Action::FetchMode(id, _)
+        | Action::ToggleMaximize(id)
+        | Action::ToggleDecorations(id)
+        | Action::RequestUserAttention(id, _)
+        | Action::GainFocus(id)
+        | Action::ChangeLevel(id, _)
+        | Action::ShowSystemMenu(id)
+        | Action::FetchId(id, _)
+        | Action::ChangeIcon(id, _)
+        | Action::RunWithHandle(id, _)
+        | Action::Screenshot(id, _) => Some(*id),
+    }
+}
+
+fn mode(window: &Window) -> Mode {
+    if !window.visible {
+        Mode::Hidden
+    } else if window.mode == WindowMode::Windowed {
+        Mode::Windowed
+    } else {
+        Mode::Fullscreen
+    }
+}
+
+pub fn apply_window_commands(
+    mut commands: Commands,
+    mut queue: ResMut<WindowCommandQueue>,
+    mut windows: Query<(Entity, &mut Window), With<PrimaryWindow>>,
+    settings: Res<IcedSettings>,
+) {
+    let pending = std::mem::take(&mut queue.commands);
+    // Without a UI, or with the setting off, the app's cursor is restored.
+    let interaction = queue
+        .interaction
+        .take()
+        .filter(|_| settings.apply_cursor_icon)
+        .unwrap_or(Interaction::Idle);
+    let Ok((entity, mut window)) = windows.get_single_mut() else {
+        return;
+    };
+    if interaction != queue.applied_interaction {
+        if interaction == Interaction::Idle {
+            if let Some(icon) = queue.app_cursor.take() {
+                window.cursor.icon = icon;
+            }
+        } else {
+            if queue.applied_interaction == Interaction::Idle {
+                queue.app_cursor = Some(window.cursor.icon);
+            }
+            window.cursor.icon = conversions::cursor_icon(interaction);
+        }
+        queue.applied_interaction = interaction;
+    }
+    for command in pending {
+        match command {
+            WindowCommand::Resize(size) => window.resolution.set(size.width, size.height),
+            WindowCommand::Move(position) => {
+                // Iced positions are logical, bevy's are physical.
+                let position = Vec2::new(position.x, position.y) * window.scale_factor();
+                window.position = WindowPosition::At(position.as_ivec2());
+            }
+            WindowCommand::Maximize(maximized) => window.set_maximized(maximized),
+            WindowCommand::Minimize(minimized) => window.set_minimized(minimized),
+            WindowCommand::ChangeMode(Mode::Hidden) => window.visible = false,
+            WindowCommand::ChangeMode(mode) => {
+                window.visible = true;
+                window.mode = match mode {
+                    Mode::Fullscreen => WindowMode::BorderlessFullscreen,
+                    _ => WindowMode::Windowed,
+                };
+            }
+            WindowCommand::ToggleDecorations => window.decorations = !window.decorations,
+            WindowCommand::ChangeLevel(level) => {
+                window.window_level = match level {
+                    Level::Normal => WindowLevel::Normal,
+                    Level::AlwaysOnBottom => WindowLevel::AlwaysOnBottom,
+                    Level::AlwaysOnTop => WindowLevel::AlwaysOnTop,
+                };
+            }
+            WindowCommand::GainFocus => window.focused = true,
+            WindowCommand::Close => {
+                commands.entity(entity).despawn();
+                return;
+            }
+        }
+    }
+}
@@ -0,0 +1,96 @@
+use bevy_derive::{Deref, DerefMut};
+use bevy_ecs::prelude::{Entity, EventReader, Query, With};
+use bevy_ecs::system::{Res, ResMut, Resource};
+use bevy_input::keyboard::{KeyCode, KeyboardInput};
+use bevy_input::mouse::{MouseScrollUnit, MouseWheel};
+use bevy_input::ButtonState;
+use bevy_window::PrimaryWindow;
+use iced_core::keyboard::Modifiers;
+
+use crate::systems::HeldKeys;
+use crate::{conversions, IcedEnabled, IcedSettings};
+
+// The pixels of a touchpad scroll that count as one wheel notch, as browsers do.
+const PIXELS_PER_LINE: f32 = 100.0;
+
+/// How Ctrl+wheel zooms the UI, see [`IcedSettings::ctrl_scroll_zoom`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ZoomConfig {
+    /// The smallest zoom, e.g. `0.5` for half the size.
+    pub min: f64,
+    /// The largest zoom.
+    pub max: f64,
+    /// The zoom added by each wheel notch up, and removed by each notch down.
+    pub step: f64,
+}
+
+impl Default for ZoomConfig {
+    fn default() -> Self {
+        Self {
+            min: 0.5,
+            max: 3.0,
+            step: 0.1,
+        }
+    }
+}
+
+/// The zoom of the window's UI, multiplying its scale factor, e.g. `1.5` to draw it half
+/// again as large.
+///
+/// [`IcedSettings::ctrl_scroll_zoom`] changes it with Ctrl+wheel and resets it with
+/// Ctrl+0. Read it to save the user's choice, and set it to restore it.
+#[derive(Clone, Copy, Debug, PartialEq, Resource, Deref, DerefMut)]
+pub struct IcedZoom(pub f64);
+
+impl Default for IcedZoom {
+    fn default() -> Self {
+        Self(1.0)
+    }
+}
+
+// Whether the wheel and Ctrl+0 zoom the UI instead of reaching it.
+pub fn is_zooming(settings: &IcedSettings, modifiers: Modifiers) -> bool {
+    settings.ctrl_scroll_zoom.is_some() && modifiers.control()
+}
+
+// Whether the key press resets the zoom.
+pub fn is_reset(key_code: KeyCode) -> bool {
+    matches!(key_code, KeyCode::Digit0 | KeyCode::Numpad0)
+}
+
+// Zoom before the viewport is updated, so that this frame's events and UIs already use
+// the new scale factor.
+pub fn ctrl_scroll_zoom(
+    mut wheel: EventReader<MouseWheel>,
+    mut keys: EventReader<KeyboardInput>,
+    held_keys: Res<HeldKeys>,
+    windows: Query<Entity, With<PrimaryWindow>>,
+    (settings, enabled): (Res<IcedSettings>, Res<IcedEnabled>),
+    mut zoom: ResMut<IcedZoom>,
+) {
+    let primary = windows.get_single().ok();
+    let zooming = enabled.0 && is_zooming(&settings, conversions::modifiers(&held_keys));
+    let Some(config) = settings.ctrl_scroll_zoom.filter(|_| zooming) else {
+        wheel.clear();
+        keys.clear();
+        return;
+    };
+
+    let notches: f32 = wheel
+        .read()
+        .filter(|ev| Some(ev.window) == primary)
+        .map(|ev| match ev.unit {
+            MouseScrollUnit::Line => ev.y,
+            MouseScrollUnit::Pixel => ev.y / PIXELS_PER_LINE,
+        })
+        .sum();
+    let reset = keys.read().any(|ev| {
+        Some(ev.window) == primary && ev.state == ButtonState::Pressed && is_reset(ev.key_code)
+    });
+
+    if reset {
+        zoom.0 = 1.0;
+    } else if notches != 0.0 {
+        zoom.0 = (zoom.0 + f64::from(notches) * config.step).clamp(config.min, config.max);
+    }
+}